@@ -0,0 +1,77 @@
+//! Throughput of each validator, the shared character pass, and the fixer.
+//!
+//! Run with `cargo bench`; compare against a saved run with
+//! `cargo bench -- --save-baseline main` and `cargo bench -- --baseline main`.
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use markdown_checker::Validator;
+use markdown_checker::fixer::fix_completely;
+use markdown_checker::policy::Policy;
+use markdown_checker::validators::{
+    AsciiValidator, FrontmatterValidator, TreeSymbolValidator, UnprintableValidator, validate_all,
+};
+use std::hint::black_box;
+
+/// About 1 MiB of each kind of content
+const TARGET_BYTES: usize = 1 << 20;
+
+/// Representative documents: mostly clean prose, prose full of Unicode, and
+/// generated output on one enormous line
+fn corpora() -> Vec<(&'static str, String)> {
+    let clean =
+        "## Usage\n\nRun `markdown-checker -f \"**/*.md\"` in CI; it exits 1 on violations.\n\n";
+    let unicode = "## Layout \u{2014} caf\u{e9}\n\n\u{251c}\u{2500}\u{2500} src/ \u{2192} \u{201c}main\u{201d} \u{2713}\n\u{2514}\u{2500}\u{2500} docs/ \u{1f4da}\n\n";
+    let single_line = "token \u{2192} value, ".repeat(TARGET_BYTES / 16);
+    vec![
+        ("clean_ascii", fill(clean)),
+        ("heavy_unicode", fill(unicode)),
+        ("single_line", single_line),
+    ]
+}
+
+fn fill(block: &str) -> String {
+    block.repeat(TARGET_BYTES / block.len())
+}
+
+fn bench_validators(c: &mut Criterion) {
+    let validators: [&dyn Validator; 4] = [
+        &AsciiValidator,
+        &UnprintableValidator,
+        &TreeSymbolValidator,
+        &FrontmatterValidator,
+    ];
+    let mut group = c.benchmark_group("validators");
+    for (name, content) in corpora() {
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        for validator in validators {
+            let id = BenchmarkId::new(validator.metadata().key, name);
+            group.bench_with_input(id, &content, |b, content| {
+                b.iter(|| validator.validate(black_box(content)))
+            });
+        }
+        // The character rules together, as a run checks them
+        group.bench_with_input(BenchmarkId::new("all", name), &content, |b, content| {
+            b.iter(|| validate_all(black_box(content)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_fixer(c: &mut Criterion) {
+    let policy = Policy::default();
+    let mut group = c.benchmark_group("fixer");
+    for (name, content) in corpora() {
+        group.throughput(Throughput::Bytes(content.len() as u64));
+        group.bench_with_input(BenchmarkId::new("fix", name), &content, |b, content| {
+            b.iter(|| fix_completely(black_box(content), &policy))
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(20);
+    targets = bench_validators, bench_fixer
+}
+criterion_main!(benches);
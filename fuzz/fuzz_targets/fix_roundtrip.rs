@@ -0,0 +1,14 @@
+//! Fixing arbitrary text keeps the fixer's invariants: only characters with
+//! a replacement change, no replacement word joins its neighbours, every
+//! violation fixing can resolve is resolved, and fixing again changes nothing.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use markdown_checker::fixer::verify_roundtrip;
+
+fuzz_target!(|content: &str| {
+    if let Err(broken) = verify_roundtrip(content) {
+        panic!("{}: {:?}", broken, content);
+    }
+});
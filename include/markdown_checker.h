@@ -0,0 +1,70 @@
+/* C interface to markdown-checker: build with `cargo build --release --features ffi`. */
+
+#ifndef MARKDOWN_CHECKER_H
+#define MARKDOWN_CHECKER_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// What a call did, numbered like the command line's exit codes
+typedef enum McStatus {
+  // No error-severity violations, or content fixed
+  MC_STATUS_OK = 0,
+  // Validation found error-severity violations
+  MC_STATUS_VIOLATIONS = 1,
+  // A required pointer was null, the content was not UTF-8, or fixed
+  // content held a NUL a C string cannot carry
+  MC_STATUS_INVALID_ARGUMENT = 2,
+} McStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Check `len` bytes of markdown at `content` with the default rules.
+//
+// On success `*json` receives a JSON object like `check` in the JavaScript
+// bindings: `passed` and one entry per rule in `results` with its violations.
+// Returns `MC_STATUS_OK` or `MC_STATUS_VIOLATIONS`, or
+// `MC_STATUS_INVALID_ARGUMENT` without touching `*json`.
+//
+// # Safety
+//
+// `content` must point to `len` readable bytes, and `json` to writable
+// storage for a pointer. Free the string written to `*json` with `mc_free`.
+enum McStatus mc_validate(const char *content, size_t len, char **json);
+
+// Replace every auto-fixable character in `len` bytes of markdown at
+// `content` with its ASCII equivalent.
+//
+// On success `*fixed` receives the fixed content and, unless `replaced` is
+// null, `*replaced` the number of characters replaced. Violations without a
+// fix remain; `mc_validate` the result to find them. Returns `MC_STATUS_OK`, or
+// `MC_STATUS_INVALID_ARGUMENT` without touching the outputs, including when
+// the content holds a NUL that the fixed C string could not carry.
+//
+// # Safety
+//
+// `content` must point to `len` readable bytes, `fixed` to writable storage
+// for a pointer, and `replaced` must be null or writable. Free the string
+// written to `*fixed` with `mc_free`.
+enum McStatus mc_fix(const char *content, size_t len, char **fixed, size_t *replaced);
+
+// Release a string returned by `mc_validate` or `mc_fix`. Null is ignored.
+//
+// # Safety
+//
+// `string` must be null or a pointer this library returned that has not
+// been freed yet.
+void mc_free(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* MARKDOWN_CHECKER_H */
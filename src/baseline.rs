@@ -0,0 +1,283 @@
+//! Baseline files recording known violations.
+//!
+//! A baseline lets a project adopt the checker on an existing docs tree: the
+//! current violations are recorded once, and later runs only fail on new ones.
+//! Violations are identified by rule, path, and a fingerprint of the offending
+//! line's content, so unrelated edits that shift line numbers do not
+//! invalidate the baseline.
+
+use crate::hash::Fnv1a;
+use crate::validators::find_rule;
+use crate::{ValidationError, ValidationResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Component, Path};
+
+const BASELINE_VERSION: u32 = 1;
+
+/// Identity of a violation that survives line-number shifts
+type Key = (String, String, String);
+
+/// One recorded violation kind and how many times it occurs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub rule: String,
+    pub path: String,
+    pub fingerprint: String,
+    pub count: usize,
+}
+
+/// On-disk baseline format
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    entries: Vec<BaselineEntry>,
+}
+
+/// Known violations, counted per (rule, path, fingerprint)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline {
+    counts: BTreeMap<Key, usize>,
+}
+
+impl Baseline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a baseline file written by `save`
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let file: BaselineFile = serde_json::from_str(&text)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if file.version != BASELINE_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported baseline version {}", file.version),
+            ));
+        }
+
+        let mut baseline = Self::new();
+        for entry in file.entries {
+            *baseline
+                .counts
+                .entry((entry.rule, entry.path, entry.fingerprint))
+                .or_insert(0) += entry.count;
+        }
+        Ok(baseline)
+    }
+
+    /// Write the baseline as sorted JSON, so it diffs cleanly under version control
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let file = BaselineFile {
+            version: BASELINE_VERSION,
+            entries: self.entries(),
+        };
+        let json = serde_json::to_string_pretty(&file).map_err(io::Error::other)?;
+        fs::write(path, json + "\n")
+    }
+
+    /// Recorded entries in sorted order
+    pub fn entries(&self) -> Vec<BaselineEntry> {
+        self.counts
+            .iter()
+            .map(|((rule, path, fingerprint), count)| BaselineEntry {
+                rule: rule.clone(),
+                path: path.clone(),
+                fingerprint: fingerprint.clone(),
+                count: *count,
+            })
+            .collect()
+    }
+
+    /// Total number of violations recorded
+    pub fn len(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Record every violation in `results` for `file`
+    pub fn record(&mut self, file: &Path, content: &str, results: &[ValidationResult]) {
+        let lines: Vec<&str> = content.lines().collect();
+        for result in results {
+            for error in &result.errors {
+                let key = violation_key(file, &lines, result, error);
+                *self.counts.entry(key).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Remove violations recorded in the baseline from `results`, consuming
+    /// baseline counts so additional occurrences are still reported.
+    /// Returns the filtered results and the number of suppressed violations.
+    pub fn suppress(
+        &mut self,
+        file: &Path,
+        content: &str,
+        results: Vec<ValidationResult>,
+    ) -> (Vec<ValidationResult>, usize) {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut suppressed = 0;
+
+        let results = results
+            .into_iter()
+            .map(|result| {
+                let keys: Vec<Key> = result
+                    .errors
+                    .iter()
+                    .map(|error| violation_key(file, &lines, &result, error))
+                    .collect();
+                let mut keys = keys.into_iter();
+
+                result.retain_errors(|_| {
+                    let key = keys.next().expect("one key per error");
+                    match self.counts.get_mut(&key) {
+                        Some(count) if *count > 0 => {
+                            *count -= 1;
+                            suppressed += 1;
+                            false
+                        }
+                        _ => true,
+                    }
+                })
+            })
+            .collect();
+
+        (results, suppressed)
+    }
+}
+
+/// Build the baseline key for a single violation
+fn violation_key(
+    file: &Path,
+    lines: &[&str],
+    result: &ValidationResult,
+    error: &ValidationError,
+) -> Key {
+    let rule = find_rule(&result.validator_name)
+        .map(|r| r.code.to_string())
+        .unwrap_or_else(|| result.validator_name.clone());
+    let line = error
+        .line_number
+        .checked_sub(1)
+        .and_then(|i| lines.get(i))
+        .map(|l| l.trim())
+        .unwrap_or("");
+
+    let mut hasher = Fnv1a::new();
+    hasher.write(rule.as_bytes());
+    hasher.write(&[0]);
+    hasher.write(line.as_bytes());
+    hasher.write(&[0]);
+    hasher.write(error.message.text().as_bytes());
+
+    (
+        rule,
+        normalize_path(file),
+        format!("{:016x}", hasher.finish()),
+    )
+}
+
+/// Render a path with `/` separators and without leading `./`
+fn normalize_path(path: &Path) -> String {
+    path.components()
+        .filter(|c| *c != Component::CurDir)
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::validate_all;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_and_suppress() {
+        let path = Path::new("./docs/a.md");
+        let content = "ok\n├── src\n";
+        let results = validate_all(content);
+
+        let mut baseline = Baseline::new();
+        baseline.record(path, content, &results);
+        assert!(!baseline.is_empty());
+
+        let (filtered, suppressed) = baseline.suppress(path, content, validate_all(content));
+        assert!(filtered.iter().all(|r| r.is_pass()));
+        assert_eq!(
+            suppressed,
+            results.iter().map(|r| r.errors.len()).sum::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_suppress_survives_line_shifts() {
+        let path = Path::new("a.md");
+        let before = "├ tree\n";
+        let after = "new intro\n\n├ tree\n";
+
+        let mut baseline = Baseline::new();
+        baseline.record(path, before, &validate_all(before));
+
+        let (filtered, _) = baseline.suppress(path, after, validate_all(after));
+        assert!(filtered.iter().all(|r| r.is_pass()));
+    }
+
+    #[test]
+    fn test_new_violations_are_reported() {
+        let path = Path::new("a.md");
+        let before = "café\n";
+        let after = "café\ncafé\n";
+
+        let mut baseline = Baseline::new();
+        baseline.record(path, before, &validate_all(before));
+
+        let (filtered, suppressed) = baseline.suppress(path, after, validate_all(after));
+        let ascii = filtered
+            .iter()
+            .find(|r| r.validator_name == "ASCII Subset")
+            .unwrap();
+        assert!(ascii.is_fail());
+        assert_eq!(ascii.errors.len(), 1);
+        assert_eq!(suppressed, 2);
+    }
+
+    #[test]
+    fn test_paths_are_normalized() {
+        let content = "café\n";
+        let mut baseline = Baseline::new();
+        baseline.record(Path::new("./a.md"), content, &validate_all(content));
+
+        let (filtered, _) = baseline.suppress(Path::new("a.md"), content, validate_all(content));
+        assert!(filtered.iter().all(|r| r.is_pass()));
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("baseline.json");
+        let content = "├── café\n";
+
+        let mut baseline = Baseline::new();
+        baseline.record(Path::new("a.md"), content, &validate_all(content));
+        baseline.save(&file).unwrap();
+
+        let loaded = Baseline::load(&file).unwrap();
+        assert_eq!(loaded, baseline);
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_version() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("baseline.json");
+        fs::write(&file, r#"{"version": 99, "entries": []}"#).unwrap();
+        assert!(Baseline::load(&file).is_err());
+    }
+}
@@ -0,0 +1,58 @@
+//! `cargo markdown-check`: check every git-tracked markdown file in the
+//! current cargo workspace.
+
+use markdown_checker::cargo_subcommand::{
+    checker_executable, user_args, workspace_markdown_files, workspace_root,
+};
+use std::io::Write;
+use std::process::{self, Command, Stdio};
+
+fn main() {
+    let args = user_args(std::env::args_os());
+
+    let root = workspace_root().unwrap_or_else(|e| {
+        eprintln!("Error: unable to find the cargo workspace root: {}", e);
+        process::exit(2);
+    });
+    let files = workspace_markdown_files(&root).unwrap_or_else(|e| {
+        eprintln!(
+            "Error: unable to list tracked files in {}: {}",
+            root.display(),
+            e
+        );
+        process::exit(2);
+    });
+    if files.is_empty() {
+        eprintln!("No tracked markdown files in {}", root.display());
+        return;
+    }
+
+    // Pass the file list on stdin; user arguments (including a `fix`
+    // subcommand) come first so --files-from applies to them
+    let checker = checker_executable();
+    let mut child = Command::new(&checker)
+        .args(&args)
+        .args(["--files-from", "-"])
+        .current_dir(&root)
+        .stdin(Stdio::piped())
+        .spawn()
+        .unwrap_or_else(|e| {
+            eprintln!("Error: unable to run {}: {}", checker.display(), e);
+            process::exit(2);
+        });
+
+    let list: String = files
+        .iter()
+        .map(|path| format!("{}\n", path.display()))
+        .collect();
+    if let Some(mut stdin) = child.stdin.take() {
+        // The checker may exit early (e.g. on a usage error) and close stdin
+        let _ = stdin.write_all(list.as_bytes());
+    }
+
+    let status = child.wait().unwrap_or_else(|e| {
+        eprintln!("Error: {} did not finish: {}", checker.display(), e);
+        process::exit(2);
+    });
+    process::exit(status.code().unwrap_or(2));
+}
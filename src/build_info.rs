@@ -0,0 +1,85 @@
+//! Build-time metadata emitted by build.rs.
+
+use serde::Serialize;
+use std::fmt;
+
+/// Version and provenance information captured when the binary was built
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BuildInfo {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub git_commit: &'static str,
+    pub build_timestamp: &'static str,
+    pub build_host: &'static str,
+}
+
+impl BuildInfo {
+    /// Build information for the current binary, with fallbacks for builds
+    /// where build.rs did not emit a value
+    pub fn current() -> Self {
+        Self {
+            name: env!("CARGO_PKG_NAME"),
+            version: option_env!("APP_VERSION").unwrap_or(env!("CARGO_PKG_VERSION")),
+            git_commit: option_env!("GIT_COMMIT_SHA").unwrap_or("unknown"),
+            build_timestamp: option_env!("BUILD_TIMESTAMP").unwrap_or("unknown"),
+            build_host: option_env!("BUILD_HOST").unwrap_or("unknown"),
+        }
+    }
+
+    /// Render as a JSON object
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("build info serializes to JSON")
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", self.name, self.version)?;
+        writeln!(f, "Commit:    {}", self.git_commit)?;
+        writeln!(f, "Built:     {}", self.build_timestamp)?;
+        write!(f, "Host:      {}", self.build_host)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_has_name_and_version() {
+        let info = BuildInfo::current();
+        assert_eq!(info.name, "markdown-checker");
+        assert!(!info.version.is_empty());
+    }
+
+    #[test]
+    fn test_display_includes_all_fields() {
+        let info = BuildInfo {
+            name: "markdown-checker",
+            version: "1.2.3",
+            git_commit: "abc1234",
+            build_timestamp: "2025-01-01T00:00:00Z",
+            build_host: "builder",
+        };
+        let text = info.to_string();
+        assert!(text.starts_with("markdown-checker 1.2.3"));
+        assert!(text.contains("abc1234"));
+        assert!(text.contains("2025-01-01T00:00:00Z"));
+        assert!(text.contains("builder"));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let info = BuildInfo {
+            name: "markdown-checker",
+            version: "1.2.3",
+            git_commit: "abc1234",
+            build_timestamp: "2025-01-01T00:00:00Z",
+            build_host: "builder",
+        };
+        let json: serde_json::Value = serde_json::from_str(&info.to_json()).unwrap();
+        assert_eq!(json["version"], "1.2.3");
+        assert_eq!(json["git_commit"], "abc1234");
+        assert_eq!(json["build_host"], "builder");
+    }
+}
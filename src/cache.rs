@@ -0,0 +1,215 @@
+//! Validation result cache persisted between runs.
+//!
+//! Results are keyed by a hash of the file content combined with a hash of the
+//! active rule configuration, so a cached entry is only reused when neither
+//! the file nor the rules that produced its results have changed.
+
+use crate::ValidationResult;
+use crate::hash::{Fnv1a, hex_digest};
+use crate::policy::Policy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Default cache directory, relative to the working directory
+pub const DEFAULT_CACHE_DIR: &str = ".markdown-checker/cache";
+
+const CACHE_FILE_NAME: &str = "results.json";
+const CACHE_VERSION: u32 = 1;
+
+/// Entries beyond this count are pruned to those used in the current run
+const MAX_ENTRIES: usize = 50_000;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    entries: HashMap<String, Vec<ValidationResult>>,
+}
+
+/// Cached validation results keyed by content and rule-configuration hash
+#[derive(Debug, Default)]
+pub struct ResultCache {
+    path: PathBuf,
+    entries: HashMap<String, Vec<ValidationResult>>,
+    used: HashMap<String, Vec<ValidationResult>>,
+    dirty: bool,
+}
+
+impl ResultCache {
+    /// Open the cache in `dir`, starting empty if it is missing or unreadable
+    pub fn open(dir: &Path) -> Self {
+        let path = dir.join(CACHE_FILE_NAME);
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str::<CacheFile>(&text).ok())
+            .filter(|file| file.version == CACHE_VERSION)
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        Self {
+            path,
+            entries,
+            used: HashMap::new(),
+            dirty: false,
+        }
+    }
+
+    /// Compute the cache key for `content` under a rule-configuration hash
+    pub fn key(content: &str, rules_hash: &str) -> String {
+        format!("{}-{}", hex_digest(content.as_bytes()), rules_hash)
+    }
+
+    /// Delete the cache in `dir`, returning how many entries it held
+    pub fn clear(dir: &Path) -> io::Result<usize> {
+        let entries = Self::open(dir).len();
+        match fs::remove_file(dir.join(CACHE_FILE_NAME)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        }
+        // Leave the directory if something else was put in it
+        let _ = fs::remove_dir(dir);
+        Ok(entries)
+    }
+
+    /// Look up cached results
+    pub fn get(&self, key: &str) -> Option<&Vec<ValidationResult>> {
+        self.used.get(key).or_else(|| self.entries.get(key))
+    }
+
+    /// Record that `key` was used this run, inserting `results` if it is new
+    pub fn insert(&mut self, key: String, results: Vec<ValidationResult>) {
+        if !self.entries.contains_key(&key) && !self.used.contains_key(&key) {
+            self.dirty = true;
+        }
+        self.entries.remove(&key);
+        self.used.insert(key, results);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len() + self.used.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Persist the cache if anything new was added
+    pub fn save(self) -> io::Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let mut entries = self.used;
+        if entries.len() + self.entries.len() <= MAX_ENTRIES {
+            entries.extend(self.entries);
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = CacheFile {
+            version: CACHE_VERSION,
+            entries,
+        };
+        let json = serde_json::to_string(&file).map_err(io::Error::other)?;
+        fs::write(&self.path, json)
+    }
+}
+
+/// Hash the settings that affect validation results, so a configuration or
+/// tool upgrade invalidates stale entries. Every field of the policy decides
+/// what is reported (rules and their options, severities, accepted
+/// characters, exemptions, format, and byte order mark handling), so all of
+/// them are hashed, through their `Debug` form, which is stable for a given
+/// version.
+pub fn rules_hash(policy: &Policy) -> String {
+    let mut hasher = Fnv1a::new();
+    hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+    hasher.write(&[0]);
+    hasher.write(format!("{:?}", policy).as_bytes());
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::validate_all;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_key_depends_on_content_and_rules() {
+        let rules = rules_hash(&Policy::default());
+        assert_eq!(ResultCache::key("a", &rules), ResultCache::key("a", &rules));
+        assert_ne!(ResultCache::key("a", &rules), ResultCache::key("b", &rules));
+        assert_ne!(
+            ResultCache::key("a", &rules),
+            ResultCache::key("a", &rules_hash(&Policy::new(vec!["MC001"])))
+        );
+    }
+
+    #[test]
+    fn test_key_depends_on_every_policy_setting() {
+        use crate::RuleOptions;
+        use crate::Severity;
+        use crate::doc_format::DocFormat;
+        use crate::encoding::Bom;
+
+        let base = rules_hash(&Policy::default());
+        let options = RuleOptions {
+            max_punctuation_run: 5,
+            ..RuleOptions::default()
+        };
+        let changed = [
+            Policy::default().options(options),
+            Policy::default().severities([("MC001", Severity::Warning)]),
+            Policy::default().allow(['\u{e9}']),
+            Policy::default().forbid(['\u{a0}']),
+            Policy::default().skip_code(true),
+            Policy::default().skip_frontmatter(true),
+            Policy::default().format(DocFormat::Rst),
+            Policy::default().bom(Bom::Keep),
+        ];
+        for policy in &changed {
+            assert_ne!(rules_hash(policy), base, "{:?}", policy);
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_through_disk() {
+        let dir = TempDir::new().unwrap();
+        let content = "├── café";
+        let key = ResultCache::key(content, &rules_hash(&Policy::default()));
+
+        let mut cache = ResultCache::open(dir.path());
+        assert!(cache.get(&key).is_none());
+        cache.insert(key.clone(), validate_all(content));
+        cache.save().unwrap();
+
+        let cache = ResultCache::open(dir.path());
+        assert_eq!(cache.get(&key), Some(&validate_all(content)));
+    }
+
+    #[test]
+    fn test_clear() {
+        let dir = TempDir::new().unwrap();
+        let cache_dir = dir.path().join("cache");
+        assert_eq!(ResultCache::clear(&cache_dir).unwrap(), 0);
+
+        let mut cache = ResultCache::open(&cache_dir);
+        cache.insert("key".to_string(), validate_all("x"));
+        cache.save().unwrap();
+        assert_eq!(ResultCache::clear(&cache_dir).unwrap(), 1);
+        assert!(!cache_dir.exists());
+        assert!(ResultCache::open(&cache_dir).is_empty());
+    }
+
+    #[test]
+    fn test_corrupt_cache_starts_empty() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(CACHE_FILE_NAME), "not json").unwrap();
+        assert!(ResultCache::open(dir.path()).is_empty());
+    }
+}
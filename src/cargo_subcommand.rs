@@ -0,0 +1,100 @@
+//! Support for running as `cargo markdown-check`.
+//!
+//! Cargo runs `cargo-markdown-check markdown-check [ARGS]` for
+//! `cargo markdown-check [ARGS]`. The wrapper finds the workspace root, lists
+//! the markdown files git tracks there, and hands them to `markdown-checker`
+//! through `--files-from -`.
+
+use crate::git;
+use std::env;
+use std::ffi::OsString;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Name cargo passes as the first argument to the subcommand binary
+pub const SUBCOMMAND_NAME: &str = "markdown-check";
+
+/// Name of the main binary the wrapper delegates to
+pub const CHECKER_BINARY: &str = "markdown-checker";
+
+/// Drop the program name and the subcommand name cargo inserts, keeping the
+/// user's arguments
+pub fn user_args<I>(args: I) -> Vec<OsString>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    let mut args = args.into_iter().skip(1).peekable();
+    if args.peek().is_some_and(|arg| arg == SUBCOMMAND_NAME) {
+        args.next();
+    }
+    args.collect()
+}
+
+/// Root directory of the cargo workspace containing the current directory
+pub fn workspace_root() -> io::Result<PathBuf> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let output = Command::new(cargo)
+        .args(["locate-project", "--workspace", "--message-format", "plain"])
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "cargo locate-project failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let manifest = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    manifest
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| io::Error::other("workspace manifest has no parent directory"))
+}
+
+/// Markdown files tracked by git in the workspace, relative to its root
+pub fn workspace_markdown_files(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let files = git::tracked_files(root)?;
+    Ok(files
+        .into_iter()
+        .map(|path| {
+            path.strip_prefix(root)
+                .map(Path::to_path_buf)
+                .unwrap_or(path)
+        })
+        .collect())
+}
+
+/// The `markdown-checker` binary installed next to this executable, falling
+/// back to looking it up on `PATH`
+pub fn checker_executable() -> PathBuf {
+    env::current_exe()
+        .ok()
+        .and_then(|exe| {
+            let sibling = exe
+                .with_file_name(CHECKER_BINARY)
+                .with_extension(env::consts::EXE_EXTENSION);
+            sibling.is_file().then_some(sibling)
+        })
+        .unwrap_or_else(|| PathBuf::from(CHECKER_BINARY))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn os(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn test_user_args_via_cargo() {
+        let args = os(&["cargo-markdown-check", "markdown-check", "--fix", "-q"]);
+        assert_eq!(user_args(args), os(&["--fix", "-q"]));
+    }
+
+    #[test]
+    fn test_user_args_run_directly() {
+        let args = os(&["cargo-markdown-check", "-q"]);
+        assert_eq!(user_args(args), os(&["-q"]));
+    }
+}
@@ -0,0 +1,210 @@
+//! Character classes for `[characters]` policies.
+//!
+//! An entry names one character (`"—"` or `"U+2014"`), a range
+//! (`"U+0370..U+03FF"`), a Unicode block (`"block:Latin-1 Supplement"`), or a
+//! Unicode script (`"script:Greek"`).
+//!
+//! Classes are also how the built-in character rules define what they flag,
+//! so one engine, [`accepts`], makes every allow/deny decision: a rule's own
+//! classes decide whether it flags a character, and the configured `allow`
+//! and `forbid` classes whether a flagged character is accepted anyway.
+
+use std::fmt;
+use std::str::FromStr;
+use unicode_blocks::{UnicodeBlock, find_unicode_block};
+use unicode_script::{Script, UnicodeScript};
+
+/// A set of characters named in configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharClass {
+    /// An inclusive range; a single character is a range of one
+    Range(char, char),
+    Block(UnicodeBlock),
+    Script(Script),
+}
+
+impl CharClass {
+    pub fn contains(&self, ch: char) -> bool {
+        match self {
+            CharClass::Range(start, end) => (*start..=*end).contains(&ch),
+            CharClass::Block(block) => block.contains(ch),
+            // Script extensions cover characters shared between a few scripts,
+            // such as the Japanese prolonged sound mark; punctuation common to
+            // every script only matches `script:Common`. Extensions contain
+            // Common and Inherited whatever they list, so those two are
+            // compared by script alone
+            CharClass::Script(script) => {
+                let extension = ch.script_extension();
+                if extension.is_common()
+                    || extension.is_inherited()
+                    || matches!(script, Script::Common | Script::Inherited)
+                {
+                    ch.script() == *script
+                } else {
+                    extension.contains_script(*script)
+                }
+            }
+        }
+    }
+}
+
+/// Whether some class in `allow` and none in `forbid` contains `ch`
+pub fn accepts(allow: &[CharClass], forbid: &[CharClass], ch: char) -> bool {
+    allow.iter().any(|class| class.contains(ch)) && !forbid.iter().any(|class| class.contains(ch))
+}
+
+impl From<char> for CharClass {
+    fn from(ch: char) -> Self {
+        CharClass::Range(ch, ch)
+    }
+}
+
+impl FromStr for CharClass {
+    type Err = String;
+
+    fn from_str(entry: &str) -> Result<Self, String> {
+        if let Some(name) = entry.strip_prefix("block:") {
+            return find_block(name.trim())
+                .map(CharClass::Block)
+                .ok_or_else(|| format!("unknown Unicode block '{}'", name.trim()));
+        }
+        if let Some(name) = entry.strip_prefix("script:") {
+            return find_script(name.trim())
+                .map(CharClass::Script)
+                .ok_or_else(|| format!("unknown Unicode script '{}'", name.trim()));
+        }
+        let invalid = || {
+            format!(
+                "invalid character '{}' (use one character, U+XXXX, a range like \
+                 U+0370..U+03FF, block:NAME, or script:NAME)",
+                entry
+            )
+        };
+        match entry.split_once("..") {
+            Some((start, end)) => {
+                let (start, end) = parse_char(start).zip(parse_char(end)).ok_or_else(invalid)?;
+                if start > end {
+                    return Err(format!("empty character range '{}'", entry));
+                }
+                Ok(CharClass::Range(start, end))
+            }
+            None => parse_char(entry).map(CharClass::from).ok_or_else(invalid),
+        }
+    }
+}
+
+impl fmt::Display for CharClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CharClass::Range(start, end) if start == end => {
+                write!(f, "U+{:04X}", *start as u32)
+            }
+            CharClass::Range(start, end) => {
+                write!(f, "U+{:04X}..U+{:04X}", *start as u32, *end as u32)
+            }
+            CharClass::Block(block) => write!(f, "block:{}", block.name()),
+            CharClass::Script(script) => write!(f, "script:{}", script.full_name()),
+        }
+    }
+}
+
+/// A character written as itself or as a `U+XXXX` code point
+fn parse_char(entry: &str) -> Option<char> {
+    let mut chars = entry.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        return Some(ch);
+    }
+    let hex = entry
+        .strip_prefix("U+")
+        .or_else(|| entry.strip_prefix("u+"))?;
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+/// The block named `name`, ignoring case
+fn find_block(name: &str) -> Option<UnicodeBlock> {
+    let mut code = 0u32;
+    while code <= char::MAX as u32 {
+        match char::from_u32(code).and_then(find_unicode_block) {
+            Some(block) if block.name().eq_ignore_ascii_case(name) => return Some(block),
+            Some(block) => code = block.end() + 1,
+            None => code += 1,
+        }
+    }
+    None
+}
+
+/// The script with full name (`Old_Italic`, `old italic`) or ISO 15924 code
+/// (`Grek`) `name`, ignoring case
+fn find_script(name: &str) -> Option<Script> {
+    let title_case = name
+        .split([' ', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join("_");
+    Script::from_full_name(&title_case).or_else(|| Script::from_short_name(&title_case))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(entry: &str) -> CharClass {
+        entry.parse().unwrap()
+    }
+
+    #[test]
+    fn test_single_characters_and_ranges() {
+        assert!(class("\u{2014}").contains('\u{2014}'));
+        assert!(class("U+00A9").contains('\u{a9}'));
+        let greek = class("U+0370..U+03FF");
+        assert!(greek.contains('\u{3b1}'));
+        assert!(!greek.contains('a'));
+        assert!("U+03FF..U+0370".parse::<CharClass>().is_err());
+        assert!("ab".parse::<CharClass>().is_err());
+        assert!("U+D800".parse::<CharClass>().is_err());
+    }
+
+    #[test]
+    fn test_blocks() {
+        let latin1 = class("block:latin-1 supplement");
+        assert!(latin1.contains('\u{e9}'));
+        assert!(!latin1.contains('\u{2014}'));
+        assert_eq!(latin1.to_string(), "block:Latin-1 Supplement");
+        assert!("block:Klingon".parse::<CharClass>().is_err());
+    }
+
+    #[test]
+    fn test_scripts() {
+        let han = class("script:Han");
+        assert!(han.contains('\u{65e5}'));
+        assert!(!han.contains('\u{3b1}'));
+        assert!(class("script:greek").contains('\u{3b1}'));
+        assert!(class("script:Grek").contains('\u{3b1}'));
+        assert!(class("script:katakana").contains('\u{30fc}'));
+        assert!(!class("script:Greek").contains('\u{2014}'));
+        assert!(class("script:Common").contains('\u{2014}'));
+        assert!(!class("script:Common").contains('\u{3b1}'));
+        assert!(!class("script:Inherited").contains('\u{e9}'));
+        assert!("script:Elvish".parse::<CharClass>().is_err());
+    }
+
+    #[test]
+    fn test_accepts() {
+        let allow = [class("script:Latin"), class("script:Common")];
+        let forbid = [class("block:Box Drawing")];
+        assert!(accepts(&allow, &forbid, '\u{e9}'));
+        assert!(accepts(&allow, &forbid, '\u{2014}'));
+        assert!(!accepts(&allow, &forbid, '\u{3b1}'));
+        assert!(!accepts(&allow, &forbid, '\u{251c}'));
+        assert!(!accepts(&[], &[], 'a'));
+    }
+}
@@ -1,10 +1,31 @@
-use clap::Parser;
+use crate::columns::ColumnUnit;
+use crate::config::{ConfigFile, RuleOverride};
+use crate::doc_format::DocFormat;
+use crate::file_ops::{DEFAULT_MAX_FILE_SIZE, FileSize};
+use crate::line_endings::LineEnding;
+use crate::navigation::Site;
+use crate::parallel::default_jobs;
+use crate::path_display::PathStyle;
+use crate::presets::Preset;
+use crate::remote;
+use crate::reporter::{DEFAULT_MAX_DISPLAY, exceeds_error_threshold, exceeds_warning_threshold};
+use crate::style::ColorChoice;
+use clap::parser::ValueSource;
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use std::ffi::OsString;
 use std::path::PathBuf;
 
+/// The files `--rustdoc` checks when `-f` is not given
+pub const RUSTDOC_PATTERN: &str = "src/**/*.rs";
+
 #[derive(Parser, Debug)]
 #[command(name = "markdown-checker")]
 #[command(author = "Michael A Wright")]
 #[command(about = "Validates markdown files for UTF-8, ASCII-subset, and unprintable characters")]
+#[command(args_conflicts_with_subcommands = true)]
 #[command(long_about = "\
 Markdown Checker - Validate and Auto-Fix Markdown Files
 
@@ -33,14 +54,22 @@ VALIDATION RULES:
   Not auto-fixable: Emojis (except common ones), unprintable control characters
 
 USAGE PATTERNS:
+  # Subcommands (flat flags below keep working)
+  markdown-checker check -f \"*.md\"         # Same as: markdown-checker -f \"*.md\"
+  markdown-checker fix -f \"*.md\"           # Same as: markdown-checker -f \"*.md\" --fix
+  markdown-checker fix --dry-run            # Same as: markdown-checker --dry-run
+  markdown-checker fix --check -f \"*.md\"    # List files fixing would change; fail if any
+  markdown-checker --tui -f \"**/*.md\"       # Review and fix violations interactively
+
   # Validate single file
-  markdown-checker                          # Checks ./README.md (or README.org if README.md not found)
+  markdown-checker                          # Checks ./README.md (or README.org, .adoc, .rst if missing)
   markdown-checker -f CONTRIBUTING.md       # Checks ./CONTRIBUTING.md
   markdown-checker -p docs -f overview.md   # Checks docs/overview.md
 
   # Validate multiple files with glob patterns
   markdown-checker -f \"*.md\"              # All .md files in current dir
   markdown-checker -p docs -f \"**/*.md\"   # All .md files in docs/ recursively
+  markdown-checker -f \"**/*\" --max-file-size 50M  # Skip files over 50 MiB (default 10M)
 
   # Auto-fix tree symbols
   markdown-checker --fix                    # Fix violations in README.md
@@ -50,14 +79,134 @@ USAGE PATTERNS:
   markdown-checker --dry-run                # Show what would be fixed
   markdown-checker -n -f \"*.md\"           # Preview fixes for all files
 
+  # Gradual adoption on legacy trees
+  markdown-checker -f \"**/*.md\" --max-errors 250  # Fail only above 250 violations
+  markdown-checker -f \"**/*.md\" --exit-zero       # Report only, never fail the build
+  markdown-checker -f \"**/*.md\" --statistics      # Counts per rule, top files, totals
+  markdown-checker -f \"**/*.md\" --group-by rule   # Every location of each rule together
+  markdown-checker -f \"**/*.md\" --report-failures-only  # Silent on passing files
+  markdown-checker -f \"**/*.md\" --sort location  # One line/column-ordered list per file
+  markdown-checker -f \"**/*.md\" --columns utf16  # Columns as LSP and VS Code count them
+  markdown-checker -f \"**/*.md\" --timings         # Time per validator and slowest files
+  markdown-checker -f \"**/*.md\" --format json     # Per-file results for tooling
+  markdown-checker -f \"**/*.md\" --format sarif    # Upload to GitHub code scanning
+  markdown-checker -f \"**/*.md\" --format junit    # Jenkins/GitLab/Buildkite test reports
+  markdown-checker -f \"**/*.md\" --format compact  # path:line:col: CODE message
+  markdown-checker -f \"**/*.md\" --format markdown # Report for a PR comment
+  markdown-checker -f \"**/*.md\" --format jsonl    # One JSON object per file, streamed
+  markdown-checker fix --dry-run -f \"**/*.md\" --format json  # Fix outcomes as JSON
+  markdown-checker -f \"**/*.md\" --baseline write .markdown-baseline.json
+  markdown-checker -f \"**/*.md\" --baseline check .markdown-baseline.json  # New only
+
+  # Pre-commit hooks: check exactly what is about to be committed
+  markdown-checker --staged                 # Staged documents, index content
+
+  # Pull request CI: ignore pre-existing violations elsewhere
+  markdown-checker --since origin/main                  # Files changed vs the ref
+  markdown-checker --since origin/main --changed-lines  # Only changed lines
+
+  # Explicit file lists, one path per line
+  git diff --name-only -- '*.md' | markdown-checker --files-from -
+  markdown-checker @changed-docs.txt        # Same as --files-from changed-docs.txt
+
+  # Doc sites: pages missing from navigation, dead entries, and broken links
+  markdown-checker --mdbook -p docs/book    # SUMMARY.md and its chapters
+  markdown-checker --mkdocs                 # mkdocs.yml nav and docs_dir
+  markdown-checker --docusaurus -p website  # sidebars.js and docs/
+
+  # Commit messages: comment lines and the --verbose diff are skipped
+  markdown-checker --commit-msg .git/COMMIT_EDITMSG
+  markdown-checker install-hooks --commit-msg  # ...checked by a commit-msg hook
+
+  # Published documents, fetched with curl (check only)
+  markdown-checker https://raw.githubusercontent.com/org/repo/main/README.md
+
+  # Rust doc comments: /// and //! are markdown, the code around them is skipped
+  markdown-checker --rustdoc                # Doc comments in src/**/*.rs
+  markdown-checker --rustdoc --fix -f \"crates/*/src/**/*.rs\"
+
+  # Plain text files: only the character rules, no markdown structure
+  markdown-checker --type text -f LICENSE
+  markdown-checker --type text -f \"examples/*.conf\"
+
+  # Exempt one line: put this comment on the line before it
+  #   <!-- markdown-checker-disable-next-line MC001 -->
+  # Exempt a region (optionally for listed rules only)
+  #   <!-- markdown-checker-disable MC003 --> ... <!-- markdown-checker-enable MC003 -->
+
+  # Damaged UTF-8: list every invalid byte sequence, with byte offsets
+  markdown-checker -f notes.md --lossy
+
+  # Fix without touching modification times (permissions are always kept)
+  markdown-checker --fix -f \"docs/**/*.md\" --keep-mtime
+  markdown-checker --fix -f \"docs/**/*.md\" --eol crlf  # Write fixed files with CRLF
+
+  # Accept any character in fenced code blocks and `inline code`
+  markdown-checker -f \"**/*.md\" --skip-code
+
+  # Accept any character in YAML/TOML frontmatter (author names, titles)
+  markdown-checker -f \"**/*.md\" --skip-frontmatter
+
+  # Skip files listed with gitignore syntax
+  markdown-checker -f \"**/*.md\" --ignore-path .markdownignore
+
+  # Discover rules
+  markdown-checker rules                    # Codes, names, severities, fixability
+  markdown-checker rules --format json
+  markdown-checker explain MC003            # Details, examples, and fix behavior
+
+  # Adopt in a new project
+  markdown-checker init                     # Write a starter .markdown-checker.toml
+  markdown-checker init --hook              # ...and install a git pre-commit hook
+  markdown-checker install-hooks            # Only install the pre-commit hook
+  markdown-checker generate-fixtures demo   # Sample files to try CI and editors on
+
+  # Large trees: skip files unchanged since the last run
+  markdown-checker -f \"**/*.md\" --cache      # Stored in .markdown-checker/cache
+  markdown-checker cache clear              # Delete the cached results
+  markdown-checker -f \"**/*.log.md\" --mmap   # Map huge files instead of copying them
+
+  # Editors
+  markdown-checker serve --lsp              # Language server: diagnostics and quick fixes
+  markdown-checker serve --http :8080       # JSON API: POST /check and /fix
+  markdown-checker serve --mcp              # MCP tools for AI agents
+
+  # Configuration
+  markdown-checker                          # Nearest .markdown-checker.toml files, closest wins
+  markdown-checker --config ci/strict.toml  # Load settings from a specific file
+  markdown-checker --no-config              # Ignore .markdown-checker.toml files
+  markdown-checker --preset portable        # Accept Latin-1; also strict, latin, structure-only
+  markdown-checker --rule ascii=off         # Override [rules] for this run (repeatable)
+  markdown-checker config --validate        # Report unknown keys and invalid values
+  markdown-checker config --schema > markdown-checker.schema.json
+
+  # Shell completions
+  markdown-checker completions bash > ~/.local/share/bash-completion/completions/markdown-checker
+  markdown-checker completions zsh > ~/.zfunc/_markdown-checker
+
+  # Colors (default: auto, disabled by NO_COLOR or when piped)
+  markdown-checker --color always | less -R
+
+  # Paths and clickable locations
+  markdown-checker -f \"**/*.md\" --paths from-root  # Paths relative to the git root
+  markdown-checker -f \"**/*.md\" --hyperlinks       # OSC 8 links in supporting terminals
+
   # Verbose output
   markdown-checker -v                       # Detailed validation progress
   markdown-checker -v --dry-run             # Verbose dry-run mode
+  markdown-checker -q -f \"**/*.md\"          # Failures only, no progress bar
+  markdown-checker --max-display 0          # Show every violation (default: 50 per file)
+  markdown-checker --fail-fast -f \"**/*.md\" # Stop at the first failing file
+  markdown-checker --fast -f \"**/*.md\"      # Stop each file at its first violation
+  markdown-checker --count -f \"**/*.md\"     # Violation counts per rule and file
 
 EXIT CODES:
   0 - Success: All files pass validation (or fixed successfully)
-  1 - Failure: Validation errors found
+  1 - Failure: Validation errors found (more than --max-errors, if given;
+      never with --exit-zero) that need manual fixing, or more warnings
+      than --max-warnings
   2 - Error: File not found, invalid arguments, or other errors
+  3 - Fixable: Validation errors found, but all are auto-fixable (run --fix)
 
 SAFETY:
   • --fix only works if ALL violations are auto-fixable
@@ -71,12 +220,14 @@ AI CODING AGENT INSTRUCTIONS:
 
   1. VALIDATION WORKFLOW:
      - Run: markdown-checker -f <file> -v
-     - Exit code 0 = pass, 1 = violations found, 2 = error
+     - Exit code 0 = pass, 1 = violations found, 2 = error,
+       3 = violations found but all are auto-fixable (run --fix)
      - Check stdout for detailed violation reports with line/column numbers
 
   2. AUTO-FIX WORKFLOW:
      - Always preview first: markdown-checker -f <file> --dry-run
      - If dry-run succeeds, apply fix: markdown-checker -f <file> --fix
+     - Exit code 3 from a check means --fix will resolve every violation
      - If --fix fails with 'non-fixable violations', the file contains:
        * Unknown Unicode characters (rare emojis, CJK chars, etc.)
        * Unprintable control characters
@@ -84,7 +235,7 @@ AI CODING AGENT INSTRUCTIONS:
 
   3. BATCH PROCESSING:
      - Use glob patterns: markdown-checker -f '**/*.md' --fix
-     - The tool processes files sequentially and reports each result
+     - Files are validated in parallel (-j N to limit); output order is sorted and stable
      - Overall exit code reflects success/failure of all files
 
   4. COMMON FIXABLE VIOLATIONS:
@@ -98,11 +249,18 @@ AI CODING AGENT INSTRUCTIONS:
 
   5. ERROR HANDLING:
      - Exit code 2 = file not found, permission denied, or invalid arguments
+     - Exit code 3 = validation failed, but --fix would resolve all violations
      - Exit code 1 = validation failed (violations present)
      - Exit code 0 = success (valid or successfully fixed)
      - Always check stderr for error messages and warnings
 
-  6. BEST PRACTICES:
+  6. MCP SERVER:
+     - Run: markdown-checker serve --mcp (stdio) and call the tools
+       check_markdown and fix_markdown with `content` or `path`
+     - Results are structured JSON: `passed`, and each rule's violations
+       with line, column, and the available fix; no exit codes to parse
+
+  7. BEST PRACTICES:
      - Use --dry-run before --fix to preview changes
      - Use -v for detailed output when debugging
      - For CI/CD: run validation only (no --fix) to enforce standards
@@ -112,50 +270,951 @@ For more information and examples, visit:
 https://github.com/softwarewrighter/markdown-checker
 ")]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+
+    #[command(flatten)]
+    pub args: CheckArgs,
+
+    /// Automatically fix violations where possible (same as the `fix` subcommand)
+    #[arg(long)]
+    pub fix: bool,
+
+    /// Preview fixes without applying them (same as `fix --dry-run`)
+    #[arg(short = 'n', long)]
+    pub dry_run: bool,
+}
+
+/// Options shared by checking and fixing
+#[derive(Args, Debug, Clone)]
+pub struct CheckArgs {
     /// Path to directory containing the file (default: current directory)
     #[arg(short, long, value_name = "PATH", default_value = ".")]
     pub path: PathBuf,
 
-    /// Name of the file to check or glob pattern (default: README.md, falls back to README.org if not found)
+    /// Name of the file to check or glob pattern (default: README.md, falls back to README.org, README.adoc, or README.rst if not found)
     #[arg(short = 'f', long, value_name = "NAME", default_value = "README.md")]
     pub file_name: String,
 
+    /// Fetch and check documents published at these http:// or https:// URLs instead of local files
+    #[arg(
+        value_name = "URL",
+        value_parser = parse_url,
+        conflicts_with_all = ["file_name", "staged", "since", "files_from", "commit_msg", "site", "tui"]
+    )]
+    pub urls: Vec<String>,
+
     /// Enable verbose output
     #[arg(short, long)]
     pub verbose: bool,
 
-    /// Automatically fix violations where possible (tree symbols only)
+    /// Only report failures: hide passing files, the summary, and the progress bar
+    #[arg(short, long, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Print nothing for files that pass, but keep the full report for failures and the summary
     #[arg(long)]
-    pub fix: bool,
+    pub report_failures_only: bool,
+
+    /// Only fail when the total number of violations exceeds N
+    #[arg(long, value_name = "N")]
+    pub max_errors: Option<usize>,
+
+    /// Fail when the total number of warnings exceeds N (warnings never fail the run otherwise)
+    #[arg(long, value_name = "N")]
+    pub max_warnings: Option<usize>,
+
+    /// Limit how deep glob patterns such as `**` descend below PATH (1 = only files directly in PATH)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_depth: Option<u32>,
+
+    /// Skip files larger than SIZE, in bytes or with a K, M, or G suffix (default: 10M)
+    #[arg(long, value_name = "SIZE")]
+    pub max_file_size: Option<FileSize>,
+
+    /// Check files of any size
+    #[arg(long, conflicts_with = "max_file_size")]
+    pub force_large: bool,
+
+    /// Check the staged (index) version of git-staged documents
+    #[arg(long)]
+    pub staged: bool,
+
+    /// Only check documents changed relative to a git ref (e.g. origin/main)
+    #[arg(long, value_name = "REF", conflicts_with = "staged")]
+    pub since: Option<String>,
+
+    /// With --since, only report violations on changed lines
+    #[arg(long, requires = "since")]
+    pub changed_lines: bool,
+
+    /// Check the paths listed in FILE, one per line ("-" reads stdin; `@FILE` is shorthand)
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["staged", "since"])]
+    pub files_from: Option<PathBuf>,
+
+    /// Check a git commit message, as a commit-msg hook does; comment lines and the diff below the scissors line are skipped
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["staged", "since", "files_from", "site", "file_name", "doc_type"]
+    )]
+    pub commit_msg: Option<PathBuf>,
+
+    /// Check the markdown in Rust doc comments (`///` and `//!`) of src/**/*.rs, or of the files given with -f
+    #[arg(
+        long,
+        conflicts_with_all = ["staged", "since", "commit_msg", "site", "doc_type"]
+    )]
+    pub rustdoc: bool,
+
+    /// Check the mdBook at PATH: SUMMARY.md, its chapters, unlisted markdown files, and links between them
+    #[arg(long, group = "site", conflicts_with_all = ["staged", "since", "files_from"])]
+    pub mdbook: bool,
+
+    /// Check the MkDocs site at PATH: mkdocs.yml nav entries, docs missing from nav, and links between them
+    #[arg(long, group = "site", conflicts_with_all = ["staged", "since", "files_from"])]
+    pub mkdocs: bool,
+
+    /// Check the Docusaurus docs at PATH: sidebar entries, docs in no sidebar, and links between them
+    #[arg(long, group = "site", conflicts_with_all = ["staged", "since", "files_from"])]
+    pub docusaurus: bool,
+
+    /// Number of files to validate concurrently (default: CPU count)
+    #[arg(short = 'j', long, value_name = "N")]
+    pub jobs: Option<usize>,
+
+    /// Load settings from this configuration file (default: the nearest .markdown-checker.toml files)
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Do not load any .markdown-checker.toml
+    #[arg(long, conflicts_with = "config")]
+    pub no_config: bool,
+
+    /// Start from a built-in preset; configuration files apply over it
+    #[arg(long, value_enum, value_name = "NAME")]
+    pub preset: Option<Preset>,
+
+    /// Set a rule to error, warning, or off over configuration files (repeatable, e.g. --rule ascii=off)
+    #[arg(long = "rule", value_name = "RULE=SETTING")]
+    pub rules: Vec<RuleOverride>,
+
+    /// Skip files matching patterns in this gitignore-style file (e.g. .markdownignore)
+    #[arg(long, value_name = "FILE")]
+    pub ignore_path: Option<PathBuf>,
+
+    /// When to use colored output (auto honors NO_COLOR and non-terminal output)
+    #[arg(long, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// Exit with code 0 even when violations are found (report-only mode)
+    #[arg(long)]
+    pub exit_zero: bool,
+
+    /// Report format: text, compact (path:line:col lines), json, jsonl, sarif, junit, or markdown
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = OutputFormat::Text,
+          conflicts_with_all = ["statistics", "timings", "tui"])]
+    pub format: OutputFormat,
+
+    /// How to show file paths in reports (default: as found)
+    #[arg(long, value_enum, value_name = "STYLE")]
+    pub paths: Option<PathStyle>,
+
+    /// Make path:line:col locations clickable (OSC 8 hyperlinks) in text output
+    #[arg(long)]
+    pub hyperlinks: bool,
+
+    /// Show at most N violations per file in text output, summarizing the rest (0 shows all)
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_MAX_DISPLAY)]
+    pub max_display: usize,
+
+    /// Group text output per file (default) or per rule across all files
+    #[arg(long, value_enum, value_name = "GROUP", default_value_t = GroupBy::File)]
+    pub group_by: GroupBy,
+
+    /// Count reported columns in graphemes (default for text and markdown), characters (default for other formats), UTF-8 bytes, or UTF-16 code units
+    #[arg(long, value_enum, value_name = "UNIT")]
+    pub columns: Option<ColumnUnit>,
+
+    /// Order each file's violations by rule (default) or merged by line and column
+    #[arg(long, value_enum, value_name = "ORDER", default_value_t = SortOrder::Rule)]
+    pub sort: SortOrder,
+
+    /// Print violation counts per rule, the files with the most violations, and totals
+    #[arg(long)]
+    pub statistics: bool,
+
+    /// Review violations in an interactive terminal UI and apply fixes per item or per rule
+    #[arg(long, conflicts_with_all = ["staged", "changed_lines", "baseline"])]
+    pub tui: bool,
+
+    /// Report how long each validator and file took, and the total run time
+    #[arg(long)]
+    pub timings: bool,
+
+    /// Print only the number of violations of each rule in each file
+    #[arg(long, conflicts_with_all = ["fast", "baseline", "statistics", "timings", "tui"])]
+    pub count: bool,
+
+    /// Stop checking each file at its first violation; enough to pass or fail CI
+    #[arg(long, conflicts_with_all = ["max_errors", "max_warnings", "baseline", "statistics", "tui"])]
+    pub fast: bool,
 
-    /// Preview fixes without applying them (dry-run mode)
+    /// Stop once the run fails (past --max-errors or --max-warnings, if given) and exit with its failure code
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Cache results in .markdown-checker/cache and skip unchanged files on later runs
+    #[arg(long)]
+    pub cache: bool,
+
+    /// Do not read or write the result cache, even if the configuration file enables it
+    #[arg(long, conflicts_with = "cache")]
+    pub no_cache: bool,
+
+    /// Memory-map files instead of copying them into memory, falling back to reading where mapping fails
+    #[arg(long, conflicts_with_all = ["staged", "tui"])]
+    pub mmap: bool,
+
+    /// Accept any character in fenced code blocks and inline code spans
+    #[arg(long)]
+    pub skip_code: bool,
+
+    /// Accept any character in YAML or TOML frontmatter
+    #[arg(long)]
+    pub skip_frontmatter: bool,
+
+    /// Read damaged UTF-8 lossily: report every invalid byte sequence with its byte offset and check the rest of the file
+    #[arg(long)]
+    pub lossy: bool,
+
+    /// When fixing, keep each file's modification time as well as its permissions
+    #[arg(long)]
+    pub keep_mtime: bool,
+
+    /// Write fixed files with these line endings instead of keeping their own
+    #[arg(long, value_name = "EOL")]
+    pub eol: Option<LineEnding>,
+
+    /// Read every file as this type instead of by extension; `text` runs only the character rules
+    #[arg(long = "type", value_name = "TYPE", conflicts_with = "site")]
+    pub doc_type: Option<DocFormat>,
+
+    /// Record current violations (write) or report only violations missing from a baseline (check)
+    #[arg(long, num_args = 2, value_names = ["MODE", "FILE"])]
+    pub baseline: Option<Vec<String>>,
+
+    /// `[files] include` patterns from the configuration file
+    #[arg(skip)]
+    pub include: Vec<String>,
+}
+
+impl CheckArgs {
+    /// The site selected by `--mdbook`, `--mkdocs`, or `--docusaurus`
+    pub fn site(&self) -> Option<Site> {
+        match (self.mdbook, self.mkdocs, self.docusaurus) {
+            (true, _, _) => Some(Site::MdBook),
+            (_, true, _) => Some(Site::MkDocs),
+            (_, _, true) => Some(Site::Docusaurus),
+            _ => None,
+        }
+    }
+
+    /// Parse the `--baseline <MODE> <FILE>` pair
+    pub fn baseline(&self) -> Result<Option<(BaselineMode, PathBuf)>, String> {
+        let Some(values) = &self.baseline else {
+            return Ok(None);
+        };
+        let mode = BaselineMode::from_str(&values[0], true).map_err(|_| {
+            format!(
+                "invalid baseline mode '{}' [possible values: write, check]",
+                values[0]
+            )
+        })?;
+        Ok(Some((mode, PathBuf::from(&values[1]))))
+    }
+
+    /// Whether configuration files are discovered by searching upward from
+    /// each checked file, rather than given with `--config` or disabled
+    pub fn discovers_config(&self) -> bool {
+        self.config.is_none() && !self.no_config
+    }
+
+    /// Take option values from the configuration file wherever the option was
+    /// not given on the command line (`explicit` lists the given argument ids)
+    pub fn apply_config_file(&mut self, file: &ConfigFile, explicit: &[String]) {
+        let given = |id: &str| explicit.iter().any(|e| e == id);
+        fn set<T>(target: &mut T, value: Option<T>, given: bool) {
+            if let (Some(value), false) = (value, given) {
+                *target = value;
+            }
+        }
+
+        let output = &file.output;
+        set(&mut self.format, output.format, given("format"));
+        set(&mut self.group_by, output.group_by, given("group_by"));
+        set(&mut self.sort, output.sort, given("sort"));
+        set(
+            &mut self.columns,
+            output.columns.map(Some),
+            given("columns"),
+        );
+        set(&mut self.eol, file.fix.eol.map(Some), given("eol"));
+        set(
+            &mut self.max_file_size,
+            file.files.max_size.map(Some),
+            given("max_file_size"),
+        );
+        set(
+            &mut self.max_display,
+            output.max_display,
+            given("max_display"),
+        );
+        set(&mut self.hyperlinks, output.hyperlinks, given("hyperlinks"));
+        set(
+            &mut self.report_failures_only,
+            output.report_failures_only,
+            given("report_failures_only"),
+        );
+        set(&mut self.color, output.color, given("color"));
+        if !given("paths") && output.paths.is_some() {
+            self.paths = output.paths;
+        }
+        // Rust sources replace the markdown files the configuration includes
+        if self.rustdoc && !given("file_name") {
+            self.file_name = RUSTDOC_PATTERN.to_string();
+        } else if !given("file_name") && !given("path") {
+            self.include = file.include_patterns();
+        }
+    }
+
+    /// Check option combinations clap cannot express across subcommands
+    pub fn validate(&self, mode: RunMode) -> Result<(), String> {
+        if let (Some((BaselineMode::Write, _)), true) = (self.baseline()?, self.fail_fast) {
+            return Err("--fail-fast cannot be combined with --baseline write".to_string());
+        }
+        // Clap rejects these on the command line; the format may also come
+        // from the configuration file
+        if self.format != OutputFormat::Text {
+            for (set, flag) in [
+                (self.statistics, "--statistics"),
+                (self.timings, "--timings"),
+                (self.tui, "--tui"),
+            ] {
+                if set {
+                    return Err(format!(
+                        "{} only applies to text output, not --format {}",
+                        flag,
+                        self.format.name()
+                    ));
+                }
+            }
+        }
+        if !self.urls.is_empty() && mode != RunMode::Check {
+            return Err("URLs can only be checked; download the file to fix it".to_string());
+        }
+        if self.tui && mode != RunMode::Check {
+            return Err(
+                "--tui applies fixes itself and cannot be combined with fix modes".to_string(),
+            );
+        }
+        if !matches!(
+            self.format,
+            OutputFormat::Text | OutputFormat::Json | OutputFormat::Jsonl
+        ) && mode != RunMode::Check
+        {
+            return Err(format!(
+                "--format {} only applies to checking, not fixing",
+                self.format.name()
+            ));
+        }
+        if self.group_by == GroupBy::Rule {
+            if self.format != OutputFormat::Text {
+                return Err(format!(
+                    "--group-by rule only applies to text output, not --format {}",
+                    self.format.name()
+                ));
+            }
+            if mode != RunMode::Check || self.tui {
+                return Err("--group-by rule only applies to checking".to_string());
+            }
+        }
+        if let Some(columns @ (ColumnUnit::Byte | ColumnUnit::Grapheme)) = self.columns
+            && self.format == OutputFormat::Sarif
+        {
+            return Err(format!(
+                "--format sarif counts columns in characters or UTF-16 code units, not --columns {}",
+                columns.name()
+            ));
+        }
+        if self.sort == SortOrder::Location
+            && (self.format != OutputFormat::Text || self.group_by != GroupBy::File)
+        {
+            return Err("--sort location only applies to text output grouped by file".to_string());
+        }
+        if self.count {
+            if mode != RunMode::Check {
+                return Err("--count only applies to checking".to_string());
+            }
+            if !matches!(self.format, OutputFormat::Text | OutputFormat::Json) {
+                return Err(format!(
+                    "--count prints text or json, not --format {}",
+                    self.format.name()
+                ));
+            }
+        }
+        if self.staged && mode == RunMode::Fix {
+            return Err(
+                "--staged cannot be combined with fixing; staged content is not written back"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Options for the `fix` subcommand
+#[derive(Args, Debug, Clone)]
+pub struct FixArgs {
+    #[command(flatten)]
+    pub args: CheckArgs,
+
+    /// Preview fixes without applying them
     #[arg(short = 'n', long)]
     pub dry_run: bool,
+
+    /// List files that fixing would change and exit non-zero if there are any, without writing
+    #[arg(long, conflicts_with = "dry_run")]
+    pub check: bool,
+}
+
+impl FixArgs {
+    pub fn mode(&self) -> RunMode {
+        if self.check {
+            RunMode::FixCheck
+        } else {
+            RunMode::from_flags(true, self.dry_run)
+        }
+    }
+}
+
+/// What a run does with the files it checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RunMode {
+    /// Report violations
+    #[default]
+    Check,
+    /// Apply fixes in place
+    Fix,
+    /// Show what fixing would change without writing
+    DryRun,
+    /// List files fixing would change, failing if there are any
+    FixCheck,
+}
+
+impl RunMode {
+    /// Mode selected by the legacy `--fix` / `--dry-run` flags
+    pub fn from_flags(fix: bool, dry_run: bool) -> Self {
+        if dry_run {
+            RunMode::DryRun
+        } else if fix {
+            RunMode::Fix
+        } else {
+            RunMode::Check
+        }
+    }
+}
+
+/// What a parsed command line asks for
+#[derive(Debug)]
+pub enum Invocation {
+    /// Check or fix files
+    Run(RunMode, CheckArgs),
+    /// Any other subcommand
+    Command(Commands),
+}
+
+impl Cli {
+    /// Parse arguments, also returning the ids of check options given on the
+    /// command line, so configuration file values fill in only the rest
+    pub fn try_parse_with_sources<I, T>(args: I) -> Result<(Self, Vec<String>), clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let matches = Self::command().try_get_matches_from(args)?;
+        let cli = Self::from_arg_matches(&matches)?;
+        let run_matches = match matches.subcommand() {
+            Some(("check" | "fix", sub)) => sub,
+            _ => &matches,
+        };
+        let explicit = run_matches
+            .ids()
+            .filter(|id| run_matches.value_source(id.as_str()) == Some(ValueSource::CommandLine))
+            .map(|id| id.to_string())
+            .collect();
+        Ok((cli, explicit))
+    }
+
+    /// Whether the run mode was left to the default (no subcommand, `--fix`,
+    /// or `--dry-run`), so the configuration file may choose it
+    pub fn uses_default_mode(&self) -> bool {
+        self.command.is_none() && !self.fix && !self.dry_run
+    }
+
+    /// Resolve legacy flags and subcommands into a single invocation
+    pub fn into_invocation(self) -> Invocation {
+        match self.command {
+            None => Invocation::Run(RunMode::from_flags(self.fix, self.dry_run), self.args),
+            Some(Commands::Check(args)) => Invocation::Run(RunMode::Check, args),
+            Some(Commands::Fix(fix)) => Invocation::Run(fix.mode(), fix.args),
+            Some(command) => Invocation::Command(command),
+        }
+    }
+}
+
+/// Expand `@FILE` arguments to `--files-from FILE`, before clap parsing
+pub fn expand_file_list_args<I>(args: I) -> Vec<OsString>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    let mut expanded = Vec::new();
+    for arg in args {
+        match arg.to_str().and_then(|a| a.strip_prefix('@')) {
+            Some(path) if !path.is_empty() && !expanded.is_empty() => {
+                expanded.push(OsString::from("--files-from"));
+                expanded.push(OsString::from(path));
+            }
+            _ => expanded.push(arg),
+        }
+    }
+    expanded
+}
+
+/// A positional argument, which must be a URL: local files are named with
+/// `-f`
+fn parse_url(arg: &str) -> Result<String, String> {
+    if remote::is_url(arg) {
+        Ok(arg.to_string())
+    } else {
+        Err(format!(
+            "expected an http:// or https:// URL; check local files with -f {}",
+            arg
+        ))
+    }
+}
+
+/// Whether `--baseline` records violations or checks against recorded ones
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaselineMode {
+    Write,
+    Check,
+}
+
+/// Output format for check results
+#[derive(ValueEnum, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    /// Human-readable report
+    #[default]
+    Text,
+    /// JSON array of per-file results with rule codes, spans, and fix availability
+    Json,
+    /// One JSON object per file, printed as soon as the file is done
+    Jsonl,
+    /// SARIF 2.1.0 log for GitHub code scanning and other SARIF consumers
+    Sarif,
+    /// JUnit XML with a test suite per file and a test case per rule
+    Junit,
+    /// One "path:line:col: CODE message" line per violation, for editors and grep
+    Compact,
+    /// ASCII-clean markdown tables of violations, for PR comments and audits
+    Markdown,
+}
+
+impl OutputFormat {
+    /// Name as accepted on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Jsonl => "jsonl",
+            OutputFormat::Sarif => "sarif",
+            OutputFormat::Junit => "junit",
+            OutputFormat::Compact => "compact",
+            OutputFormat::Markdown => "markdown",
+        }
+    }
+
+    /// What columns count without `--columns`: graphemes in reports for
+    /// people, where an emoji or an accented letter written with a combining
+    /// mark is one column, and characters in those for tools
+    pub fn default_columns(self) -> ColumnUnit {
+        match self {
+            OutputFormat::Text | OutputFormat::Markdown => ColumnUnit::Grapheme,
+            _ => ColumnUnit::Char,
+        }
+    }
+}
+
+/// How text output is organized
+#[derive(ValueEnum, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum GroupBy {
+    /// Each file with its violations
+    #[default]
+    File,
+    /// Each rule with every location violating it, across all files
+    Rule,
+}
+
+/// Order of violations within a file in text output
+#[derive(ValueEnum, Deserialize, JsonSchema, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortOrder {
+    /// A section per rule, each listing its violations
+    #[default]
+    Rule,
+    /// All rules' violations in one list, by line and column
+    Location,
+}
+
+/// Output format for informational commands
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InfoFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheAction {
+    /// Delete every cached result
+    Clear,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Commands {
+    /// Validate files and report violations (the default when no subcommand is given)
+    Check(CheckArgs),
+
+    /// Fix auto-fixable violations in place
+    Fix(FixArgs),
+
+    /// Generate shell completion scripts
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+
+    /// List every available rule with its code, severity, and fixability
+    Rules {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = InfoFormat::Text)]
+        format: InfoFormat,
+    },
+
+    /// Explain a rule in detail: what it flags, why, examples, and fix behavior
+    Explain {
+        /// Rule code, key, or name (e.g. MC003 or tree-symbols)
+        rule: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = InfoFormat::Text)]
+        format: InfoFormat,
+    },
+
+    /// Write a commented starter .markdown-checker.toml
+    Init {
+        /// Directory to initialize (default: current directory)
+        #[arg(short, long, value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+
+        /// Also install a git pre-commit hook that checks staged files
+        #[arg(long)]
+        hook: bool,
+
+        /// Overwrite existing files
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Install a git pre-commit hook that checks staged markdown files
+    InstallHooks {
+        /// Directory inside the git repository (default: current directory)
+        #[arg(short, long, value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+
+        /// Also install a commit-msg hook that checks commit messages
+        #[arg(long)]
+        commit_msg: bool,
+
+        /// Overwrite existing hooks
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Write sample files that exercise each rule, to try CI wiring and editor integration
+    GenerateFixtures {
+        /// Directory to write the files into (created if needed)
+        #[arg(value_name = "DIR")]
+        dir: PathBuf,
+
+        /// Overwrite existing files
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Check configuration files for mistakes, or print their JSON Schema
+    #[command(group(clap::ArgGroup::new("action").required(true)))]
+    Config {
+        /// Load the configuration and report unknown keys and invalid values
+        #[arg(long, group = "action")]
+        validate: bool,
+
+        /// Print a JSON Schema of .markdown-checker.toml for editor autocompletion
+        #[arg(long, group = "action")]
+        schema: bool,
+
+        /// File to validate (default: the .markdown-checker.toml files that apply here)
+        #[arg(long, value_name = "FILE", requires = "validate")]
+        config: Option<PathBuf>,
+    },
+
+    /// Run as a server for editors
+    #[command(group(clap::ArgGroup::new("protocol").required(true)))]
+    Serve {
+        /// Speak the Language Server Protocol over stdio: diagnostics and quick fixes
+        #[arg(long, group = "protocol")]
+        lsp: bool,
+
+        /// Speak the Model Context Protocol over stdio: check_markdown and fix_markdown tools
+        #[arg(long, group = "protocol")]
+        mcp: bool,
+
+        /// Serve POST /check and /fix over HTTP on ADDR, e.g. :8080 or 127.0.0.1:8080
+        #[arg(long, value_name = "ADDR", group = "protocol")]
+        http: Option<String>,
+    },
+
+    /// Manage the result cache kept by --cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// Print version and build information
+    Version {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = InfoFormat::Text)]
+        format: InfoFormat,
+    },
 }
 
 #[derive(Debug, Clone)]
 pub struct Config {
     pub path: PathBuf,
     pub filename: String,
+    /// Glob patterns from the configuration file, checked instead of `filename`
+    pub include: Vec<String>,
     pub verbose: bool,
-    pub fix: bool,
-    pub dry_run: bool,
+    pub quiet: bool,
+    /// Print nothing for passing files
+    pub failures_only: bool,
+    pub mode: RunMode,
+    pub max_errors: Option<usize>,
+    pub max_warnings: Option<usize>,
+    pub staged: bool,
+    pub since: Option<String>,
+    pub changed_lines: bool,
+    pub files_from: Option<PathBuf>,
+    /// Commit message file checked instead of any other files
+    pub commit_msg: Option<PathBuf>,
+    /// Documents fetched and checked instead of local files
+    pub urls: Vec<String>,
+    /// Check the site at `path` and its navigation
+    pub site: Option<Site>,
+    pub max_depth: Option<usize>,
+    /// Files larger than this are skipped; `None` checks files of any size
+    pub max_file_size: Option<FileSize>,
+    pub jobs: usize,
+    /// Rule codes turned off by configuration
+    pub disabled_rules: Vec<&'static str>,
+    pub file_config: ConfigFile,
+    /// Whether to emit ANSI colors
+    pub color: bool,
+    pub exit_zero: bool,
+    pub format: OutputFormat,
+    pub group_by: GroupBy,
+    pub sort: SortOrder,
+    pub columns: ColumnUnit,
+    /// Most violations shown per file; `None` shows all
+    pub max_display: Option<usize>,
+    pub paths: Option<PathStyle>,
+    pub hyperlinks: bool,
+    pub fail_fast: bool,
+    /// Stop checking each file at its first violation
+    pub fast: bool,
+    /// Print violation counts instead of violations
+    pub count: bool,
+    pub statistics: bool,
+    pub timings: bool,
+    pub tui: bool,
+    pub baseline: Option<(BaselineMode, PathBuf)>,
+    pub cache: bool,
+    /// Memory-map files that are only read
+    pub mmap: bool,
+    /// Exempt code regions in every file, whatever the configuration says
+    pub skip_code: bool,
+    /// Exempt frontmatter in every file, whatever the configuration says
+    pub skip_frontmatter: bool,
+    /// Read files that are not UTF-8 and cannot be converted lossily
+    pub lossy: bool,
+    /// Restore the modification time of fixed files
+    pub keep_mtime: bool,
+    /// Line endings of fixed files, when not kept as they were
+    pub eol: Option<LineEnding>,
+    /// Format every file is read as, whatever its extension
+    pub doc_type: Option<DocFormat>,
+    /// `--rule` settings, applied over every file's configuration
+    pub rule_overrides: Vec<RuleOverride>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("."),
+            filename: "README.md".to_string(),
+            include: Vec::new(),
+            verbose: false,
+            quiet: false,
+            failures_only: false,
+            mode: RunMode::Check,
+            max_errors: None,
+            max_warnings: None,
+            staged: false,
+            since: None,
+            changed_lines: false,
+            files_from: None,
+            commit_msg: None,
+            urls: Vec::new(),
+            site: None,
+            max_depth: None,
+            max_file_size: Some(DEFAULT_MAX_FILE_SIZE),
+            jobs: default_jobs(),
+            disabled_rules: Vec::new(),
+            file_config: ConfigFile::default(),
+            color: false,
+            exit_zero: false,
+            format: OutputFormat::Text,
+            group_by: GroupBy::File,
+            sort: SortOrder::Rule,
+            columns: ColumnUnit::Char,
+            max_display: Some(DEFAULT_MAX_DISPLAY),
+            paths: None,
+            hyperlinks: false,
+            fail_fast: false,
+            fast: false,
+            count: false,
+            statistics: false,
+            timings: false,
+            tui: false,
+            baseline: None,
+            cache: false,
+            mmap: false,
+            skip_code: false,
+            skip_frontmatter: false,
+            lossy: false,
+            keep_mtime: false,
+            eol: None,
+            doc_type: None,
+            rule_overrides: Vec::new(),
+        }
+    }
 }
 
 impl Config {
-    pub fn from_cli(cli: Cli) -> Self {
+    /// Build the runtime configuration; command-line flags take precedence over
+    /// values from the configuration file
+    pub fn from_args(mode: RunMode, cli: CheckArgs, file_config: ConfigFile) -> Self {
+        let baseline = cli.baseline().ok().flatten();
+        let site = cli.site();
         Self {
             path: cli.path,
             filename: cli.file_name,
+            include: cli.include,
             verbose: cli.verbose,
-            fix: cli.fix,
-            dry_run: cli.dry_run,
+            quiet: cli.quiet,
+            failures_only: cli.report_failures_only,
+            mode,
+            max_errors: cli.max_errors.or(file_config.max_errors),
+            max_warnings: cli.max_warnings.or(file_config.max_warnings),
+            staged: cli.staged,
+            since: cli.since,
+            changed_lines: cli.changed_lines,
+            files_from: cli.files_from,
+            commit_msg: cli.commit_msg.clone(),
+            urls: cli.urls,
+            site,
+            max_depth: cli.max_depth.map(|n| n as usize),
+            max_file_size: (!cli.force_large)
+                .then(|| cli.max_file_size.unwrap_or(DEFAULT_MAX_FILE_SIZE)),
+            jobs: cli
+                .jobs
+                .or(file_config.jobs)
+                .unwrap_or_else(default_jobs)
+                .max(1),
+            disabled_rules: file_config.disabled_rules(),
+            color: cli.color.should_color(),
+            exit_zero: cli.exit_zero,
+            format: cli.format,
+            group_by: cli.group_by,
+            sort: cli.sort,
+            columns: cli.columns.unwrap_or(cli.format.default_columns()),
+            max_display: (cli.max_display > 0).then_some(cli.max_display),
+            paths: cli.paths,
+            hyperlinks: cli.hyperlinks,
+            fail_fast: cli.fail_fast,
+            fast: cli.fast,
+            count: cli.count,
+            statistics: cli.statistics,
+            timings: cli.timings,
+            tui: cli.tui,
+            baseline,
+            cache: cli.cache || (!cli.no_cache && file_config.cache.unwrap_or(false)),
+            mmap: cli.mmap,
+            skip_code: cli.skip_code,
+            skip_frontmatter: cli.skip_frontmatter,
+            lossy: cli.lossy,
+            keep_mtime: cli.keep_mtime,
+            eol: cli.eol,
+            doc_type: match (&cli.commit_msg, cli.rustdoc) {
+                (Some(_), _) => Some(DocFormat::CommitMessage),
+                (None, true) => Some(DocFormat::Rustdoc),
+                (None, false) => cli.doc_type,
+            },
+            rule_overrides: cli.rules,
+            // Moved last so the fields above can read from it
+            file_config,
         }
     }
 
     pub fn file_path(&self) -> PathBuf {
         self.path.join(&self.filename)
     }
+
+    /// Whether fixes are computed (applied or previewed)
+    pub fn is_fixing(&self) -> bool {
+        self.mode != RunMode::Check
+    }
+
+    /// Whether error and warning totals fail the run, given `--max-errors`
+    /// and `--max-warnings`
+    pub fn exceeds_thresholds(&self, errors: usize, warnings: usize) -> bool {
+        exceeds_error_threshold(errors, self.max_errors)
+            || exceeds_warning_threshold(warnings, self.max_warnings)
+    }
 }
 
 #[cfg(test)]
@@ -167,21 +1226,384 @@ mod tests {
         let config = Config {
             path: PathBuf::from("/tmp"),
             filename: "test.md".to_string(),
-            verbose: false,
-            fix: false,
-            dry_run: false,
+            ..Config::default()
         };
         assert_eq!(config.file_path(), PathBuf::from("/tmp/test.md"));
     }
 
+    #[test]
+    fn test_parse_completions_subcommand() {
+        let cli = Cli::parse_from(["markdown-checker", "completions", "zsh"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Completions { shell: Shell::Zsh })
+        ));
+    }
+
+    #[test]
+    fn test_cache_controls() {
+        let cli = Cli::parse_from(["markdown-checker", "cache", "clear"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Cache {
+                action: CacheAction::Clear
+            })
+        ));
+
+        let file_config: ConfigFile = toml::from_str("cache = true").unwrap();
+        let config = |args: &[&str]| {
+            let cli =
+                Cli::parse_from(std::iter::once("markdown-checker").chain(args.iter().copied()));
+            Config::from_args(RunMode::Check, cli.args, file_config.clone())
+        };
+        assert!(config(&[]).cache);
+        assert!(!config(&["--no-cache"]).cache);
+        assert!(Cli::try_parse_from(["markdown-checker", "--cache", "--no-cache"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_version_subcommand_json() {
+        let cli = Cli::parse_from(["markdown-checker", "version", "--format", "json"]);
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Version {
+                format: InfoFormat::Json
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_without_subcommand() {
+        let cli = Cli::parse_from(["markdown-checker", "-f", "docs.md"]);
+        assert!(cli.command.is_none());
+        assert_eq!(cli.args.file_name, "docs.md");
+    }
+
+    fn run(args: &[&str]) -> (RunMode, CheckArgs) {
+        match Cli::parse_from(args).into_invocation() {
+            Invocation::Run(mode, args) => (mode, args),
+            Invocation::Command(command) => panic!("unexpected command {:?}", command),
+        }
+    }
+
+    #[test]
+    fn test_legacy_flags_select_run_mode() {
+        assert_eq!(run(&["markdown-checker", "--fix"]).0, RunMode::Fix);
+        assert_eq!(
+            run(&["markdown-checker", "--fix", "--dry-run"]).0,
+            RunMode::DryRun
+        );
+        assert_eq!(run(&["markdown-checker"]).0, RunMode::Check);
+    }
+
+    #[test]
+    fn test_check_and_fix_subcommands() {
+        let (mode, args) = run(&["markdown-checker", "check", "-f", "*.md"]);
+        assert_eq!(mode, RunMode::Check);
+        assert_eq!(args.file_name, "*.md");
+
+        let (mode, args) = run(&["markdown-checker", "fix", "-n", "-p", "docs"]);
+        assert_eq!(mode, RunMode::DryRun);
+        assert_eq!(args.path, PathBuf::from("docs"));
+    }
+
+    #[test]
+    fn test_fix_check_mode() {
+        assert_eq!(
+            run(&["markdown-checker", "fix", "--check"]).0,
+            RunMode::FixCheck
+        );
+        assert!(Cli::try_parse_from(["markdown-checker", "fix", "--check", "-n"]).is_err());
+        assert!(Cli::try_parse_from(["markdown-checker", "--check"]).is_err());
+    }
+
+    #[test]
+    fn test_flat_flags_conflict_with_subcommands() {
+        assert!(Cli::try_parse_from(["markdown-checker", "-v", "check"]).is_err());
+    }
+
+    #[test]
+    fn test_expand_file_list_args() {
+        let args = ["markdown-checker", "-q", "@changed.txt", "@"].map(OsString::from);
+        assert_eq!(
+            expand_file_list_args(args),
+            ["markdown-checker", "-q", "--files-from", "changed.txt", "@"].map(OsString::from)
+        );
+
+        let args = ["markdown-checker", "@changed.txt"].map(OsString::from);
+        let cli = Cli::parse_from(expand_file_list_args(args));
+        assert_eq!(cli.args.files_from, Some(PathBuf::from("changed.txt")));
+    }
+
+    #[test]
+    fn test_fail_fast_conflicts() {
+        let (mode, args) = run(&[
+            "markdown-checker",
+            "--fail-fast",
+            "--baseline",
+            "write",
+            "b.json",
+        ]);
+        assert!(args.validate(mode).is_err());
+
+        let (mode, args) = run(&[
+            "markdown-checker",
+            "--fail-fast",
+            "--baseline",
+            "check",
+            "b.json",
+        ]);
+        assert!(args.validate(mode).is_ok());
+    }
+
+    #[test]
+    fn test_fail_fast_with_thresholds() {
+        let cli = Cli::parse_from(["markdown-checker", "--fail-fast", "--max-errors", "3"]);
+        let config = Config::from_args(RunMode::Check, cli.args, ConfigFile::default());
+        assert!(config.fail_fast);
+        // Violations within the threshold do not stop the run
+        assert!(!config.exceeds_thresholds(3, 100));
+        assert!(config.exceeds_thresholds(4, 0));
+
+        let cli = Cli::parse_from(["markdown-checker", "--fail-fast", "--max-warnings", "2"]);
+        let config = Config::from_args(RunMode::Check, cli.args, ConfigFile::default());
+        assert!(config.exceeds_thresholds(1, 0));
+        assert!(!config.exceeds_thresholds(0, 2));
+        assert!(config.exceeds_thresholds(0, 3));
+    }
+
+    #[test]
+    fn test_staged_rejected_when_fixing() {
+        let (mode, args) = run(&["markdown-checker", "fix", "--staged"]);
+        assert!(args.validate(mode).is_err());
+        assert!(args.validate(RunMode::DryRun).is_ok());
+    }
+
+    #[test]
+    fn test_group_by_rule() {
+        let (mode, args) = run(&["markdown-checker", "--group-by", "rule"]);
+        assert_eq!(args.group_by, GroupBy::Rule);
+        assert!(args.validate(mode).is_ok());
+
+        let (mode, args) = run(&["markdown-checker", "--group-by", "rule", "--format", "json"]);
+        assert!(args.validate(mode).is_err());
+        let (mode, args) = run(&["markdown-checker", "--group-by", "rule", "--fix"]);
+        assert!(args.validate(mode).is_err());
+    }
+
+    #[test]
+    fn test_sort_by_location() {
+        let (mode, args) = run(&["markdown-checker", "--sort", "location"]);
+        assert_eq!(args.sort, SortOrder::Location);
+        assert!(args.validate(mode).is_ok());
+        assert_eq!(run(&["markdown-checker"]).1.sort, SortOrder::Rule);
+
+        let (mode, args) = run(&[
+            "markdown-checker",
+            "--sort",
+            "location",
+            "--group-by",
+            "rule",
+        ]);
+        assert!(args.validate(mode).is_err());
+        let (mode, args) = run(&["markdown-checker", "--sort", "location", "--format", "json"]);
+        assert!(args.validate(mode).is_err());
+    }
+
+    #[test]
+    fn test_column_units() {
+        let (mode, args) = run(&["markdown-checker", "--columns", "utf16"]);
+        assert_eq!(args.columns, Some(ColumnUnit::Utf16));
+        assert!(args.validate(mode).is_ok());
+        let columns = |args: &[&str]| {
+            let (mode, args) = run(args);
+            Config::from_args(mode, args, ConfigFile::default()).columns
+        };
+        assert_eq!(columns(&["markdown-checker"]), ColumnUnit::Grapheme);
+        assert_eq!(
+            columns(&["markdown-checker", "--format", "compact"]),
+            ColumnUnit::Char
+        );
+        assert_eq!(
+            columns(&["markdown-checker", "--columns", "char"]),
+            ColumnUnit::Char
+        );
+        let (mode, args) = run(&["markdown-checker", "--format", "sarif"]);
+        assert!(args.validate(mode).is_ok());
+
+        let (mode, args) = run(&[
+            "markdown-checker",
+            "--format",
+            "sarif",
+            "--columns",
+            "utf16",
+        ]);
+        assert!(args.validate(mode).is_ok());
+        let (mode, args) = run(&["markdown-checker", "--format", "sarif", "--columns", "byte"]);
+        assert!(args.validate(mode).is_err());
+    }
+
+    #[test]
+    fn test_output_format() {
+        let (mode, args) = run(&["markdown-checker", "--format", "json"]);
+        assert_eq!(args.format, OutputFormat::Json);
+        assert!(args.validate(mode).is_ok());
+
+        let (mode, args) = run(&["markdown-checker", "fix", "--format", "json"]);
+        assert!(args.validate(mode).is_ok());
+        let (mode, args) = run(&["markdown-checker", "fix", "--format", "jsonl"]);
+        assert!(args.validate(mode).is_ok());
+        let (mode, args) = run(&["markdown-checker", "fix", "--format", "sarif"]);
+        assert!(args.validate(mode).is_err());
+        assert!(
+            Cli::try_parse_from(["markdown-checker", "--format", "json", "--statistics"]).is_err()
+        );
+        assert_eq!(run(&["markdown-checker"]).1.format, OutputFormat::Text);
+    }
+
+    #[test]
+    fn test_cli_flags_override_config_file() {
+        let file_config =
+            ConfigFile::parse("max-errors = 10\nmax-warnings = 20\njobs = 3\n").unwrap();
+        let cli = Cli::parse_from(["markdown-checker", "--max-errors", "5"]);
+        let config = Config::from_args(RunMode::Check, cli.args, file_config);
+        assert_eq!(config.max_errors, Some(5));
+        assert_eq!(config.max_warnings, Some(20));
+        assert_eq!(config.jobs, 3);
+
+        let file_config = ConfigFile::parse("max-warnings = 20\n").unwrap();
+        let cli = Cli::parse_from(["markdown-checker", "--max-warnings", "0"]);
+        let config = Config::from_args(RunMode::Check, cli.args, file_config);
+        assert_eq!(config.max_warnings, Some(0));
+    }
+
+    #[test]
+    fn test_config_file_fills_options_not_given() {
+        let file_config = ConfigFile::parse(
+            "[output]\nformat = \"compact\"\nmax-display = 5\nhyperlinks = true\n\n\
+             [files]\ninclude = [\"docs/*.md\"]\n",
+        )
+        .unwrap();
+
+        let args = ["markdown-checker", "fix", "-n", "--max-display", "0"];
+        let (cli, explicit) = Cli::try_parse_with_sources(args).unwrap();
+        assert!(!cli.uses_default_mode());
+        let Invocation::Run(_, mut args) = cli.into_invocation() else {
+            panic!("expected a run");
+        };
+        args.apply_config_file(&file_config, &explicit);
+        assert_eq!(args.format, OutputFormat::Compact);
+        assert_eq!(args.max_display, 0);
+        assert!(args.hyperlinks);
+        assert_eq!(args.include, vec!["docs/*.md"]);
+
+        let args = ["markdown-checker", "-f", "x.md", "--format", "text"];
+        let (cli, explicit) = Cli::try_parse_with_sources(args).unwrap();
+        assert!(cli.uses_default_mode());
+        let mut args = cli.args;
+        args.apply_config_file(&file_config, &explicit);
+        assert_eq!(args.format, OutputFormat::Text);
+        assert!(args.include.is_empty());
+    }
+
+    #[test]
+    fn test_config_file_format_conflicts() {
+        let file_config = ConfigFile::parse("[output]\nformat = \"json\"\n").unwrap();
+        let (mode, mut args) = run(&["markdown-checker", "--statistics"]);
+        args.apply_config_file(&file_config, &["statistics".to_string()]);
+        assert!(args.validate(mode).is_err());
+        assert!(Cli::try_parse_from(["markdown-checker", "--no-config", "--config", "a"]).is_err());
+    }
+
+    #[test]
+    fn test_config_file_disables_rules() {
+        let file_config = ConfigFile::parse("[rules]\ntree-symbols = \"off\"\n").unwrap();
+        let cli = Cli::parse_from(["markdown-checker"]);
+        let config = Config::from_args(RunMode::Check, cli.args, file_config);
+        assert_eq!(config.disabled_rules, vec!["MC003"]);
+    }
+
+    #[test]
+    fn test_parse_baseline() {
+        let cli = Cli::parse_from(["markdown-checker", "--baseline", "check", "base.json"]);
+        assert_eq!(
+            cli.args.baseline().unwrap(),
+            Some((BaselineMode::Check, PathBuf::from("base.json")))
+        );
+
+        let cli = Cli::parse_from(["markdown-checker", "--baseline", "bogus", "base.json"]);
+        assert!(cli.args.baseline().is_err());
+    }
+
+    #[test]
+    fn test_parse_urls() {
+        let url = "https://example.com/README.md";
+        let cli = Cli::parse_from(["markdown-checker", url]);
+        assert_eq!(cli.args.urls, vec![url.to_string()]);
+        assert!(cli.args.validate(RunMode::Check).is_ok());
+        assert!(cli.args.validate(RunMode::Fix).is_err());
+
+        assert!(Cli::try_parse_from(["markdown-checker", "README.md"]).is_err());
+        assert!(Cli::try_parse_from(["markdown-checker", "-f", "a.md", url]).is_err());
+    }
+
+    #[test]
+    fn test_rustdoc_replaces_default_files() {
+        let file = ConfigFile::parse("[files]\ninclude = [\"docs/**/*.md\"]\n").unwrap();
+        let (cli, explicit) =
+            Cli::try_parse_with_sources(["markdown-checker", "--rustdoc"]).unwrap();
+        let mut args = cli.args;
+        args.apply_config_file(&file, &explicit);
+        assert_eq!(args.file_name, RUSTDOC_PATTERN);
+        assert!(args.include.is_empty());
+
+        let (cli, explicit) =
+            Cli::try_parse_with_sources(["markdown-checker", "--rustdoc", "-f", "lib.rs"]).unwrap();
+        let mut args = cli.args;
+        args.apply_config_file(&file, &explicit);
+        assert_eq!(args.file_name, "lib.rs");
+        let config = Config::from_args(RunMode::Check, args, file);
+        assert_eq!(config.doc_type, Some(DocFormat::Rustdoc));
+    }
+
+    #[test]
+    fn test_max_file_size() {
+        let max_file_size = |args: &[&str], file: &str| {
+            let (cli, explicit) = Cli::try_parse_with_sources(args).unwrap();
+            let file = ConfigFile::parse(file).unwrap();
+            let mut args = cli.args;
+            args.apply_config_file(&file, &explicit);
+            Config::from_args(RunMode::Check, args, file).max_file_size
+        };
+        assert_eq!(
+            max_file_size(&["markdown-checker"], ""),
+            Some(DEFAULT_MAX_FILE_SIZE)
+        );
+        let file = "[files]\nmax-size = \"1M\"\n";
+        assert_eq!(
+            max_file_size(&["markdown-checker"], file),
+            Some(FileSize(1 << 20))
+        );
+        assert_eq!(
+            max_file_size(&["markdown-checker", "--max-file-size", "64K"], file),
+            Some(FileSize(64 << 10))
+        );
+        assert_eq!(
+            max_file_size(&["markdown-checker", "--force-large"], file),
+            None
+        );
+        assert!(
+            Cli::try_parse_from(["markdown-checker", "--max-file-size", "1M", "--force-large"])
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_config_file_path_current_dir() {
         let config = Config {
             path: PathBuf::from("."),
             filename: "README.md".to_string(),
-            verbose: false,
-            fix: false,
-            dry_run: false,
+            ..Config::default()
         };
         assert_eq!(config.file_path(), PathBuf::from("./README.md"));
     }
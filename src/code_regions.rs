@@ -0,0 +1,370 @@
+//! Code regions of a markdown file: fenced code blocks and inline code spans.
+//!
+//! Program output and string literals legitimately contain characters the
+//! rules flag, so `skip-code` exempts these regions. Spans are matched within
+//! a line; a fence left open runs to the end of the file, as in CommonMark.
+//!
+//! AsciiDoc code is its listing, literal, and passthrough blocks (`----`,
+//! `....`, `++++`) and backtick monospace. In reStructuredText it is literal
+//! blocks, the indented lines after a paragraph ending in `::` or a `code`
+//! directive, and ``double backtick`` literals. Rust doc comments are read as
+//! markdown, after their `///` or `//!`.
+
+use crate::doc_format::DocFormat;
+use crate::rustdoc;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+/// Lines and columns covered by code
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodeRegions {
+    /// 1-based line ranges of fenced blocks, including their fences
+    blocks: Vec<RangeInclusive<usize>>,
+    /// Column ranges of inline code spans, by 1-based line number
+    spans: BTreeMap<usize, Vec<RangeInclusive<usize>>>,
+}
+
+/// An opening fence: its character and length
+pub(crate) type Fence = (char, usize);
+
+impl CodeRegions {
+    /// Find the code regions in markdown `content`
+    pub fn parse(content: &str) -> Self {
+        Self::parse_as(content, DocFormat::Markdown)
+    }
+
+    /// Find the code regions in `content` written in `format`
+    pub fn parse_as(content: &str, format: DocFormat) -> Self {
+        let mut parser = CodeRegionsParser::new(format);
+        for line in content.lines() {
+            parser.push_line(line);
+        }
+        parser.finish()
+    }
+
+    /// Treat these whole lines as code too
+    pub fn insert_lines(&mut self, lines: RangeInclusive<usize>) {
+        self.blocks.push(lines);
+    }
+
+    /// Treat these columns of `line` as code too
+    pub fn insert_span(&mut self, line: usize, columns: RangeInclusive<usize>) {
+        self.spans.entry(line).or_default().push(columns);
+    }
+
+    /// Add the regions of `other`
+    pub fn merge(&mut self, other: CodeRegions) {
+        self.blocks.extend(other.blocks);
+        for (line, spans) in other.spans {
+            self.spans.entry(line).or_default().extend(spans);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty() && self.spans.is_empty()
+    }
+
+    /// Whether the character at 1-based `line` and `column` is code. Without
+    /// a column, whether the whole line is.
+    pub fn contains(&self, line: usize, column: Option<usize>) -> bool {
+        if self.blocks.iter().any(|block| block.contains(&line)) {
+            return true;
+        }
+        match column {
+            Some(column) => self
+                .spans
+                .get(&line)
+                .is_some_and(|spans| spans.iter().any(|span| span.contains(&column))),
+            None => false,
+        }
+    }
+}
+
+/// Builds [`CodeRegions`] one line at a time, so a file can be read as a
+/// stream
+#[derive(Debug, Default)]
+pub struct CodeRegionsParser {
+    format: DocFormat,
+    regions: CodeRegions,
+    /// The open fence and the line it is on
+    open: Option<(Fence, usize)>,
+    /// The rST literal block being read
+    literal: Option<Literal>,
+    line_number: usize,
+}
+
+/// An rST literal block: the indentation of the line introducing it, and the
+/// first and last of its indented lines seen so far
+#[derive(Debug, Clone, Copy)]
+struct Literal {
+    indent: usize,
+    lines: Option<(usize, usize)>,
+}
+
+impl CodeRegionsParser {
+    pub fn new(format: DocFormat) -> Self {
+        Self {
+            format,
+            ..Self::default()
+        }
+    }
+
+    /// Add the next line, without its line ending
+    pub fn push_line(&mut self, line: &str) {
+        self.line_number += 1;
+        match self.format {
+            DocFormat::Markdown | DocFormat::Mdx => self.push_markdown(line, 0),
+            // Code outside doc comments is exempt as a whole (see `rustdoc`)
+            DocFormat::Rustdoc => {
+                if let Some((offset, text)) = rustdoc::doc_text(line) {
+                    self.push_markdown(text, offset);
+                }
+            }
+            DocFormat::AsciiDoc => self.push_asciidoc(line),
+            DocFormat::Rst => self.push_rst(line),
+            // Plain text has no code to tell apart
+            DocFormat::Text | DocFormat::CommitMessage => {}
+        }
+    }
+
+    /// Read `line` as markdown, `offset` characters into the source line
+    fn push_markdown(&mut self, line: &str, offset: usize) {
+        let line_number = self.line_number;
+        match (self.open, fence(line)) {
+            (Some((opening, start)), Some((ch, len, rest)))
+                if ch == opening.0 && len >= opening.1 && rest.trim().is_empty() =>
+            {
+                self.regions.blocks.push(start..=line_number);
+                self.open = None;
+            }
+            (Some(_), _) => {}
+            // Backtick fences cannot have backticks in their info string
+            (None, Some((ch, len, rest))) if ch == '~' || !rest.contains('`') => {
+                self.open = Some(((ch, len), line_number));
+            }
+            (None, _) => {
+                let spans: Vec<_> = code_spans(line)
+                    .into_iter()
+                    .map(|span| span.start() + offset..=span.end() + offset)
+                    .collect();
+                if !spans.is_empty() {
+                    self.regions.spans.insert(line_number, spans);
+                }
+            }
+        }
+    }
+
+    fn push_asciidoc(&mut self, line: &str) {
+        let line_number = self.line_number;
+        match (self.open, asciidoc_delimiter(line)) {
+            (Some((opening, start)), Some(delimiter))
+                if delimiter.0 == opening.0
+                    && (delimiter == opening || (opening.0 == '`' && delimiter.1 >= opening.1)) =>
+            {
+                self.regions.blocks.push(start..=line_number);
+                self.open = None;
+            }
+            (Some(_), _) => {}
+            (None, Some(delimiter)) => self.open = Some((delimiter, line_number)),
+            (None, None) => {
+                let spans = code_spans(line);
+                if !spans.is_empty() {
+                    self.regions.spans.insert(line_number, spans);
+                }
+            }
+        }
+    }
+
+    fn push_rst(&mut self, line: &str) {
+        let line_number = self.line_number;
+        let indent = line.len() - line.trim_start().len();
+        let blank = line.trim().is_empty();
+        if let Some(literal) = self.literal.as_mut() {
+            if blank {
+                return;
+            }
+            if indent > literal.indent {
+                let start = literal.lines.map_or(line_number, |(start, _)| start);
+                literal.lines = Some((start, line_number));
+                return;
+            }
+            self.end_literal();
+        }
+        if opens_literal(line) {
+            self.literal = Some(Literal {
+                indent,
+                lines: None,
+            });
+        }
+        // Only double backticks mark literals; single ones are roles and
+        // interpreted text
+        let spans: Vec<_> = code_spans(line)
+            .into_iter()
+            .filter(|span| line.chars().nth(*span.start()) == Some('`'))
+            .collect();
+        if !spans.is_empty() {
+            self.regions.spans.insert(line_number, spans);
+        }
+    }
+
+    fn end_literal(&mut self) {
+        if let Some(Literal {
+            lines: Some((start, end)),
+            ..
+        }) = self.literal.take()
+        {
+            self.regions.blocks.push(start..=end);
+        }
+    }
+
+    /// The regions found; a fence left open runs to the end of the file
+    pub fn finish(mut self) -> CodeRegions {
+        if let Some((_, start)) = self.open {
+            self.regions.blocks.push(start..=self.line_number);
+        }
+        self.end_literal();
+        self.regions
+    }
+}
+
+/// The delimiter of an AsciiDoc listing, literal, or passthrough block, or a
+/// markdown-style backtick fence, which AsciiDoc also accepts
+fn asciidoc_delimiter(line: &str) -> Option<Fence> {
+    let line = line.trim_end();
+    let ch = line.chars().next()?;
+    let len = line.len() - line.trim_start_matches(ch).len();
+    match ch {
+        '-' | '.' | '+' if len == line.len() && len >= 4 => Some((ch, len)),
+        '`' if len >= 3 && !line[len..].contains('`') => Some((ch, len)),
+        _ => None,
+    }
+}
+
+/// Whether the indented lines after `line` are an rST literal block: a
+/// paragraph ending in `::` or a `code` directive. Other directives, such as
+/// `.. note::`, hold prose.
+fn opens_literal(line: &str) -> bool {
+    let line = line.trim();
+    match line.strip_prefix(".. ") {
+        Some(directive) => ["code::", "code-block::", "sourcecode::"]
+            .iter()
+            .any(|name| directive.starts_with(name)),
+        None => line.ends_with("::"),
+    }
+}
+
+/// Fence character, length, and the text after it, if `line` is a fence
+pub(crate) fn fence(line: &str) -> Option<(char, usize, &str)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = &line[indent..];
+    let ch = rest.chars().next().filter(|ch| matches!(ch, '`' | '~'))?;
+    let len = rest.len() - rest.trim_start_matches(ch).len();
+    (len >= 3).then(|| (ch, len, &rest[len..]))
+}
+
+/// Column ranges of the inline code spans on `line`, each including its
+/// backticks
+pub(crate) fn code_spans(line: &str) -> Vec<RangeInclusive<usize>> {
+    let chars: Vec<char> = line.chars().collect();
+    let run_at = |start: usize| chars[start..].iter().take_while(|&&ch| ch == '`').count();
+    let mut spans = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        match chars[index] {
+            '\\' => index += 2,
+            '`' => {
+                let len = run_at(index);
+                // The closing run must have exactly as many backticks
+                let mut close = index + len;
+                while close < chars.len() {
+                    let run = run_at(close);
+                    if run == len {
+                        break;
+                    }
+                    close += run.max(1);
+                }
+                if close < chars.len() {
+                    spans.push(index + 1..=close + len);
+                    index = close + len;
+                } else {
+                    index += len;
+                }
+            }
+            _ => index += 1,
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fenced_blocks() {
+        let content = "a\n```text\n\u{251c}\n```\nb\n~~~~\n```\n\u{2192}\n~~~~\nc\n";
+        let regions = CodeRegions::parse(content);
+        let covered: Vec<usize> = (1..=10).filter(|&l| regions.contains(l, None)).collect();
+        assert_eq!(covered, vec![2, 3, 4, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_unclosed_fence_runs_to_end_of_file() {
+        let regions = CodeRegions::parse("```\n\u{251c}\n\u{251c}\n");
+        assert!(regions.contains(3, Some(1)));
+    }
+
+    #[test]
+    fn test_inline_code_spans() {
+        let regions = CodeRegions::parse("a `\u{e9}` b ``x ` y`` \\`\u{e9}` `\n");
+        assert!(regions.contains(1, Some(4)));
+        assert!(!regions.contains(1, Some(7)));
+        assert!(regions.contains(1, Some(13)));
+        // The escaped backtick does not open a span
+        assert!(!regions.contains(1, Some(21)));
+        assert!(!regions.contains(1, None));
+    }
+
+    #[test]
+    fn test_asciidoc_blocks() {
+        let content = "----\n\u{251c}\n\n-----\n----\n....\n\u{2192}\n....\n\
+                       ```ruby\nx\n```\nUse `\u{2192}` here \u{2192}\n---\n";
+        let regions = CodeRegions::parse_as(content, DocFormat::AsciiDoc);
+        let covered: Vec<usize> = (1..=13).filter(|&l| regions.contains(l, None)).collect();
+        assert_eq!(covered, vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+        assert!(regions.contains(12, Some(6)));
+        assert!(!regions.contains(12, Some(14)));
+    }
+
+    #[test]
+    fn test_rst_literal_blocks() {
+        let content = "Example::\n\n    \u{251c} a\n\n    \u{2514} b\n\nProse \u{2192}\n\
+                       .. note::\n\n   \u{2192}\n\n.. code-block:: text\n\n   \u{2192}\n\
+                       Use ``\u{2192}`` or `\u{2192}`\n";
+        let regions = CodeRegions::parse_as(content, DocFormat::Rst);
+        let covered: Vec<usize> = (1..=15).filter(|&l| regions.contains(l, None)).collect();
+        assert_eq!(covered, vec![3, 4, 5, 14]);
+        assert!(regions.contains(15, Some(7)));
+        assert!(!regions.contains(15, Some(15)));
+    }
+
+    #[test]
+    fn test_rustdoc_comments() {
+        let content = "/// Example:\n///\n/// ```\n/// \u{251c} tree\n/// ```\n\
+                       \x20   /// Use `\u{2192}` here\nfn f() {}\n";
+        let regions = CodeRegions::parse_as(content, DocFormat::Rustdoc);
+        let covered: Vec<usize> = (1..=7).filter(|&l| regions.contains(l, None)).collect();
+        assert_eq!(covered, vec![3, 4, 5]);
+        assert!(regions.contains(6, Some(14)));
+        assert!(!regions.contains(6, Some(10)));
+    }
+
+    #[test]
+    fn test_indented_fence_is_not_a_fence() {
+        assert!(CodeRegions::parse("    ```\n\u{251c}\n").is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+//! Column units for reported locations (`--columns`).
+//!
+//! Validators count columns in Unicode scalar values. Editors disagree: LSP
+//! and VS Code count UTF-16 code units, many tools count bytes, and users see
+//! grapheme clusters. Columns are converted only when a location is written,
+//! so carets, byte spans, and fixes keep working on characters.
+
+use crate::ValidationResult;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// What a reported column counts
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, schemars::JsonSchema))]
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColumnUnit {
+    /// Unicode scalar values (characters)
+    #[default]
+    Char,
+    /// UTF-8 bytes
+    Byte,
+    /// UTF-16 code units, as used by LSP and VS Code
+    Utf16,
+    /// Grapheme clusters, as a reader counts them
+    Grapheme,
+}
+
+impl ColumnUnit {
+    /// Name as accepted on the command line
+    pub fn name(self) -> &'static str {
+        match self {
+            ColumnUnit::Char => "char",
+            ColumnUnit::Byte => "byte",
+            ColumnUnit::Utf16 => "utf16",
+            ColumnUnit::Grapheme => "grapheme",
+        }
+    }
+
+    /// 1-based column, in this unit, of the character at 1-based character
+    /// column `column` of `line`. Positions past the end of the line are
+    /// returned unchanged.
+    pub fn start(self, line: &str, column: usize) -> usize {
+        self.span(line, column).map_or(column, |(start, _)| start)
+    }
+
+    /// Column just past the character at `column`, for end-exclusive ranges
+    pub fn end(self, line: &str, column: usize) -> usize {
+        self.span(line, column).map_or(column + 1, |(_, end)| end)
+    }
+
+    /// Column of a violation on 1-based `line_number` of `lines`
+    pub fn locate(self, lines: &[&str], line_number: usize, column: usize) -> usize {
+        match line_number.checked_sub(1).and_then(|i| lines.get(i)) {
+            Some(line) => self.start(line, column),
+            None => column,
+        }
+    }
+
+    /// Copy of `results` with every column counted in this unit, for output
+    /// that only prints locations
+    pub fn convert(self, content: &str, results: &[ValidationResult]) -> Vec<ValidationResult> {
+        let mut converted = results.to_vec();
+        if self == ColumnUnit::Char {
+            return converted;
+        }
+        let lines: Vec<&str> = content.lines().collect();
+        for error in converted.iter_mut().flat_map(|r| &mut r.errors) {
+            if let Some(column) = error.column {
+                error.column = Some(self.locate(&lines, error.line_number, column));
+            }
+        }
+        converted
+    }
+
+    fn span(self, line: &str, column: usize) -> Option<(usize, usize)> {
+        let (offset, ch) = line.char_indices().nth(column.checked_sub(1)?)?;
+        let before = &line[..offset];
+        let span = match self {
+            ColumnUnit::Char => (column, column + 1),
+            ColumnUnit::Byte => (offset + 1, offset + ch.len_utf8() + 1),
+            ColumnUnit::Utf16 => {
+                let start = before.encode_utf16().count() + 1;
+                (start, start + ch.len_utf16())
+            }
+            ColumnUnit::Grapheme => {
+                // The cluster containing the character, which may have begun
+                // before it (e.g. a combining mark)
+                let start = line
+                    .grapheme_indices(true)
+                    .take_while(|(index, _)| *index <= offset)
+                    .count();
+                (start, start + 1)
+            }
+        };
+        Some(span)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_in_each_unit() {
+        // "é" is 2 bytes, "😀" is 4 bytes and 2 UTF-16 units
+        let line = "é😀x";
+        assert_eq!(ColumnUnit::Char.start(line, 3), 3);
+        assert_eq!(ColumnUnit::Byte.start(line, 3), 7);
+        assert_eq!(ColumnUnit::Utf16.start(line, 3), 4);
+        assert_eq!(ColumnUnit::Grapheme.start(line, 3), 3);
+
+        assert_eq!(ColumnUnit::Byte.end(line, 2), 7);
+        assert_eq!(ColumnUnit::Utf16.end(line, 2), 4);
+    }
+
+    #[test]
+    fn test_grapheme_clusters() {
+        // "e" + combining acute accent is one cluster
+        let line = "e\u{301}x\u{2192}";
+        assert_eq!(ColumnUnit::Grapheme.start(line, 2), 1);
+        assert_eq!(ColumnUnit::Grapheme.start(line, 4), 3);
+    }
+
+    #[test]
+    fn test_convert_results() {
+        let content = "ok\n😀 ├\n";
+        let results = crate::validators::validate_all(content);
+        let converted = ColumnUnit::Utf16.convert(content, &results);
+        let tree = &converted[2].errors[0];
+        assert_eq!((tree.line_number, tree.column), (2, Some(4)));
+        assert_eq!(results[2].errors[0].column, Some(3));
+    }
+
+    #[test]
+    fn test_positions_past_the_line_are_unchanged() {
+        assert_eq!(ColumnUnit::Utf16.start("ab", 5), 5);
+        assert_eq!(ColumnUnit::Utf16.end("ab", 5), 6);
+    }
+}
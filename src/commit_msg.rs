@@ -0,0 +1,69 @@
+//! Commit message regions git strips (`--commit-msg`).
+//!
+//! Git drops comment lines, those starting with `#`, from the message it
+//! records, and with `commit --verbose` everything from the scissors line on,
+//! which is the diff being committed. Those lines are exempt from every rule,
+//! so only the message itself is checked and fixed.
+
+use crate::code_regions::CodeRegions;
+
+/// The line above the diff `git commit --verbose` appends
+pub const SCISSORS: &str = "# ------------------------ >8 ------------------------";
+
+/// Find the lines of commit message `content` git strips
+pub fn parse(content: &str) -> CodeRegions {
+    let mut parser = CommitMessageParser::default();
+    for line in content.lines() {
+        parser.push_line(line);
+    }
+    parser.finish()
+}
+
+/// Builds the stripped regions of a commit message one line at a time, so a
+/// file can be read as a stream
+#[derive(Debug, Default)]
+pub struct CommitMessageParser {
+    regions: CodeRegions,
+    line_number: usize,
+    /// Past the scissors line
+    cut: bool,
+}
+
+impl CommitMessageParser {
+    /// Add the next line, without its line ending
+    pub fn push_line(&mut self, line: &str) {
+        self.line_number += 1;
+        if self.cut || !line.starts_with('#') {
+            return;
+        }
+        if line == SCISSORS {
+            self.cut = true;
+            self.regions.insert_lines(self.line_number..=usize::MAX);
+        } else {
+            self.regions
+                .insert_lines(self.line_number..=self.line_number);
+        }
+    }
+
+    /// The stripped regions found
+    pub fn finish(self) -> CodeRegions {
+        self.regions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comments_and_diff_are_stripped() {
+        let content = format!(
+            "Fix caf\u{e9} \u{2192} cafe\n\n# Please enter the commit message\n\
+             Body #1 \u{2014}\n{}\n+ \u{251c} diff\n",
+            SCISSORS
+        );
+        let regions = parse(&content);
+        let covered: Vec<usize> = (1..=7).filter(|&l| regions.contains(l, None)).collect();
+        assert_eq!(covered, vec![3, 5, 6, 7]);
+    }
+}
@@ -0,0 +1,1349 @@
+//! Configuration file loading (`.markdown-checker.toml`).
+//!
+//! Without `--config`, each checked file uses the configuration files found by
+//! searching upward from its directory, merged so the closest one wins, the way
+//! `.editorconfig` works. A file with `root = true` stops the search.
+
+use crate::charset::CharClass;
+use crate::cli::{GroupBy, OutputFormat, RunMode, SortOrder};
+use crate::columns::ColumnUnit;
+use crate::doc_format::DocFormat;
+use crate::encoding::Bom;
+use crate::external::ExternalValidator;
+use crate::file_ops::FileSize;
+use crate::line_endings::LineEnding;
+use crate::path_display::PathStyle;
+use crate::policy::Policy;
+use crate::presets::Preset;
+use crate::remote::{fetch, is_url};
+use crate::scaffold::CONFIG_FILE_NAME;
+use crate::style::ColorChoice;
+use crate::validators::{all_rules, find_rule, opt_in_rules};
+use crate::{RuleOptions, Severity};
+use glob::Pattern;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+
+/// Setting for a single rule in the `[rules]` table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    /// Report violations and fail the run
+    Error,
+    /// Report violations without failing the run
+    Warning,
+    /// Do not run the rule
+    Off,
+}
+
+/// A `--rule RULE=SETTING` override of a `[rules]` setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleOverride {
+    pub code: &'static str,
+    pub level: RuleLevel,
+}
+
+impl FromStr for RuleOverride {
+    type Err = String;
+
+    fn from_str(arg: &str) -> Result<Self, String> {
+        let (rule, setting) = arg
+            .split_once('=')
+            .ok_or_else(|| format!("expected RULE=SETTING, e.g. ascii=off, not '{}'", arg))?;
+        let (rule, option) = match rule.split_once('.') {
+            Some((rule, option)) => (rule, Some(option)),
+            None => (rule, None),
+        };
+        let code = find_rule(rule.trim())
+            .ok_or_else(|| {
+                format!(
+                    "unknown rule '{}'{} (run `markdown-checker rules` to list rules)",
+                    rule,
+                    rule_suggestion(rule)
+                )
+            })?
+            .code;
+        if let Some(option) = option {
+            return Err(format!(
+                "rule '{}' has no option '{}'; set it to error, warning, or off",
+                rule, option
+            ));
+        }
+        let level = match setting.trim().to_ascii_lowercase().as_str() {
+            "error" => RuleLevel::Error,
+            "warning" => RuleLevel::Warning,
+            "off" => RuleLevel::Off,
+            _ => {
+                return Err(format!(
+                    "invalid setting '{}' for rule '{}' (expected error, warning, or off)",
+                    setting, rule
+                ));
+            }
+        };
+        Ok(Self { code, level })
+    }
+}
+
+/// `[files]` table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct FilesSection {
+    /// Glob patterns, relative to the config file, of files to check when
+    /// no `-f`/`-p` is given
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Glob patterns, relative to the config file, of files to skip
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    /// Like `--max-file-size`: files larger than this are skipped
+    #[schemars(with = "Option<String>")]
+    pub max_size: Option<FileSize>,
+}
+
+/// `[characters]` table: characters, ranges, blocks, and scripts to accept or flag
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct CharactersSection {
+    /// Characters no rule flags and fixing leaves alone
+    #[serde(default)]
+    pub allow: Vec<String>,
+    /// Characters flagged even when `allow` covers them
+    #[serde(default)]
+    pub forbid: Vec<String>,
+    /// Accept any character in fenced code blocks and inline code spans
+    pub skip_code: Option<bool>,
+    /// Accept any character in YAML or TOML frontmatter
+    pub skip_frontmatter: Option<bool>,
+}
+
+/// `[duplicate-words]` table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct DuplicateWordsSection {
+    /// Words accepted twice in a row, as in "that that", besides the
+    /// built-in ones
+    #[serde(default)]
+    pub allow: Vec<String>,
+}
+
+/// `[repeated-punctuation]` table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct RepeatedPunctuationSection {
+    /// Longest run of one punctuation mark accepted (default 1)
+    pub max_run: Option<usize>,
+    /// Accept `...` whatever `max-run` is (default true)
+    pub allow_ellipsis: Option<bool>,
+}
+
+/// `[link-text]` table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct LinkTextSection {
+    /// Link text to flag, replacing the built-in phrases ("click here",
+    /// "here", "this link", ...)
+    pub phrases: Option<Vec<String>>,
+}
+
+/// `[output]` table: defaults for report options not given on the command line
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct OutputSection {
+    /// Like `--format`
+    pub format: Option<OutputFormat>,
+    /// Like `--group-by`
+    pub group_by: Option<GroupBy>,
+    /// Like `--sort`
+    pub sort: Option<SortOrder>,
+    /// Like `--columns`
+    pub columns: Option<ColumnUnit>,
+    /// Like `--paths`
+    pub paths: Option<PathStyle>,
+    /// Like `--max-display`
+    pub max_display: Option<usize>,
+    /// Like `--hyperlinks`
+    pub hyperlinks: Option<bool>,
+    /// Like `--report-failures-only`
+    pub report_failures_only: Option<bool>,
+    /// Like `--color`
+    pub color: Option<ColorChoice>,
+}
+
+impl OutputSection {
+    /// Settings from `self`, falling back to `fallback` for those unset
+    fn or(self, fallback: Self) -> Self {
+        Self {
+            format: self.format.or(fallback.format),
+            group_by: self.group_by.or(fallback.group_by),
+            sort: self.sort.or(fallback.sort),
+            columns: self.columns.or(fallback.columns),
+            paths: self.paths.or(fallback.paths),
+            max_display: self.max_display.or(fallback.max_display),
+            hyperlinks: self.hyperlinks.or(fallback.hyperlinks),
+            report_failures_only: self.report_failures_only.or(fallback.report_failures_only),
+            color: self.color.or(fallback.color),
+        }
+    }
+}
+
+/// What a run without a subcommand, `--fix`, or `--dry-run` does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum AutoFix {
+    /// Report violations
+    #[default]
+    Off,
+    /// Fix them in place, like `--fix`
+    Apply,
+    /// Preview fixes, like `--dry-run`
+    DryRun,
+}
+
+impl AutoFix {
+    pub fn mode(self) -> RunMode {
+        match self {
+            AutoFix::Off => RunMode::Check,
+            AutoFix::Apply => RunMode::Fix,
+            AutoFix::DryRun => RunMode::DryRun,
+        }
+    }
+}
+
+/// `[fix]` table
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FixSection {
+    /// What a plain run does
+    pub auto: Option<AutoFix>,
+    /// Like `--eol`
+    pub eol: Option<LineEnding>,
+    /// Accept a byte order mark starting a file, and keep it (`keep`) or add
+    /// one (`add`) when fixing, instead of removing it (`remove`)
+    pub bom: Option<Bom>,
+}
+
+/// Settings loaded from a configuration file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ConfigFile {
+    /// Stop searching parent directories for more configuration files
+    #[serde(default)]
+    pub root: bool,
+    /// Preset, file, or URL these settings apply over
+    pub extends: Option<String>,
+    /// Rule settings keyed by rule key or code
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleLevel>,
+    #[serde(default)]
+    pub files: FilesSection,
+    /// Rules to skip for files matching each glob pattern, relative to the
+    /// config file
+    #[serde(default)]
+    pub per_file_ignores: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub characters: CharactersSection,
+    #[serde(default)]
+    pub duplicate_words: DuplicateWordsSection,
+    #[serde(default)]
+    pub repeated_punctuation: RepeatedPunctuationSection,
+    #[serde(default)]
+    pub link_text: LinkTextSection,
+    #[serde(default)]
+    pub output: OutputSection,
+    #[serde(default)]
+    pub fix: FixSection,
+    /// Like `--max-errors`
+    pub max_errors: Option<usize>,
+    /// Like `--max-warnings`
+    pub max_warnings: Option<usize>,
+    /// Like `--jobs`
+    pub jobs: Option<usize>,
+    /// Reuse results for unchanged files between runs
+    pub cache: Option<bool>,
+    /// Commands whose findings are reported with the built-in rules, by name
+    #[serde(default)]
+    pub external_validators: BTreeMap<String, ExternalValidator>,
+    /// Directory containing the config file; file patterns are relative to it
+    #[serde(skip)]
+    pub base_dir: PathBuf,
+}
+
+/// Error loading or interpreting a configuration file
+#[derive(Debug)]
+pub struct ConfigError {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl ConfigFile {
+    /// The configuration file in `dir`, if there is one
+    pub fn discover(dir: &Path) -> Option<PathBuf> {
+        let path = dir.join(CONFIG_FILE_NAME);
+        path.is_file().then_some(path)
+    }
+
+    /// Load and validate a configuration file, applying it over the
+    /// configuration it extends
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let error = |message: String| ConfigError {
+            path: path.to_path_buf(),
+            message,
+        };
+
+        let text = fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+        let mut config = Self::parse(&text).map_err(error)?;
+        config.base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        let source = ConfigSource::File(path.to_path_buf());
+        config
+            .resolve_extends(&source, &mut vec![source.key()])
+            .map_err(error)
+    }
+
+    /// Parse and validate configuration text, applying it over the preset it
+    /// extends. Other `extends` targets are resolved by [`ConfigFile::load`].
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let config: Self =
+            toml::from_str(text).map_err(|e| with_field_suggestion(e.to_string()))?;
+        let preset = config.extends.as_deref().and_then(Preset::from_name);
+
+        for key in config.rules.keys() {
+            if find_rule(key).is_none() {
+                return Err(format!(
+                    "unknown rule '{}' in [rules]{} (run `markdown-checker rules` to list rules)",
+                    key,
+                    rule_suggestion(key)
+                ));
+            }
+        }
+
+        for (pattern, rules) in &config.per_file_ignores {
+            Pattern::new(pattern)
+                .map_err(|e| format!("invalid per-file-ignores pattern '{}': {}", pattern, e))?;
+            if let Some(rule) = rules.iter().find(|rule| find_rule(rule).is_none()) {
+                return Err(format!(
+                    "unknown rule '{}' in [per-file-ignores] for '{}'{}",
+                    rule,
+                    pattern,
+                    rule_suggestion(rule)
+                ));
+            }
+        }
+
+        for (key, entries) in [
+            ("allow", &config.characters.allow),
+            ("forbid", &config.characters.forbid),
+        ] {
+            for entry in entries {
+                entry
+                    .parse::<CharClass>()
+                    .map_err(|e| format!("{} in [characters] {}", e, key))?;
+            }
+        }
+
+        if config.repeated_punctuation.max_run == Some(0) {
+            return Err("[repeated-punctuation] max-run must be at least 1".to_string());
+        }
+
+        for name in config.external_validators.keys() {
+            if find_rule(name).is_some() {
+                return Err(format!(
+                    "external validator '{}' has the name of a built-in rule",
+                    name
+                ));
+            }
+        }
+
+        for (kind, patterns) in [
+            ("include", &config.files.include),
+            ("ignore", &config.files.ignore),
+        ] {
+            for pattern in patterns {
+                Pattern::new(pattern)
+                    .map_err(|e| format!("invalid {} pattern '{}': {}", kind, pattern, e))?;
+            }
+        }
+
+        Ok(match preset {
+            Some(preset) => preset.config().overridden_by(config),
+            None => config,
+        })
+    }
+
+    /// Codes of rules turned off in the `[rules]` table
+    pub fn disabled_rules(&self) -> Vec<&'static str> {
+        self.rules
+            .iter()
+            .filter(|(_, level)| **level == RuleLevel::Off)
+            .filter_map(|(key, _)| find_rule(key).map(|rule| rule.code))
+            .collect()
+    }
+
+    /// Severities set in the `[rules]` table, by rule code
+    pub fn severities(&self) -> Vec<(&'static str, Severity)> {
+        self.rules
+            .iter()
+            .filter_map(|(key, level)| {
+                let severity = match level {
+                    RuleLevel::Error => Severity::Error,
+                    RuleLevel::Warning => Severity::Warning,
+                    RuleLevel::Off => return None,
+                };
+                find_rule(key).map(|rule| (rule.code, severity))
+            })
+            .collect()
+    }
+
+    /// Codes of rules turned off for `path`: those off in `[rules]`, opt-in
+    /// rules `[rules]` does not turn on, and those listed for a matching
+    /// `[per-file-ignores]` pattern
+    pub fn disabled_rules_for(&self, path: &Path) -> Vec<&'static str> {
+        let mut codes = self.disabled_rules();
+        let configured: Vec<_> = self
+            .rules
+            .keys()
+            .filter_map(|key| find_rule(key))
+            .map(|rule| rule.code)
+            .collect();
+        for code in opt_in_rules() {
+            if !configured.contains(&code) && !codes.contains(&code) {
+                codes.push(code);
+            }
+        }
+        let path = normalize(&absolute(path));
+        let matching = self
+            .per_file_ignores
+            .iter()
+            .filter(|(pattern, _)| self.matches(pattern, &path));
+        for rule in matching
+            .flat_map(|(_, rules)| rules)
+            .filter_map(|r| find_rule(r))
+        {
+            if !codes.contains(&rule.code) {
+                codes.push(rule.code);
+            }
+        }
+        codes
+    }
+
+    /// Rules, severities, and allowed characters for `path`
+    pub fn policy_for(&self, path: &Path) -> Policy {
+        let classes = |entries: &[String]| -> Vec<CharClass> {
+            entries
+                .iter()
+                .filter_map(|entry| entry.parse().ok())
+                .collect()
+        };
+        Policy::new(self.disabled_rules_for(path))
+            .severities(self.severities())
+            .allow(classes(&self.characters.allow))
+            .forbid(classes(&self.characters.forbid))
+            .skip_code(self.characters.skip_code.unwrap_or(false))
+            .skip_frontmatter(self.characters.skip_frontmatter.unwrap_or(false))
+            .format(DocFormat::from_path(path))
+            .bom(self.fix.bom.unwrap_or_default())
+            .options(self.rule_options())
+    }
+
+    /// Settings of the rules that take them
+    pub fn rule_options(&self) -> RuleOptions {
+        let defaults = RuleOptions::default();
+        RuleOptions {
+            duplicate_words_allowed: self.duplicate_words.allow.clone(),
+            max_punctuation_run: self
+                .repeated_punctuation
+                .max_run
+                .unwrap_or(defaults.max_punctuation_run),
+            allow_ellipsis: self
+                .repeated_punctuation
+                .allow_ellipsis
+                .unwrap_or(defaults.allow_ellipsis),
+            link_text_phrases: self
+                .link_text
+                .phrases
+                .clone()
+                .unwrap_or(defaults.link_text_phrases),
+            ..defaults
+        }
+    }
+
+    /// Run mode for plain runs
+    pub fn auto_fix(&self) -> AutoFix {
+        self.fix.auto.unwrap_or_default()
+    }
+
+    /// Apply these settings over the file or URL they extend, `chain` holding
+    /// the configurations extending this one to detect cycles
+    fn resolve_extends(
+        self,
+        source: &ConfigSource,
+        chain: &mut Vec<String>,
+    ) -> Result<Self, String> {
+        let Some(target) = self
+            .extends
+            .as_deref()
+            .filter(|target| Preset::from_name(target).is_none())
+        else {
+            return Ok(self);
+        };
+        let base_source = source.join(target);
+        let key = base_source.key();
+        if chain.contains(&key) {
+            chain.push(key);
+            return Err(format!("extends cycle: {}", chain.join(" -> ")));
+        }
+
+        let text = base_source.read().map_err(|e| {
+            format!(
+                "cannot read extended configuration '{}': {} (extends takes a path, a URL, or \
+                 a preset: {})",
+                target,
+                e,
+                Preset::names().join(", ")
+            )
+        })?;
+        let base = Self::parse(&text).map_err(|e| format!("{}: {}", base_source, e))?;
+        chain.push(key);
+        let base = base.resolve_extends(&base_source, chain)?;
+        chain.pop();
+        Ok(base.overridden_by(self))
+    }
+
+    /// Apply `config` over these settings, as though both were in its directory
+    pub fn overridden_by(mut self, config: ConfigFile) -> ConfigFile {
+        self.base_dir = config.base_dir.clone();
+        self.merged_with(config)
+    }
+
+    /// Apply `--rule` overrides over the `[rules]` table
+    pub fn with_rule_overrides(mut self, overrides: &[RuleOverride]) -> ConfigFile {
+        if !overrides.is_empty() {
+            self.rules = by_code(self.rules);
+            self.rules.extend(
+                overrides
+                    .iter()
+                    .map(|rule| (rule.code.to_string(), rule.level)),
+            );
+        }
+        self
+    }
+
+    /// Merge `closer`, a configuration file in a subdirectory of this one's,
+    /// over this one: its settings win, ignore patterns accumulate, and
+    /// patterns are rebased onto its directory
+    pub fn merged_with(self, closer: ConfigFile) -> ConfigFile {
+        let up = relative_parent(&closer.base_dir, &self.base_dir);
+        let rebase = |patterns: Vec<String>| -> Vec<String> {
+            patterns
+                .into_iter()
+                .map(|p| format!("{}{}", up, p))
+                .collect()
+        };
+
+        let mut rules = by_code(self.rules);
+        rules.extend(by_code(closer.rules));
+        let include = if closer.files.include.is_empty() {
+            rebase(self.files.include)
+        } else {
+            closer.files.include
+        };
+        let mut ignore = rebase(self.files.ignore);
+        ignore.extend(closer.files.ignore);
+        let mut per_file_ignores: BTreeMap<String, Vec<String>> = self
+            .per_file_ignores
+            .into_iter()
+            .map(|(pattern, rules)| (format!("{}{}", up, pattern), rules))
+            .collect();
+        per_file_ignores.extend(closer.per_file_ignores);
+        let mut allow = self.characters.allow;
+        allow.extend(closer.characters.allow);
+        let mut forbid = self.characters.forbid;
+        forbid.extend(closer.characters.forbid);
+        let mut duplicate_words = self.duplicate_words.allow;
+        duplicate_words.extend(closer.duplicate_words.allow);
+        let mut external_validators = self.external_validators;
+        external_validators.extend(closer.external_validators);
+
+        ConfigFile {
+            root: closer.root,
+            extends: closer.extends,
+            rules,
+            files: FilesSection {
+                include,
+                ignore,
+                max_size: closer.files.max_size.or(self.files.max_size),
+            },
+            per_file_ignores,
+            characters: CharactersSection {
+                allow,
+                forbid,
+                skip_code: closer.characters.skip_code.or(self.characters.skip_code),
+                skip_frontmatter: closer
+                    .characters
+                    .skip_frontmatter
+                    .or(self.characters.skip_frontmatter),
+            },
+            duplicate_words: DuplicateWordsSection {
+                allow: duplicate_words,
+            },
+            repeated_punctuation: RepeatedPunctuationSection {
+                max_run: closer
+                    .repeated_punctuation
+                    .max_run
+                    .or(self.repeated_punctuation.max_run),
+                allow_ellipsis: closer
+                    .repeated_punctuation
+                    .allow_ellipsis
+                    .or(self.repeated_punctuation.allow_ellipsis),
+            },
+            link_text: LinkTextSection {
+                phrases: closer.link_text.phrases.or(self.link_text.phrases),
+            },
+            output: closer.output.or(self.output),
+            fix: FixSection {
+                auto: closer.fix.auto.or(self.fix.auto),
+                eol: closer.fix.eol.or(self.fix.eol),
+                bom: closer.fix.bom.or(self.fix.bom),
+            },
+            max_errors: closer.max_errors.or(self.max_errors),
+            max_warnings: closer.max_warnings.or(self.max_warnings),
+            jobs: closer.jobs.or(self.jobs),
+            cache: closer.cache.or(self.cache),
+            external_validators,
+            base_dir: closer.base_dir,
+        }
+    }
+
+    /// `[files] include` patterns, resolved against the config file's directory
+    pub fn include_patterns(&self) -> Vec<String> {
+        self.files
+            .include
+            .iter()
+            .map(|pattern| self.base_dir.join(pattern).to_string_lossy().into_owned())
+            .collect()
+    }
+
+    /// Check whether a file matches one of the `[files] ignore` patterns
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        // Compare absolute paths, since patterns inherited from a parent
+        // directory's configuration may reach above the working directory
+        let path = normalize(&absolute(path));
+        self.files
+            .ignore
+            .iter()
+            .any(|pattern| self.matches(pattern, &path))
+    }
+
+    /// Whether `pattern`, relative to the config file, matches the normalized
+    /// absolute `path`
+    fn matches(&self, pattern: &str, path: &Path) -> bool {
+        let (prefix, rest) = split_leading_dirs(pattern);
+        let base = normalize(&absolute(&self.base_dir.join(prefix)));
+        let base = Pattern::escape(&base.to_string_lossy());
+        let full = format!("{}/{}", base.trim_end_matches('/'), rest);
+        Pattern::new(&full)
+            .map(|p| p.matches_path(path))
+            .unwrap_or(false)
+    }
+}
+
+/// Configuration files found by searching upward from checked files'
+/// directories, merged per directory so the closest file's settings win
+#[derive(Debug, Default)]
+pub struct ConfigCascade {
+    merged: HashMap<PathBuf, ConfigFile>,
+    /// Every configuration file loaded, in load order
+    loaded: Vec<PathBuf>,
+    /// Settings beneath the outermost configuration file, such as a preset
+    base: ConfigFile,
+}
+
+impl ConfigCascade {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply every configuration file over `base`
+    pub fn with_base(base: ConfigFile) -> Self {
+        Self {
+            base,
+            ..Self::default()
+        }
+    }
+
+    /// Merged configuration for files in `dir`
+    pub fn for_dir(&mut self, dir: &Path) -> Result<&ConfigFile, ConfigError> {
+        let mut dir = normalize(dir);
+        if dir.as_os_str().is_empty() {
+            dir = PathBuf::from(".");
+        }
+        if !self.merged.contains_key(&dir) {
+            let local = match ConfigFile::discover(&dir) {
+                Some(path) => {
+                    let config = ConfigFile::load(&path)?;
+                    self.loaded.push(path);
+                    Some(config)
+                }
+                None => None,
+            };
+            let merged = match (local, parent_dir(&dir)) {
+                (Some(local), _) if local.root => self.base.clone().overridden_by(local),
+                (local, Some(parent)) => {
+                    let inherited = self.for_dir(&parent)?.clone();
+                    match local {
+                        Some(local) => inherited.merged_with(local),
+                        None => inherited,
+                    }
+                }
+                (Some(local), None) => self.base.clone().overridden_by(local),
+                (None, None) => self.base.clone(),
+            };
+            self.merged.insert(dir.clone(), merged);
+        }
+        Ok(&self.merged[&dir])
+    }
+
+    /// Merged configuration for a checked file
+    pub fn for_file(&mut self, path: &Path) -> Result<&ConfigFile, ConfigError> {
+        self.for_dir(path.parent().unwrap_or(Path::new(".")))
+    }
+
+    /// Configuration files loaded so far, closest to the first directory
+    /// looked up first
+    pub fn loaded(&self) -> &[PathBuf] {
+        &self.loaded
+    }
+}
+
+/// JSON Schema of the configuration file, for editor autocompletion and
+/// validation
+pub fn json_schema() -> serde_json::Value {
+    let mut schema = schemars::schema_for!(ConfigFile).to_value();
+    schema["title"] = "markdown-checker configuration (.markdown-checker.toml)".into();
+    // Rule keys and codes come from the validators rather than the types
+    let rules: Vec<&str> = all_rules()
+        .iter()
+        .flat_map(|rule| [rule.key, rule.code])
+        .collect();
+    let properties = &mut schema["properties"];
+    properties["rules"]["propertyNames"] = json!({ "enum": rules });
+    properties["per-file-ignores"]["additionalProperties"]["items"] = json!({ "enum": rules });
+    properties["extends"]["examples"] = json!(Preset::names());
+    schema
+}
+
+/// Where a configuration was loaded from
+#[derive(Debug, Clone)]
+enum ConfigSource {
+    File(PathBuf),
+    Url(String),
+}
+
+impl ConfigSource {
+    /// The configuration `target` names, relative to this one
+    fn join(&self, target: &str) -> ConfigSource {
+        if is_url(target) {
+            return ConfigSource::Url(target.to_string());
+        }
+        match self {
+            ConfigSource::File(path) => {
+                ConfigSource::File(parent_dir(path).unwrap_or_default().join(target))
+            }
+            ConfigSource::Url(url) => {
+                let dir = url.rfind('/').map_or(url.as_str(), |end| &url[..=end]);
+                ConfigSource::Url(format!("{}{}", dir, target))
+            }
+        }
+    }
+
+    /// Identity for cycle detection
+    fn key(&self) -> String {
+        match self {
+            ConfigSource::File(path) => normalize(&absolute(path)).display().to_string(),
+            ConfigSource::Url(url) => url.clone(),
+        }
+    }
+
+    fn read(&self) -> Result<String, String> {
+        match self {
+            ConfigSource::File(path) => fs::read_to_string(path).map_err(|e| e.to_string()),
+            ConfigSource::Url(url) => fetch(url),
+        }
+    }
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::File(path) => write!(f, "{}", path.display()),
+            ConfigSource::Url(url) => f.write_str(url),
+        }
+    }
+}
+
+/// Rule settings keyed by rule code, so `ascii` and `MC001` merge as one rule
+/// The candidate closest to a misspelled `name`, if any is close enough
+fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (strsim::jaro_winkler(name, candidate), candidate))
+        .filter(|(similarity, _)| *similarity > 0.8)
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, candidate)| candidate)
+}
+
+/// "; did you mean '<key>'?" for a misspelled rule, or nothing
+fn rule_suggestion(name: &str) -> String {
+    let keys = all_rules().into_iter().map(|rule| rule.key);
+    closest(name, keys).map_or_else(String::new, |key| format!("; did you mean '{}'?", key))
+}
+
+/// A TOML error message, with a suggestion if it reports a misspelled key
+fn with_field_suggestion(message: String) -> String {
+    let suggestion = (|| {
+        let (_, rest) = message.split_once("unknown field `")?;
+        let (field, rest) = rest.split_once('`')?;
+        let (_, expected) = rest.split_once("expected ")?;
+        let expected = expected.lines().next()?.trim_start_matches("one of ");
+        closest(
+            field,
+            expected.split(", ").map(|name| name.trim_matches('`')),
+        )
+    })();
+    match suggestion {
+        Some(name) => format!("{}\nhelp: did you mean `{}`?", message.trim_end(), name),
+        None => message,
+    }
+}
+
+fn by_code(rules: BTreeMap<String, RuleLevel>) -> BTreeMap<String, RuleLevel> {
+    rules
+        .into_iter()
+        .map(|(key, level)| {
+            let code = find_rule(&key).map_or(key, |rule| rule.code.to_string());
+            (code, level)
+        })
+        .collect()
+}
+
+/// The directory above `dir`, or `None` at the filesystem root
+fn parent_dir(dir: &Path) -> Option<PathBuf> {
+    match dir.components().next_back() {
+        Some(Component::Normal(_)) => dir
+            .parent()
+            .map(|p| {
+                if p.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    p
+                }
+            })
+            .map(Path::to_path_buf),
+        _ => {
+            let canonical = dir.canonicalize().ok()?;
+            canonical.parent()?;
+            Some(normalize(&dir.join("..")))
+        }
+    }
+}
+
+/// `../` repeated once per directory between `ancestor` and `dir`
+fn relative_parent(dir: &Path, ancestor: &Path) -> String {
+    let depth = |p: &Path| normalize(&absolute(p)).components().count();
+    "../".repeat(depth(dir).saturating_sub(depth(ancestor)))
+}
+
+/// Split a pattern's leading `./` and `../` components from the rest
+fn split_leading_dirs(pattern: &str) -> (&str, &str) {
+    let mut rest = pattern;
+    while let Some(stripped) = rest.strip_prefix("./").or_else(|| rest.strip_prefix("../")) {
+        rest = stripped;
+    }
+    (&pattern[..pattern.len() - rest.len()], rest)
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Resolve `.` and `..` components lexically, so `./docs/a.md`, `docs/a.md`,
+/// and `docs/x/../a.md` compare equal
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::external::OutputParser;
+    use crate::scaffold::starter_config;
+
+    #[test]
+    fn test_parse_empty_config() {
+        let config = ConfigFile::parse("").unwrap();
+        assert_eq!(config, ConfigFile::default());
+    }
+
+    #[test]
+    fn test_parse_starter_config() {
+        let config = ConfigFile::parse(&starter_config()).unwrap();
+        assert_eq!(config.rules.len(), 9);
+        assert_eq!(
+            config.disabled_rules(),
+            vec!["MC007", "MC004", "MC009", "MC008"]
+        );
+    }
+
+    #[test]
+    fn test_disabled_rules_by_key_or_code() {
+        let config = ConfigFile::parse("[rules]\nascii = \"off\"\nMC003 = \"off\"\n").unwrap();
+        assert_eq!(config.disabled_rules(), vec!["MC003", "MC001"]);
+    }
+
+    #[test]
+    fn test_rule_severities() {
+        let config = ConfigFile::parse(
+            "[rules]\ntree-symbols = \"warning\"\nascii = \"off\"\nMC002 = \"error\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.severities(),
+            vec![("MC002", Severity::Error), ("MC003", Severity::Warning)]
+        );
+        assert_eq!(config.disabled_rules(), vec!["MC001"]);
+        assert!(ConfigFile::parse("[rules]\nascii = \"info\"\n").is_err());
+    }
+
+    #[test]
+    fn test_unknown_rule_rejected() {
+        let err = ConfigFile::parse("[rules]\nbogus = \"off\"\n").unwrap_err();
+        assert!(err.contains("unknown rule 'bogus'"));
+    }
+
+    #[test]
+    fn test_unknown_key_rejected() {
+        assert!(ConfigFile::parse("colour = true\n").is_err());
+    }
+
+    #[test]
+    fn test_top_level_settings() {
+        let config = ConfigFile::parse("max-errors = 10\nmax-warnings = 25\njobs = 2\n").unwrap();
+        assert_eq!(config.max_errors, Some(10));
+        assert_eq!(config.max_warnings, Some(25));
+        assert_eq!(config.jobs, Some(2));
+    }
+
+    #[test]
+    fn test_external_validators() {
+        let config = ConfigFile::parse(
+            "[external-validators]\n\
+             vale = { cmd = \"vale --output=JSON {file}\", parser = \"vale\" }\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.external_validators["vale"].parser,
+            OutputParser::Vale
+        );
+
+        let err = ConfigFile::parse(
+            "[external-validators]\nascii = { cmd = \"x\", parser = \"compact\" }\n",
+        )
+        .unwrap_err();
+        assert!(err.contains("built-in rule"), "{}", err);
+        assert!(
+            ConfigFile::parse("[external-validators]\nx = { cmd = \"x\", parser = \"yaml\" }\n")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_is_ignored() {
+        let mut config =
+            ConfigFile::parse("[files]\nignore = [\"CHANGELOG.md\", \"vendor/**\"]\n").unwrap();
+        config.base_dir = PathBuf::from(".");
+        assert!(config.is_ignored(Path::new("./CHANGELOG.md")));
+        assert!(config.is_ignored(Path::new("vendor/lib/README.md")));
+        assert!(!config.is_ignored(Path::new("./README.md")));
+    }
+
+    #[test]
+    fn test_output_and_fix_sections() {
+        let config = ConfigFile::parse(
+            "[output]\nformat = \"compact\"\ncolumns = \"utf16\"\npaths = \"from-root\"\n\
+             max-display = 0\n\n[fix]\nauto = \"dry-run\"\neol = \"crlf\"\nbom = \"keep\"\n",
+        )
+        .unwrap();
+        assert_eq!(config.output.format, Some(OutputFormat::Compact));
+        assert_eq!(config.output.columns, Some(ColumnUnit::Utf16));
+        assert_eq!(config.output.paths, Some(PathStyle::FromRoot));
+        assert_eq!(config.output.max_display, Some(0));
+        assert_eq!(config.auto_fix().mode(), RunMode::DryRun);
+        assert_eq!(config.fix.eol, Some(LineEnding::Crlf));
+        assert_eq!(config.policy_for(Path::new("a.md")).bom, Bom::Keep);
+
+        assert!(ConfigFile::parse("[output]\nformat = \"yaml\"\n").is_err());
+        assert!(ConfigFile::parse("[fix]\nauto = true\n").is_err());
+    }
+
+    #[test]
+    fn test_include_patterns() {
+        let mut config =
+            ConfigFile::parse("[files]\ninclude = [\"README.md\", \"docs/**/*.md\"]\n").unwrap();
+        config.base_dir = PathBuf::from("site");
+        assert_eq!(
+            config.include_patterns(),
+            vec!["site/README.md", "site/docs/**/*.md"]
+        );
+        assert!(ConfigFile::parse("[files]\ninclude = [\"[\"]\n").is_err());
+    }
+
+    #[test]
+    fn test_discover() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(ConfigFile::discover(dir.path()), None);
+        fs::write(dir.path().join(CONFIG_FILE_NAME), "").unwrap();
+        assert_eq!(
+            ConfigFile::discover(dir.path()),
+            Some(dir.path().join(CONFIG_FILE_NAME))
+        );
+    }
+
+    #[test]
+    fn test_cascade_merges_closest_last() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let sub = root.join("packages/web");
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(
+            root.join(CONFIG_FILE_NAME),
+            "root = true\nmax-errors = 5\n\n[rules]\nascii = \"off\"\n\n\
+             [files]\nignore = [\"packages/web/vendor/**\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            sub.join(CONFIG_FILE_NAME),
+            "[rules]\nMC001 = \"error\"\ntree-symbols = \"off\"\n\n\
+             [files]\nignore = [\"CHANGELOG.md\"]\n",
+        )
+        .unwrap();
+
+        let mut cascade = ConfigCascade::new();
+        let top = cascade.for_dir(root).unwrap().clone();
+        assert_eq!(top.disabled_rules(), vec!["MC001"]);
+
+        let merged = cascade.for_file(&sub.join("docs/a.md")).unwrap();
+        assert_eq!(merged.disabled_rules(), vec!["MC003"]);
+        assert_eq!(merged.max_errors, Some(5));
+        assert!(merged.is_ignored(&sub.join("vendor/lib.md")));
+        assert!(merged.is_ignored(&sub.join("CHANGELOG.md")));
+        assert!(!merged.is_ignored(&root.join("CHANGELOG.md")));
+    }
+
+    #[test]
+    fn test_extends_preset() {
+        let config =
+            ConfigFile::parse("extends = \"structure-only\"\n\n[rules]\nascii = \"error\"\n")
+                .unwrap();
+        assert_eq!(config.disabled_rules(), vec!["MC002", "MC003"]);
+
+        let config =
+            ConfigFile::parse("extends = \"portable\"\n\n[characters]\nallow = [\"U+2014\"]\n")
+                .unwrap();
+        let policy = config.policy_for(Path::new("README.md"));
+        assert!(policy.allows('\u{e9}'));
+        assert!(policy.allows('\u{2014}'));
+    }
+
+    #[test]
+    fn test_extends_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("shared")).unwrap();
+        fs::create_dir_all(root.join("repo")).unwrap();
+        fs::write(
+            root.join("shared/base.toml"),
+            "extends = \"portable\"\nmax-errors = 10\n\n[rules]\ntree-symbols = \"off\"\n\n\
+             [files]\nignore = [\"CHANGELOG.md\"]\n",
+        )
+        .unwrap();
+        let path = root.join("repo").join(CONFIG_FILE_NAME);
+        fs::write(
+            &path,
+            "extends = \"../shared/base.toml\"\nmax-errors = 3\n\n[rules]\nascii = \"off\"\n",
+        )
+        .unwrap();
+
+        let config = ConfigFile::load(&path).unwrap();
+        assert_eq!(config.max_errors, Some(3));
+        assert_eq!(config.disabled_rules(), vec!["MC001", "MC003"]);
+        assert!(config.policy_for(Path::new("a.md")).allows('\u{e9}'));
+        // Patterns in the extended file are relative to the extending one
+        assert!(config.is_ignored(&root.join("repo/CHANGELOG.md")));
+    }
+
+    #[test]
+    fn test_extends_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("a.toml"), "extends = \"b.toml\"\n").unwrap();
+        fs::write(root.join("b.toml"), "extends = \"./a.toml\"\n").unwrap();
+        let err = ConfigFile::load(&root.join("a.toml")).unwrap_err();
+        assert!(err.message.starts_with("extends cycle: "));
+        assert!(err.message.ends_with("a.toml"));
+
+        fs::write(root.join("c.toml"), "extends = \"lenient\"\n").unwrap();
+        let err = ConfigFile::load(&root.join("c.toml")).unwrap_err();
+        assert!(
+            err.message
+                .contains("cannot read extended configuration 'lenient'")
+        );
+        assert!(
+            err.message
+                .contains("strict, portable, latin, structure-only")
+        );
+
+        fs::write(root.join("d.toml"), "[rules]\nbogus = \"off\"\n").unwrap();
+        fs::write(root.join("e.toml"), "extends = \"d.toml\"\n").unwrap();
+        let err = ConfigFile::load(&root.join("e.toml")).unwrap_err();
+        assert!(err.message.contains("d.toml: unknown rule 'bogus'"));
+    }
+
+    #[test]
+    fn test_cascade_applies_over_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(
+            root.join(CONFIG_FILE_NAME),
+            "root = true\n\n[rules]\nMC001 = \"error\"\n",
+        )
+        .unwrap();
+
+        let mut cascade = ConfigCascade::with_base(Preset::StructureOnly.config());
+        let merged = cascade.for_dir(root).unwrap();
+        assert_eq!(merged.disabled_rules(), vec!["MC002", "MC003"]);
+    }
+
+    #[test]
+    fn test_opt_in_rules() {
+        let path = Path::new("README.md");
+        let config = ConfigFile::parse("").unwrap();
+        assert_eq!(
+            config.disabled_rules_for(path),
+            vec!["MC004", "MC007", "MC008", "MC009"]
+        );
+        let config = ConfigFile::parse("[rules]\nfrontmatter = \"warning\"\n").unwrap();
+        assert_eq!(
+            config.disabled_rules_for(path),
+            vec!["MC007", "MC008", "MC009"]
+        );
+        let config = ConfigFile::parse("[rules]\nMC004 = \"off\"\n").unwrap();
+        assert_eq!(
+            config.disabled_rules_for(path),
+            vec!["MC004", "MC007", "MC008", "MC009"]
+        );
+    }
+
+    #[test]
+    fn test_rule_overrides() {
+        let parse = |arg: &str| arg.parse::<RuleOverride>();
+        assert_eq!(
+            parse("ascii=off"),
+            Ok(RuleOverride {
+                code: "MC001",
+                level: RuleLevel::Off
+            })
+        );
+        assert_eq!(parse("MC004=Warning").unwrap().level, RuleLevel::Warning);
+        assert!(
+            parse("ascii")
+                .unwrap_err()
+                .contains("expected RULE=SETTING")
+        );
+        assert!(
+            parse("bogus=off")
+                .unwrap_err()
+                .contains("unknown rule 'bogus'")
+        );
+        assert!(
+            parse("ascii=loud")
+                .unwrap_err()
+                .contains("invalid setting 'loud'")
+        );
+        assert!(
+            parse("ascii.max=100")
+                .unwrap_err()
+                .contains("rule 'ascii' has no option 'max'")
+        );
+
+        let config = ConfigFile::parse("[rules]\nascii = \"off\"\nMC003 = \"warning\"\n")
+            .unwrap()
+            .with_rule_overrides(&[
+                parse("MC001=error").unwrap(),
+                parse("tree-symbols=off").unwrap(),
+            ]);
+        assert_eq!(config.disabled_rules(), vec!["MC003"]);
+        assert_eq!(config.severities(), vec![("MC001", Severity::Error)]);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let err = ConfigFile::parse("[output]\ncolr = \"auto\"\n").unwrap_err();
+        assert!(err.contains("unknown field `colr`"));
+        assert!(err.ends_with("help: did you mean `color`?"));
+        let err = ConfigFile::parse("[rules]\nasci = \"off\"\n").unwrap_err();
+        assert!(err.contains("unknown rule 'asci' in [rules]; did you mean 'ascii'?"));
+        let err = ConfigFile::parse("[rules]\nzzz = \"off\"\n").unwrap_err();
+        assert!(!err.contains("did you mean"));
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let schema = json_schema();
+        let properties = &schema["properties"];
+        for key in [
+            "root",
+            "extends",
+            "rules",
+            "files",
+            "per-file-ignores",
+            "characters",
+            "external-validators",
+        ] {
+            assert!(properties.get(key).is_some(), "missing {}", key);
+        }
+        assert!(properties.get("base-dir").is_none());
+        assert_eq!(properties["rules"]["propertyNames"]["enum"][0], "ascii");
+        assert_eq!(schema["additionalProperties"], false);
+    }
+
+    #[test]
+    fn test_per_file_ignores() {
+        let mut config = ConfigFile::parse(
+            "[rules]\nMC002 = \"off\"\n\n[per-file-ignores]\n\
+             \"docs/i18n/**\" = [\"ascii\", \"MC002\"]\n\"CHANGELOG.md\" = [\"MC003\"]\n",
+        )
+        .unwrap();
+        config.base_dir = PathBuf::from(".");
+        assert_eq!(
+            config.disabled_rules_for(Path::new("docs/i18n/fr/index.md")),
+            vec!["MC002", "MC004", "MC007", "MC008", "MC009", "MC001"]
+        );
+        assert_eq!(
+            config.disabled_rules_for(Path::new("./CHANGELOG.md")),
+            vec!["MC002", "MC004", "MC007", "MC008", "MC009", "MC003"]
+        );
+        assert_eq!(
+            config.disabled_rules_for(Path::new("docs/guide.md")),
+            vec!["MC002", "MC004", "MC007", "MC008", "MC009"]
+        );
+
+        let err = ConfigFile::parse("[per-file-ignores]\n\"*.md\" = [\"bogus\"]\n").unwrap_err();
+        assert!(err.contains("unknown rule 'bogus'"));
+    }
+
+    #[test]
+    fn test_allowed_characters() {
+        let config = ConfigFile::parse(
+            "[characters]\nallow = [\"\u{2014}\", \"U+00A9\", \"u+2192\", \"script:Greek\"]\n\
+             forbid = [\"U+03A3\"]\n",
+        )
+        .unwrap();
+        let policy = config.policy_for(Path::new("README.md"));
+        for ch in ['\u{2014}', '\u{a9}', '\u{2192}', '\u{3b1}'] {
+            assert!(policy.allows(ch));
+        }
+        assert!(!policy.allows('\u{3a3}'));
+        assert!(!policy.skip_code);
+
+        let config = ConfigFile::parse("[characters]\nskip-code = true\n").unwrap();
+        assert!(config.policy_for(Path::new("README.md")).skip_code);
+
+        let config = ConfigFile::parse("[characters]\nskip-frontmatter = true\n").unwrap();
+        assert!(config.policy_for(Path::new("README.md")).skip_frontmatter);
+
+        assert!(ConfigFile::parse("[characters]\nallow = [\"ab\"]\n").is_err());
+        let err = ConfigFile::parse("[characters]\nforbid = [\"block:Nope\"]\n").unwrap_err();
+        assert!(err.contains("unknown Unicode block 'Nope' in [characters] forbid"));
+    }
+
+    #[test]
+    fn test_duplicate_words_section() {
+        let base = ConfigFile::parse("[duplicate-words]\nallow = [\"very\"]\n").unwrap();
+        let closer = ConfigFile::parse("[duplicate-words]\nallow = [\"bla\"]\n").unwrap();
+        let policy = base.merged_with(closer).policy_for(Path::new("README.md"));
+        assert_eq!(policy.options.duplicate_words_allowed, ["very", "bla"]);
+        assert!(ConfigFile::parse("[duplicate-words]\nwords = []\n").is_err());
+    }
+
+    #[test]
+    fn test_repeated_punctuation_section() {
+        let base = ConfigFile::parse("[repeated-punctuation]\nmax-run = 2\n").unwrap();
+        let closer = ConfigFile::parse("[repeated-punctuation]\nallow-ellipsis = false\n").unwrap();
+        let options = base.merged_with(closer).rule_options();
+        assert_eq!(options.max_punctuation_run, 2);
+        assert!(!options.allow_ellipsis);
+        assert_eq!(
+            ConfigFile::parse("").unwrap().rule_options(),
+            RuleOptions::default()
+        );
+        let err = ConfigFile::parse("[repeated-punctuation]\nmax-run = 0\n").unwrap_err();
+        assert!(err.contains("max-run must be at least 1"));
+    }
+
+    #[test]
+    fn test_link_text_section() {
+        let base = ConfigFile::parse("[link-text]\nphrases = [\"go\"]\n").unwrap();
+        assert_eq!(base.rule_options().link_text_phrases, ["go"]);
+        let closer = ConfigFile::parse("[link-text]\nphrases = [\"see\", \"here\"]\n").unwrap();
+        let options = base.merged_with(closer).rule_options();
+        assert_eq!(options.link_text_phrases, ["see", "here"]);
+        let options = ConfigFile::parse("").unwrap().rule_options();
+        assert!(
+            options
+                .link_text_phrases
+                .contains(&"click here".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parent_dir() {
+        assert_eq!(parent_dir(Path::new("docs")), Some(PathBuf::from(".")));
+        assert_eq!(parent_dir(Path::new("a/b")), Some(PathBuf::from("a")));
+        assert_eq!(parent_dir(Path::new(".")), Some(PathBuf::from("..")));
+        assert_eq!(parent_dir(Path::new("/")), None);
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let err = ConfigFile::load(Path::new("/nonexistent/config.toml")).unwrap_err();
+        assert!(err.to_string().contains("/nonexistent/config.toml"));
+    }
+}
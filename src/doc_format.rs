@@ -0,0 +1,107 @@
+//! Document formats, detected from file names and extensions.
+//!
+//! The character rules read every format the same way; what differs is the
+//! syntax around the text: which regions are code, whether a file may start
+//! with frontmatter, and whether it embeds JavaScript, as MDX does. Plain text
+//! has no syntax at all, so only the character rules run on it, as on commit
+//! messages, whose comment lines git strips. Rust sources are markdown only in
+//! their doc comments.
+
+use std::path::Path;
+
+/// The markup a file is written in
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DocFormat {
+    /// Markdown (`.md` and anything unrecognized)
+    #[default]
+    Markdown,
+    /// Markdown with JSX and ESM (`.mdx`)
+    Mdx,
+    /// AsciiDoc (`.adoc`, `.asciidoc`, `.asc`)
+    #[cfg_attr(feature = "cli", value(name = "asciidoc"))]
+    AsciiDoc,
+    /// reStructuredText (`.rst`)
+    Rst,
+    /// Plain text (`.txt`): only the character rules run
+    Text,
+    /// A git commit message (`COMMIT_EDITMSG`, or `--commit-msg`): plain
+    /// text without the lines git strips
+    #[cfg_attr(feature = "cli", value(skip))]
+    CommitMessage,
+    /// Rust source (`.rs`, or `--rustdoc`): the markdown of its `///` and
+    /// `//!` doc comments
+    #[cfg_attr(feature = "cli", value(skip))]
+    Rustdoc,
+}
+
+impl DocFormat {
+    /// The format of the file at `path`, Markdown unless its name or
+    /// extension says otherwise
+    pub fn from_path(path: &Path) -> Self {
+        let name = path.file_name().unwrap_or_default();
+        if ["COMMIT_EDITMSG", "MERGE_MSG", "SQUASH_MSG", "TAG_EDITMSG"]
+            .iter()
+            .any(|message| name == *message)
+        {
+            return DocFormat::CommitMessage;
+        }
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("mdx") => DocFormat::Mdx,
+            Some("adoc" | "asciidoc" | "asc") => DocFormat::AsciiDoc,
+            Some("rst") => DocFormat::Rst,
+            Some("txt") => DocFormat::Text,
+            Some("rs") => DocFormat::Rustdoc,
+            _ => DocFormat::Markdown,
+        }
+    }
+
+    /// Whether files may start with a YAML or TOML frontmatter block
+    pub fn has_frontmatter(self) -> bool {
+        matches!(self, DocFormat::Markdown | DocFormat::Mdx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(
+            DocFormat::from_path(Path::new("README.md")),
+            DocFormat::Markdown
+        );
+        assert_eq!(
+            DocFormat::from_path(Path::new("LICENSE")),
+            DocFormat::Markdown
+        );
+        assert_eq!(
+            DocFormat::from_path(Path::new("docs/a.mdx")),
+            DocFormat::Mdx
+        );
+        assert_eq!(
+            DocFormat::from_path(Path::new("guide.ADOC")),
+            DocFormat::AsciiDoc
+        );
+        assert_eq!(DocFormat::from_path(Path::new("index.rst")), DocFormat::Rst);
+        assert_eq!(
+            DocFormat::from_path(Path::new("NOTES.txt")),
+            DocFormat::Text
+        );
+        assert!(!DocFormat::Rst.has_frontmatter());
+        assert!(!DocFormat::Text.has_frontmatter());
+        assert_eq!(
+            DocFormat::from_path(Path::new(".git/COMMIT_EDITMSG")),
+            DocFormat::CommitMessage
+        );
+        assert_eq!(
+            DocFormat::from_path(Path::new("src/lib.rs")),
+            DocFormat::Rustdoc
+        );
+        assert!(!DocFormat::Rustdoc.has_frontmatter());
+    }
+}
@@ -0,0 +1,466 @@
+//! Encodings of files that are not UTF-8: the `encoding` rule (MC006).
+//!
+//! Files exported from Windows tools are often UTF-16 or Windows-1252, and
+//! reading them as UTF-8 fails at the first byte that does not fit. Rather
+//! than stop at that read error, the checker names the encoding the bytes
+//! appear to be in and reports it as a violation of the file, and `--fix`
+//! converts the file to UTF-8.
+
+use crate::validators::EncodingRule;
+use crate::{ValidationError, ValidationResult};
+use std::borrow::Cow;
+use std::{fmt, io};
+
+/// A byte order mark, as the character that starts the content
+pub const BOM: char = '\u{feff}';
+
+/// What happens to a byte order mark at the start of a file
+#[cfg_attr(feature = "cli", derive(schemars::JsonSchema))]
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum Bom {
+    /// Flag it like any other U+FEFF, and remove it when fixing
+    #[default]
+    Remove,
+    /// Accept it, and keep it in fixed files
+    Keep,
+    /// Accept it, and add one to fixed files without one
+    Add,
+}
+
+impl Bom {
+    /// Whether a byte order mark at the start of the content is accepted
+    pub fn accepts(self) -> bool {
+        self != Bom::Remove
+    }
+
+    /// `fixed` content as it is written, starting with a byte order mark
+    /// when one is added
+    pub fn apply(self, fixed: &str) -> Cow<'_, str> {
+        if self == Bom::Add && !fixed.starts_with(BOM) {
+            Cow::Owned(format!("{}{}", BOM, fixed))
+        } else {
+            Cow::Borrowed(fixed)
+        }
+    }
+}
+
+/// The encoding of content that is not UTF-8
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// UTF-16, little-endian (what Windows calls "Unicode")
+    Utf16Le,
+    /// UTF-16, big-endian
+    Utf16Be,
+    /// Windows-1252: Latin-1 with smart quotes, dashes, and the euro sign in
+    /// bytes 0x80 to 0x9F
+    Windows1252,
+    /// ISO-8859-1 (Latin-1)
+    Latin1,
+    /// None of the above, most often UTF-8 damaged by truncation or a bad
+    /// merge
+    Unknown,
+}
+
+impl Encoding {
+    /// The encoding of `bytes`, or `None` if they are UTF-8
+    pub fn detect(bytes: &[u8]) -> Option<Self> {
+        if std::str::from_utf8(bytes).is_ok() {
+            return None;
+        }
+        if let Some(utf16) = Self::detect_utf16(bytes) {
+            return Some(utf16);
+        }
+
+        // Mostly well-formed UTF-8, or cut-off UTF-8 sequences (a lead byte
+        // and its continuation bytes), is damaged UTF-8; lone bytes outside
+        // ASCII are an 8-bit encoding
+        let (mut utf8, mut lone, mut invalid) = (0, 0, Vec::new());
+        for chunk in bytes.utf8_chunks() {
+            utf8 += chunk.valid().chars().filter(|ch| !ch.is_ascii()).count();
+            match chunk.invalid().len() {
+                0 => {}
+                1 => lone += 1,
+                _ => utf8 += 1,
+            }
+            invalid.extend_from_slice(chunk.invalid());
+        }
+        if utf8 >= lone {
+            return Some(Encoding::Unknown);
+        }
+        // Bytes Windows-1252 leaves undefined
+        if invalid.iter().any(|&b| windows_1252(b).is_none()) {
+            return Some(Encoding::Unknown);
+        }
+        if invalid.iter().any(|b| (0x80..=0x9F).contains(b)) {
+            Some(Encoding::Windows1252)
+        } else {
+            Some(Encoding::Latin1)
+        }
+    }
+
+    /// The UTF-16 encoding of `bytes`, if they look like UTF-16: a byte order
+    /// mark, or a NUL in every other byte, as text in the Latin range has
+    pub fn detect_utf16(bytes: &[u8]) -> Option<Self> {
+        match bytes {
+            [0xFF, 0xFE, ..] => return Some(Encoding::Utf16Le),
+            [0xFE, 0xFF, ..] => return Some(Encoding::Utf16Be),
+            _ => {}
+        }
+        let pairs = bytes.len() / 2;
+        let nuls = |offset: usize| {
+            bytes
+                .iter()
+                .skip(offset)
+                .step_by(2)
+                .filter(|&&b| b == 0)
+                .count()
+        };
+        let (even, odd) = (nuls(0), nuls(1));
+        if odd * 2 > pairs && even * 10 < pairs {
+            Some(Encoding::Utf16Le)
+        } else if even * 2 > pairs && odd * 10 < pairs {
+            Some(Encoding::Utf16Be)
+        } else {
+            None
+        }
+    }
+
+    /// Convert `bytes` in this encoding to UTF-8. A UTF-16 byte order mark
+    /// becomes a UTF-8 one, which fixing removes or keeps as the policy's
+    /// [`Bom`] setting says. `None` for unknown encodings and bytes the
+    /// encoding cannot hold, such as a UTF-16 file of odd length.
+    pub fn transcode(self, bytes: &[u8]) -> Option<String> {
+        match self {
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                if !bytes.len().is_multiple_of(2) {
+                    return None;
+                }
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| match self {
+                        Encoding::Utf16Le => u16::from_le_bytes([pair[0], pair[1]]),
+                        _ => u16::from_be_bytes([pair[0], pair[1]]),
+                    })
+                    .collect();
+                String::from_utf16(&units).ok()
+            }
+            Encoding::Windows1252 => bytes.iter().map(|&b| windows_1252(b)).collect(),
+            Encoding::Latin1 => Some(bytes.iter().map(|&b| char::from(b)).collect()),
+            Encoding::Unknown => None,
+        }
+    }
+
+    /// The encoding's usual name
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Windows1252 => "Windows-1252",
+            Encoding::Latin1 => "ISO-8859-1",
+            Encoding::Unknown => "unknown",
+        }
+    }
+}
+
+/// The character for byte `b` in Windows-1252, which differs from Latin-1 in
+/// 0x80 to 0x9F and leaves five of those bytes undefined
+fn windows_1252(b: u8) -> Option<char> {
+    const HIGH: [Option<char>; 32] = [
+        Some('\u{20ac}'),
+        None,
+        Some('\u{201a}'),
+        Some('\u{192}'),
+        Some('\u{201e}'),
+        Some('\u{2026}'),
+        Some('\u{2020}'),
+        Some('\u{2021}'),
+        Some('\u{2c6}'),
+        Some('\u{2030}'),
+        Some('\u{160}'),
+        Some('\u{2039}'),
+        Some('\u{152}'),
+        None,
+        Some('\u{17d}'),
+        None,
+        None,
+        Some('\u{2018}'),
+        Some('\u{2019}'),
+        Some('\u{201c}'),
+        Some('\u{201d}'),
+        Some('\u{2022}'),
+        Some('\u{2013}'),
+        Some('\u{2014}'),
+        Some('\u{2dc}'),
+        Some('\u{2122}'),
+        Some('\u{161}'),
+        Some('\u{203a}'),
+        Some('\u{153}'),
+        None,
+        Some('\u{17e}'),
+        Some('\u{178}'),
+    ];
+    match b {
+        0x80..=0x9F => HIGH[usize::from(b - 0x80)],
+        _ => Some(char::from(b)),
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Content that is not UTF-8: its bytes, their likely encoding, and where
+/// they first stop being UTF-8
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotUtf8 {
+    pub encoding: Encoding,
+    /// 1-based line of the first byte that is not UTF-8
+    pub line: usize,
+    /// 1-based column of that byte, counting the characters before it on its
+    /// line; `None` for UTF-16, which is not read byte by byte
+    pub column: Option<usize>,
+    bytes: Vec<u8>,
+}
+
+impl NotUtf8 {
+    /// Describe `bytes`, or `None` if they are UTF-8
+    pub fn find(bytes: Vec<u8>) -> Option<Self> {
+        let encoding = Encoding::detect(&bytes)?;
+        if matches!(encoding, Encoding::Utf16Le | Encoding::Utf16Be) {
+            return Some(Self {
+                encoding,
+                line: 1,
+                column: None,
+                bytes,
+            });
+        }
+        let valid = bytes.utf8_chunks().next().map_or("", |chunk| chunk.valid());
+        let (line, before) = match valid.rsplit_once('\n') {
+            Some((above, before)) => (above.matches('\n').count() + 2, before),
+            None => (1, valid),
+        };
+        let column = Some(before.chars().count() + 1);
+        Some(Self {
+            encoding,
+            line,
+            column,
+            bytes,
+        })
+    }
+
+    /// The content converted from its encoding to UTF-8, if it can be
+    pub fn converted(&self) -> Option<String> {
+        self.encoding.transcode(&self.bytes)
+    }
+
+    /// The content read as UTF-8 with each invalid byte sequence replaced by
+    /// U+FFFD, and the violation of the `encoding` rule listing every one of
+    /// those sequences, at the replacement character, with its byte offset
+    pub fn lossy(&self) -> (String, ValidationResult) {
+        let mut text = String::with_capacity(self.bytes.len());
+        let mut errors = Vec::new();
+        let (mut offset, mut line, mut column) = (0, 1, 1);
+        for chunk in self.bytes.utf8_chunks() {
+            for ch in chunk.valid().chars() {
+                (line, column) = if ch == '\n' {
+                    (line + 1, 1)
+                } else {
+                    (line, column + 1)
+                };
+            }
+            text.push_str(chunk.valid());
+            offset += chunk.valid().len();
+            let invalid = chunk.invalid();
+            if invalid.is_empty() {
+                continue;
+            }
+            let hex: Vec<String> = invalid.iter().map(|b| format!("{:02X}", b)).collect();
+            errors.push(
+                ValidationError::new(
+                    line,
+                    format!(
+                        "Invalid UTF-8 byte sequence {} at byte {}",
+                        hex.join(" "),
+                        offset
+                    ),
+                )
+                .with_column(column),
+            );
+            text.push(char::REPLACEMENT_CHARACTER);
+            offset += invalid.len();
+            column += 1;
+        }
+        let name = EncodingRule::METADATA.name.to_string();
+        (text, ValidationResult::fail(name, errors))
+    }
+
+    /// The message reported for the file
+    pub fn message(&self) -> String {
+        match self.encoding {
+            Encoding::Unknown => "Invalid UTF-8 byte sequence".to_string(),
+            encoding => format!("File is {}, expected UTF-8", encoding),
+        }
+    }
+
+    /// The violation of the `encoding` rule
+    pub fn result(&self) -> ValidationResult {
+        let mut error = ValidationError::new(self.line, self.message());
+        if let Some(column) = self.column {
+            error = error.with_column(column);
+        }
+        ValidationResult::fail(EncodingRule::METADATA.name.to_string(), vec![error])
+    }
+}
+
+impl fmt::Display for NotUtf8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message())?;
+        match self.column {
+            Some(column) => write!(f, " (line {}, column {})", self.line, column),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for NotUtf8 {}
+
+/// `bytes` as text, or an `InvalidData` error carrying the [`NotUtf8`] that
+/// describes them
+pub fn decode(bytes: Vec<u8>) -> io::Result<String> {
+    String::from_utf8(bytes).map_err(|e| invalid_data(e.into_bytes()))
+}
+
+/// The `InvalidData` error for `bytes`, which are not UTF-8
+pub fn invalid_data(bytes: Vec<u8>) -> io::Error {
+    match NotUtf8::find(bytes) {
+        Some(found) => io::Error::new(io::ErrorKind::InvalidData, found),
+        None => io::Error::new(
+            io::ErrorKind::InvalidData,
+            "stream did not contain valid UTF-8",
+        ),
+    }
+}
+
+/// The [`NotUtf8`] a read error carries, if the bytes read were not UTF-8
+pub fn not_utf8(error: &io::Error) -> Option<&NotUtf8> {
+    error.get_ref()?.downcast_ref()
+}
+
+/// Take the [`NotUtf8`] out of a read error
+pub fn into_not_utf8(error: io::Error) -> Option<NotUtf8> {
+    error.into_inner()?.downcast().ok().map(|found| *found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16le(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(Encoding::detect("caf\u{e9}\n".as_bytes()), None);
+
+        let mut bom = vec![0xFF, 0xFE];
+        bom.extend(utf16le("# Title\n"));
+        assert_eq!(Encoding::detect(&bom), Some(Encoding::Utf16Le));
+        let unmarked = utf16le("# Caf\u{e9}\n");
+        assert_eq!(Encoding::detect(&unmarked), Some(Encoding::Utf16Le));
+        let big: Vec<u8> = "# Caf\u{e9}\n"
+            .encode_utf16()
+            .flat_map(u16::to_be_bytes)
+            .collect();
+        assert_eq!(Encoding::detect(&big), Some(Encoding::Utf16Be));
+
+        assert_eq!(
+            Encoding::detect(b"It\x92s \x93quoted\x94\n"),
+            Some(Encoding::Windows1252)
+        );
+        assert_eq!(
+            Encoding::detect(b"Caf\xe9 cr\xe8me\n"),
+            Some(Encoding::Latin1)
+        );
+        let damaged = ["\u{2014} \u{2192} ".as_bytes(), b"\xe2\x80"].concat();
+        assert_eq!(Encoding::detect(&damaged), Some(Encoding::Unknown));
+        assert_eq!(Encoding::detect(b"bad \xe2\x80\n"), Some(Encoding::Unknown));
+    }
+
+    #[test]
+    fn test_find_reports_first_bad_byte() {
+        let found = NotUtf8::find(b"# Title\n\nCaf\xe9 \xe9\n".to_vec()).unwrap();
+        assert_eq!(found.encoding, Encoding::Latin1);
+        assert_eq!((found.line, found.column), (3, Some(4)));
+        assert_eq!(found.message(), "File is ISO-8859-1, expected UTF-8");
+
+        let found = NotUtf8::find(utf16le("\u{feff}# Title\n")).unwrap();
+        assert_eq!((found.line, found.column), (1, None));
+        assert_eq!(found.to_string(), "File is UTF-16LE, expected UTF-8");
+        assert!(NotUtf8::find(b"# Title\n".to_vec()).is_none());
+    }
+
+    #[test]
+    fn test_lossy_reports_every_sequence() {
+        let found = NotUtf8::find(b"# T\xc3\xa9\n\nbad \xe2\x80 and \xff!\n".to_vec()).unwrap();
+        let (text, result) = found.lossy();
+        assert_eq!(text, "# T\u{e9}\n\nbad \u{fffd} and \u{fffd}!\n");
+        let errors: Vec<_> = result
+            .errors
+            .iter()
+            .map(|e| (e.line_number, e.column, e.message.to_string()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                (
+                    3,
+                    Some(5),
+                    "Invalid UTF-8 byte sequence E2 80 at byte 11".to_string()
+                ),
+                (
+                    3,
+                    Some(11),
+                    "Invalid UTF-8 byte sequence FF at byte 18".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transcode() {
+        let bom = utf16le("\u{feff}# Caf\u{e9} \u{2192}\r\n");
+        assert_eq!(
+            Encoding::Utf16Le.transcode(&bom).as_deref(),
+            Some("\u{feff}# Caf\u{e9} \u{2192}\r\n")
+        );
+        assert_eq!(Encoding::Utf16Le.transcode(&bom[1..]), None);
+        assert_eq!(
+            Encoding::Windows1252
+                .transcode(b"It\x92s \x93caf\xe9\x94 \x80")
+                .as_deref(),
+            Some("It\u{2019}s \u{201c}caf\u{e9}\u{201d} \u{20ac}")
+        );
+        assert_eq!(Encoding::Windows1252.transcode(b"\x81"), None);
+        assert_eq!(
+            Encoding::Latin1.transcode(b"Caf\xe9").as_deref(),
+            Some("Caf\u{e9}")
+        );
+        assert_eq!(Encoding::Unknown.transcode(b"\xe2\x80"), None);
+    }
+
+    #[test]
+    fn test_decode_errors_carry_the_encoding() {
+        assert_eq!(decode(b"# Title\n".to_vec()).unwrap(), "# Title\n");
+        let error = decode(b"It\x92s\n".to_vec()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(not_utf8(&error).unwrap().encoding, Encoding::Windows1252);
+        assert_eq!(
+            error.to_string(),
+            "File is Windows-1252, expected UTF-8 (line 1, column 3)"
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! External validators from the `[external-validators]` table.
+//!
+//! Prose linters and other markdown linters already exist; rather than
+//! reimplementing them, the checker runs them on each file and reports their
+//! findings alongside its own rules, so one command and one report cover a
+//! docs repository. Each entry names a command and the parser for its output:
+//!
+//! ```toml
+//! [external-validators]
+//! vale = { cmd = "vale --output=JSON {file}", parser = "vale" }
+//! ```
+//!
+//! The command is split into words like a shell would, without running one;
+//! `{file}` is replaced by the file's path, which is appended when absent.
+//! Linters exit non-zero when they find problems, so the exit status only
+//! matters when the output cannot be parsed.
+
+use crate::{Severity, ValidationError, ValidationResult};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::Path;
+use std::process::Command;
+
+/// An `[external-validators]` entry
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExternalValidator {
+    /// Command to run on each file; `{file}` is replaced by its path
+    pub cmd: String,
+    /// Format of the command's output
+    pub parser: OutputParser,
+}
+
+/// Output formats external validators' findings are read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputParser {
+    /// Vale's `--output=JSON`
+    Vale,
+    /// markdownlint's `--json` (markdownlint-cli) or JSON formatter
+    /// (markdownlint-cli2)
+    Markdownlint,
+    /// `path:line:column: message` or `path:line: message` lines, as
+    /// compilers and many linters print them
+    Compact,
+}
+
+/// A violation reported by an external validator
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Finding {
+    line: usize,
+    column: Option<usize>,
+    message: String,
+    severity: Severity,
+}
+
+impl ExternalValidator {
+    /// Run the command on `file` and report its findings as results named
+    /// `name`: one for errors and one for warnings, or a pass if it found
+    /// nothing
+    pub fn run(&self, name: &str, file: &Path) -> Result<Vec<ValidationResult>, String> {
+        let args = command_args(&self.cmd, file)?;
+        let output = Command::new(&args[0])
+            .args(&args[1..])
+            .output()
+            .map_err(|e| {
+                format!(
+                    "Cannot run external validator {} ({}): {}",
+                    name, args[0], e
+                )
+            })?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let findings = self.parser.parse(&stdout).map_err(|e| {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let detail = if output.status.success() || stderr.trim().is_empty() {
+                e
+            } else {
+                stderr.trim().to_string()
+            };
+            format!(
+                "External validator {} failed on {}: {}",
+                name,
+                file.display(),
+                detail
+            )
+        })?;
+        Ok(results(name, findings))
+    }
+}
+
+/// Group findings into a result per severity, errors first
+fn results(name: &str, findings: Vec<Finding>) -> Vec<ValidationResult> {
+    if findings.is_empty() {
+        return vec![ValidationResult::pass(name.to_string())];
+    }
+    [Severity::Error, Severity::Warning]
+        .into_iter()
+        .filter_map(|severity| {
+            let errors: Vec<ValidationError> = findings
+                .iter()
+                .filter(|finding| finding.severity == severity)
+                .map(|finding| {
+                    let error = ValidationError::new(finding.line, finding.message.clone());
+                    match finding.column {
+                        Some(column) => error.with_column(column),
+                        None => error,
+                    }
+                })
+                .collect();
+            (!errors.is_empty())
+                .then(|| ValidationResult::fail(name.to_string(), errors).with_severity(severity))
+        })
+        .collect()
+}
+
+impl OutputParser {
+    fn parse(self, output: &str) -> Result<Vec<Finding>, String> {
+        if output.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut findings = match self {
+            OutputParser::Vale => parse_vale(output)?,
+            OutputParser::Markdownlint => parse_markdownlint(output)?,
+            OutputParser::Compact => parse_compact(output),
+        };
+        findings.sort_by_key(|finding| (finding.line, finding.column));
+        Ok(findings)
+    }
+}
+
+/// `{"path": [{"Line": 3, "Span": [5, 8], "Check": "...", "Message": "...",
+/// "Severity": "error"}]}`; suggestions are reported as warnings
+fn parse_vale(output: &str) -> Result<Vec<Finding>, String> {
+    let json: Value =
+        serde_json::from_str(output).map_err(|e| format!("invalid Vale output: {}", e))?;
+    let files = json
+        .as_object()
+        .ok_or("invalid Vale output: expected an object of files")?;
+    Ok(files
+        .values()
+        .filter_map(Value::as_array)
+        .flatten()
+        .map(|alert| Finding {
+            line: number(&alert["Line"]).unwrap_or(1),
+            column: number(&alert["Span"][0]),
+            message: labeled(&alert["Check"], &alert["Message"]),
+            severity: severity(&alert["Severity"]),
+        })
+        .collect())
+}
+
+/// `[{"lineNumber": 3, "ruleNames": ["MD013", "line-length"],
+/// "ruleDescription": "...", "errorDetail": "...", "errorRange": [81, 40]}]`
+fn parse_markdownlint(output: &str) -> Result<Vec<Finding>, String> {
+    let json: Value =
+        serde_json::from_str(output).map_err(|e| format!("invalid markdownlint output: {}", e))?;
+    let issues = json
+        .as_array()
+        .ok_or("invalid markdownlint output: expected an array of issues")?;
+    Ok(issues
+        .iter()
+        .map(|issue| {
+            let rules: Vec<&str> = issue["ruleNames"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .collect();
+            let mut message = format!(
+                "{} {}",
+                rules.join("/"),
+                issue["ruleDescription"].as_str().unwrap_or_default()
+            );
+            if let Some(detail) = issue["errorDetail"].as_str() {
+                message.push_str(&format!(" [{}]", detail));
+            }
+            Finding {
+                line: number(&issue["lineNumber"]).unwrap_or(1),
+                column: number(&issue["errorRange"][0]),
+                message: message.trim().to_string(),
+                severity: severity(&issue["severity"]),
+            }
+        })
+        .collect())
+}
+
+/// `path:line:column: message` and `path:line: message` lines; others are
+/// ignored. A message starting with `warning` is a warning.
+fn parse_compact(output: &str) -> Vec<Finding> {
+    output.lines().filter_map(compact_line).collect()
+}
+
+fn compact_line(line: &str) -> Option<Finding> {
+    // The path may itself contain colons, as in `C:\docs`, so take the first
+    // `:N:` that is followed by a message
+    let mut rest = line;
+    let (line_number, after) = loop {
+        let (_, after_colon) = rest.split_once(':')?;
+        if let Some((digits, after)) = after_colon.split_once(':')
+            && let Ok(line_number) = digits.parse::<usize>()
+        {
+            break (line_number, after);
+        }
+        rest = after_colon;
+    };
+    let (column, message) = match after.split_once(':') {
+        Some((digits, message)) if digits.parse::<usize>().is_ok() => {
+            (digits.parse().ok(), message)
+        }
+        _ => (None, after),
+    };
+    let message = message.trim();
+    let severity = if message.to_ascii_lowercase().starts_with("warning") {
+        Severity::Warning
+    } else {
+        Severity::Error
+    };
+    Some(Finding {
+        line: line_number.max(1),
+        column,
+        message: message.to_string(),
+        severity,
+    })
+}
+
+fn number(value: &Value) -> Option<usize> {
+    value
+        .as_u64()
+        .and_then(|n| usize::try_from(n).ok())
+        .filter(|&n| n > 0)
+}
+
+/// `check: message`, or the message alone without a check name
+fn labeled(check: &Value, message: &Value) -> String {
+    let message = message.as_str().unwrap_or_default();
+    match check.as_str() {
+        Some(check) => format!("{}: {}", check, message),
+        None => message.to_string(),
+    }
+}
+
+/// Error unless the tool says `warning` or `suggestion`
+fn severity(value: &Value) -> Severity {
+    match value.as_str().map(str::to_ascii_lowercase).as_deref() {
+        Some("warning" | "suggestion" | "info") => Severity::Warning,
+        _ => Severity::Error,
+    }
+}
+
+/// Split `cmd` into words, honoring single and double quotes and
+/// backslashes, and put `file` where `{file}` appears, or at the end
+fn command_args(cmd: &str, file: &Path) -> Result<Vec<String>, String> {
+    let path = file.to_string_lossy();
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote = None;
+    let mut chars = cmd.chars();
+    while let Some(ch) = chars.next() {
+        match (quote, ch) {
+            (None, ch) if ch.is_whitespace() => words.extend(word.take()),
+            (None, '\'' | '"') => {
+                quote = Some(ch);
+                word.get_or_insert_default();
+            }
+            (Some(open), ch) if ch == open => quote = None,
+            (Some('\''), ch) => word.get_or_insert_default().push(ch),
+            (_, '\\') => {
+                let escaped = chars.next().unwrap_or('\\');
+                word.get_or_insert_default().push(escaped);
+            }
+            (_, ch) => word.get_or_insert_default().push(ch),
+        }
+    }
+    if quote.is_some() {
+        return Err(format!("unclosed quote in command: {}", cmd));
+    }
+    words.extend(word);
+    if words.is_empty() {
+        return Err("empty command".to_string());
+    }
+    let mentions_file = words.iter().any(|word| word.contains("{file}"));
+    let mut args: Vec<String> = words
+        .into_iter()
+        .map(|word| word.replace("{file}", &path))
+        .collect();
+    if !mentions_file {
+        args.push(path.into_owned());
+    }
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_args() {
+        let file = Path::new("docs/my notes.md");
+        assert_eq!(
+            command_args("vale --output=JSON {file}", file).unwrap(),
+            ["vale", "--output=JSON", "docs/my notes.md"]
+        );
+        assert_eq!(
+            command_args("lint --config 'a b.yml' \"--x=\\\"y\\\"\"", file).unwrap(),
+            [
+                "lint",
+                "--config",
+                "a b.yml",
+                "--x=\"y\"",
+                "docs/my notes.md"
+            ]
+        );
+        assert_eq!(
+            command_args("tool --file={file} ''", file).unwrap().len(),
+            3
+        );
+        assert!(command_args("tool 'open", file).is_err());
+        assert!(command_args("  ", file).is_err());
+    }
+
+    #[test]
+    fn test_parse_vale() {
+        let output = r#"{"README.md": [
+            {"Check": "Vale.Spelling", "Line": 5, "Message": "Did you really mean 'teh'?",
+             "Severity": "error", "Span": [3, 5]},
+            {"Check": "Google.Passive", "Line": 2, "Message": "Avoid passive voice.",
+             "Severity": "suggestion", "Span": [1, 9]}
+        ]}"#;
+        let findings = OutputParser::Vale.parse(output).unwrap();
+        assert_eq!(findings[0].line, 2);
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert_eq!(findings[1].column, Some(3));
+        assert_eq!(
+            findings[1].message,
+            "Vale.Spelling: Did you really mean 'teh'?"
+        );
+        assert!(OutputParser::Vale.parse("{}\n").unwrap().is_empty());
+        assert!(OutputParser::Vale.parse("[]").is_err());
+    }
+
+    #[test]
+    fn test_parse_markdownlint() {
+        let output = r#"[{"fileName": "README.md", "lineNumber": 3,
+            "ruleNames": ["MD013", "line-length"], "ruleDescription": "Line length",
+            "errorDetail": "Expected: 80; Actual: 95", "errorContext": null,
+            "errorRange": [81, 15]}]"#;
+        let findings = OutputParser::Markdownlint.parse(output).unwrap();
+        assert_eq!(
+            findings,
+            [Finding {
+                line: 3,
+                column: Some(81),
+                message: "MD013/line-length Line length [Expected: 80; Actual: 95]".to_string(),
+                severity: Severity::Error,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_compact() {
+        let output = "C:\\docs\\a.md:4:2: warning: trailing space\n\
+                      a.md:7: missing title\nSummary: 2 problems\n";
+        let findings = OutputParser::Compact.parse(output).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!((findings[0].line, findings[0].column), (4, Some(2)));
+        assert_eq!(findings[0].severity, Severity::Warning);
+        assert_eq!((findings[1].line, findings[1].column), (7, None));
+        assert_eq!(findings[1].message, "missing title");
+    }
+
+    #[test]
+    fn test_results_by_severity() {
+        let finding = |severity| Finding {
+            line: 1,
+            column: None,
+            message: "m".to_string(),
+            severity,
+        };
+        let results = results(
+            "lint",
+            vec![finding(Severity::Warning), finding(Severity::Error)],
+        );
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_error());
+        assert!(results[1].is_fail() && !results[1].is_error());
+        assert!(super::results("lint", Vec::new())[0].is_pass());
+    }
+}
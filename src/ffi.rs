@@ -0,0 +1,227 @@
+//! C ABI for embedding the checker (the `ffi` feature).
+//!
+//! Strings cross the boundary as pointer and length, so content need not be
+//! NUL-terminated; results come back as NUL-terminated strings owned by the
+//! library, released with [`mc_free`]. Calls return an [`McStatus`] numbered
+//! like the command line's exit codes: 0 for success, 1 for error-severity
+//! violations, 2 for invalid arguments. The header is `include/markdown_checker.h`, generated with
+//! `cbindgen --config cbindgen.toml --output include/markdown_checker.h`.
+
+use crate::fixer::fix_allowed;
+use crate::policy::Policy;
+use serde_json::json;
+use std::ffi::{CString, c_char};
+
+/// What a call did, numbered like the command line's exit codes
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum McStatus {
+    /// No error-severity violations, or content fixed
+    Ok = 0,
+    /// Validation found error-severity violations
+    Violations = 1,
+    /// A required pointer was null, the content was not UTF-8, or fixed
+    /// content held a NUL a C string cannot carry
+    InvalidArgument = 2,
+}
+
+/// Check `len` bytes of markdown at `content` with the default rules.
+///
+/// On success `*json` receives a JSON object like `check` in the JavaScript
+/// bindings: `passed` and one entry per rule in `results` with its violations.
+/// Returns `MC_STATUS_OK` or `MC_STATUS_VIOLATIONS`, or
+/// `MC_STATUS_INVALID_ARGUMENT` without touching `*json`.
+///
+/// # Safety
+///
+/// `content` must point to `len` readable bytes, and `json` to writable
+/// storage for a pointer. Free the string written to `*json` with `mc_free`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mc_validate(
+    content: *const c_char,
+    len: usize,
+    json: *mut *mut c_char,
+) -> McStatus {
+    // SAFETY: the caller guarantees `content` points to `len` bytes
+    let Some(content) = (unsafe { content_str(content, len) }) else {
+        return McStatus::InvalidArgument;
+    };
+    if json.is_null() {
+        return McStatus::InvalidArgument;
+    }
+    let results = Policy::default().validate(content);
+    let passed = !results.iter().any(|result| result.is_error());
+    let report = json!({ "passed": passed, "results": results }).to_string();
+    // JSON escapes NUL, so the report always fits in a C string
+    let report = CString::new(report).expect("JSON has no raw NUL");
+    // SAFETY: checked non-null; the caller guarantees it is writable
+    unsafe { *json = report.into_raw() };
+    if passed {
+        McStatus::Ok
+    } else {
+        McStatus::Violations
+    }
+}
+
+/// Replace every auto-fixable character in `len` bytes of markdown at
+/// `content` with its ASCII equivalent.
+///
+/// On success `*fixed` receives the fixed content and, unless `replaced` is
+/// null, `*replaced` the number of characters replaced. Violations without a
+/// fix remain; `mc_validate` the result to find them. Returns `MC_STATUS_OK`, or
+/// `MC_STATUS_INVALID_ARGUMENT` without touching the outputs, including when
+/// the content holds a NUL that the fixed C string could not carry.
+///
+/// # Safety
+///
+/// `content` must point to `len` readable bytes, `fixed` to writable storage
+/// for a pointer, and `replaced` must be null or writable. Free the string
+/// written to `*fixed` with `mc_free`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mc_fix(
+    content: *const c_char,
+    len: usize,
+    fixed: *mut *mut c_char,
+    replaced: *mut usize,
+) -> McStatus {
+    // SAFETY: the caller guarantees `content` points to `len` bytes
+    let Some(content) = (unsafe { content_str(content, len) }) else {
+        return McStatus::InvalidArgument;
+    };
+    if fixed.is_null() {
+        return McStatus::InvalidArgument;
+    }
+    let (text, count) = fix_allowed(content, &Policy::default());
+    // No rule strips U+0000, and C would read the text only up to it
+    let Ok(text) = CString::new(text) else {
+        return McStatus::InvalidArgument;
+    };
+    // SAFETY: checked non-null; the caller guarantees both are writable
+    unsafe {
+        *fixed = text.into_raw();
+        if !replaced.is_null() {
+            *replaced = count;
+        }
+    }
+    McStatus::Ok
+}
+
+/// Release a string returned by `mc_validate` or `mc_fix`. Null is ignored.
+///
+/// # Safety
+///
+/// `string` must be null or a pointer this library returned that has not
+/// been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mc_free(string: *mut c_char) {
+    if !string.is_null() {
+        // SAFETY: the caller guarantees it came from `CString::into_raw`
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Borrow `len` bytes at `content` as UTF-8, or `None` if null or invalid
+unsafe fn content_str<'a>(content: *const c_char, len: usize) -> Option<&'a str> {
+    if content.is_null() {
+        // An empty buffer may come without one
+        return (len == 0).then_some("");
+    }
+    // SAFETY: the caller guarantees `content` points to `len` readable bytes
+    let bytes = unsafe { std::slice::from_raw_parts(content.cast::<u8>(), len) };
+    std::str::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+    use std::ptr::{self, null_mut};
+
+    fn validate(content: &[u8]) -> (McStatus, String) {
+        let mut json = null_mut();
+        let code = unsafe { mc_validate(content.as_ptr().cast(), content.len(), &mut json) };
+        let report = unsafe { CStr::from_ptr(json) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { mc_free(json) };
+        (code, report)
+    }
+
+    #[test]
+    fn test_validate() {
+        let (code, report) = validate(b"# Title\n");
+        assert_eq!(code, McStatus::Ok);
+        assert!(report.starts_with("{\"passed\":true"));
+
+        let (code, report) = validate("a \u{2192} b\n".as_bytes());
+        assert_eq!(code, McStatus::Violations);
+        assert!(report.contains("\"line_number\":1"));
+    }
+
+    #[test]
+    fn test_fix() {
+        let content = "\u{251c}\u{2500}\u{2500} src\n";
+        let mut fixed = null_mut();
+        let mut replaced = 0;
+        let code = unsafe {
+            mc_fix(
+                content.as_ptr().cast(),
+                content.len(),
+                &mut fixed,
+                &mut replaced,
+            )
+        };
+        assert_eq!(code, McStatus::Ok);
+        assert_eq!(unsafe { CStr::from_ptr(fixed) }.to_str(), Ok("+-- src\n"));
+        assert_eq!(replaced, 3);
+        unsafe { mc_free(fixed) };
+    }
+
+    #[test]
+    fn test_fix_rejects_embedded_nul() {
+        let content = "a\0b \u{251c}\n";
+        let mut fixed = null_mut();
+        let mut replaced = 7;
+        let code = unsafe {
+            mc_fix(
+                content.as_ptr().cast(),
+                content.len(),
+                &mut fixed,
+                &mut replaced,
+            )
+        };
+        assert_eq!(code, McStatus::InvalidArgument);
+        assert!(fixed.is_null());
+        assert_eq!(replaced, 7);
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let mut json = null_mut();
+        let invalid = [0xff, 0xfe];
+        assert_eq!(
+            unsafe { mc_validate(invalid.as_ptr().cast(), invalid.len(), &mut json) },
+            McStatus::InvalidArgument
+        );
+        assert!(json.is_null());
+        assert_eq!(
+            unsafe { mc_validate(ptr::null_mut(), 1, &mut json) },
+            McStatus::InvalidArgument
+        );
+        assert_eq!(
+            unsafe { mc_fix(b"a".as_ptr().cast(), 1, null_mut(), null_mut()) },
+            McStatus::InvalidArgument
+        );
+        // Empty content may be passed as null
+        assert_eq!(validate_empty(), McStatus::Ok);
+        unsafe { mc_free(null_mut()) };
+    }
+
+    fn validate_empty() -> McStatus {
+        let mut json = null_mut();
+        let code = unsafe { mc_validate(ptr::null_mut(), 0, &mut json) };
+        unsafe { mc_free(json) };
+        code
+    }
+}
@@ -1,15 +1,319 @@
+use crate::encoding::{Bom, Encoding, decode, into_not_utf8, invalid_data};
+use crate::line_endings::LineEnding;
+use std::fmt;
 use std::fs;
-use std::io;
-use std::path::Path;
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::ops::Deref;
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
 
-/// Read file content and validate it's UTF-8
+/// A file's text, either read into memory or mapped from disk
+pub enum FileContent {
+    Read(String),
+    /// Mapped bytes, already checked to be UTF-8
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl FileContent {
+    /// Map the file into memory instead of copying it, falling back to
+    /// reading it where mapping is unsupported or fails
+    pub fn map(path: &Path) -> io::Result<Self> {
+        #[cfg(feature = "mmap")]
+        if let Some(map) = map_file(path) {
+            std::str::from_utf8(&map).map_err(|_| invalid_data(map.to_vec()))?;
+            return Ok(Self::Mapped(map));
+        }
+        read_file_content(path).map(Self::Read)
+    }
+
+    /// Whether the content is mapped rather than read
+    pub fn is_mapped(&self) -> bool {
+        !matches!(self, Self::Read(_))
+    }
+
+    pub fn into_string(self) -> String {
+        match self {
+            Self::Read(content) => content,
+            #[cfg(feature = "mmap")]
+            Self::Mapped(_) => self.to_string(),
+        }
+    }
+}
+
+impl From<String> for FileContent {
+    fn from(content: String) -> Self {
+        Self::Read(content)
+    }
+}
+
+impl Deref for FileContent {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match self {
+            Self::Read(content) => content,
+            // SAFETY: `map` checked the bytes are UTF-8 when mapping them
+            #[cfg(feature = "mmap")]
+            Self::Mapped(map) => unsafe { std::str::from_utf8_unchecked(map) },
+        }
+    }
+}
+
+/// Map a file read-only, or `None` if it is empty or cannot be mapped
+#[cfg(feature = "mmap")]
+fn map_file(path: &Path) -> Option<memmap2::Mmap> {
+    let file = fs::File::open(path).ok()?;
+    // Zero-length mappings fail on some platforms
+    if file.metadata().ok()?.len() == 0 {
+        return None;
+    }
+    // SAFETY: the map is read-only and the checker does not write a file
+    // while its content is mapped; another process truncating the file
+    // mid-check is the same hazard every mmap-based tool accepts
+    unsafe { memmap2::Mmap::map(&file) }.ok()
+}
+
+/// Read file content and validate it's UTF-8. Content that is not fails with
+/// an `InvalidData` error carrying its likely encoding
+/// ([`crate::encoding::not_utf8`]).
 pub fn read_file_content(path: &Path) -> io::Result<String> {
-    fs::read_to_string(path)
+    decode(fs::read(path)?)
+}
+
+/// Open a file for reading line by line
+pub fn open_buffered(path: &Path) -> io::Result<BufReader<fs::File>> {
+    fs::File::open(path).map(BufReader::new)
+}
+
+/// How much of a file is read to tell whether it is binary
+const SNIFF_LEN: u64 = 8192;
+
+/// Magic numbers of binary formats likely to sit beside documentation
+const MAGIC_NUMBERS: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "PNG image"),
+    (b"\xff\xd8\xff", "JPEG image"),
+    (b"GIF87a", "GIF image"),
+    (b"GIF89a", "GIF image"),
+    (b"%PDF-", "PDF document"),
+    (b"PK\x03\x04", "ZIP archive"),
+    (b"\x1f\x8b", "gzip archive"),
+    (b"\x7fELF", "ELF executable"),
+    (b"\0asm", "WebAssembly module"),
+];
+
+/// What kind of binary content `head`, the start of a file, is: a known
+/// magic number, or a NUL byte outside UTF-16 text
+pub fn binary_kind(head: &[u8]) -> Option<&'static str> {
+    if let Some((_, kind)) = MAGIC_NUMBERS
+        .iter()
+        .find(|(magic, _)| head.starts_with(magic))
+    {
+        return Some(kind);
+    }
+    (head.contains(&0) && Encoding::detect_utf16(head).is_none()).then_some("binary data")
+}
+
+/// What kind of binary file `path` is, if it obviously is one
+pub fn sniff_binary(path: &Path) -> io::Result<Option<&'static str>> {
+    let mut head = Vec::new();
+    fs::File::open(path)?
+        .take(SNIFF_LEN)
+        .read_to_end(&mut head)?;
+    Ok(binary_kind(&head))
 }
 
-/// Write content to file
+/// Size of a file in bytes
+pub fn file_size(path: &Path) -> io::Result<u64> {
+    fs::metadata(path).map(|metadata| metadata.len())
+}
+
+/// Files larger than this are skipped unless `--max-file-size` or
+/// `--force-large` says otherwise
+pub const DEFAULT_MAX_FILE_SIZE: FileSize = FileSize(10 * 1024 * 1024);
+
+/// A number of bytes, written with an optional K, M, or G suffix (KiB, MiB,
+/// or GiB) in options and configuration files
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(try_from = "String")]
+pub struct FileSize(pub u64);
+
+impl FromStr for FileSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "invalid size '{}': expected a number of bytes, optionally followed by K, M, or G",
+                s
+            )
+        };
+        let digits = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (number, unit) = s.split_at(digits);
+        let number: u64 = number.parse().map_err(|_| invalid())?;
+        let unit = unit.trim().to_ascii_uppercase();
+        let shift = match unit.trim_end_matches("IB").trim_end_matches('B') {
+            "" => 0,
+            "K" => 10,
+            "M" => 20,
+            "G" => 30,
+            _ => return Err(invalid()),
+        };
+        number
+            .checked_mul(1 << shift)
+            .map(FileSize)
+            .ok_or_else(|| format!("size '{}' is too large", s))
+    }
+}
+
+impl TryFrom<String> for FileSize {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for FileSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (unit, shift) in [("GiB", 30), ("MiB", 20), ("KiB", 10)] {
+            if self.0 >= 1 << shift {
+                return if self.0.is_multiple_of(1 << shift) {
+                    write!(f, "{} {}", self.0 >> shift, unit)
+                } else {
+                    let size = self.0 as f64 / (1u64 << shift) as f64;
+                    write!(f, "{:.1} {}", size, unit)
+                };
+            }
+        }
+        write!(f, "{} bytes", self.0)
+    }
+}
+
+/// Write content to file, keeping the permissions (such as the executable
+/// bit) of the file it replaces
 pub fn write_file_content(path: &Path, content: &str) -> io::Result<()> {
-    fs::write(path, content)
+    let permissions = fs::metadata(path)
+        .ok()
+        .map(|metadata| metadata.permissions());
+    fs::write(path, content)?;
+    match permissions {
+        Some(permissions) => fs::set_permissions(path, permissions),
+        None => Ok(()),
+    }
+}
+
+/// Replace the content of `path`, checked as `checked` (after any conversion
+/// to UTF-8), with `fixed` while holding an exclusive advisory lock on it, so
+/// concurrent fixing runs (a watch and a manual run, say) cannot interleave
+/// their writes. The file is read again under the lock: if another run has
+/// already written `fixed` there is nothing to do, and if it holds anything
+/// else but `checked` it is left alone with an error. The file is rewritten
+/// in place, so its permissions stay, and with `keep_mtime` so does its
+/// modification time, for build tools that compare timestamps
+pub fn write_fixed_content(
+    path: &Path,
+    checked: &str,
+    fixed: &str,
+    keep_mtime: bool,
+) -> io::Result<()> {
+    let mut file = fs::File::options().read(true).write(true).open(path)?;
+    match file.lock() {
+        Err(e) if e.kind() == io::ErrorKind::Unsupported => {}
+        locked => locked?,
+    }
+    let modified = file.metadata()?.modified();
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let (current, utf8) = match decode(bytes) {
+        Ok(content) => (Some(content), true),
+        Err(e) => (into_not_utf8(e).and_then(|found| found.converted()), false),
+    };
+    match current.as_deref() {
+        // Content converted from another encoding still has to be written
+        Some(current) if utf8 && current == fixed => return Ok(()),
+        Some(current) if current == checked => {}
+        _ => return Err(io::Error::other("file changed while it was being checked")),
+    }
+    file.seek(SeekFrom::Start(0))?;
+    file.set_len(0)?;
+    file.write_all(fixed.as_bytes())?;
+    if keep_mtime {
+        file.set_modified(modified?)?;
+    }
+    Ok(())
+}
+
+/// Write `fixed`, the fix of `checked`, to `path` as every fixing front end
+/// does: with the line endings of `eol` when given and the byte order mark
+/// `bom` adds, through [`write_fixed_content`]. Returns the text written.
+pub fn write_fix(
+    path: &Path,
+    checked: &str,
+    fixed: &str,
+    bom: Bom,
+    eol: Option<LineEnding>,
+    keep_mtime: bool,
+) -> io::Result<String> {
+    let fixed = match eol {
+        Some(eol) => eol.apply(fixed),
+        None => fixed.into(),
+    };
+    let fixed = bom.apply(&fixed).into_owned();
+    write_fixed_content(path, checked, &fixed, keep_mtime)?;
+    Ok(fixed)
+}
+
+/// Read a newline-separated list of paths from a file, or from stdin when
+/// `source` is `-`
+pub fn read_file_list(source: &Path) -> io::Result<Vec<PathBuf>> {
+    let text = if source == Path::new("-") {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        text
+    } else {
+        fs::read_to_string(source)?
+    };
+    Ok(parse_file_list(&text))
+}
+
+/// Parse one path per line, ignoring blank lines and surrounding whitespace
+pub fn parse_file_list(text: &str) -> Vec<PathBuf> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
+/// Number of path components of `path` below `root`, so a file directly
+/// inside `root` has depth 1
+pub fn path_depth(path: &Path, root: &Path) -> usize {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .count()
+}
+
+/// Rewrite a glob pattern so each `**/` matches at most a bounded number of
+/// directories, keeping the total match depth within `max_depth`.
+///
+/// The `glob` crate has no depth limit, so `**/` is expanded into explicit
+/// `*/` sequences. This keeps traversal out of deeply nested trees; callers
+/// should still filter results with [`path_depth`] since the bound is loose.
+pub fn depth_limited_patterns(pattern: &str, max_depth: usize) -> Vec<String> {
+    let Some((head, tail)) = pattern.split_once("**/") else {
+        return vec![pattern.to_string()];
+    };
+    (0..max_depth)
+        .flat_map(|extra| {
+            let prefix = format!("{}{}", head, "*/".repeat(extra));
+            depth_limited_patterns(tail, max_depth - extra)
+                .into_iter()
+                .map(move |rest| format!("{}{}", prefix, rest))
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -40,7 +344,145 @@ mod tests {
         // Write invalid UTF-8 bytes
         temp_file.write_all(&[0xFF, 0xFE, 0xFD]).unwrap();
 
-        let result = read_file_content(temp_file.path());
-        assert!(result.is_err());
+        let error = read_file_content(temp_file.path()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            crate::encoding::not_utf8(&error).map(|found| found.encoding),
+            Some(crate::encoding::Encoding::Utf16Le)
+        );
+    }
+
+    #[test]
+    fn test_map_file_content() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "caf\u{e9}").unwrap();
+        let content = FileContent::map(temp_file.path()).unwrap();
+        assert_eq!(&*content, "caf\u{e9}\n");
+        assert_eq!(content.is_mapped(), cfg!(feature = "mmap"));
+
+        let empty = NamedTempFile::new().unwrap();
+        let content = FileContent::map(empty.path()).unwrap();
+        assert!(!content.is_mapped());
+        assert_eq!(&*content, "");
+
+        temp_file.write_all(&[0xFF]).unwrap();
+        let error = FileContent::map(temp_file.path()).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_parse_file_list() {
+        let list = parse_file_list("README.md\n\n  docs/a.md  \r\ndocs/b.md\n");
+        assert_eq!(
+            list,
+            vec![
+                PathBuf::from("README.md"),
+                PathBuf::from("docs/a.md"),
+                PathBuf::from("docs/b.md"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_file_list_from_file() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "a.md\nb.md").unwrap();
+
+        let list = read_file_list(temp_file.path()).unwrap();
+        assert_eq!(list, vec![PathBuf::from("a.md"), PathBuf::from("b.md")]);
+    }
+
+    #[test]
+    fn test_path_depth() {
+        assert_eq!(path_depth(Path::new("./a.md"), Path::new(".")), 1);
+        assert_eq!(path_depth(Path::new("docs/api/a.md"), Path::new("docs")), 2);
+        assert_eq!(path_depth(Path::new("a/b/c.md"), Path::new("other")), 3);
+    }
+
+    #[test]
+    fn test_depth_limited_patterns() {
+        assert_eq!(depth_limited_patterns("*.md", 2), vec!["*.md"]);
+        assert_eq!(
+            depth_limited_patterns("**/*.md", 3),
+            vec!["*.md", "*/*.md", "*/*/*.md"]
+        );
+        assert_eq!(
+            depth_limited_patterns("docs/**/*.md", 2),
+            vec!["docs/*.md", "docs/*/*.md"]
+        );
+        assert!(depth_limited_patterns("**/*.md", 0).is_empty());
+    }
+
+    #[test]
+    fn test_depth_limited_patterns_nested() {
+        let patterns = depth_limited_patterns("**/api/**/*.md", 2);
+        assert_eq!(patterns, vec!["api/*.md", "api/*/*.md", "*/api/*.md"]);
+    }
+
+    #[test]
+    fn test_binary_kind() {
+        assert_eq!(binary_kind(b"\x89PNG\r\n\x1a\n\0\0"), Some("PNG image"));
+        assert_eq!(binary_kind(b"%PDF-1.7\n"), Some("PDF document"));
+        assert_eq!(binary_kind(b"text\0with a NUL"), Some("binary data"));
+        assert_eq!(binary_kind(b"# Title\n\nPlain text.\n"), None);
+        // UTF-16 has NULs but is text, reported by the encoding rule
+        assert_eq!(binary_kind(b"#\0 \0T\0i\0t\0l\0e\0"), None);
+        assert_eq!(binary_kind(b"\xff\xfe#\0"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+        let temp_file = NamedTempFile::new().unwrap();
+        fs::set_permissions(temp_file.path(), fs::Permissions::from_mode(0o755)).unwrap();
+        write_file_content(temp_file.path(), "#!/bin/sh\n").unwrap();
+        let mode = fs::metadata(temp_file.path()).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[test]
+    fn test_write_fixed_content_keeping_mtime() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "caf\u{e9}, checked\r\n").unwrap();
+        let modified =
+            std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+        temp_file.as_file().set_modified(modified).unwrap();
+        let path = temp_file.path();
+        write_fixed_content(path, "caf\u{e9}, checked\r\n", "cafe, fixed\r\n", true).unwrap();
+        assert_eq!(fs::metadata(path).unwrap().modified().unwrap(), modified);
+        assert_eq!(read_file_content(path).unwrap(), "cafe, fixed\r\n");
+    }
+
+    #[test]
+    fn test_write_fixed_content_checks_for_changes() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "edited since").unwrap();
+        let path = temp_file.path();
+        assert!(write_fixed_content(path, "checked\n", "fixed\n", false).is_err());
+        assert_eq!(read_file_content(path).unwrap(), "edited since\n");
+        // Another run got there first with the same fix
+        write_fixed_content(path, "checked\n", "edited since\n", false).unwrap();
+        // Checked content converted from another encoding
+        fs::write(path, b"caf\xe9\n").unwrap();
+        write_fixed_content(path, "caf\u{e9}\n", "cafe\n", false).unwrap();
+        assert_eq!(read_file_content(path).unwrap(), "cafe\n");
+        // Converting is a fix even when no character changes
+        fs::write(path, b"caf\xe9\n").unwrap();
+        write_fixed_content(path, "caf\u{e9}\n", "caf\u{e9}\n", false).unwrap();
+        assert_eq!(read_file_content(path).unwrap(), "caf\u{e9}\n");
+    }
+
+    #[test]
+    fn test_file_size() {
+        assert_eq!("512".parse(), Ok(FileSize(512)));
+        assert_eq!("10M".parse(), Ok(FileSize(10 * 1024 * 1024)));
+        assert_eq!("64 KiB".parse(), Ok(FileSize(64 * 1024)));
+        assert_eq!("2gb".parse(), Ok(FileSize(2 << 30)));
+        assert!("10 MB of text".parse::<FileSize>().is_err());
+        assert!("M".parse::<FileSize>().is_err());
+        assert_eq!(DEFAULT_MAX_FILE_SIZE.to_string(), "10 MiB");
+        assert_eq!(FileSize(1536).to_string(), "1.5 KiB");
+        assert_eq!(FileSize(100).to_string(), "100 bytes");
     }
 }
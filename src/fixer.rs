@@ -3,86 +3,411 @@
 //! Currently supports:
 //! - Tree symbols: Replaces Unicode box-drawing characters with ASCII equivalents
 //! - Common Unicode characters: Checkmarks, arrows, accented letters, etc.
+//!
+//! A fix only replaces characters with their [`replacement`], within lines,
+//! and never where a replacement word would run into the text around it
+//! (`20\u{b0}C` is left alone rather than read `20 degreesC`). Fixing fixed
+//! content changes nothing more. [`verify_roundtrip`] checks these
+//! properties on any content, for tests and the `fix_roundtrip` fuzz target.
+
+use crate::policy::Policy;
+use crate::suppressions::Suppressions;
+use crate::validators::{find_char_rule, find_rule};
+use std::fmt;
 
 /// Fixes tree symbols and common Unicode characters by replacing them with ASCII equivalents.
 pub fn fix_tree_symbols(content: &str) -> String {
     let mut fixed = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        fix_line(line, &mut fixed, |_, _| false);
+    }
+    fixed
+}
+
+/// Number of characters [`fix_completely`] replaces in `content`
+pub fn count_replacements(content: &str, policy: &Policy) -> usize {
+    fix_allowed(content, policy).1
+}
+
+/// Fix every character except those the policy allows or exempts as code or
+/// frontmatter and those on lines exempted by suppression comments. Returns the fixed content
+/// and the number of characters replaced.
+pub fn fix_allowed(content: &str, policy: &Policy) -> (String, usize) {
+    let suppressions = Suppressions::parse(content);
+    let exempt = policy.exempt_regions(content);
+    let mut fixed = String::with_capacity(content.len());
+    let mut replaced = 0;
 
-    for ch in content.chars() {
-        match ch {
-            // Box-drawing characters to ASCII (single char)
-            '├' | '┤' | '┼' | '┬' | '┴' | '╋' => fixed.push('+'),
-            '└' | '┘' | '┌' | '┐' | '╰' | '╯' | '╭' | '╮' => fixed.push('+'),
-            '│' | '┃' | '║' => fixed.push('|'),
-            '─' | '━' | '═' => fixed.push('-'),
-
-            // Common Unicode symbols to ASCII (multi-char)
-            '✓' | '✔' | '✅' | '❌' | '✗' | '✘' => fixed.push_str("[x]"),
-            '→' | '⇒' | '⟶' | '➔' | '➜' | '➡' => fixed.push_str("->"),
-            '←' | '⇐' | '⟵' | '➘' => fixed.push_str("<-"),
-            '⬆' | '⇧' | '↑' => fixed.push('^'),
-            '⬇' | '⇩' | '↓' => fixed.push('v'),
-            '•' | '·' | '●' => fixed.push('*'),
-            '…' => fixed.push_str("..."),
-            '©' => fixed.push_str("(c)"),
-            '®' => fixed.push_str("(R)"),
-            '™' => fixed.push_str("(TM)"),
-            '§' => fixed.push('S'),
-            '°' => fixed.push_str(" degrees"),
-            '±' => fixed.push_str("+/-"),
-            '×' => fixed.push('x'),
-            '÷' => fixed.push('/'),
-            '≤' => fixed.push_str("<="),
-            '≥' => fixed.push_str(">="),
-            '≠' => fixed.push_str("!="),
-            '≈' => fixed.push_str("~="),
-            '∞' => fixed.push_str("infinity"),
-            '🚧' => fixed.push_str("[WIP]"),
-            '⚠' | '⚡' => fixed.push_str("[!]"),
-            'α' => fixed.push_str("alpha"),
-            'β' => fixed.push_str("beta"),
-            'γ' => fixed.push_str("gamma"),
-            'δ' => fixed.push_str("delta"),
-            'π' => fixed.push_str("pi"),
-            'Σ' => fixed.push_str("Sigma"),
-            'µ' => fixed.push_str("micro"),
-
-            // Accented letters (common European)
-            'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' | 'ā' => fixed.push('a'),
-            'é' | 'è' | 'ê' | 'ë' | 'ē' => fixed.push('e'),
-            'í' | 'ì' | 'î' | 'ï' | 'ī' => fixed.push('i'),
-            'ó' | 'ò' | 'ô' | 'ö' | 'õ' | 'ō' => fixed.push('o'),
-            'ú' | 'ù' | 'û' | 'ü' | 'ū' => fixed.push('u'),
-            'ý' | 'ÿ' => fixed.push('y'),
-            'ñ' => fixed.push('n'),
-            'ç' => fixed.push('c'),
-            'Á' | 'À' | 'Â' | 'Ä' | 'Ã' | 'Å' | 'Ā' => fixed.push('A'),
-            'É' | 'È' | 'Ê' | 'Ë' | 'Ē' => fixed.push('E'),
-            'Í' | 'Ì' | 'Î' | 'Ï' | 'Ī' => fixed.push('I'),
-            'Ó' | 'Ò' | 'Ô' | 'Ö' | 'Õ' | 'Ō' => fixed.push('O'),
-            'Ú' | 'Ù' | 'Û' | 'Ü' | 'Ū' => fixed.push('U'),
-            'Ý' | 'Ÿ' => fixed.push('Y'),
-            'Ñ' => fixed.push('N'),
-            'Ç' => fixed.push('C'),
-
-            // Quotation marks
-            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => fixed.push('"'), // Smart double quotes (", ", „, ‟)
-            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => fixed.push('\''), // Smart single quotes (', ', ‚, ‛)
-            '«' | '»' => fixed.push('"'),
-
-            // Dashes
-            '–' => fixed.push('-'),
-            '—' => fixed.push_str("--"),
-
-            // Fallback for other box-drawing chars
-            _ if is_box_drawing(ch) => fixed.push('+'),
-
-            // Keep everything else as-is
-            _ => fixed.push(ch),
+    for (index, line) in content.split_inclusive('\n').enumerate() {
+        if suppressions.covers_line(index + 1) {
+            fixed.push_str(line);
+            continue;
         }
+        replaced += fix_line(line, &mut fixed, |column, ch| {
+            policy.allows(ch) || exempt.contains(index + 1, Some(column + 1))
+        });
     }
 
-    fixed
+    (fixed, replaced)
+}
+
+/// Append `line` to `fixed` with every character replaced but those `keep`
+/// accepts at their 0-based column. Returns the number replaced.
+fn fix_line(line: &str, fixed: &mut String, keep: impl Fn(usize, char) -> bool) -> usize {
+    let mut replaced = 0;
+    let mut chars = line.chars().enumerate();
+    while let Some((column, ch)) = chars.next() {
+        let fixable = replacement(ch).filter(|ascii| {
+            !keep(column, ch)
+                && !joins(
+                    fixed.chars().next_back(),
+                    ascii,
+                    fixed_after(chars.clone(), &keep),
+                )
+        });
+        match fixable {
+            Some(ascii) => {
+                fixed.push_str(ascii);
+                replaced += 1;
+            }
+            None => fixed.push(ch),
+        }
+    }
+    replaced
+}
+
+/// The first character the rest of a line is fixed to, skipping characters
+/// fixing removes, given the characters with their 0-based columns
+fn fixed_after(
+    mut rest: impl Iterator<Item = (usize, char)>,
+    keep: impl Fn(usize, char) -> bool,
+) -> Option<char> {
+    rest.find_map(|(column, next)| match replacement(next) {
+        Some(ascii) if !keep(column, next) => ascii.chars().next(),
+        _ => Some(next),
+    })
+}
+
+/// Whether a replacement is a word (`"alpha"`, `" degrees"`) rather than
+/// letters or symbols standing for the character (`"e"`, `"(c)"`)
+fn is_word(ascii: &str) -> bool {
+    let word = ascii.trim();
+    word.len() > 1 && word.bytes().all(|byte| byte.is_ascii_alphabetic())
+}
+
+/// Whether the replacement `ascii` runs into a letter or digit written
+/// `before` or `after` it, changing the words around it
+fn joins(before: Option<char>, ascii: &str, after: Option<char>) -> bool {
+    let touches = |edge: Option<char>, neighbour: Option<char>| {
+        edge.is_some_and(|edge| edge.is_ascii_alphabetic())
+            && neighbour.is_some_and(char::is_alphanumeric)
+    };
+    is_word(ascii)
+        && (touches(ascii.chars().next(), before) || touches(ascii.chars().next_back(), after))
+}
+
+/// Replacement for `ch` between the characters `before` and `after` it, or
+/// `None` when it has none or its replacement word would run into them
+pub fn replacement_between(
+    before: Option<char>,
+    ch: char,
+    after: Option<char>,
+) -> Option<&'static str> {
+    replacement(ch).filter(|ascii| !joins(before, ascii, after))
+}
+
+/// Replacement for the character at a 1-based column (counted in
+/// characters) of `line` if it were the only one fixed
+pub fn replacement_in(line: &str, column: usize) -> Option<&'static str> {
+    let index = column.checked_sub(1)?;
+    let mut chars = line.chars().skip(index.saturating_sub(1));
+    let before = if index > 0 { chars.next() } else { None };
+    let ch = chars.next()?;
+    replacement_between(before, ch, chars.next())
+}
+
+/// ASCII replacement for a fixable character, or `None` to keep it as-is
+pub fn replacement(ch: char) -> Option<&'static str> {
+    match ch {
+        // Box-drawing characters to ASCII (single char)
+        '├' | '┤' | '┼' | '┬' | '┴' | '╋' => Some("+"),
+        '└' | '┘' | '┌' | '┐' | '╰' | '╯' | '╭' | '╮' => Some("+"),
+        '│' | '┃' | '║' => Some("|"),
+        '─' | '━' | '═' => Some("-"),
+
+        // Common Unicode symbols to ASCII (multi-char)
+        '✓' | '✔' | '✅' | '❌' | '✗' | '✘' => Some("[x]"),
+        '→' | '⇒' | '⟶' | '➔' | '➜' | '➡' => Some("->"),
+        '←' | '⇐' | '⟵' | '➘' => Some("<-"),
+        '⬆' | '⇧' | '↑' => Some("^"),
+        '⬇' | '⇩' | '↓' => Some("v"),
+        '•' | '·' | '●' => Some("*"),
+        '…' => Some("..."),
+        '©' => Some("(c)"),
+        '®' => Some("(R)"),
+        '™' => Some("(TM)"),
+        '§' => Some("S"),
+        '°' => Some(" degrees"),
+        '±' => Some("+/-"),
+        '×' => Some("x"),
+        '÷' => Some("/"),
+        '≤' => Some("<="),
+        '≥' => Some(">="),
+        '≠' => Some("!="),
+        '≈' => Some("~="),
+        '∞' => Some("infinity"),
+        '🚧' => Some("[WIP]"),
+        '⚠' | '⚡' => Some("[!]"),
+        'α' => Some("alpha"),
+        'β' => Some("beta"),
+        'γ' => Some("gamma"),
+        'δ' => Some("delta"),
+        'π' => Some("pi"),
+        'Σ' => Some("Sigma"),
+        'µ' => Some("micro"),
+
+        // Accented letters (common European)
+        'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' | 'ā' => Some("a"),
+        'é' | 'è' | 'ê' | 'ë' | 'ē' => Some("e"),
+        'í' | 'ì' | 'î' | 'ï' | 'ī' => Some("i"),
+        'ó' | 'ò' | 'ô' | 'ö' | 'õ' | 'ō' => Some("o"),
+        'ú' | 'ù' | 'û' | 'ü' | 'ū' => Some("u"),
+        'ý' | 'ÿ' => Some("y"),
+        'ñ' => Some("n"),
+        'ç' => Some("c"),
+        'Á' | 'À' | 'Â' | 'Ä' | 'Ã' | 'Å' | 'Ā' => Some("A"),
+        'É' | 'È' | 'Ê' | 'Ë' | 'Ē' => Some("E"),
+        'Í' | 'Ì' | 'Î' | 'Ï' | 'Ī' => Some("I"),
+        'Ó' | 'Ò' | 'Ô' | 'Ö' | 'Õ' | 'Ō' => Some("O"),
+        'Ú' | 'Ù' | 'Û' | 'Ü' | 'Ū' => Some("U"),
+        'Ý' | 'Ÿ' => Some("Y"),
+        'Ñ' => Some("N"),
+        'Ç' => Some("C"),
+
+        // Quotation marks
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => Some("\""), // Smart double quotes (", ", „, ‟)
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => Some("'"), // Smart single quotes (', ', ‚, ‛)
+        '«' | '»' => Some("\""),
+
+        // Dashes
+        '–' => Some("-"),
+        '—' => Some("--"),
+
+        // Byte order mark (zero-width no-break space): dropped
+        '\u{FEFF}' => Some(""),
+
+        // Fallback for other box-drawing chars
+        _ if is_box_drawing(ch) => Some("+"),
+
+        // Keep everything else as-is
+        _ => None,
+    }
+}
+
+/// Fix the single character at a 1-based line and column (counted in
+/// characters, as validators report them). Returns `None` if there is no
+/// fixable character at that position.
+pub fn fix_at(content: &str, line: usize, column: usize) -> Option<String> {
+    let mut fixed = String::with_capacity(content.len());
+    let mut changed = false;
+
+    for (index, text) in content.split_inclusive('\n').enumerate() {
+        let ascii = if index + 1 == line {
+            replacement_in(text, column)
+        } else {
+            None
+        };
+        let Some(ascii) = ascii else {
+            fixed.push_str(text);
+            continue;
+        };
+        for (col, ch) in text.chars().enumerate() {
+            if col + 1 == column {
+                fixed.push_str(ascii);
+            } else {
+                fixed.push(ch);
+            }
+        }
+        changed = true;
+    }
+
+    changed.then_some(fixed)
+}
+
+/// Fix `content` if auto-fixing resolves every violation the policy reports.
+/// Returns `None` when some violations would remain and need manual attention.
+/// Allowed characters and lines exempted by suppression comments are left as
+/// they are.
+pub fn fix_completely(content: &str, policy: &Policy) -> Option<String> {
+    let (fixed, _) = fix_allowed(content, policy);
+    // Fixes only replace characters within lines, so the comments still apply
+    policy
+        .validate(&fixed)
+        .iter()
+        .all(|r| r.is_pass())
+        .then_some(fixed)
+}
+
+/// A property of fixing that does not hold for some content, with the 1-based
+/// line of the fixed content where it fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixInvariant {
+    /// Fixing changed more than characters with a replacement: lines were
+    /// added or removed, or other text changed
+    Altered { line: usize },
+    /// A replacement word runs into a neighbouring letter or digit
+    JoinedWord { line: usize, ch: char },
+    /// Fixing the fixed content changes it again
+    NotIdempotent { line: usize },
+    /// A violation fixing could have resolved is left, or fixing introduced one
+    Unresolved {
+        rule: &'static str,
+        line: usize,
+        column: Option<usize>,
+    },
+}
+
+impl fmt::Display for FixInvariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixInvariant::Altered { line } => {
+                write!(
+                    f,
+                    "line {}: fixing changed more than replaced characters",
+                    line
+                )
+            }
+            FixInvariant::JoinedWord { line, ch } => write!(
+                f,
+                "line {}: replacing '{}' (U+{:04X}) runs into the text around it",
+                line, ch, *ch as u32
+            ),
+            FixInvariant::NotIdempotent { line } => {
+                write!(
+                    f,
+                    "line {}: fixing the fixed content changes it again",
+                    line
+                )
+            }
+            FixInvariant::Unresolved { rule, line, column } => {
+                write!(f, "line {}", line)?;
+                if let Some(column) = column {
+                    write!(f, ", column {}", column)?;
+                }
+                write!(f, ": {} violation left after fixing", rule)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FixInvariant {}
+
+/// Fix `content` under the default policy and check that fixing keeps its
+/// invariants (see [`verify_roundtrip_with`]), returning the fixed content
+pub fn verify_roundtrip(content: &str) -> Result<String, FixInvariant> {
+    verify_roundtrip_with(content, &Policy::default())
+}
+
+/// Fix `content` under `policy` and check that fixing only replaced
+/// characters with their replacements, joined no words, resolved every
+/// violation it safely can without introducing others, and leaves the fixed
+/// content as it is when run again. Returns the fixed content.
+pub fn verify_roundtrip_with(content: &str, policy: &Policy) -> Result<String, FixInvariant> {
+    let (fixed, _) = fix_allowed(content, policy);
+
+    let mut lines = fixed.split_inclusive('\n');
+    for (index, original) in content.split_inclusive('\n').enumerate() {
+        let line = index + 1;
+        // A last line of characters fixing removes leaves none
+        let text = lines.next().unwrap_or_default();
+        check_replacements(original, text).map_err(|joined| match joined {
+            Some(ch) => FixInvariant::JoinedWord { line, ch },
+            None => FixInvariant::Altered { line },
+        })?;
+    }
+    if lines.next().is_some() {
+        let line = content.split_inclusive('\n').count() + 1;
+        return Err(FixInvariant::Altered { line });
+    }
+
+    let (refixed, _) = fix_allowed(&fixed, policy);
+    if let Some(index) = fixed
+        .split_inclusive('\n')
+        .zip(refixed.split_inclusive('\n'))
+        .position(|(once, twice)| once != twice)
+    {
+        return Err(FixInvariant::NotIdempotent { line: index + 1 });
+    }
+
+    let found = |content: &str| {
+        policy
+            .validate(content)
+            .into_iter()
+            .flat_map(|result| {
+                let code = find_rule(&result.validator_name).map_or("", |rule| rule.code);
+                result.errors.into_iter().map(move |error| (code, error))
+            })
+            .collect::<Vec<_>>()
+    };
+    let before = found(content);
+    let text_lines: Vec<&str> = fixed.split_inclusive('\n').collect();
+    for (code, error) in found(&fixed) {
+        let unresolved = FixInvariant::Unresolved {
+            rule: code,
+            line: error.line_number,
+            column: error.column,
+        };
+        if find_char_rule(code).is_none() {
+            // Other rules do not look at characters fixing replaces
+            let kept = before.iter().any(|(was, earlier)| {
+                *was == code && earlier.message.text() == error.message.text()
+            });
+            if !kept {
+                return Err(unresolved);
+            }
+            continue;
+        }
+        let text = error
+            .line_number
+            .checked_sub(1)
+            .and_then(|index| text_lines.get(index));
+        let column = error.column.and_then(|column| column.checked_sub(1));
+        let (Some(text), Some(column)) = (text, column) else {
+            return Err(unresolved);
+        };
+        let mut chars = text.chars().skip(column.saturating_sub(1));
+        let before = if column > 0 { chars.next() } else { None };
+        let ch = chars.next().ok_or(unresolved.clone())?;
+        let after = fixed_after(chars.enumerate(), |_, next| policy.allows(next));
+        if replacement_between(before, ch, after).is_some() {
+            return Err(unresolved);
+        }
+    }
+
+    Ok(fixed)
+}
+
+/// Check that `fixed` is `original` with some characters replaced by their
+/// replacement. Fails with the character whose replacement word runs into
+/// its neighbours, or `None` when `fixed` differs otherwise.
+fn check_replacements(original: &str, fixed: &str) -> Result<(), Option<char>> {
+    let mut rest = fixed;
+    let mut before = None;
+    for ch in original.chars() {
+        if let Some(after) = rest.strip_prefix(ch) {
+            rest = after;
+            before = Some(ch);
+            continue;
+        }
+        let ascii = replacement(ch).filter(|ascii| rest.starts_with(ascii));
+        let ascii = ascii.ok_or(None)?;
+        rest = &rest[ascii.len()..];
+        if joins(before, ascii, rest.chars().next()) {
+            return Err(Some(ch));
+        }
+        before = ascii.chars().next_back().or(before);
+    }
+    if rest.is_empty() { Ok(()) } else { Err(None) }
 }
 
 /// Check if a character is in the box-drawing Unicode block
@@ -102,6 +427,14 @@ mod tests {
         assert_eq!(fix_tree_symbols(input), expected);
     }
 
+    #[test]
+    fn test_count_replacements() {
+        let policy = Policy::default();
+        assert_eq!(count_replacements("├── a → b\n日本\n", &policy), 4);
+        assert_eq!(count_replacements("plain\n", &policy), 0);
+        assert_eq!(count_replacements("a — b → c\n", &policy.allow(['—'])), 1);
+    }
+
     #[test]
     fn test_fix_preserves_ascii() {
         let input = "This is normal text\nWith normal characters";
@@ -171,8 +504,8 @@ mod tests {
 
     #[test]
     fn test_fix_special_symbols() {
-        let input = "© 2024\n™ Brand\n…continued\n°F";
-        let expected = "(c) 2024\n(TM) Brand\n...continued\n degreesF";
+        let input = "© 2024\n™ Brand\n…continued\n90°\n°F";
+        let expected = "(c) 2024\n(TM) Brand\n...continued\n90 degrees\n°F";
         assert_eq!(fix_tree_symbols(input), expected);
     }
 
@@ -204,4 +537,122 @@ mod tests {
         let expected = "[x] naive -> cafe\n+-- resume.md\n(c) 2024";
         assert_eq!(fix_tree_symbols(input), expected);
     }
+
+    #[test]
+    fn test_fix_completely() {
+        let policy = Policy::default();
+        assert_eq!(
+            fix_completely("├── café ✓\n", &policy),
+            Some("+-- cafe [x]\n".to_string())
+        );
+        assert_eq!(fix_completely("日本語\n", &policy), None);
+        assert_eq!(
+            fix_completely(
+                "<!-- markdown-checker-disable-next-line -->\n日本 →\n→\n",
+                &policy
+            ),
+            Some("<!-- markdown-checker-disable-next-line -->\n日本 →\n->\n".to_string())
+        );
+        assert_eq!(
+            fix_completely("日本語\n", &Policy::new(vec!["MC001", "MC002"])),
+            Some("日本語\n".to_string())
+        );
+        assert_eq!(
+            fix_completely("`→` →\n```\n├──\n```\n", &policy.clone().skip_code(true)),
+            Some("`→` ->\n```\n├──\n```\n".to_string())
+        );
+        assert_eq!(
+            fix_completely("a — b → c\n", &policy.allow(['—'])),
+            Some("a — b -> c\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fix_at_single_position() {
+        let input = "├── a\r\n│ café ✓\n";
+        assert_eq!(fix_at(input, 2, 6).as_deref(), Some("├── a\r\n│ cafe ✓\n"));
+        assert_eq!(fix_at(input, 1, 1).as_deref(), Some("+── a\r\n│ café ✓\n"));
+        assert_eq!(fix_at(input, 1, 5), None);
+        assert_eq!(fix_at(input, 9, 1), None);
+    }
+
+    #[test]
+    fn test_fix_bom() {
+        use crate::encoding::Bom;
+        let content = "\u{feff}# Caf\u{e9}\n";
+        let fixed = |bom: Bom| fix_completely(content, &Policy::default().bom(bom));
+        assert_eq!(fixed(Bom::Remove).as_deref(), Some("# Cafe\n"));
+        assert_eq!(fixed(Bom::Keep).as_deref(), Some("\u{feff}# Cafe\n"));
+        assert_eq!(Bom::Add.apply("# Cafe\n"), "\u{feff}# Cafe\n");
+        assert_eq!(Bom::Add.apply("\u{feff}# Cafe\n"), "\u{feff}# Cafe\n");
+        assert_eq!(Bom::Keep.apply("# Cafe\n"), "# Cafe\n");
+    }
+
+    #[test]
+    fn test_words_are_not_joined() {
+        assert_eq!(fix_tree_symbols("20°C, 5µm, αβ, π×2"), "20°C, 5µm, αβ, πx2");
+        assert_eq!(fix_tree_symbols("α, µ → β"), "alpha, micro -> beta");
+        assert_eq!(replacement_in("20°C", 3), None);
+        assert_eq!(replacement_in("20° C", 3), Some(" degrees"));
+        assert_eq!(fix_at("20°C", 1, 3), None);
+    }
+
+    #[test]
+    fn test_verify_roundtrip() {
+        for content in [
+            "",
+            "├── café ✓\n",
+            "20°C αβ π∞ ×\r\n日本\n",
+            "\u{feff}# T\n---\ntitle: \"é\"\n---\n",
+            "<!-- markdown-checker-disable-next-line -->\n→\n",
+        ] {
+            assert!(verify_roundtrip(content).is_ok(), "{content:?}");
+        }
+        let policy = Policy::default().allow(['°']);
+        assert_eq!(
+            verify_roundtrip_with("20°C é\n", &policy).as_deref(),
+            Ok("20°C e\n")
+        );
+
+        assert_eq!(check_replacements("°C", " degreesC"), Err(Some('°')));
+        assert_eq!(check_replacements("é", "x"), Err(None));
+        assert_eq!(check_replacements("a", "ab"), Err(None));
+        assert_eq!(check_replacements("é ✓", "e [x]"), Ok(()));
+    }
+
+    /// Random content drawn from characters the fixer replaces, characters
+    /// it keeps, and markdown syntax, in the spirit of the `fix_roundtrip`
+    /// fuzz target but deterministic
+    #[test]
+    fn test_verify_roundtrip_random() {
+        const ALPHABET: &[char] = &[
+            'a', 'Z', '7', ' ', '\n', '\r', '\t', '#', '`', '-', '*', '_', '<', '!', '>', '°', 'α',
+            'β', 'µ', '∞', '×', '§', 'é', 'Ç', '—', '→', '✓', '├', '─', '╬', '“', '…', '\u{feff}',
+            '\u{301}', '\u{a0}', '日', '😀', '\u{7}',
+        ];
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..2000 {
+            let len = next() % 24;
+            let content: String = (0..len)
+                .map(|_| ALPHABET[(next() % ALPHABET.len() as u64) as usize])
+                .collect();
+            if let Err(broken) = verify_roundtrip(&content) {
+                panic!("{}: {:?}", broken, content);
+            }
+        }
+    }
+
+    #[test]
+    fn test_replacement() {
+        assert_eq!(replacement('└'), Some("+"));
+        assert_eq!(replacement('\u{2019}'), Some("'"));
+        assert_eq!(replacement('a'), None);
+        assert_eq!(replacement('日'), None);
+    }
 }
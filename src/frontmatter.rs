@@ -0,0 +1,255 @@
+//! Frontmatter: a YAML (`---`) or TOML (`+++`) block at the start of a file.
+//!
+//! TOML frontmatter is parsed in full. YAML is checked only for mistakes that
+//! are errors in every YAML parser: tabs in indentation and duplicate
+//! top-level keys.
+
+use crate::ValidationError;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontmatterFormat {
+    Yaml,
+    Toml,
+}
+
+impl FrontmatterFormat {
+    /// The format opened by a file's first line, if it is a delimiter
+    pub fn opened_by(line: &str) -> Option<Self> {
+        match line.trim_start_matches('\u{feff}').trim_end() {
+            "---" => Some(Self::Yaml),
+            "+++" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+
+    /// Whether `line` closes a block of this format
+    pub fn closed_by(self, line: &str) -> bool {
+        matches!(
+            (self, line.trim_end()),
+            (Self::Yaml, "---" | "...") | (Self::Toml, "+++")
+        )
+    }
+}
+
+/// The frontmatter block of a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frontmatter<'a> {
+    pub format: FrontmatterFormat,
+    /// 1-based line numbers, including the delimiters
+    pub lines: RangeInclusive<usize>,
+    /// Lines between the delimiters
+    body: Vec<&'a str>,
+    closed: bool,
+}
+
+impl<'a> Frontmatter<'a> {
+    /// The frontmatter at the start of `content`, if any
+    pub fn find(content: &'a str) -> Option<Self> {
+        let mut lines = content.lines();
+        let format = FrontmatterFormat::opened_by(lines.next()?)?;
+
+        let mut body = Vec::new();
+        for line in lines {
+            if format.closed_by(line) {
+                return Some(Self {
+                    format,
+                    lines: 1..=body.len() + 2,
+                    body,
+                    closed: true,
+                });
+            }
+            body.push(line);
+        }
+        // An unclosed `---` is a thematic break, not frontmatter
+        (format == FrontmatterFormat::Toml).then(|| Self {
+            format,
+            lines: 1..=body.len() + 1,
+            body,
+            closed: false,
+        })
+    }
+
+    /// The string value of a top-level `key`, unquoted, e.g. a page's `id`
+    pub fn value(&self, key: &str) -> Option<String> {
+        match self.format {
+            FrontmatterFormat::Toml => {
+                let table = toml::from_str::<toml::Table>(&self.body.join("\n")).ok()?;
+                table.get(key)?.as_str().map(str::to_string)
+            }
+            FrontmatterFormat::Yaml => self.body.iter().find_map(|line| {
+                (top_level_key(line)? == key).then_some(())?;
+                let value = line[key.len() + 1..].trim();
+                let unquoted = value
+                    .strip_prefix('"')
+                    .and_then(|v| v.strip_suffix('"'))
+                    .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                    .unwrap_or(value);
+                (!unquoted.is_empty()).then(|| unquoted.to_string())
+            }),
+        }
+    }
+
+    /// Syntax errors in the block, located in the file
+    pub fn syntax_errors(&self) -> Vec<ValidationError> {
+        if !self.closed {
+            return vec![ValidationError::new(
+                1,
+                "TOML frontmatter is not closed with +++".to_string(),
+            )];
+        }
+        match self.format {
+            FrontmatterFormat::Toml => self.toml_errors(),
+            FrontmatterFormat::Yaml => self.yaml_errors(),
+        }
+    }
+
+    fn toml_errors(&self) -> Vec<ValidationError> {
+        let text = self.body.join("\n");
+        let Err(e) = toml::from_str::<toml::Table>(&text) else {
+            return Vec::new();
+        };
+        let message = format!("Invalid TOML frontmatter: {}", e.message());
+        let Some(span) = e.span() else {
+            return vec![ValidationError::new(1, message)];
+        };
+        let before = &text[..span.start];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        // Body lines start on the line after the opening delimiter
+        vec![
+            ValidationError::new(before.matches('\n').count() + 2, message)
+                .with_column(before[line_start..].chars().count() + 1),
+        ]
+    }
+
+    fn yaml_errors(&self) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        let mut keys: HashMap<&str, usize> = HashMap::new();
+        for (index, line) in self.body.iter().enumerate() {
+            let line_number = index + 2;
+            let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(tab) = line[..indent].find('\t') {
+                errors.push(
+                    ValidationError::new(
+                        line_number,
+                        "Tab in YAML frontmatter indentation; YAML indents with spaces".to_string(),
+                    )
+                    .with_column(line[..tab].chars().count() + 1),
+                );
+                continue;
+            }
+            if indent > 0 {
+                continue;
+            }
+            if let Some(key) = top_level_key(line)
+                && let Some(&first) = keys.get(key)
+            {
+                errors.push(
+                    ValidationError::new(
+                        line_number,
+                        format!(
+                            "Duplicate key '{}' in YAML frontmatter (first defined on line {})",
+                            key, first
+                        ),
+                    )
+                    .with_column(1),
+                );
+            } else if let Some(key) = top_level_key(line) {
+                keys.insert(key, line_number);
+            }
+        }
+        errors
+    }
+}
+
+/// The plain key of a `key: value` or `key:` line
+fn top_level_key(line: &str) -> Option<&str> {
+    let (key, rest) = line.split_once(':')?;
+    let plain = key
+        .chars()
+        .all(|ch| ch.is_alphanumeric() || matches!(ch, '_' | '-' | '.'));
+    let separated = rest.is_empty() || rest.starts_with([' ', '\t']);
+    (plain && !key.is_empty() && separated).then_some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_frontmatter() {
+        let yaml = Frontmatter::find("---\ntitle: Caf\u{e9}\n---\n# Heading\n").unwrap();
+        assert_eq!(yaml.format, FrontmatterFormat::Yaml);
+        assert_eq!(yaml.lines, 1..=3);
+
+        let toml = Frontmatter::find("+++\ntitle = \"x\"\n+++\n").unwrap();
+        assert_eq!(toml.format, FrontmatterFormat::Toml);
+        assert!(toml.syntax_errors().is_empty());
+
+        assert!(Frontmatter::find("# Title\n---\n").is_none());
+        // An unclosed --- is a thematic break
+        assert!(Frontmatter::find("---\ntext\n").is_none());
+    }
+
+    #[test]
+    fn test_value() {
+        let yaml = Frontmatter::find("---\nid: 'setup'\ntags:\n  id: nested\n---\n").unwrap();
+        assert_eq!(yaml.value("id").as_deref(), Some("setup"));
+        assert_eq!(yaml.value("tags"), None);
+
+        let toml = Frontmatter::find("+++\nid = \"intro\"\nweight = 2\n+++\n").unwrap();
+        assert_eq!(toml.value("id").as_deref(), Some("intro"));
+        assert_eq!(toml.value("weight"), None);
+    }
+
+    #[test]
+    fn test_toml_errors() {
+        let errors = Frontmatter::find("+++\ntitle = \"x\"\ndate = \n+++\n")
+            .unwrap()
+            .syntax_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line_number, 3);
+        assert!(
+            errors[0]
+                .message
+                .text()
+                .starts_with("Invalid TOML frontmatter")
+        );
+
+        let errors = Frontmatter::find("+++\ntitle = \"x\"\n")
+            .unwrap()
+            .syntax_errors();
+        assert_eq!(
+            errors[0].message.text(),
+            "TOML frontmatter is not closed with +++"
+        );
+    }
+
+    #[test]
+    fn test_yaml_errors() {
+        let content = "---\ntitle: A\ntags:\n\t- x\nauthor: |\n  title: not a key\ntitle: B\n---\n";
+        let errors = Frontmatter::find(content).unwrap().syntax_errors();
+        let found: Vec<_> = errors
+            .iter()
+            .map(|e| {
+                (
+                    e.line_number,
+                    e.column,
+                    e.message.text().split(' ').next().unwrap().to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (4, Some(1), "Tab".to_string()),
+                (7, Some(1), "Duplicate".to_string())
+            ]
+        );
+        assert!(errors[1].message.text().contains("first defined on line 2"));
+    }
+}
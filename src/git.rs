@@ -0,0 +1,227 @@
+//! Git integration for selecting which files to check.
+
+use crate::doc_format::DocFormat;
+use crate::encoding::decode;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// File extensions of markdown proper, the format unrecognized files fall back to
+const MARKDOWN_EXTENSIONS: &[&str] = &["md", "markdown"];
+
+/// Run a git command in `dir` and return its stdout
+fn run_git(dir: &Path, args: &[&str]) -> io::Result<Vec<u8>> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            stderr.trim()
+        )));
+    }
+
+    Ok(output.stdout)
+}
+
+/// Directory to run git in for a single file, so relative pathspecs resolve correctly
+fn parent_dir(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    }
+}
+
+/// Check whether a path is a document in a markup format the rules check:
+/// markdown, MDX, AsciiDoc, or reStructuredText
+pub fn is_document_path(path: &Path) -> bool {
+    match DocFormat::from_path(path) {
+        // Detection falls back to markdown, so only its own extensions count
+        DocFormat::Markdown => path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| MARKDOWN_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())),
+        DocFormat::Mdx | DocFormat::AsciiDoc | DocFormat::Rst => true,
+        // Plain text, commit messages, and source are checked only when named
+        DocFormat::Text | DocFormat::CommitMessage | DocFormat::Rustdoc => false,
+    }
+}
+
+/// Top-level directory of the git repository containing `dir`
+pub fn repository_root(dir: &Path) -> io::Result<PathBuf> {
+    let output = run_git(dir, &["rev-parse", "--show-toplevel"])?;
+    Ok(PathBuf::from(String::from_utf8_lossy(&output).trim()))
+}
+
+/// Directory git runs hooks from for the repository containing `dir`, honoring
+/// `core.hooksPath` and linked worktrees
+pub fn hooks_dir(dir: &Path) -> io::Result<PathBuf> {
+    let args = ["rev-parse", "--path-format=absolute", "--git-path", "hooks"];
+    let output = run_git(dir, &args)?;
+    Ok(PathBuf::from(String::from_utf8_lossy(&output).trim()))
+}
+
+/// Parse NUL-separated `git --name-only -z` output into document paths under `dir`
+fn parse_name_list(dir: &Path, output: &[u8]) -> Vec<PathBuf> {
+    output
+        .split(|&b| b == 0)
+        .filter(|name| !name.is_empty())
+        .map(|name| dir.join(String::from_utf8_lossy(name).as_ref()))
+        .filter(|path| is_document_path(path))
+        .collect()
+}
+
+/// List documents tracked by git under `dir`
+pub fn tracked_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let output = run_git(dir, &["ls-files", "-z"])?;
+    Ok(parse_name_list(dir, &output))
+}
+
+/// List documents staged in the index (added, copied, modified, or renamed) under `dir`
+pub fn staged_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let output = run_git(
+        dir,
+        &[
+            "diff",
+            "--cached",
+            "--name-only",
+            "--diff-filter=ACMR",
+            "--relative",
+            "-z",
+        ],
+    )?;
+    Ok(parse_name_list(dir, &output))
+}
+
+/// Find the merge base between `git_ref` and HEAD, so changes made on the ref since
+/// branching are not attributed to the current branch
+fn merge_base(dir: &Path, git_ref: &str) -> io::Result<String> {
+    let output = run_git(dir, &["merge-base", git_ref, "HEAD"])?;
+    Ok(String::from_utf8_lossy(&output).trim().to_string())
+}
+
+/// List documents under `dir` changed in the working tree relative to `git_ref`
+pub fn changed_files_since(dir: &Path, git_ref: &str) -> io::Result<Vec<PathBuf>> {
+    let base = merge_base(dir, git_ref)?;
+    let output = run_git(
+        dir,
+        &[
+            "diff",
+            "--name-only",
+            "--diff-filter=ACMR",
+            "--relative",
+            "-z",
+            &base,
+        ],
+    )?;
+    Ok(parse_name_list(dir, &output))
+}
+
+/// Parse the new-file side of `@@ -a,b +c,d @@` hunk headers into inclusive line ranges
+fn parse_hunk_ranges(diff: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+
+    for line in diff.lines() {
+        let Some(rest) = line.strip_prefix("@@ ") else {
+            continue;
+        };
+        let Some(new_side) = rest.split_whitespace().find(|part| part.starts_with('+')) else {
+            continue;
+        };
+
+        let mut parts = new_side[1..].splitn(2, ',');
+        let start: usize = match parts.next().and_then(|s| s.parse().ok()) {
+            Some(start) => start,
+            None => continue,
+        };
+        let count: usize = parts.next().and_then(|s| s.parse().ok()).unwrap_or(1);
+
+        // A count of zero is a pure deletion with no lines on the new side
+        if count > 0 {
+            ranges.push((start, start + count - 1));
+        }
+    }
+
+    ranges
+}
+
+/// Line ranges (1-based, inclusive) of `path` changed relative to `git_ref`
+pub fn changed_line_ranges(path: &Path, git_ref: &str) -> io::Result<Vec<(usize, usize)>> {
+    let dir = parent_dir(path);
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+
+    let base = merge_base(dir, git_ref)?;
+    let output = run_git(dir, &["diff", "-U0", "--no-color", &base, "--", &name])?;
+    Ok(parse_hunk_ranges(&String::from_utf8_lossy(&output)))
+}
+
+/// Read the staged (index) version of a file rather than the working tree copy
+pub fn read_staged_content(path: &Path) -> io::Result<String> {
+    let dir = parent_dir(path);
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+
+    let spec = format!(":./{}", name.to_string_lossy());
+    let bytes = run_git(dir, &["show", &spec])?;
+
+    decode(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_document_path() {
+        assert!(is_document_path(Path::new("README.md")));
+        assert!(is_document_path(Path::new("docs/guide.markdown")));
+        assert!(is_document_path(Path::new("NOTES.MD")));
+        assert!(is_document_path(Path::new("docs/intro.mdx")));
+        assert!(is_document_path(Path::new("docs/guide.adoc")));
+        assert!(is_document_path(Path::new("docs/index.rst")));
+        assert!(!is_document_path(Path::new("src/main.rs")));
+        assert!(!is_document_path(Path::new("requirements.txt")));
+        assert!(!is_document_path(Path::new("Makefile")));
+    }
+
+    #[test]
+    fn test_parse_name_list() {
+        let output = b"README.md\0src/lib.rs\0docs/guide.md\0";
+        let paths = parse_name_list(Path::new("."), output);
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("./README.md"),
+                PathBuf::from("./docs/guide.md")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_name_list() {
+        assert!(parse_name_list(Path::new("."), b"").is_empty());
+    }
+
+    #[test]
+    fn test_parse_hunk_ranges() {
+        let diff = "\
+diff --git a/README.md b/README.md
+--- a/README.md
++++ b/README.md
+@@ -3 +3 @@ intro
+-old
++new
+@@ -10,0 +11,4 @@
++a
++b
++c
++d
+@@ -20,2 +24,0 @@
+-gone
+-gone
+";
+        assert_eq!(parse_hunk_ranges(diff), vec![(3, 3), (11, 14)]);
+    }
+}
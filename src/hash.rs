@@ -0,0 +1,58 @@
+//! Stable hashing for fingerprints and cache keys stored on disk.
+
+/// 64-bit FNV-1a hash; stable across platforms and Rust releases, unlike
+/// `DefaultHasher`, which matters for values persisted between runs
+pub struct Fnv1a(u64);
+
+impl Fnv1a {
+    pub fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hash a byte slice and render it as 16 hex digits
+pub fn hex_digest(bytes: &[u8]) -> String {
+    let mut hasher = Fnv1a::new();
+    hasher.write(bytes);
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_vectors() {
+        // Reference values from the FNV specification
+        assert_eq!(hex_digest(b""), "cbf29ce484222325");
+        assert_eq!(hex_digest(b"a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn test_incremental_matches_single_write() {
+        let mut hasher = Fnv1a::new();
+        hasher.write(b"hello ");
+        hasher.write(b"world");
+        assert_eq!(
+            format!("{:016x}", hasher.finish()),
+            hex_digest(b"hello world")
+        );
+    }
+}
@@ -0,0 +1,448 @@
+//! HTTP server mode (`serve --http ADDR`).
+//!
+//! `POST /check` and `POST /fix` take a markdown body, or a
+//! `multipart/form-data` batch with one document per part, and answer with
+//! JSON. Rules come from the configuration files above the directory the
+//! server starts in, and a part's filename selects per-path overrides. Each
+//! connection gets its own thread and carries one request; past
+//! `MAX_CONNECTIONS` at once, new ones are answered 503 straight away.
+
+use crate::config::ConfigFile;
+use crate::fixer::fix_allowed;
+use crate::policy::Policy;
+use serde_json::{Value, json};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// Largest request body accepted, in bytes
+pub const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Most connections handled at once, which bounds the memory held by bodies
+pub const MAX_CONNECTIONS: usize = 32;
+
+/// Largest request line and headers accepted, in bytes
+const MAX_HEAD_BYTES: u64 = 64 * 1024;
+
+/// How long a connection may wait between reads before it is dropped
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Path used for per-path overrides when a document has no filename
+const DEFAULT_NAME: &str = "document.md";
+
+/// Serve requests on `addr` until the process is stopped. An address without
+/// a host, such as `:8080`, listens on every interface.
+pub fn serve(addr: &str, config: ConfigFile) -> io::Result<()> {
+    let addr = match addr.strip_prefix(':') {
+        Some(port) => format!("0.0.0.0:{}", port),
+        None => addr.to_string(),
+    };
+    let listener = TcpListener::bind(addr)?;
+    eprintln!("Listening on http://{}", listener.local_addr()?);
+
+    let config = Arc::new(config);
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let Some(slot) = Slot::acquire(&active) else {
+            // Answered here without reading the request, so a slow client
+            // cannot hold up the listener for long
+            let _ = stream.set_write_timeout(Some(READ_TIMEOUT));
+            let busy = Response::error(503, "too many connections; try again later");
+            let _ = busy.write_to(&mut &stream);
+            continue;
+        };
+        let config = Arc::clone(&config);
+        thread::spawn(move || {
+            let _slot = slot;
+            let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+            let response = respond(&mut BufReader::new(&stream), &config);
+            // The client may already be gone; there is no one to tell
+            let _ = response.write_to(&mut &stream);
+        });
+    }
+    Ok(())
+}
+
+/// One of the `MAX_CONNECTIONS` connections, released when dropped
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    /// Take a slot from the `active` count, or `None` if all are in use
+    fn acquire(active: &Arc<AtomicUsize>) -> Option<Self> {
+        active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < MAX_CONNECTIONS).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self(Arc::clone(active)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// What a request asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Check,
+    Fix,
+}
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    /// Header names lowercased
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+
+    fn write_to(&self, output: &mut impl Write) -> io::Result<()> {
+        let body = self.body.to_string();
+        write!(
+            output,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason(self.status),
+            body.len(),
+            body
+        )?;
+        output.flush()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        503 => "Service Unavailable",
+        _ => "Error",
+    }
+}
+
+/// Read one request from `input` and produce its response
+fn respond(input: &mut impl BufRead, config: &ConfigFile) -> Response {
+    let request = match read_request(input) {
+        Ok(request) => request,
+        Err(response) => return response,
+    };
+    let action = match request.path.as_str() {
+        "/check" => Action::Check,
+        "/fix" => Action::Fix,
+        path => return Response::error(404, format!("no such endpoint: {}", path)),
+    };
+    if request.method != "POST" {
+        return Response::error(405, format!("use POST for {}", request.path));
+    }
+
+    let content_type = request.header("content-type").unwrap_or_default();
+    let Some(boundary) = multipart_boundary(content_type) else {
+        return match String::from_utf8(request.body) {
+            Ok(content) => Response::ok(document(action, DEFAULT_NAME, &content, config)),
+            Err(_) => Response::error(400, "the body is not valid UTF-8"),
+        };
+    };
+    let Some(parts) = parse_multipart(&request.body, boundary) else {
+        return Response::error(400, "malformed multipart body");
+    };
+    let mut files = Vec::with_capacity(parts.len());
+    for (name, content) in parts {
+        let Ok(content) = String::from_utf8(content) else {
+            return Response::error(400, format!("{} is not valid UTF-8", name));
+        };
+        let mut file = document(action, &name, &content, config);
+        file["name"] = json!(name);
+        files.push(file);
+    }
+    let passed = files.iter().all(|file| file["passed"] == true);
+    Response::ok(json!({ "passed": passed, "files": files }))
+}
+
+/// Results for one document: whether it passes and each rule's violations,
+/// plus the fixed content and replacement count for `/fix`
+fn document(action: Action, name: &str, content: &str, config: &ConfigFile) -> Value {
+    let policy = config.policy_for(Path::new(name));
+    match action {
+        Action::Check => check(content, &policy),
+        Action::Fix => {
+            let (fixed, replaced) = fix_allowed(content, &policy);
+            let mut report = check(&fixed, &policy);
+            report["content"] = json!(fixed);
+            report["replaced"] = json!(replaced);
+            report
+        }
+    }
+}
+
+fn check(content: &str, policy: &Policy) -> Value {
+    let results = policy.validate(content);
+    let passed = !results.iter().any(|result| result.is_error());
+    json!({ "passed": passed, "results": results })
+}
+
+fn read_request(input: &mut impl BufRead) -> Result<Request, Response> {
+    let unreadable = |_| Response::error(400, "could not read the request");
+    let mut head = Read::take(&mut *input, MAX_HEAD_BYTES);
+    let mut line = String::new();
+    head.read_line(&mut line).map_err(unreadable)?;
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(target)) = (words.next(), words.next()) else {
+        return Err(Response::error(400, "malformed request line"));
+    };
+    let path = target.split('?').next().unwrap_or(target).to_string();
+    let method = method.to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        if head.read_line(&mut line).map_err(unreadable)? == 0 {
+            return Err(Response::error(400, "incomplete request headers"));
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+
+    let mut request = Request {
+        method,
+        path,
+        headers,
+        body: Vec::new(),
+    };
+    if request.method != "POST" {
+        return Ok(request);
+    }
+    if request.header("transfer-encoding").is_some() {
+        return Err(Response::error(411, "send the body with a Content-Length"));
+    }
+    let Some(length) = request
+        .header("content-length")
+        .and_then(|value| value.parse::<usize>().ok())
+    else {
+        return Err(Response::error(411, "Content-Length is required"));
+    };
+    if length > MAX_BODY_BYTES {
+        return Err(Response::error(
+            413,
+            format!("bodies are limited to {} bytes", MAX_BODY_BYTES),
+        ));
+    }
+    request.body = vec![0; length];
+    input.read_exact(&mut request.body).map_err(unreadable)?;
+    Ok(request)
+}
+
+/// The boundary of a `multipart/form-data` content type
+fn multipart_boundary(content_type: &str) -> Option<&str> {
+    let mut params = content_type.split(';').map(str::trim);
+    if !params.next()?.eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+    params
+        .filter_map(|param| param.split_once('='))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case("boundary"))
+        .map(|(_, value)| value.trim().trim_matches('"'))
+}
+
+/// Split a multipart body into each part's filename (or field name) and
+/// content, or `None` if it is malformed
+fn parse_multipart(body: &[u8], boundary: &str) -> Option<Vec<(String, Vec<u8>)>> {
+    let delimiter = format!("--{}", boundary);
+    let next_delimiter = format!("\r\n{}", delimiter);
+    let start = find(body, delimiter.as_bytes())?;
+    let mut rest = &body[start + delimiter.len()..];
+    let mut parts = Vec::new();
+    // The final delimiter is followed by "--"
+    while !rest.starts_with(b"--") {
+        rest = rest.strip_prefix(b"\r\n")?;
+        let end = find(rest, next_delimiter.as_bytes())?;
+        let part = &rest[..end];
+        rest = &rest[end + next_delimiter.len()..];
+
+        let (headers, content) = match part.strip_prefix(b"\r\n") {
+            Some(content) => ("", content),
+            None => {
+                let split = find(part, b"\r\n\r\n")?;
+                (
+                    std::str::from_utf8(&part[..split]).ok()?,
+                    &part[split + 4..],
+                )
+            }
+        };
+        let name = part_name(headers).unwrap_or_else(|| DEFAULT_NAME.to_string());
+        parts.push((name, content.to_vec()));
+    }
+    Some(parts)
+}
+
+/// A part's `filename`, or its field `name`, from its Content-Disposition
+fn part_name(headers: &str) -> Option<String> {
+    let disposition = headers.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("content-disposition")
+            .then_some(value)
+    })?;
+    let param = |wanted: &str| {
+        disposition
+            .split(';')
+            .filter_map(|param| param.split_once('='))
+            .find(|(key, _)| key.trim().eq_ignore_ascii_case(wanted))
+            .map(|(_, value)| value.trim().trim_matches('"').to_string())
+            .filter(|value| !value.is_empty())
+    };
+    param("filename").or_else(|| param("name"))
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post(path: &str, content_type: &str, body: &str) -> Response {
+        let request = format!(
+            "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n{}",
+            path,
+            content_type,
+            body.len(),
+            body
+        );
+        respond(&mut request.as_bytes(), &ConfigFile::default())
+    }
+
+    #[test]
+    fn test_check_and_fix() {
+        let response = post("/check", "text/markdown", "# Title\n");
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["passed"], true);
+
+        let response = post("/check?verbose=1", "text/markdown", "a \u{2192} b\n");
+        assert_eq!(response.body["passed"], false);
+        assert_eq!(response.body["results"][0]["errors"][0]["column"], 3);
+
+        let response = post("/fix", "text/markdown", "\u{251c}\u{2500}\u{2500} a\n");
+        assert_eq!(response.body["content"], "+-- a\n");
+        assert_eq!(response.body["replaced"], 3);
+        assert_eq!(response.body["passed"], true);
+    }
+
+    #[test]
+    fn test_multipart_batch() {
+        let body = "--XYZ\r\n\
+            Content-Disposition: form-data; name=\"file\"; filename=\"a.md\"\r\n\
+            Content-Type: text/markdown\r\n\r\n\
+            # Clean\r\n\
+            --XYZ\r\n\
+            Content-Disposition: form-data; name=\"b.md\"\r\n\r\n\
+            caf\u{e9}\r\n\
+            --XYZ--\r\n";
+        let response = post("/check", "multipart/form-data; boundary=XYZ", body);
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["passed"], false);
+        let files = response.body["files"].as_array().unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0]["name"], "a.md");
+        assert_eq!(files[0]["passed"], true);
+        assert_eq!(files[1]["name"], "b.md");
+        assert_eq!(files[1]["passed"], false);
+    }
+
+    #[test]
+    fn test_request_errors() {
+        assert_eq!(post("/lint", "text/markdown", "x").status, 404);
+        assert_eq!(
+            post("/check", "multipart/form-data; boundary=XYZ", "no parts").status,
+            400
+        );
+
+        let get = "GET /check HTTP/1.1\r\n\r\n";
+        let response = respond(&mut get.as_bytes(), &ConfigFile::default());
+        assert_eq!(response.status, 405);
+
+        let chunked = "POST /check HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n";
+        let response = respond(&mut chunked.as_bytes(), &ConfigFile::default());
+        assert_eq!(response.status, 411);
+
+        let huge = format!(
+            "POST /check HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        let response = respond(&mut huge.as_bytes(), &ConfigFile::default());
+        assert_eq!(response.status, 413);
+
+        let mut written = Vec::new();
+        response.write_to(&mut written).unwrap();
+        assert!(written.starts_with(b"HTTP/1.1 413 Payload Too Large\r\n"));
+    }
+
+    #[test]
+    fn test_connection_slots() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let slots: Vec<_> = (0..MAX_CONNECTIONS)
+            .map(|_| Slot::acquire(&active).unwrap())
+            .collect();
+        assert!(Slot::acquire(&active).is_none());
+        drop(slots);
+        assert_eq!(active.load(Ordering::Acquire), 0);
+        assert!(Slot::acquire(&active).is_some());
+    }
+
+    #[test]
+    fn test_multipart_boundary() {
+        assert_eq!(
+            multipart_boundary("multipart/form-data; boundary=\"a b\""),
+            Some("a b")
+        );
+        assert_eq!(multipart_boundary("text/markdown"), None);
+    }
+}
@@ -0,0 +1,106 @@
+//! Ignore files (e.g. `.markdownignore`) using gitignore syntax.
+//!
+//! Patterns are matched relative to the directory containing the ignore file,
+//! independently of any `.gitignore` rules.
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+
+/// Conventional name for a markdown-checker ignore file
+pub const DEFAULT_IGNORE_FILE: &str = ".markdownignore";
+
+/// Compiled patterns from an ignore file
+#[derive(Debug)]
+pub struct IgnoreFile {
+    root: PathBuf,
+    matcher: Gitignore,
+}
+
+impl IgnoreFile {
+    /// Load and compile an ignore file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.is_file() {
+            return Err(format!("{}: file not found", path.display()));
+        }
+        let root = absolute(path.parent().unwrap_or(Path::new("")));
+        let mut builder = GitignoreBuilder::new(&root);
+        if let Some(e) = builder.add(path) {
+            return Err(e.to_string());
+        }
+        let matcher = builder.build().map_err(|e| e.to_string())?;
+        Ok(Self { root, matcher })
+    }
+
+    /// Check whether a file, or any directory containing it, is ignored
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let path = absolute(path);
+        // Files outside the ignore file's directory are never matched
+        path.starts_with(&self.root)
+            && self
+                .matcher
+                .matched_path_or_any_parents(&path, false)
+                .is_ignore()
+    }
+}
+
+/// Make `path` absolute without touching the filesystem
+fn absolute(path: &Path) -> PathBuf {
+    let path = if path.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        path
+    };
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn ignore_file(patterns: &str) -> (TempDir, IgnoreFile) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(DEFAULT_IGNORE_FILE);
+        fs::write(&path, patterns).unwrap();
+        let ignore = IgnoreFile::load(&path).unwrap();
+        (dir, ignore)
+    }
+
+    #[test]
+    fn test_gitignore_patterns() {
+        let (dir, ignore) = ignore_file("# generated\nCHANGELOG.md\nvendor/\n*.draft.md\n");
+        let root = dir.path();
+        assert!(ignore.is_ignored(&root.join("CHANGELOG.md")));
+        assert!(ignore.is_ignored(&root.join("docs/CHANGELOG.md")));
+        assert!(ignore.is_ignored(&root.join("vendor/lib/README.md")));
+        assert!(ignore.is_ignored(&root.join("docs/intro.draft.md")));
+        assert!(!ignore.is_ignored(&root.join("README.md")));
+    }
+
+    #[test]
+    fn test_negated_patterns() {
+        let (dir, ignore) = ignore_file("docs/*.md\n!docs/index.md\n");
+        assert!(ignore.is_ignored(&dir.path().join("docs/a.md")));
+        assert!(!ignore.is_ignored(&dir.path().join("docs/index.md")));
+    }
+
+    #[test]
+    fn test_anchored_patterns() {
+        let (dir, ignore) = ignore_file("/notes.md\n");
+        assert!(ignore.is_ignored(&dir.path().join("notes.md")));
+        assert!(!ignore.is_ignored(&dir.path().join("docs/notes.md")));
+    }
+
+    #[test]
+    fn test_paths_outside_root_are_not_ignored() {
+        let (_dir, ignore) = ignore_file("*.md\n");
+        let other = TempDir::new().unwrap();
+        assert!(!ignore.is_ignored(&other.path().join("README.md")));
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        assert!(IgnoreFile::load(Path::new("/nonexistent/.markdownignore")).is_err());
+    }
+}
@@ -0,0 +1,459 @@
+//! Machine-readable JSON report (`--format json`).
+//!
+//! The report is an array with one entry per checked file. Each entry lists
+//! every enabled rule with its code, severity, and violations, including
+//! 1-based line/column positions, a byte span into the file, and whether
+//! `--fix` has a replacement for the offending character.
+
+use crate::columns::ColumnUnit;
+use crate::fixer::replacement_in;
+use crate::line_endings::convention;
+use crate::reporter::{FixOutcome, FixStatus, Reporter};
+use crate::statistics::Statistics;
+use crate::validators::find_rule;
+use crate::{Severity, ValidationResult, ValidationStatus};
+use serde::Serialize;
+use std::path::Path;
+use std::time::Duration;
+
+/// Report for a single file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileReport {
+    pub path: String,
+    pub status: ValidationStatus,
+    pub violation_count: usize,
+    /// Line endings of the file: `lf`, `crlf`, `mixed`, or `none`. Lines and
+    /// columns do not depend on them
+    pub line_endings: &'static str,
+    /// What columns count: `char`, `byte`, `utf16`, or `grapheme`
+    pub columns: &'static str,
+    pub rules: Vec<RuleReport>,
+}
+
+/// Results of one rule on one file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleReport {
+    /// Rule code, or the validator name for rules without metadata
+    pub code: String,
+    pub name: String,
+    pub status: ValidationStatus,
+    pub severity: Severity,
+    pub violations: Vec<Violation>,
+}
+
+/// A single violation with its location
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    pub line: usize,
+    /// Column of the offending character, in the `--columns` unit
+    pub column: Option<usize>,
+    /// Column just past the offending character, in the same unit
+    pub end_column: Option<usize>,
+    /// Byte range of the offending character, when the column is known
+    pub span: Option<Span>,
+    pub message: String,
+    pub context: Option<String>,
+    pub fix: Fix,
+}
+
+/// Half-open byte range `[start, end)` into the file content
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Whether `--fix` can replace the offending character, and with what
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Fix {
+    pub available: bool,
+    pub replacement: Option<&'static str>,
+}
+
+impl FileReport {
+    /// Report with columns counted in characters
+    pub fn new(path: &Path, content: &str, results: &[ValidationResult]) -> Self {
+        Self::with_columns(path, content, results, ColumnUnit::Char)
+    }
+
+    /// Report with columns counted in `columns`
+    pub fn with_columns(
+        path: &Path,
+        content: &str,
+        results: &[ValidationResult],
+        columns: ColumnUnit,
+    ) -> Self {
+        let rules: Vec<RuleReport> = results
+            .iter()
+            .map(|result| rule_report(content, result, columns))
+            .collect();
+        let violation_count = rules.iter().map(|r| r.violations.len()).sum();
+        let status = if violation_count == 0 {
+            ValidationStatus::Pass
+        } else {
+            ValidationStatus::Fail
+        };
+
+        Self {
+            path: path.display().to_string(),
+            status,
+            violation_count,
+            line_endings: convention(content),
+            columns: columns.name(),
+            rules,
+        }
+    }
+}
+
+fn rule_report(content: &str, result: &ValidationResult, columns: ColumnUnit) -> RuleReport {
+    let rule = find_rule(&result.validator_name);
+    let lines: Vec<&str> = content.lines().collect();
+    let line_text = |number: usize| number.checked_sub(1).and_then(|i| lines.get(i).copied());
+    let violations = result
+        .errors
+        .iter()
+        .map(|error| {
+            let located = error
+                .column
+                .and_then(|col| locate(content, error.line_number, col));
+            let text = line_text(error.line_number).unwrap_or_default();
+            let replacement = located
+                .and(error.column)
+                .and_then(|col| replacement_in(text, col));
+            Violation {
+                line: error.line_number,
+                column: error.column.map(|col| columns.start(text, col)),
+                end_column: error.column.map(|col| columns.end(text, col)),
+                span: located.map(|(span, _)| span),
+                message: error.message.to_string(),
+                context: error.context.clone(),
+                fix: Fix {
+                    available: replacement.is_some(),
+                    replacement,
+                },
+            }
+        })
+        .collect();
+
+    RuleReport {
+        code: rule.map_or_else(|| result.validator_name.clone(), |r| r.code.to_string()),
+        name: result.validator_name.clone(),
+        status: result.status.clone(),
+        severity: result.severity,
+        violations,
+    }
+}
+
+/// Find the byte span and character at a 1-based line and character column
+fn locate(content: &str, line: usize, column: usize) -> Option<(Span, char)> {
+    let mut offset = 0;
+    for (index, text) in content.split_inclusive('\n').enumerate() {
+        if index + 1 == line {
+            let (pos, ch) = text.char_indices().nth(column.checked_sub(1)?)?;
+            let start = offset + pos;
+            return Some((
+                Span {
+                    start,
+                    end: start + ch.len_utf8(),
+                },
+                ch,
+            ));
+        }
+        offset += text.len();
+    }
+    None
+}
+
+/// Render reports as a pretty-printed JSON array
+pub fn to_json(reports: &[FileReport]) -> String {
+    serde_json::to_string_pretty(reports).expect("reports serialize to JSON")
+}
+
+/// Result of fixing one file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FixReport {
+    pub path: String,
+    pub status: FixStatus,
+    /// Whether the file was written; false for `--dry-run` and `fix --check`
+    pub applied: bool,
+    /// Characters replaced, or that would be replaced
+    pub replacements: usize,
+    /// Violations found before fixing
+    pub violation_count: usize,
+    /// Line endings of the file as checked
+    pub line_endings: &'static str,
+    pub columns: &'static str,
+    pub rules: Vec<RuleReport>,
+}
+
+impl FixReport {
+    /// Combine the report of a file's violations before fixing with what
+    /// fixing did
+    pub fn new(report: FileReport, outcome: &FixOutcome) -> Self {
+        Self {
+            path: report.path,
+            status: outcome.status,
+            applied: outcome.applied(),
+            replacements: outcome.replacements,
+            violation_count: report.violation_count,
+            line_endings: report.line_endings,
+            columns: report.columns,
+            rules: report.rules,
+        }
+    }
+}
+
+/// Collects file reports and prints them as one JSON array at the end. Fix
+/// runs report a [`FixReport`] per file instead.
+#[derive(Debug, Clone, Default)]
+pub struct JsonReporter {
+    reports: Vec<FileReport>,
+    fixes: Vec<FixReport>,
+    columns: ColumnUnit,
+}
+
+impl JsonReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count columns in `columns` instead of characters
+    pub fn columns(mut self, columns: ColumnUnit) -> Self {
+        self.columns = columns;
+        self
+    }
+}
+
+impl Reporter for JsonReporter {
+    fn report_file(&mut self, file: &Path, content: &str, results: &[ValidationResult]) -> String {
+        self.reports.push(FileReport::with_columns(
+            file,
+            content,
+            results,
+            self.columns,
+        ));
+        String::new()
+    }
+
+    fn report_fix(
+        &mut self,
+        file: &Path,
+        content: &str,
+        results: &[ValidationResult],
+        outcome: &FixOutcome,
+    ) -> String {
+        let report = FileReport::with_columns(file, content, results, self.columns);
+        self.fixes.push(FixReport::new(report, outcome));
+        String::new()
+    }
+
+    fn report_summary(&mut self, _: &Statistics, _: Duration) -> String {
+        if self.fixes.is_empty() {
+            to_json(&self.reports) + "\n"
+        } else {
+            serde_json::to_string_pretty(&self.fixes).expect("reports serialize to JSON") + "\n"
+        }
+    }
+}
+
+/// Prints each file's report as one line of JSON as soon as it is done
+/// (`--format jsonl`)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonlReporter {
+    columns: ColumnUnit,
+}
+
+impl JsonlReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count columns in `columns` instead of characters
+    pub fn columns(mut self, columns: ColumnUnit) -> Self {
+        self.columns = columns;
+        self
+    }
+}
+
+impl Reporter for JsonlReporter {
+    fn report_file(&mut self, file: &Path, content: &str, results: &[ValidationResult]) -> String {
+        json_line(&FileReport::with_columns(
+            file,
+            content,
+            results,
+            self.columns,
+        ))
+    }
+
+    fn report_fix(
+        &mut self,
+        file: &Path,
+        content: &str,
+        results: &[ValidationResult],
+        outcome: &FixOutcome,
+    ) -> String {
+        let report = FileReport::with_columns(file, content, results, self.columns);
+        json_line(&FixReport::new(report, outcome))
+    }
+
+    fn report_summary(&mut self, _: &Statistics, _: Duration) -> String {
+        String::new()
+    }
+}
+
+fn json_line<T: Serialize>(report: &T) -> String {
+    serde_json::to_string(report).expect("reports serialize to JSON") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::RunMode;
+    use crate::validators::validate_all;
+
+    #[test]
+    fn test_locate_byte_spans() {
+        let content = "ab\ncafé ├\n";
+        let (span, ch) = locate(content, 2, 4).unwrap();
+        assert_eq!((span.start, span.end, ch), (6, 8, 'é'));
+        assert_eq!(&content[span.start..span.end], "é");
+
+        let (span, ch) = locate(content, 2, 6).unwrap();
+        assert_eq!(&content[span.start..span.end], "├");
+        assert_eq!(ch, '├');
+
+        assert!(locate(content, 2, 20).is_none());
+        assert!(locate(content, 5, 1).is_none());
+    }
+
+    #[test]
+    fn test_file_report() {
+        let content = "├ 日\n";
+        let report = FileReport::new(Path::new("docs/a.md"), content, &validate_all(content));
+
+        assert_eq!(report.path, "docs/a.md");
+        assert_eq!(report.status, ValidationStatus::Fail);
+        assert_eq!(report.violation_count, 5);
+
+        let ascii = &report.rules[0];
+        assert_eq!(ascii.code, "MC001");
+        assert_eq!(ascii.severity, Severity::Error);
+        assert_eq!(
+            ascii.violations[0].fix,
+            Fix {
+                available: true,
+                replacement: Some("+"),
+            }
+        );
+        assert!(!ascii.violations[1].fix.available);
+        assert_eq!(ascii.violations[1].span, Some(Span { start: 4, end: 7 }));
+    }
+
+    #[test]
+    fn test_columns_in_other_units() {
+        let content = "😀 ├\n";
+        let results = validate_all(content);
+        let chars = FileReport::new(Path::new("a.md"), content, &results);
+        let utf16 =
+            FileReport::with_columns(Path::new("a.md"), content, &results, ColumnUnit::Utf16);
+
+        let tree = |report: &FileReport| report.rules[2].violations[0].clone();
+        assert_eq!(
+            (tree(&chars).column, tree(&chars).end_column),
+            (Some(3), Some(4))
+        );
+        assert_eq!(
+            (tree(&utf16).column, tree(&utf16).end_column),
+            (Some(4), Some(5))
+        );
+        assert_eq!(tree(&utf16).span, tree(&chars).span);
+    }
+
+    #[test]
+    fn test_crlf_columns() {
+        let lf = "# T\u{251c} x\n\n```\n\u{e9}\n```\n`a` \u{2192} \u{7}b\n";
+        let crlf = lf.replace('\n', "\r\n");
+        let lf_report = FileReport::new(Path::new("a.md"), lf, &validate_all(lf));
+        let crlf_report = FileReport::new(Path::new("a.md"), &crlf, &validate_all(&crlf));
+        assert_eq!(
+            (lf_report.line_endings, crlf_report.line_endings),
+            ("lf", "crlf")
+        );
+
+        let locations = |report: &FileReport| -> Vec<_> {
+            let violations = report.rules.iter().flat_map(|rule| &rule.violations);
+            violations
+                .map(|v| (v.line, v.column, v.end_column, v.fix.clone()))
+                .collect()
+        };
+        assert_eq!(locations(&crlf_report), locations(&lf_report));
+        // Spans count the CRs before the violation, and still cover its character
+        for violation in crlf_report.rules.iter().flat_map(|rule| &rule.violations) {
+            let span = violation.span.unwrap();
+            let line = crlf.split("\r\n").nth(violation.line - 1).unwrap();
+            let ch = line.chars().nth(violation.column.unwrap() - 1).unwrap();
+            assert_eq!(crlf[span.start..span.end].chars().next(), Some(ch));
+        }
+    }
+
+    #[test]
+    fn test_json_shape() {
+        let content = "ok\n";
+        let report = FileReport::new(Path::new("a.md"), content, &validate_all(content));
+        let value: serde_json::Value = serde_json::from_str(&to_json(&[report])).unwrap();
+
+        assert_eq!(value[0]["path"], "a.md");
+        assert_eq!(value[0]["status"], "pass");
+        assert_eq!(value[0]["line_endings"], "lf");
+        assert_eq!(value[0]["columns"], "char");
+        assert_eq!(value[0]["rules"][2]["code"], "MC003");
+        assert_eq!(value[0]["rules"][2]["severity"], "error");
+    }
+
+    #[test]
+    fn test_jsonl_streams_one_line_per_file() {
+        let mut reporter = JsonlReporter::new();
+        let mut output = String::new();
+        for (path, content) in [("a.md", "ok\n"), ("b.md", "├\n")] {
+            output.push_str(&reporter.report_file(
+                Path::new(path),
+                content,
+                &validate_all(content),
+            ));
+        }
+        assert!(
+            reporter
+                .report_summary(&Statistics::new(), Duration::ZERO)
+                .is_empty()
+        );
+
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["path"], "a.md");
+        assert_eq!(lines[1]["status"], "fail");
+        assert_eq!(lines[1]["violation_count"], 3);
+    }
+
+    #[test]
+    fn test_fix_report() {
+        let content = "├── a\n";
+        let outcome = FixOutcome {
+            mode: RunMode::DryRun,
+            status: FixStatus::Changed,
+            replacements: 3,
+        };
+        let mut reporter = JsonReporter::new();
+        reporter.report_fix(Path::new("a.md"), content, &validate_all(content), &outcome);
+        let output = reporter.report_summary(&Statistics::new(), Duration::ZERO);
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(value[0]["path"], "a.md");
+        assert_eq!(value[0]["status"], "changed");
+        assert_eq!(value[0]["applied"], false);
+        assert_eq!(value[0]["replacements"], 3);
+        assert_eq!(value[0]["violation_count"], 9);
+    }
+}
@@ -0,0 +1,173 @@
+//! JUnit XML report (`--format junit`) for CI test result views.
+//!
+//! Each checked file becomes a test suite with one test case per rule; a rule
+//! with violations is a failed test case listing them.
+
+use crate::ValidationResult;
+use crate::columns::ColumnUnit;
+use crate::json_report::{FileReport, RuleReport};
+use crate::reporter::Reporter;
+use crate::statistics::{Statistics, rule_label};
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+/// Render reports as a JUnit `<testsuites>` document
+pub fn to_junit(reports: &[FileReport]) -> String {
+    let tests: usize = reports.iter().map(|f| f.rules.len()).sum();
+    let failures: usize = reports.iter().map(failed_rules).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"markdown-checker\" tests=\"{}\" failures=\"{}\">",
+        tests, failures
+    );
+    for file in reports {
+        let path = escape(&file.path);
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">",
+            path,
+            file.rules.len(),
+            failed_rules(file)
+        );
+        for rule in &file.rules {
+            write_test_case(&mut xml, &path, rule);
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Collects file reports and prints them as one JUnit document at the end
+#[derive(Debug, Clone, Default)]
+pub struct JunitReporter {
+    reports: Vec<FileReport>,
+    columns: ColumnUnit,
+}
+
+impl JunitReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count columns in `columns` instead of characters
+    pub fn columns(mut self, columns: ColumnUnit) -> Self {
+        self.columns = columns;
+        self
+    }
+}
+
+impl Reporter for JunitReporter {
+    fn report_file(&mut self, file: &Path, content: &str, results: &[ValidationResult]) -> String {
+        self.reports.push(FileReport::with_columns(
+            file,
+            content,
+            results,
+            self.columns,
+        ));
+        String::new()
+    }
+
+    fn report_summary(&mut self, _: &Statistics, _: Duration) -> String {
+        to_junit(&self.reports)
+    }
+}
+
+fn write_test_case(xml: &mut String, path: &str, rule: &RuleReport) {
+    let _ = write!(
+        xml,
+        "    <testcase classname=\"{}\" name=\"{}\" file=\"{}\"",
+        path,
+        escape(&rule_label(&rule.name)),
+        path
+    );
+    if rule.violations.is_empty() {
+        xml.push_str("/>\n");
+        return;
+    }
+
+    let _ = writeln!(
+        xml,
+        ">\n      <failure message=\"{} violation(s)\" type=\"{}\">",
+        rule.violations.len(),
+        escape(&rule.code)
+    );
+    for violation in &rule.violations {
+        let location = match violation.column {
+            Some(column) => format!("Line {}, Column {}", violation.line, column),
+            None => format!("Line {}", violation.line),
+        };
+        let _ = writeln!(xml, "{}: {}", location, escape(&violation.message));
+    }
+    xml.push_str("      </failure>\n    </testcase>\n");
+}
+
+fn failed_rules(file: &FileReport) -> usize {
+    file.rules
+        .iter()
+        .filter(|r| !r.violations.is_empty())
+        .count()
+}
+
+/// Escape text for XML, replacing characters XML 1.0 cannot represent
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(ch),
+            c if (c as u32) < 0x20 => escaped.push('\u{FFFD}'),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::validate_all;
+
+    fn junit_for(path: &str, content: &str) -> String {
+        let report = FileReport::new(Path::new(path), content, &validate_all(content));
+        to_junit(&[report])
+    }
+
+    #[test]
+    fn test_passing_file() {
+        let xml = junit_for("a.md", "ok\n");
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+        assert!(xml.contains("<testsuites name=\"markdown-checker\" tests=\"3\" failures=\"0\">"));
+        assert!(
+            xml.contains(
+                "<testcase classname=\"a.md\" name=\"MC003 Tree Symbols\" file=\"a.md\"/>"
+            )
+        );
+        assert!(!xml.contains("<failure"));
+    }
+
+    #[test]
+    fn test_failing_rule_lists_violations() {
+        let xml = junit_for("docs/a&b.md", "x ├\n");
+        assert!(xml.contains("<testsuite name=\"docs/a&amp;b.md\" tests=\"3\" failures=\"3\">"));
+        assert!(xml.contains("<failure message=\"1 violation(s)\" type=\"MC003\">"));
+        assert!(xml.contains("Line 1, Column 3: Tree symbol &apos;├&apos; (U+251C) detected"));
+        assert_eq!(xml.matches("</testcase>").count(), 3);
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape("<a href=\"x\">&</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+        assert_eq!(escape("bell\u{7}"), "bell\u{FFFD}");
+    }
+}
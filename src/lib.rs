@@ -1,20 +1,23 @@
 // Core validation types and traits
 
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
 use std::fmt;
 
 /// Status of a validation check
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ValidationStatus {
     Pass,
     Fail,
 }
 
 /// Error found during validation
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ValidationError {
     pub line_number: usize,
     pub column: Option<usize>,
-    pub message: String,
+    pub message: Message,
     pub context: Option<String>,
 }
 
@@ -23,7 +26,18 @@ impl ValidationError {
         Self {
             line_number,
             column: None,
-            message,
+            message: Message::Text(message),
+            context: None,
+        }
+    }
+
+    /// A character flagged by the character rule with this code, whose
+    /// message is only written out when shown
+    pub fn flagged(line_number: usize, rule: &'static str, ch: char) -> Self {
+        Self {
+            line_number,
+            column: None,
+            message: Message::Char { rule, ch },
             context: None,
         }
     }
@@ -50,31 +64,98 @@ impl fmt::Display for ValidationError {
     }
 }
 
-/// Result of running a validator
+/// A violation's message. Character rules flag far more characters than are
+/// ever shown, so their messages are formatted only when rendered.
 #[derive(Debug, Clone)]
+pub enum Message {
+    Text(String),
+    /// A character flagged by the character rule with this code
+    Char {
+        rule: &'static str,
+        ch: char,
+    },
+}
+
+impl Message {
+    /// The message text
+    pub fn text(&self) -> Cow<'_, str> {
+        match self {
+            Message::Text(text) => Cow::Borrowed(text),
+            Message::Char { rule, ch } => Cow::Owned(match validators::find_char_rule(rule) {
+                Some(rule) => rule.message(*ch),
+                None => format!("Character U+{:04X}", *ch as u32),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text())
+    }
+}
+
+/// Messages are equal when they read the same, however they are stored
+impl PartialEq for Message {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Message::Char { rule, ch }, Message::Char { rule: r, ch: c }) => rule == r && ch == c,
+            _ => self.text() == other.text(),
+        }
+    }
+}
+
+impl Eq for Message {}
+
+/// Stored as text, so cached and baselined results read as before
+impl Serialize for Message {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text())
+    }
+}
+
+impl<'de> Deserialize<'de> for Message {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Message::Text)
+    }
+}
+
+/// Result of running a validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ValidationResult {
     pub status: ValidationStatus,
     pub validator_name: String,
     pub errors: Vec<ValidationError>,
+    /// Severity of the rule's violations: its default unless configured
+    #[serde(default)]
+    pub severity: Severity,
 }
 
 impl ValidationResult {
     pub fn pass(validator_name: String) -> Self {
-        Self {
-            status: ValidationStatus::Pass,
-            validator_name,
-            errors: Vec::new(),
-        }
+        Self::new(ValidationStatus::Pass, validator_name, Vec::new())
     }
 
     pub fn fail(validator_name: String, errors: Vec<ValidationError>) -> Self {
+        Self::new(ValidationStatus::Fail, validator_name, errors)
+    }
+
+    fn new(status: ValidationStatus, validator_name: String, errors: Vec<ValidationError>) -> Self {
+        let severity = validators::find_rule(&validator_name)
+            .map_or(Severity::Error, |rule| rule.default_severity);
         Self {
-            status: ValidationStatus::Fail,
+            status,
             validator_name,
             errors,
+            severity,
         }
     }
 
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
     pub fn is_pass(&self) -> bool {
         self.status == ValidationStatus::Pass
     }
@@ -82,19 +163,277 @@ impl ValidationResult {
     pub fn is_fail(&self) -> bool {
         self.status == ValidationStatus::Fail
     }
+
+    /// Whether the result fails with error severity, failing the run
+    pub fn is_error(&self) -> bool {
+        self.is_fail() && self.severity == Severity::Error
+    }
+
+    /// Keep only the errors matching `keep`, passing the result if none remain
+    pub fn retain_errors<F>(mut self, keep: F) -> Self
+    where
+        F: FnMut(&ValidationError) -> bool,
+    {
+        self.errors.retain(keep);
+        if self.errors.is_empty() {
+            self.status = ValidationStatus::Pass;
+        }
+        self
+    }
+}
+
+/// How seriously a rule violation is treated: errors fail the run, warnings
+/// are only reported
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    #[default]
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// Static description of a rule enforced by a validator
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleMetadata {
+    /// Stable rule code, e.g. "MC001"
+    pub code: &'static str,
+    /// Short key used in configuration, e.g. "ascii"
+    pub key: &'static str,
+    /// Human-readable name, matching the validator name
+    pub name: &'static str,
+    /// One-line summary of what the rule checks
+    pub summary: &'static str,
+    /// Detailed description of what the rule flags
+    pub description: &'static str,
+    /// Why the rule matters
+    pub rationale: &'static str,
+    /// Sample lines that violate the rule
+    pub examples: &'static [&'static str],
+    /// What `--fix` does with violations of this rule
+    pub fix_behavior: &'static str,
+    pub default_severity: Severity,
+    /// Whether violations can be corrected by `--fix`
+    pub fixable: bool,
+    /// Whether the rule runs without being turned on in `[rules]`
+    pub enabled_by_default: bool,
+}
+
+impl RuleMetadata {
+    /// Setting the rule has when `[rules]` does not mention it: its default
+    /// severity, or "off" for opt-in rules
+    pub fn default_level(&self) -> &'static str {
+        match (self.enabled_by_default, self.default_severity) {
+            (false, _) => "off",
+            (true, Severity::Warning) => "warning",
+            (true, Severity::Error) => "error",
+        }
+    }
 }
 
 /// Trait for validators
 pub trait Validator {
     fn name(&self) -> &str;
+    fn metadata(&self) -> &'static RuleMetadata;
     fn validate(&self, content: &str) -> ValidationResult;
+
+    /// Validate with the configured rule settings; rules without settings
+    /// ignore them
+    fn validate_with(&self, content: &str, _options: &RuleOptions) -> ValidationResult {
+        self.validate(content)
+    }
+}
+
+/// Settings of the rules that take them, from each rule's configuration
+/// table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleOptions {
+    /// Words `duplicate-words` accepts repeated, besides its built-in ones
+    pub duplicate_words_allowed: Vec<String>,
+    /// Longest run of one punctuation mark `repeated-punctuation` accepts
+    pub max_punctuation_run: usize,
+    /// Whether `repeated-punctuation` accepts `...` whatever the longest run
+    pub allow_ellipsis: bool,
+    /// Link text `link-text` flags
+    pub link_text_phrases: Vec<String>,
+    /// Markup the content is written in, whose code the prose rules skip;
+    /// set by [`policy::Policy::format`]
+    pub format: doc_format::DocFormat,
+}
+
+impl Default for RuleOptions {
+    fn default() -> Self {
+        Self {
+            duplicate_words_allowed: Vec::new(),
+            max_punctuation_run: 1,
+            allow_ellipsis: true,
+            link_text_phrases: validators::LinkTextValidator::PHRASES
+                .iter()
+                .map(|phrase| phrase.to_string())
+                .collect(),
+            format: doc_format::DocFormat::Markdown,
+        }
+    }
+}
+
+/// Coarse class of a character, computed once per character when the
+/// character rules scan the content
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharKind {
+    /// Printable ASCII, tab, or carriage return
+    Plain,
+    /// Any other ASCII control character
+    Control,
+    NonAscii,
+}
+
+impl CharKind {
+    pub fn of(ch: char) -> Self {
+        match ch {
+            ' '..='~' | '\t' | '\r' => CharKind::Plain,
+            _ if ch.is_ascii() => CharKind::Control,
+            _ => CharKind::NonAscii,
+        }
+    }
+}
+
+/// Trait for validators that judge each character on its own. They share one
+/// pass over the content ([`validators::scan`]) instead of a pass each.
+pub trait CharRule: Validator {
+    /// Whether the rule may flag characters of this kind; it is not asked
+    /// about the others
+    fn flags(&self, kind: CharKind) -> bool;
+
+    /// Whether `ch` violates the rule
+    fn violates(&self, ch: char) -> bool;
+
+    /// Message for a violating `ch`, formatted only when a violation is shown
+    fn message(&self, ch: char) -> String;
+}
+
+/// Trait for validators that read prose one line at a time, outside code.
+/// Lines are offered whole, code spans included, and violations starting in
+/// code are dropped afterwards ([`validators::prose`]), so files too large to
+/// hold in memory can be checked line by line too.
+pub trait ProseRule: Validator {
+    /// Violations in `line`, the `line_number`th of the file
+    fn check_line(
+        &self,
+        line_number: usize,
+        line: &str,
+        options: &RuleOptions,
+    ) -> Vec<ValidationError>;
 }
 
+/// How much of a file is checked once a violation is found. Stopping early
+/// is enough when only pass or fail matters, as when gating CI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StopAt {
+    /// Report every violation
+    #[default]
+    Never,
+    /// Report at most one violation per rule
+    FirstPerRule,
+    /// Report at most one violation per file
+    First,
+}
+
+/// Whether a violation of the rule with this code is exempt, so it is not
+/// reported and does not stop validation early
+pub type Exemption<'a> = dyn Fn(&str, &ValidationError) -> bool + 'a;
+
+pub mod build_info;
+pub mod charset;
+pub mod code_regions;
+pub mod columns;
+pub mod commit_msg;
+pub mod doc_format;
+pub mod encoding;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fixer;
+pub mod frontmatter;
+pub mod hash;
+pub mod line_endings;
+pub mod mdx;
+pub mod overlap;
+pub mod policy;
+pub mod rule_groups;
+pub mod rustdoc;
+pub mod statistics;
+pub mod suppressions;
+pub mod testing;
+pub mod timings;
+pub mod validators;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+// Files, processes, the terminal, and reporting: the `cli` feature. Without
+// it the library is the validators, policy, and fixer, as wasm32 builds need
+#[cfg(feature = "cli")]
+pub mod baseline;
+#[cfg(feature = "cli")]
+pub mod cache;
+#[cfg(feature = "cli")]
+pub mod cargo_subcommand;
+#[cfg(feature = "cli")]
 pub mod cli;
+#[cfg(feature = "cli")]
+pub mod config;
+#[cfg(feature = "cli")]
+pub mod external;
+#[cfg(feature = "cli")]
 pub mod file_ops;
-pub mod fixer;
+#[cfg(feature = "cli")]
+pub mod git;
+#[cfg(feature = "cli")]
+pub mod http;
+#[cfg(feature = "cli")]
+pub mod ignore_file;
+#[cfg(feature = "cli")]
+pub mod json_report;
+#[cfg(feature = "cli")]
+pub mod junit;
+#[cfg(feature = "cli")]
+pub mod lsp;
+#[cfg(feature = "cli")]
+pub mod markdown_report;
+#[cfg(feature = "cli")]
+pub mod mcp;
+#[cfg(feature = "cli")]
+pub mod navigation;
+#[cfg(feature = "cli")]
+pub mod parallel;
+#[cfg(feature = "cli")]
+pub mod path_display;
+#[cfg(feature = "cli")]
+pub mod presets;
+#[cfg(feature = "cli")]
+pub mod progress;
+#[cfg(feature = "cli")]
+pub mod remote;
+#[cfg(feature = "cli")]
 pub mod reporter;
-pub mod validators;
+#[cfg(feature = "cli")]
+pub mod sarif;
+#[cfg(feature = "cli")]
+pub mod scaffold;
+#[cfg(feature = "cli")]
+pub mod stream;
+#[cfg(feature = "cli")]
+pub mod style;
+#[cfg(feature = "cli")]
+pub mod tui;
 
 #[cfg(test)]
 mod tests {
@@ -105,10 +444,25 @@ mod tests {
         let error = ValidationError::new(5, "Test error".to_string());
         assert_eq!(error.line_number, 5);
         assert_eq!(error.column, None);
-        assert_eq!(error.message, "Test error");
+        assert_eq!(error.message.text(), "Test error");
         assert_eq!(error.context, None);
     }
 
+    #[test]
+    fn test_flagged_message_is_formatted_when_shown() {
+        let error = ValidationError::flagged(1, "MC001", '\u{e9}');
+        assert_eq!(
+            error.message.text(),
+            "Non-ASCII character: '\u{e9}' (U+00E9)"
+        );
+
+        // Stored as text, and equal to the same message read back
+        let json = serde_json::to_string(&error).unwrap();
+        let read: ValidationError = serde_json::from_str(&json).unwrap();
+        assert!(matches!(read.message, Message::Text(_)));
+        assert_eq!(read, error);
+    }
+
     #[test]
     fn test_validation_error_with_column() {
         let error = ValidationError::new(10, "Error".to_string()).with_column(15);
@@ -155,4 +509,28 @@ mod tests {
         assert!(!result.is_pass());
         assert!(result.is_fail());
     }
+
+    #[test]
+    fn test_severity_display_and_order() {
+        assert_eq!(Severity::Error.to_string(), "error");
+        assert_eq!(Severity::Warning.to_string(), "warning");
+        assert!(Severity::Error > Severity::Warning);
+    }
+
+    #[test]
+    fn test_validation_result_retain_errors() {
+        let errors = vec![
+            ValidationError::new(1, "Error 1".to_string()),
+            ValidationError::new(2, "Error 2".to_string()),
+        ];
+        let result = ValidationResult::fail("Test Validator".to_string(), errors.clone())
+            .retain_errors(|e| e.line_number == 2);
+        assert!(result.is_fail());
+        assert_eq!(result.errors.len(), 1);
+
+        let result = ValidationResult::fail("Test Validator".to_string(), errors)
+            .retain_errors(|e| e.line_number > 5);
+        assert!(result.is_pass());
+        assert!(result.errors.is_empty());
+    }
 }
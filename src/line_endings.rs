@@ -0,0 +1,96 @@
+//! Line endings of checked and written files (`--eol`).
+//!
+//! A line ending is LF or CRLF, as for `str::lines`, and is never part of its
+//! line: every validator, reporter, and fixer counts lines and columns the
+//! same way in LF and CRLF files, so a CR cannot shift a column. Reports
+//! name the convention a file uses (`convention`).
+//!
+//! Fixing replaces characters and leaves line endings as they were, so a CRLF
+//! file stays CRLF. Teams that standardize on one convention can have fixed
+//! files written with it instead; files that need no fixing are not touched.
+
+use std::borrow::Cow;
+
+/// Line-ending convention for fixed files
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum, schemars::JsonSchema))]
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LineEnding {
+    /// `\n`, as on Unix and macOS
+    Lf,
+    /// `\r\n`, as on Windows
+    Crlf,
+    /// Whichever of the two this platform uses
+    Native,
+}
+
+impl LineEnding {
+    /// The characters that end a line
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+            LineEnding::Native if cfg!(windows) => "\r\n",
+            LineEnding::Native => "\n",
+        }
+    }
+
+    /// `content` with every line ending, LF or CRLF, replaced by this one.
+    /// A lone CR is not a line ending and is kept.
+    pub fn apply(self, content: &str) -> Cow<'_, str> {
+        let eol = self.as_str();
+        let differs = content.split_inclusive('\n').any(|line| {
+            let crlf = line.ends_with("\r\n");
+            line.ends_with('\n') && crlf != (eol == "\r\n")
+        });
+        if !differs {
+            return Cow::Borrowed(content);
+        }
+        let mut applied = String::with_capacity(content.len() + content.len() / 32);
+        for line in content.split_inclusive('\n') {
+            match line.strip_suffix('\n') {
+                Some(text) => {
+                    applied.push_str(text.strip_suffix('\r').unwrap_or(text));
+                    applied.push_str(eol);
+                }
+                None => applied.push_str(line),
+            }
+        }
+        Cow::Owned(applied)
+    }
+}
+
+/// Line endings `content` uses: `"lf"`, `"crlf"`, `"mixed"`, or `"none"`
+/// when it has a single line. A lone CR is not a line ending.
+pub fn convention(content: &str) -> &'static str {
+    let crlf = content.matches("\r\n").count();
+    let lf = content.matches('\n').count() - crlf;
+    match (lf, crlf) {
+        (0, 0) => "none",
+        (_, 0) => "lf",
+        (0, _) => "crlf",
+        _ => "mixed",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply() {
+        let mixed = "# Title\r\nbody\nlast";
+        assert_eq!(LineEnding::Lf.apply(mixed), "# Title\nbody\nlast");
+        assert_eq!(LineEnding::Crlf.apply(mixed), "# Title\r\nbody\r\nlast");
+        assert_eq!(LineEnding::Crlf.apply("a\r\r\n\rb\n"), "a\r\r\n\rb\r\n");
+        assert!(matches!(LineEnding::Lf.apply("a\nb\n"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_convention() {
+        assert_eq!(convention("a\nb\n"), "lf");
+        assert_eq!(convention("a\r\nb"), "crlf");
+        assert_eq!(convention("a\r\nb\n"), "mixed");
+        assert_eq!(convention("a\rb"), "none");
+    }
+}
@@ -0,0 +1,439 @@
+//! Language Server Protocol mode (`serve --lsp`).
+//!
+//! A small JSON-RPC server over stdio: open documents are checked on every
+//! change and their violations published as diagnostics, and code actions
+//! replace fixable characters using the fixer's suggestions. Documents are
+//! synchronized in full, and positions are counted in UTF-16 code units as
+//! LSP requires.
+
+use crate::columns::ColumnUnit;
+use crate::config::ConfigCascade;
+use crate::fixer::{fix_allowed, replacement_in};
+use crate::policy::Policy;
+use crate::scaffold::CONFIG_FILE_NAME;
+use crate::validators::find_rule;
+use crate::{Severity, ValidationError};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+const SOURCE: &str = "markdown-checker";
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_REQUEST: i64 = -32600;
+
+/// Serve LSP requests from `input` until the client sends `exit`. Returns
+/// whether the client asked to shut down first, as a clean exit requires.
+pub fn serve(mut input: impl BufRead, output: impl Write) -> io::Result<bool> {
+    let mut server = Server::new(output);
+    while let Some(message) = read_message(&mut input)? {
+        if !server.handle(message)? {
+            break;
+        }
+    }
+    Ok(server.shut_down)
+}
+
+struct Server<W> {
+    output: W,
+    /// Text of each open document, by URI
+    documents: HashMap<String, String>,
+    cascade: ConfigCascade,
+    shut_down: bool,
+}
+
+impl<W: Write> Server<W> {
+    fn new(output: W) -> Self {
+        Self {
+            output,
+            documents: HashMap::new(),
+            cascade: ConfigCascade::new(),
+            shut_down: false,
+        }
+    }
+
+    /// Handle one message; returns `false` once the client sends `exit`
+    fn handle(&mut self, message: Value) -> io::Result<bool> {
+        let method = message["method"].as_str().unwrap_or_default().to_string();
+        let params = &message["params"];
+        let Some(id) = message.get("id").cloned() else {
+            return self.notify(&method, params);
+        };
+        // A response to a request of ours; the server sends none
+        if method.is_empty() {
+            return Ok(true);
+        }
+        let result = match method.as_str() {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    "textDocumentSync": { "openClose": true, "change": 1, "save": true },
+                    "codeActionProvider": {
+                        "codeActionKinds": ["quickfix", "source.fixAll"]
+                    }
+                },
+                "serverInfo": { "name": SOURCE, "version": env!("CARGO_PKG_VERSION") }
+            })),
+            "shutdown" => {
+                self.shut_down = true;
+                Ok(Value::Null)
+            }
+            "textDocument/codeAction" => Ok(self.code_actions(params)),
+            _ if self.shut_down => Err((INVALID_REQUEST, "the server is shutting down")),
+            _ => Err((METHOD_NOT_FOUND, "method not supported")),
+        };
+        let response = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": format!("{}: {}", message, method) }
+            }),
+        };
+        write_message(&mut self.output, &response)?;
+        Ok(true)
+    }
+
+    fn notify(&mut self, method: &str, params: &Value) -> io::Result<bool> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        match method {
+            "exit" => return Ok(false),
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.clone(), text.to_string());
+                self.publish(&uri)?;
+            }
+            "textDocument/didChange" => {
+                // Full sync: the last change holds the whole text
+                let changes = params["contentChanges"].as_array();
+                if let Some(text) = changes.and_then(|c| c.last()?["text"].as_str()) {
+                    self.documents.insert(uri.clone(), text.to_string());
+                    self.publish(&uri)?;
+                }
+            }
+            "textDocument/didSave" if uri.ends_with(CONFIG_FILE_NAME) => {
+                // Reload configuration and recheck everything it may affect
+                self.cascade = ConfigCascade::new();
+                let open: Vec<String> = self.documents.keys().cloned().collect();
+                for uri in open {
+                    self.publish(&uri)?;
+                }
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                self.send_diagnostics(&uri, Vec::new())?;
+            }
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// The rules that apply to the document at `uri`
+    fn policy(&mut self, uri: &str) -> Policy {
+        let Some(path) = uri_to_path(uri) else {
+            return Policy::default();
+        };
+        match self.cascade.for_file(&path) {
+            Ok(config) => config.policy_for(&path),
+            Err(e) => {
+                eprintln!("Error loading config: {}", e);
+                Policy::default()
+            }
+        }
+    }
+
+    fn publish(&mut self, uri: &str) -> io::Result<()> {
+        let policy = self.policy(uri);
+        let content = &self.documents[uri];
+        let diagnostics = diagnostics(content, &policy);
+        self.send_diagnostics(uri, diagnostics)
+    }
+
+    fn send_diagnostics(&mut self, uri: &str, diagnostics: Vec<Value>) -> io::Result<()> {
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics }
+        });
+        write_message(&mut self.output, &notification)
+    }
+
+    /// A quick fix for each fixable diagnostic in the request, and one action
+    /// fixing every fixable character in the document
+    fn code_actions(&mut self, params: &Value) -> Value {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        let policy = self.policy(uri);
+        let Some(content) = self.documents.get(uri) else {
+            return json!([]);
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        let mut actions = Vec::new();
+
+        let requested = params["context"]["diagnostics"].as_array();
+        for diagnostic in requested.into_iter().flatten() {
+            if diagnostic["source"] != SOURCE {
+                continue;
+            }
+            let position = |key: &str| diagnostic["data"][key].as_u64().map(|n| n as usize);
+            let (Some(line), Some(column)) = (position("line"), position("column")) else {
+                continue;
+            };
+            let Some(text) = line.checked_sub(1).and_then(|line| lines.get(line)) else {
+                continue;
+            };
+            let Some(ch) = column.checked_sub(1).and_then(|col| text.chars().nth(col)) else {
+                continue;
+            };
+            let Some(ascii) = replacement_in(text, column) else {
+                continue;
+            };
+            actions.push(json!({
+                "title": format!("Replace '{}' with '{}'", ch, ascii),
+                "kind": "quickfix",
+                "diagnostics": [diagnostic],
+                "isPreferred": true,
+                "edit": { "changes": { uri: [
+                    { "range": diagnostic["range"], "newText": ascii }
+                ] } }
+            }));
+        }
+
+        let (fixed, replaced) = fix_allowed(content, &policy);
+        if replaced > 0 {
+            actions.push(json!({
+                "title": format!("Fix {} auto-fixable character(s)", replaced),
+                "kind": "source.fixAll",
+                "edit": { "changes": { uri: [
+                    { "range": range(0, 0, lines.len() + 1, 0), "newText": fixed }
+                ] } }
+            }));
+        }
+
+        // Clients may ask only for some kinds, such as fix-all on save
+        if let Some(only) = params["context"]["only"].as_array() {
+            actions.retain(|action| {
+                let kind = action["kind"].as_str().unwrap_or_default();
+                only.iter()
+                    .filter_map(Value::as_str)
+                    .any(|wanted| kind == wanted || kind.starts_with(&format!("{}.", wanted)))
+            });
+        }
+        Value::Array(actions)
+    }
+}
+
+/// Diagnostics for the violations the policy reports in `content`
+fn diagnostics(content: &str, policy: &Policy) -> Vec<Value> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut diagnostics = Vec::new();
+    for result in policy.validate(content) {
+        let code = find_rule(&result.validator_name).map(|rule| rule.code);
+        let severity = match result.severity {
+            Severity::Error => 1,
+            Severity::Warning => 2,
+        };
+        for error in &result.errors {
+            diagnostics.push(json!({
+                "range": error_range(&lines, error),
+                "severity": severity,
+                "code": code,
+                "source": SOURCE,
+                "message": error.message.to_string(),
+                "data": { "line": error.line_number, "column": error.column },
+            }));
+        }
+    }
+    diagnostics
+}
+
+/// The flagged character, or the whole line without a column, in UTF-16
+fn error_range(lines: &[&str], error: &ValidationError) -> Value {
+    let line = error.line_number.saturating_sub(1);
+    let text = lines.get(line).copied().unwrap_or_default();
+    match error.column {
+        Some(column) => range(
+            line,
+            ColumnUnit::Utf16.start(text, column) - 1,
+            line,
+            ColumnUnit::Utf16.end(text, column) - 1,
+        ),
+        None => range(line, 0, line, text.encode_utf16().count()),
+    }
+}
+
+fn range(start_line: usize, start: usize, end_line: usize, end: usize) -> Value {
+    json!({
+        "start": { "line": start_line, "character": start },
+        "end": { "line": end_line, "character": end }
+    })
+}
+
+/// The local path of a `file://` URI
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = percent_decode(uri.strip_prefix("file://")?)?;
+    // file:///C:/docs/a.md names C:/docs/a.md on Windows
+    let path = match path.as_bytes() {
+        [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => path[1..].to_string(),
+        _ => path,
+    };
+    Some(PathBuf::from(path))
+}
+
+pub(crate) fn percent_decode(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Read one `Content-Length`-framed message, or `None` at end of input
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    let mut length = None;
+    let mut header = String::new();
+    loop {
+        header.clear();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("Content-Length")
+        {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let length = length.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "message without Content-Length")
+    })?;
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(message: Value) -> String {
+        let body = message.to_string();
+        format!("Content-Length: {}\r\n\r\n{}", body.len(), body)
+    }
+
+    /// Run a session and return the messages the server sent
+    fn session(messages: Vec<Value>) -> (bool, Vec<Value>) {
+        let input: String = messages.into_iter().map(frame).collect();
+        let mut output = Vec::new();
+        let shut_down = serve(input.as_bytes(), &mut output).unwrap();
+        let mut sent = Vec::new();
+        let mut reader = output.as_slice();
+        while let Some(message) = read_message(&mut reader).unwrap() {
+            sent.push(message);
+        }
+        (shut_down, sent)
+    }
+
+    #[test]
+    fn test_diagnostics_and_code_actions() {
+        let uri = "untitled:Untitled-1";
+        let (shut_down, sent) = session(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+                "textDocument": { "uri": uri, "languageId": "markdown", "version": 1,
+                                  "text": "# \u{1f600} \u{251c}\n" }
+            } }),
+        ]);
+        assert!(!shut_down);
+        assert!(sent[0]["result"]["capabilities"]["codeActionProvider"].is_object());
+
+        let diagnostics = sent[1]["params"]["diagnostics"].as_array().unwrap();
+        let tree = diagnostics
+            .iter()
+            .find(|d| d["code"] == "MC003")
+            .unwrap()
+            .clone();
+        // The emoji before it is two UTF-16 code units
+        assert_eq!(tree["range"], range(0, 5, 0, 6));
+        assert_eq!(tree["severity"], 1);
+
+        let (_, sent) = session(vec![
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+                "textDocument": { "uri": uri, "text": "# \u{1f600} \u{251c}\n" }
+            } }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "textDocument/codeAction", "params": {
+                "textDocument": { "uri": uri },
+                "range": tree["range"],
+                "context": { "diagnostics": [tree] }
+            } }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ]);
+        let actions = sent[1]["result"].as_array().unwrap();
+        assert_eq!(actions[0]["title"], "Replace '\u{251c}' with '+'");
+        assert_eq!(actions[0]["edit"]["changes"][uri][0]["newText"], json!("+"));
+        assert_eq!(actions[1]["kind"], "source.fixAll");
+        assert_eq!(sent[2]["result"], Value::Null);
+    }
+
+    #[test]
+    fn test_unknown_request_and_close() {
+        let uri = "untitled:a";
+        let (shut_down, sent) = session(vec![
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "textDocument/hover", "params": {} }),
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+                "textDocument": { "uri": uri, "text": "\u{e9}" }
+            } }),
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didClose", "params": {
+                "textDocument": { "uri": uri }
+            } }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ]);
+        assert!(shut_down);
+        assert_eq!(sent[0]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(
+            sent[1]["params"]["diagnostics"].as_array().unwrap().len(),
+            1
+        );
+        assert!(
+            sent[2]["params"]["diagnostics"]
+                .as_array()
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_uri_to_path() {
+        assert_eq!(
+            uri_to_path("file:///home/me/My%20Docs/a.md"),
+            Some(PathBuf::from("/home/me/My Docs/a.md"))
+        );
+        assert_eq!(
+            uri_to_path("file:///C:/docs/a.md"),
+            Some(PathBuf::from("C:/docs/a.md"))
+        );
+        assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+    }
+}
@@ -1,28 +1,183 @@
-use clap::Parser;
+use clap::CommandFactory;
 use glob::glob;
-use markdown_checker::cli::{Cli, Config};
-use markdown_checker::file_ops::{read_file_content, write_file_content};
-use markdown_checker::fixer::fix_tree_symbols;
-use markdown_checker::reporter::{format_results, should_exit_with_error};
-use markdown_checker::validators::validate_all;
-use std::path::PathBuf;
+use markdown_checker::baseline::Baseline;
+use markdown_checker::build_info::BuildInfo;
+use markdown_checker::cache::{DEFAULT_CACHE_DIR, ResultCache, rules_hash};
+use markdown_checker::cli::{
+    BaselineMode, CacheAction, Cli, Commands, Config, InfoFormat, Invocation, OutputFormat,
+    RunMode, expand_file_list_args,
+};
+use markdown_checker::config::{ConfigCascade, ConfigFile, json_schema};
+use markdown_checker::encoding::{NotUtf8, decode, into_not_utf8};
+use markdown_checker::file_ops::{
+    FileContent, FileSize, depth_limited_patterns, file_size, open_buffered, path_depth,
+    read_file_content, read_file_list, sniff_binary, write_fix,
+};
+use markdown_checker::fixer::{count_replacements, fix_completely};
+use markdown_checker::git;
+use markdown_checker::http;
+use markdown_checker::ignore_file::IgnoreFile;
+use markdown_checker::lsp;
+use markdown_checker::mcp;
+use markdown_checker::navigation::Navigation;
+use markdown_checker::parallel::{parallel_for_each, parallel_map};
+use markdown_checker::path_display::PathDisplay;
+use markdown_checker::policy::{Policy, RuleCount};
+use markdown_checker::presets::Preset;
+use markdown_checker::progress::Progress;
+use markdown_checker::remote::{fetch_bytes, url_of};
+use markdown_checker::reporter::{
+    FixOutcome, FixStatus, count_error_violations, count_violations, count_warning_violations,
+    exceeds_error_threshold, exceeds_warning_threshold, format_rule_explanation, format_rules,
+    format_statistics, format_timings, reporter_for, should_exit_with_error,
+};
+use markdown_checker::scaffold;
+use markdown_checker::statistics::Statistics;
+use markdown_checker::stream::{STREAM_THRESHOLD, validate_stream};
+use markdown_checker::timings::Timings;
+use markdown_checker::tui::{self, Review, ReviewFile};
+use markdown_checker::validators::{EncodingRule, NavigationRule, all_rules, find_rule};
+use markdown_checker::{Severity, StopAt, ValidationResult};
+use std::collections::BTreeMap;
+use std::io::{self, IsTerminal};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
 fn main() {
     // Check for version flag with detailed output
     let args: Vec<String> = std::env::args().collect();
     if args.len() > 1 && (args[1] == "-V" || args[1] == "--version") {
-        println!("{}", sw_cli::version!());
+        let json = args
+            .windows(2)
+            .any(|w| w[0] == "--format" && w[1] == "json")
+            || args.iter().any(|a| a == "--format=json");
+        print_version(if json {
+            InfoFormat::Json
+        } else {
+            InfoFormat::Text
+        });
         return;
     }
 
-    let cli = Cli::parse();
-    let config = Config::from_cli(cli);
+    let (cli, explicit) = Cli::try_parse_with_sources(expand_file_list_args(std::env::args_os()))
+        .unwrap_or_else(|e| e.exit());
+    let default_mode = cli.uses_default_mode();
+    let (mut mode, mut args) = match cli.into_invocation() {
+        Invocation::Run(mode, args) => (mode, args),
+        Invocation::Command(command) => {
+            run_command(command);
+            return;
+        }
+    };
+
+    // Without --config, each file uses the configuration files above it; run
+    // settings come from those above the working directory. All of them
+    // apply over the preset, and --rule settings apply over them.
+    let base = args.preset.map(Preset::config).unwrap_or_default();
+    let mut cascade = args
+        .discovers_config()
+        .then(|| ConfigCascade::with_base(base.clone()));
+    let loaded = match (&args.config, cascade.as_mut()) {
+        (Some(path), _) => ConfigFile::load(path).map(|config| base.overridden_by(config)),
+        (None, Some(cascade)) => cascade.for_dir(Path::new(".")).cloned(),
+        (None, None) => Ok(base),
+    };
+    let file_config = loaded
+        .unwrap_or_else(|e| {
+            eprintln!("Error loading config: {}", e);
+            process::exit(2);
+        })
+        .with_rule_overrides(&args.rules);
+    args.apply_config_file(&file_config, &explicit);
+    if default_mode {
+        mode = file_config.auto_fix().mode();
+    }
+
+    if let Err(e) = args.validate(mode) {
+        Cli::command()
+            .error(clap::error::ErrorKind::ArgumentConflict, e)
+            .exit();
+    }
 
-    // Resolve file pattern to list of files
-    let files = match resolve_files(&config) {
-        Ok(f) if f.is_empty() => {
-            // Special case: if looking for README.md and neither README.md nor README.org exist,
+    let ignore_file = args.ignore_path.as_deref().map(|path| {
+        IgnoreFile::load(path).unwrap_or_else(|e| {
+            eprintln!("Error loading ignore file: {}", e);
+            process::exit(2);
+        })
+    });
+    let config = Config::from_args(mode, args, file_config);
+    let navigation = config.site.map(|site| {
+        Navigation::load(site, &config.path).unwrap_or_else(|e| {
+            eprintln!("Error loading site navigation: {}", e);
+            process::exit(2);
+        })
+    });
+
+    // Resolve file pattern to list of files, dropping those ignored by
+    // configuration or the ignore file, and find the rules for each. Binary
+    // and oversized files are skipped with a notice and counted.
+    let mut skipped = 0;
+    let resolved = resolve_files(&config, navigation.as_ref(), &mut skipped).map(|files| {
+        files
+            .into_iter()
+            .filter(|f| !ignore_file.as_ref().is_some_and(|i| i.is_ignored(f)))
+            .filter(|f| {
+                let within = within_size_limit(f, &config);
+                skipped += usize::from(!within);
+                within
+            })
+            .filter_map(|f| {
+                // Fetched documents have no directory to search for configuration
+                let local = match cascade.as_mut().filter(|_| url_of(&f).is_none()) {
+                    Some(cascade) => cascade.for_file(&f).unwrap_or_else(|e| {
+                        eprintln!("Error loading config: {}", e);
+                        process::exit(2);
+                    }),
+                    None => &config.file_config,
+                };
+                (!local.is_ignored(&f)).then(|| {
+                    let mut policy = if config.rule_overrides.is_empty() {
+                        local.policy_for(&f)
+                    } else {
+                        local
+                            .clone()
+                            .with_rule_overrides(&config.rule_overrides)
+                            .policy_for(&f)
+                    };
+                    if let Some(format) = config.doc_type {
+                        policy = policy.format(format);
+                    }
+                    policy.skip_code |= config.skip_code;
+                    policy.skip_frontmatter |= config.skip_frontmatter;
+                    if config.fast {
+                        policy.stop_at = StopAt::First;
+                    }
+                    (f, policy)
+                })
+            })
+            .unzip::<_, _, Vec<_>, Vec<_>>()
+    });
+    let (files, policies) = match resolved {
+        Ok((f, _)) if f.is_empty() => {
+            // Nothing staged, changed, or listed means nothing to check
+            if config.staged || config.since.is_some() || config.files_from.is_some() {
+                process::exit(0);
+            }
+            // Every match was skipped as noted, which is not a missing file
+            if skipped > 0 {
+                process::exit(0);
+            }
+            if !config.include.is_empty() {
+                eprintln!(
+                    "No files found matching [files] include patterns: {}",
+                    config.include.join(", ")
+                );
+                process::exit(2);
+            }
+            // Special case: if looking for README.md and no README exists,
             // this is just a warning, not an error - exit successfully
             if config.filename == "README.md" {
                 process::exit(0);
@@ -37,150 +192,1049 @@ fn main() {
         }
     };
 
+    if config.count {
+        run_count(&files, &policies, &config);
+        return;
+    }
+
+    // Known violations to suppress, or the baseline being recorded
+    let mut baseline = match &config.baseline {
+        Some((BaselineMode::Check, path)) => match Baseline::load(path) {
+            Ok(b) => b,
+            Err(e) => {
+                eprintln!("Error loading baseline {}: {}", path.display(), e);
+                process::exit(2);
+            }
+        },
+        _ => Baseline::new(),
+    };
+    let mut baseline_suppressed = 0;
+
     let mut overall_success = true;
+    // Whether every failure so far could be resolved by running --fix
+    let mut all_fixable = true;
     let mut files_processed = 0;
+    let mut total_violations = 0;
+    let mut total_warnings = 0;
+    let mut statistics = Statistics::new();
+    let mut timings = Timings::new();
+    let mut reporter = reporter_for(&config, files.len());
+    let path_display = PathDisplay::new(config.paths);
+    let run_start = Instant::now();
+
+    // Results from previous runs, reused for files whose content is unchanged
+    let mut cache = config
+        .cache
+        .then(|| ResultCache::open(Path::new(DEFAULT_CACHE_DIR)));
 
-    for file_path in &files {
-        // Read file content
-        let content = match read_file_content(file_path) {
+    // With --fail-fast, workers skip files after the first one known to fail.
+    // Baseline suppression happens later, a file --fix resolves does not
+    // fail, and under --max-errors or --max-warnings one failing file need
+    // not fail the run, so each disables the shortcut.
+    let first_failure = AtomicUsize::new(usize::MAX);
+    let skip_after_failure = config.fail_fast
+        && config.mode == RunMode::Check
+        && config.baseline.is_none()
+        && config.max_errors.is_none()
+        && config.max_warnings.is_none();
+
+    // Read and validate files concurrently; outcomes come back in file order.
+    // Streaming formats report each file as soon as it and all earlier files
+    // are done, so there is no progress bar to interleave with.
+    let streaming = config.format == OutputFormat::Jsonl && !config.tui;
+    let progress = Progress::for_batch(files.len(), config.quiet || streaming);
+    let indexed: Vec<(usize, &PathBuf)> = files.iter().enumerate().collect();
+    let check = |&(index, file_path): &(usize, &PathBuf)| {
+        if index > first_failure.load(Ordering::Relaxed) {
+            return None;
+        }
+        let policy = &policies[index];
+        let check = check_file(file_path, &config, policy, cache.as_ref()).map(|mut check| {
+            // Structure problems are reported when checking; fixing leaves them
+            if let Some(navigation) = &navigation
+                && config.mode == RunMode::Check
+                && !policy
+                    .disabled_rules
+                    .contains(&NavigationRule::METADATA.code)
+            {
+                let result = navigation.validate(file_path, &check.content);
+                let (results, suppressed) = policy.filter(&check.content, vec![result]);
+                check.results.extend(results);
+                check.suppressed += suppressed;
+            }
+            check
+        });
+        // External validators run on the file itself and only when checking
+        let check = check.and_then(|mut check| {
+            if config.mode == RunMode::Check && url_of(file_path).is_none() {
+                for (name, validator) in &config.file_config.external_validators {
+                    let results = validator.run(name, file_path)?;
+                    let (results, suppressed) = policy.filter(&check.content, results);
+                    check.results.extend(results);
+                    check.suppressed += suppressed;
+                }
+            }
+            Ok(check)
+        });
+        let violations = check
+            .as_ref()
+            .map(|c| count_violations(&c.results))
+            .unwrap_or(0);
+        let failing = check
+            .as_ref()
+            .map_or(true, |c| should_exit_with_error(&c.results));
+        if skip_after_failure && failing {
+            first_failure.fetch_min(index, Ordering::Relaxed);
+        }
+        progress.tick(file_path, violations);
+        Some(check)
+    };
+
+    if config.tui {
+        let checks = parallel_map(&indexed, config.jobs, check);
+        progress.finish();
+        review_in_tui(&files, &policies, checks, &config);
+        return;
+    }
+
+    // Fresh results to store in the cache once every file has been checked
+    let mut cache_entries = Vec::new();
+    let mut handle = |&(index, file_path): &(usize, &PathBuf),
+                      check: Option<Result<FileCheck, String>>| {
+        // Skipped because an earlier file failed under --fail-fast
+        let Some(check) = check else {
+            return ControlFlow::Break(());
+        };
+        let FileCheck {
+            content,
+            results,
+            cache_entry,
+            suppressed,
+            elapsed,
+            validator_times,
+            streamed,
+            decoding,
+        } = match check {
             Ok(c) => c,
             Err(e) => {
-                eprintln!("Error reading file {}: {}", file_path.display(), e);
+                eprintln!("{}", e);
                 overall_success = false;
-                continue;
+                all_fixable = false;
+                files_processed += 1;
+                statistics.record_unreadable();
+                if config.fail_fast {
+                    return ControlFlow::Break(());
+                }
+                return ControlFlow::Continue(());
             }
         };
 
-        // Run all validators
-        let results = validate_all(&content);
+        if let Some(entry) = cache_entry {
+            cache_entries.push(entry);
+        }
+
+        let results = match &config.baseline {
+            Some((BaselineMode::Write, _)) => {
+                baseline.record(file_path, &content, &results);
+                results
+            }
+            Some((BaselineMode::Check, _)) => {
+                let (results, suppressed) = baseline.suppress(file_path, &content, results);
+                baseline_suppressed += suppressed;
+                results
+            }
+            None => results,
+        };
+
+        // Reports show the path in the --paths style; file IO keeps the real one
+        let shown = path_display.display(file_path);
+        statistics.record(&shown, &results);
+        statistics.record_suppressed(suppressed);
+        timings.record(&shown, elapsed, &validator_times);
 
         // Check if we need to fix anything
         let needs_fixing = results.iter().any(|r| r.is_fail());
 
-        if config.fix || config.dry_run {
-            if needs_fixing {
-                // Try to fix by replacing tree symbols
-                let fixed_content = fix_tree_symbols(&content);
-
-                // Re-validate the fixed content to see if all violations are resolved
-                let fixed_results = validate_all(&fixed_content);
-                let all_fixed = fixed_results.iter().all(|r| r.is_pass());
-
-                if all_fixed {
-                    // All violations were tree symbols and have been fixed
-                    let tree_result = results.iter().find(|r| r.validator_name == "Tree Symbols");
-                    let violation_count = tree_result.map(|r| r.errors.len()).unwrap_or(0);
-
-                    if config.dry_run {
-                        // Dry-run mode: show what would be changed
-                        println!("🔍 Dry-run mode for: {}", file_path.display());
-                        println!("   Would fix {} tree symbol violation(s)", violation_count);
-                        if config.verbose {
-                            println!("\nOriginal violations:");
-                            let output =
-                                format_results(&results, &file_path.display().to_string(), false);
-                            print!("{}", output);
-                            println!("\n✓ After fix: All violations would be resolved");
-                        }
-                        println!();
-                    } else {
-                        // Apply the fix
-                        match write_file_content(file_path, &fixed_content) {
-                            Ok(_) => {
-                                println!(
-                                    "✓ Fixed {} tree symbol violation(s) in: {}",
-                                    violation_count,
-                                    file_path.display()
-                                );
-                            }
-                            Err(e) => {
-                                eprintln!(
-                                    "✗ Error writing fixed content to {}: {}",
-                                    file_path.display(),
-                                    e
-                                );
-                                overall_success = false;
-                            }
-                        }
-                    }
+        if config.is_fixing() {
+            // Files that could not be converted to UTF-8 have no text to fix
+            let fixed = if needs_fixing && decoding != Decoding::Failed {
+                // Keep the fix only if re-validation shows all violations are resolved
+                fix_completely(&content, &policies[index])
+            } else {
+                None
+            };
+            let status = match (&fixed, needs_fixing) {
+                (_, false) => FixStatus::Unchanged,
+                (Some(_), true) => FixStatus::Changed,
+                (None, true) => FixStatus::Unfixable,
+            };
+            let outcome = FixOutcome {
+                mode: config.mode,
+                status,
+                replacements: if fixed.is_some() {
+                    count_replacements(&content, &policies[index])
                 } else {
-                    // File has non-tree-symbol violations that cannot be auto-fixed
-                    let output =
-                        format_results(&results, &file_path.display().to_string(), config.verbose);
-                    print!("{}", output);
-                    eprintln!("\n⚠️  Cannot auto-fix: File contains non-fixable violations.");
-                    eprintln!(
-                        "Common Unicode characters can be auto-fixed (tree symbols, checkmarks, arrows, accents, quotes, etc.)."
-                    );
-                    eprintln!(
-                        "This file has other Unicode characters or unprintable control characters that cannot be safely converted."
-                    );
+                    0
+                },
+            };
+
+            // A file that could not be written has no outcome to report
+            let mut written = true;
+            match status {
+                FixStatus::Changed if config.mode == RunMode::FixCheck => {
+                    // Like a formatter's --check, files that would change fail the run
                     overall_success = false;
                 }
-            } else {
-                // No violations
-                if config.verbose || files.len() == 1 {
-                    println!("✓ File validation successful: {}", file_path.display());
+                FixStatus::Changed if config.mode == RunMode::Fix => {
+                    if let Err(e) = write_fix(
+                        file_path,
+                        &content,
+                        fixed.as_deref().unwrap_or_default(),
+                        policies[index].bom,
+                        config.eol,
+                        config.keep_mtime,
+                    ) {
+                        eprintln!(
+                            "✗ Error writing fixed content to {}: {}",
+                            shown.display(),
+                            e
+                        );
+                        overall_success = false;
+                        all_fixable = false;
+                        written = false;
+                    } else {
+                        statistics.record_fixed(&shown);
+                    }
                 }
+                // Warnings left unfixed do not fail the run
+                FixStatus::Unfixable if should_exit_with_error(&results) => {
+                    overall_success = false;
+                    all_fixable = false;
+                }
+                _ => {}
+            }
+            if written {
+                print!(
+                    "{}",
+                    reporter.report_fix(&shown, &content, &results, &outcome)
+                );
+            }
+            if status == FixStatus::Unfixable && config.format == OutputFormat::Text {
+                eprintln!("\n⚠️  Cannot auto-fix: File contains non-fixable violations.");
+                eprintln!(
+                    "Common Unicode characters can be auto-fixed (tree symbols, checkmarks, arrows, accents, quotes, etc.)."
+                );
+                eprintln!(
+                    "This file has other Unicode characters or unprintable control characters that cannot be safely converted."
+                );
             }
         } else {
-            // Normal validation mode (no fix/dry-run)
-            let output = format_results(&results, &file_path.display().to_string(), config.verbose);
-            print!("{}", output);
+            // Normal validation mode (no fix/dry-run), rendered by the selected reporter
+            print!("{}", reporter.report_file(&shown, &content, &results));
+            total_warnings += count_warning_violations(&results);
 
             if should_exit_with_error(&results) {
-                overall_success = false;
+                total_violations += count_error_violations(&results);
+                // Streamed content is not kept and fetched documents cannot be
+                // written, so there is nothing to fix, and fixing content
+                // cannot resolve navigation or external validators, which
+                // re-validating the fixed content does not run
+                let unfixable_rule = results.iter().any(|r| {
+                    r.is_error()
+                        && find_rule(&r.validator_name)
+                            .is_none_or(|rule| rule.code == NavigationRule::METADATA.code)
+                });
+                if all_fixable
+                    && (streamed
+                        || decoding == Decoding::Failed
+                        || url_of(file_path).is_some()
+                        || unfixable_rule
+                        || fix_completely(&content, &policies[index]).is_none())
+                {
+                    all_fixable = false;
+                }
             }
         }
 
         files_processed += 1;
+
+        // Stop once the run has failed, not at violations within --max-errors
+        if config.fail_fast
+            && (!overall_success || config.exceeds_thresholds(total_violations, total_warnings))
+        {
+            return ControlFlow::Break(());
+        }
+        ControlFlow::Continue(())
+    };
+
+    if streaming {
+        parallel_for_each(&indexed, config.jobs, check, handle);
+    } else {
+        let checks = parallel_map(&indexed, config.jobs, check);
+        progress.finish();
+        for (item, check) in indexed.iter().zip(checks) {
+            if handle(item, check).is_break() {
+                break;
+            }
+        }
+    }
+    if let Some(cache) = cache.as_mut() {
+        for (key, raw_results) in cache_entries {
+            cache.insert(key, raw_results);
+        }
+    }
+
+    if config.fail_fast && files_processed < files.len() {
+        eprintln!(
+            "Stopped once the run failed (--fail-fast); {} file(s) not checked",
+            files.len() - files_processed
+        );
+    }
+
+    print!(
+        "{}",
+        reporter.report_summary(&statistics, run_start.elapsed())
+    );
+
+    if config.statistics {
+        println!("\n{}", format_statistics(&statistics).trim_end());
+    }
+
+    if config.timings {
+        timings.set_total(run_start.elapsed());
+        println!("\n{}", format_timings(&timings).trim_end());
+    }
+
+    match &config.baseline {
+        Some((BaselineMode::Write, path)) => {
+            if let Err(e) = baseline.save(path) {
+                eprintln!("Error writing baseline {}: {}", path.display(), e);
+                process::exit(2);
+            }
+            let note = format!(
+                "Recorded {} violation(s) in baseline {}",
+                baseline.len(),
+                path.display()
+            );
+            // Keep stdout parseable for machine-readable formats
+            if config.format != OutputFormat::Text {
+                eprintln!("{}", note);
+            } else {
+                println!("{}", note);
+            }
+            // Recorded violations are accepted, not failures
+            total_violations = 0;
+            total_warnings = 0;
+        }
+        Some((BaselineMode::Check, path)) if baseline_suppressed > 0 => {
+            eprintln!(
+                "Note: {} known violation(s) suppressed by baseline {}",
+                baseline_suppressed,
+                path.display()
+            );
+        }
+        _ => {}
+    }
+
+    // The text summary counts them when there is one
+    let summarized = config.format == OutputFormat::Text && statistics.files_checked() > 1;
+    if statistics.suppressed() > 0 && !summarized && !config.quiet {
+        eprintln!(
+            "Note: {} violation(s) suppressed by inline comments",
+            statistics.suppressed()
+        );
+    }
+
+    if total_violations > 0 {
+        if exceeds_error_threshold(total_violations, config.max_errors) {
+            overall_success = false;
+        } else if let Some(max) = config.max_errors {
+            eprintln!(
+                "Note: {} violation(s) found, within --max-errors threshold of {}",
+                total_violations, max
+            );
+        }
+    }
+
+    if exceeds_warning_threshold(total_warnings, config.max_warnings) {
+        eprintln!(
+            "{} warning(s) found, over the --max-warnings threshold of {}",
+            total_warnings,
+            config.max_warnings.unwrap_or_default()
+        );
+        overall_success = false;
+        // --fix only promises to resolve errors, so exit code 3 does not apply
+        all_fixable = false;
+    }
+
+    if let Some(cache) = cache
+        && let Err(e) = cache.save()
+    {
+        eprintln!("Warning: Unable to write result cache: {}", e);
+    }
+
+    if !overall_success && !config.exit_zero {
+        // Exit code 3 tells CI and agents that `--fix` would resolve everything
+        process::exit(if all_fixable { 3 } else { 1 });
+    }
+}
+
+/// Print how many violations of each rule each file has, counting them
+/// without collecting or formatting any
+fn run_count(files: &[PathBuf], policies: &[Policy], config: &Config) {
+    let path_display = PathDisplay::new(config.paths);
+    let indexed: Vec<(&PathBuf, &Policy)> = files.iter().zip(policies).collect();
+    let counted = parallel_map(
+        &indexed,
+        config.jobs,
+        |&(file_path, policy)| match read_content(file_path, config) {
+            Ok(content) => Ok(policy.count(&content)),
+            Err(ReadError {
+                not_utf8: Some(found),
+                ..
+            }) if policy.checks_encoding() => Ok(vec![RuleCount {
+                code: EncodingRule::METADATA.code,
+                severity: policy.severity_of(EncodingRule::METADATA.code),
+                count: not_utf8_content(&found, config).2.errors.len(),
+            }]),
+            Err(e) => Err(e.message),
+        },
+    );
+
+    let mut failed = false;
+    let mut errors = 0;
+    let mut warnings = 0;
+    let mut totals: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut reports = Vec::new();
+    for (file_path, counts) in files.iter().zip(counted) {
+        let counts = match counts {
+            Ok(counts) => counts,
+            Err(e) => {
+                eprintln!("{}", e);
+                failed = true;
+                continue;
+            }
+        };
+        let found: Vec<_> = counts.iter().filter(|count| count.count > 0).collect();
+        for count in &found {
+            *totals.entry(count.code).or_default() += count.count;
+            match count.severity {
+                Severity::Error => errors += count.count,
+                Severity::Warning => warnings += count.count,
+            }
+        }
+        let shown = path_display.display(file_path);
+        let total: usize = found.iter().map(|count| count.count).sum();
+        if config.format == OutputFormat::Json {
+            let rules: BTreeMap<_, _> = found
+                .iter()
+                .map(|count| (count.code, count.count))
+                .collect();
+            reports.push(serde_json::json!({
+                "path": shown,
+                "total": total,
+                "rules": rules,
+            }));
+        } else if total > 0 || !(config.quiet || config.failures_only) {
+            println!(
+                "{}: {}",
+                shown.display(),
+                format_counts(total, found.iter().map(|c| (c.code, c.count)))
+            );
+        }
     }
 
-    if files.len() > 1 {
-        println!("\n📊 Processed {} file(s)", files_processed);
+    let total: usize = totals.values().sum();
+    if config.format == OutputFormat::Json {
+        let report = serde_json::json!({
+            "files": reports,
+            "total": total,
+            "rules": totals,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("counts serialize to JSON")
+        );
+    } else if files.len() > 1 {
+        println!(
+            "Total: {} in {} file(s)",
+            format_counts(total, totals.iter().map(|(code, count)| (*code, *count))),
+            files.len()
+        );
     }
 
-    if !overall_success {
+    if (failed || config.exceeds_thresholds(errors, warnings)) && !config.exit_zero {
         process::exit(1);
     }
 }
 
-/// Resolve file pattern (glob or single file) to list of file paths
-fn resolve_files(config: &Config) -> Result<Vec<PathBuf>, String> {
-    // Check if filename contains glob patterns
-    if config.filename.contains('*')
-        || config.filename.contains('?')
-        || config.filename.contains('[')
-    {
-        // It's a glob pattern
-        let pattern = config.file_path().display().to_string();
-        let mut paths = Vec::new();
+/// "5 (MC001 3, MC003 2)", or "0"
+fn format_counts<'a>(total: usize, counts: impl Iterator<Item = (&'a str, usize)>) -> String {
+    let counts: Vec<String> = counts
+        .map(|(code, count)| format!("{} {}", code, count))
+        .collect();
+    if counts.is_empty() {
+        total.to_string()
+    } else {
+        format!("{} ({})", total, counts.join(", "))
+    }
+}
 
-        for entry in glob(&pattern).map_err(|e| format!("Invalid glob pattern: {}", e))? {
-            match entry {
-                Ok(path) => {
-                    if path.is_file() {
-                        paths.push(path);
+/// Run a subcommand
+fn run_command(command: Commands) {
+    match command {
+        Commands::Check(_) | Commands::Fix(_) => unreachable!("handled as a run"),
+        Commands::Completions { shell } => {
+            let mut cmd = Cli::command();
+            let name = cmd.get_name().to_string();
+            clap_complete::generate(shell, &mut cmd, name, &mut std::io::stdout());
+        }
+        Commands::Rules { format } => {
+            let rules = all_rules();
+            match format {
+                InfoFormat::Text => print!("{}", format_rules(&rules)),
+                InfoFormat::Json => println!(
+                    "{}",
+                    serde_json::to_string_pretty(&rules).expect("rules serialize to JSON")
+                ),
+            }
+        }
+        Commands::Explain { rule, format } => {
+            let Some(metadata) = find_rule(&rule) else {
+                eprintln!(
+                    "Unknown rule: {} (run `markdown-checker rules` to list rules)",
+                    rule
+                );
+                process::exit(2);
+            };
+            match format {
+                InfoFormat::Text => print!("{}", format_rule_explanation(metadata)),
+                InfoFormat::Json => println!(
+                    "{}",
+                    serde_json::to_string_pretty(metadata).expect("rule serializes to JSON")
+                ),
+            }
+        }
+        Commands::Init { path, hook, force } => {
+            let config_path = path.join(scaffold::CONFIG_FILE_NAME);
+            match scaffold::write_new_file(&config_path, scaffold::starter_config(), force) {
+                Ok(()) => println!("✓ Created {}", config_path.display()),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(2);
+                }
+            }
+
+            if hook {
+                match scaffold::install_pre_commit_hook(&path, force) {
+                    Ok(hook_path) => println!("✓ Installed {}", hook_path.display()),
+                    Err(e) => {
+                        eprintln!("Error installing pre-commit hook: {}", e);
+                        process::exit(2);
                     }
                 }
-                Err(e) => eprintln!("Warning: Error reading glob entry: {}", e),
             }
         }
+        Commands::InstallHooks {
+            path,
+            commit_msg,
+            force,
+        } => {
+            let installed = git::hooks_dir(&path).and_then(|hooks_dir| {
+                let mut hooks = vec![scaffold::write_pre_commit_hook(&hooks_dir, force)?];
+                if commit_msg {
+                    hooks.push(scaffold::write_commit_msg_hook(&hooks_dir, force)?);
+                }
+                Ok(hooks)
+            });
+            match installed {
+                Ok(hooks) => {
+                    for hook_path in hooks {
+                        println!("✓ Installed {}", hook_path.display());
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error installing git hooks: {}", e);
+                    process::exit(2);
+                }
+            }
+        }
+        Commands::GenerateFixtures { dir, force } => match scaffold::write_fixtures(&dir, force) {
+            Ok(paths) => {
+                for (path, fixture) in paths.iter().zip(scaffold::FIXTURES) {
+                    println!("✓ Created {}: {}", path.display(), fixture.description);
+                }
+                println!(
+                    "\nCheck them with: markdown-checker -p {} -f \"*.md\"",
+                    dir.display()
+                );
+            }
+            Err(e) => {
+                eprintln!("Error writing fixtures: {}", e);
+                process::exit(2);
+            }
+        },
+        Commands::Config { schema: true, .. } => println!(
+            "{}",
+            serde_json::to_string_pretty(&json_schema()).expect("schema serializes to JSON")
+        ),
+        Commands::Config { config, .. } => validate_config(config.as_deref()),
+        Commands::Serve {
+            http: Some(addr), ..
+        } => {
+            // Rules come from the configuration files above the working directory
+            let config = match ConfigCascade::new().for_dir(Path::new(".")) {
+                Ok(config) => config.clone(),
+                Err(e) => {
+                    eprintln!("Error loading config: {}", e);
+                    process::exit(2);
+                }
+            };
+            if let Err(e) = http::serve(&addr, config) {
+                eprintln!("Error serving HTTP on {}: {}", addr, e);
+                process::exit(2);
+            }
+        }
+        Commands::Serve { mcp: true, .. } => {
+            if let Err(e) = mcp::serve(std::io::stdin().lock(), std::io::stdout().lock()) {
+                eprintln!("Error serving MCP: {}", e);
+                process::exit(2);
+            }
+        }
+        Commands::Serve { .. } => {
+            match lsp::serve(std::io::stdin().lock(), std::io::stdout().lock()) {
+                // Exiting without a shutdown request is an error in LSP
+                Ok(shut_down) => process::exit(if shut_down { 0 } else { 1 }),
+                Err(e) => {
+                    eprintln!("Error serving LSP: {}", e);
+                    process::exit(2);
+                }
+            }
+        }
+        Commands::Cache {
+            action: CacheAction::Clear,
+        } => match ResultCache::clear(Path::new(DEFAULT_CACHE_DIR)) {
+            Ok(0) => println!("No cached results in {}", DEFAULT_CACHE_DIR),
+            Ok(entries) => println!(
+                "✓ Removed {} cached result(s) from {}",
+                entries, DEFAULT_CACHE_DIR
+            ),
+            Err(e) => {
+                eprintln!("Error clearing cache {}: {}", DEFAULT_CACHE_DIR, e);
+                process::exit(2);
+            }
+        },
+        Commands::Version { format } => print_version(format),
+    }
+}
+
+/// Load the configuration file at `path`, or those that apply to the current
+/// directory, and report whether they are valid
+fn validate_config(path: Option<&Path>) {
+    let mut cascade = ConfigCascade::new();
+    let loaded = match path {
+        Some(path) => ConfigFile::load(path).map(|_| vec![path.to_path_buf()]),
+        None => match cascade.for_dir(Path::new(".")) {
+            Ok(_) => Ok(cascade.loaded().to_vec()),
+            Err(e) => Err(e),
+        },
+    };
+    match loaded {
+        Ok(paths) if paths.is_empty() => {
+            println!(
+                "No {} found; the built-in defaults apply",
+                scaffold::CONFIG_FILE_NAME
+            );
+        }
+        Ok(paths) => {
+            for path in paths {
+                println!("✓ {} is valid", path.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("✗ {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Open the interactive review UI on the files that have violations
+fn review_in_tui(
+    files: &[PathBuf],
+    policies: &[Policy],
+    checks: Vec<Option<Result<FileCheck, String>>>,
+    config: &Config,
+) {
+    if !std::io::stdout().is_terminal() {
+        eprintln!("Error: --tui requires an interactive terminal");
+        process::exit(2);
+    }
 
-        Ok(paths)
+    let mut review_files = Vec::new();
+    for ((path, policy), check) in files.iter().zip(policies).zip(checks.into_iter().flatten()) {
+        match check {
+            // Files that are not UTF-8 have no text to review
+            Ok(check) if check.decoding != Decoding::Utf8 => {
+                for error in check.results.iter().flat_map(|r| &r.errors) {
+                    eprintln!("{}: {}", path.display(), error.message);
+                }
+            }
+            Ok(check) if check.results.iter().any(|r| r.is_fail()) => {
+                review_files.push(
+                    ReviewFile::new(path.clone(), check.content.into_string(), check.results)
+                        .policy(policy.clone()),
+                );
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+    if review_files.is_empty() {
+        println!("✓ No violations to review");
+        return;
+    }
+
+    let mut review = Review::new(review_files).writing(config.eol, config.keep_mtime);
+    if let Err(e) = tui::run(&mut review) {
+        eprintln!("Error running terminal UI: {}", e);
+        process::exit(2);
+    }
+    // Discarded fixes leave their violations on disk
+    if (review.remaining() > 0 || review.has_unsaved_changes()) && !config.exit_zero {
+        process::exit(1);
+    }
+}
+
+/// Print version and build information
+fn print_version(format: InfoFormat) {
+    let info = BuildInfo::current();
+    match format {
+        InfoFormat::Text => println!("{}", info),
+        InfoFormat::Json => println!("{}", info.to_json()),
+    }
+}
+
+/// Content and validation results for a single file
+struct FileCheck {
+    content: FileContent,
+    results: Vec<ValidationResult>,
+    /// Cache key and unfiltered results, when caching is enabled
+    cache_entry: Option<(String, Vec<ValidationResult>)>,
+    /// Violations hidden by inline suppression comments
+    suppressed: usize,
+    /// Wall-clock time to read and check the file
+    elapsed: Duration,
+    /// Time spent in each validator; empty on a cache hit
+    validator_times: Vec<(String, Duration)>,
+    /// Validated line by line without keeping the content, which is empty
+    streamed: bool,
+    /// How the file's bytes became its content
+    decoding: Decoding,
+}
+
+/// How a file's bytes became the content checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Decoding {
+    Utf8,
+    /// Converted from another encoding; fixing writes the content as UTF-8
+    Converted,
+    /// Not UTF-8 and not convertible, so the content is empty, or read
+    /// lossily with --lossy, and must not be written back
+    Failed,
+}
+
+/// Read and validate a single file, applying any changed-line restriction.
+/// With a cache, validation is skipped when the content was seen before.
+fn check_file(
+    file_path: &Path,
+    config: &Config,
+    policy: &Policy,
+    cache: Option<&ResultCache>,
+) -> Result<FileCheck, String> {
+    let start = Instant::now();
+
+    // Files too large to hold in memory are only checked, line by line,
+    // unless they are mapped
+    if !maps_files(config) && streams(file_path, config) {
+        let reader = open_buffered(file_path)
+            .map_err(|e| format!("Error reading file {}: {}", file_path.display(), e))?;
+        match validate_stream(reader, policy) {
+            Ok((results, suppressed)) => {
+                return Ok(FileCheck {
+                    content: String::new().into(),
+                    results: restrict_to_changed_lines(file_path, config, results)?,
+                    cache_entry: None,
+                    suppressed,
+                    elapsed: start.elapsed(),
+                    validator_times: Vec::new(),
+                    streamed: true,
+                    decoding: Decoding::Utf8,
+                });
+            }
+            // Not UTF-8: read it whole below to tell what it is
+            Err(e) if e.kind() == io::ErrorKind::InvalidData => {}
+            Err(e) => return Err(format!("Error reading file {}: {}", file_path.display(), e)),
+        }
+    }
+
+    let content = match read_content(file_path, config) {
+        Ok(content) => content,
+        Err(ReadError {
+            not_utf8: Some(found),
+            ..
+        }) if policy.checks_encoding() => {
+            // Check the converted text too, so fixing it can be judged
+            let (content, decoding, encoding) = not_utf8_content(&found, config);
+            let mut results: Vec<_> = policy
+                .validate_timed(&content)
+                .into_iter()
+                .map(|(result, _)| {
+                    // Replacement characters stand for the bytes reported below
+                    result.retain_errors(|error| {
+                        decoding == Decoding::Converted
+                            || !encoding.errors.iter().any(|bad| {
+                                (bad.line_number, bad.column) == (error.line_number, error.column)
+                            })
+                    })
+                })
+                .collect();
+            results.push(encoding);
+            let (results, suppressed) = policy.filter(&content, results);
+            return Ok(FileCheck {
+                content: content.into(),
+                results: restrict_to_changed_lines(file_path, config, results)?,
+                cache_entry: None,
+                suppressed,
+                elapsed: start.elapsed(),
+                validator_times: Vec::new(),
+                streamed: false,
+                decoding,
+            });
+        }
+        Err(e) => return Err(e.message),
+    };
+
+    // Run all validators, unless the cache already has results for this content.
+    // Results cut short by --fast are incomplete, so they are not cached.
+    let cache = cache.filter(|_| policy.stop_at == StopAt::Never);
+    let cache_key = cache.map(|_| ResultCache::key(&content, &rules_hash(policy)));
+    let cached = cache
+        .zip(cache_key.as_ref())
+        .and_then(|(cache, key)| cache.get(key).cloned());
+    let mut validator_times = Vec::new();
+    let results = cached.unwrap_or_else(|| {
+        policy
+            .validate_timed(&content)
+            .into_iter()
+            .map(|(result, duration)| {
+                validator_times.push((result.validator_name.clone(), duration));
+                result
+            })
+            .collect()
+    });
+    let cache_entry = cache_key.map(|key| (key, results.clone()));
+
+    // Drop violations of allowed characters and those exempted by comments
+    let (results, suppressed) = policy.filter(&content, results);
+
+    Ok(FileCheck {
+        content,
+        results: restrict_to_changed_lines(file_path, config, results)?,
+        cache_entry,
+        suppressed,
+        elapsed: start.elapsed(),
+        validator_times,
+        streamed: false,
+        decoding: Decoding::Utf8,
+    })
+}
+
+/// The content to check for a file that is not UTF-8, how it was obtained,
+/// and the file's encoding violation: the file converted to UTF-8, read
+/// lossily with --lossy, or nothing
+fn not_utf8_content(found: &NotUtf8, config: &Config) -> (String, Decoding, ValidationResult) {
+    match found.converted() {
+        Some(text) => (text, Decoding::Converted, found.result()),
+        None if config.lossy => {
+            let (text, result) = found.lossy();
+            (text, Decoding::Failed, result)
+        }
+        None => (String::new(), Decoding::Failed, found.result()),
+    }
+}
+
+/// Why a file could not be read: the message to print, and what its bytes
+/// are when the problem is that they are not UTF-8
+struct ReadError {
+    message: String,
+    not_utf8: Option<NotUtf8>,
+}
+
+impl ReadError {
+    fn new(file_path: &Path, error: io::Error) -> Self {
+        Self {
+            message: format!("Error reading file {}: {}", file_path.display(), error),
+            not_utf8: into_not_utf8(error),
+        }
+    }
+}
+
+/// Read a file's content: the staged blob when checking the index, or the
+/// mapped file with --mmap
+fn read_content(file_path: &Path, config: &Config) -> Result<FileContent, ReadError> {
+    if let Some(url) = url_of(file_path) {
+        let bytes = fetch_bytes(url, config.max_file_size).map_err(|e| ReadError {
+            message: format!("Error fetching {}: {}", url, e),
+            not_utf8: None,
+        })?;
+        return decode(bytes)
+            .map(FileContent::from)
+            .map_err(|e| ReadError::new(file_path, e));
+    }
+    let read_result = if config.staged {
+        git::read_staged_content(file_path).map(FileContent::from)
+    } else if maps_files(config) {
+        FileContent::map(file_path)
+    } else {
+        read_file_content(file_path).map(FileContent::from)
+    };
+    read_result.map_err(|e| ReadError::new(file_path, e))
+}
+
+/// Whether a file is large enough to stream and is only being checked:
+/// fixing, baselines, the review UI, staged blobs, and site links need the
+/// whole content
+fn streams(file_path: &Path, config: &Config) -> bool {
+    config.mode == RunMode::Check
+        && !config.staged
+        && config.site.is_none()
+        && !config.tui
+        && config.baseline.is_none()
+        && file_size(file_path).is_ok_and(|size| size > STREAM_THRESHOLD)
+}
+
+/// Whether files are memory-mapped: `--fix` rewrites files, which must not
+/// happen while they are mapped
+fn maps_files(config: &Config) -> bool {
+    config.mmap && config.mode != RunMode::Fix
+}
+
+/// Keep only violations on changed lines when requested
+fn restrict_to_changed_lines(
+    file_path: &Path,
+    config: &Config,
+    results: Vec<ValidationResult>,
+) -> Result<Vec<ValidationResult>, String> {
+    let (Some(git_ref), true) = (&config.since, config.changed_lines) else {
+        return Ok(results);
+    };
+    let ranges = git::changed_line_ranges(file_path, git_ref)
+        .map_err(|e| format!("Error diffing file {}: {}", file_path.display(), e))?;
+    Ok(results
+        .into_iter()
+        .map(|r| {
+            r.retain_errors(|e| {
+                ranges
+                    .iter()
+                    .any(|&(start, end)| (start..=end).contains(&e.line_number))
+            })
+        })
+        .collect())
+}
+
+/// Resolve file pattern (glob or single file) to list of file paths,
+/// adding the binary files a glob matched but left out to `skipped`
+fn resolve_files(
+    config: &Config,
+    navigation: Option<&Navigation>,
+    skipped: &mut usize,
+) -> Result<Vec<PathBuf>, String> {
+    if let Some(navigation) = navigation {
+        return Ok(navigation.files());
+    }
+
+    if !config.urls.is_empty() {
+        return Ok(config.urls.iter().map(PathBuf::from).collect());
+    }
+
+    if let Some(message) = &config.commit_msg {
+        return if message.is_file() {
+            Ok(vec![message.clone()])
+        } else {
+            Err(format!("File not found: {}", message.display()))
+        };
+    }
+
+    if config.staged {
+        return git::staged_files(&config.path)
+            .map(sorted)
+            .map_err(|e| format!("Unable to list staged files: {}", e));
+    }
+
+    if let Some(list) = &config.files_from {
+        let paths = read_file_list(list)
+            .map_err(|e| format!("Unable to read file list {}: {}", list.display(), e))?;
+        // Listed files may have been deleted since the list was produced
+        let (present, missing): (Vec<_>, Vec<_>) = paths.into_iter().partition(|p| p.is_file());
+        for path in missing {
+            eprintln!("Warning: Skipping missing file: {}", path.display());
+        }
+        return Ok(sorted(present));
+    }
+
+    if let Some(git_ref) = &config.since {
+        return git::changed_files_since(&config.path, git_ref)
+            .map(sorted)
+            .map_err(|e| format!("Unable to list files changed since {}: {}", git_ref, e));
+    }
+
+    // Patterns from the configuration file replace the default README
+    if !config.include.is_empty() {
+        let mut paths = Vec::new();
+        for pattern in &config.include {
+            paths.extend(glob_files(
+                Path::new(""),
+                pattern,
+                config.max_depth,
+                skipped,
+            )?);
+        }
+        return Ok(sorted(paths));
+    }
+
+    // Check if filename contains glob patterns
+    if config.filename.contains('*')
+        || config.filename.contains('?')
+        || config.filename.contains('[')
+    {
+        // It's a glob pattern, bounded in depth when requested
+        glob_files(&config.path, &config.filename, config.max_depth, skipped).map(sorted)
     } else {
         // It's a single file path
         let path = config.file_path();
 
-        // Special handling for README files: try README.org as fallback
+        // Special handling for README files: try README.org, README.adoc,
+        // and README.rst as fallbacks
         if config.filename == "README.md" && !path.exists() {
-            let readme_org = config.path.join("README.org");
-            if readme_org.exists() {
-                eprintln!("Note: README.md not found, using README.org as alternative");
-                return Ok(vec![readme_org]);
+            let alternative = ["README.org", "README.adoc", "README.rst"]
+                .iter()
+                .map(|name| config.path.join(name))
+                .find(|path| path.exists());
+            if let Some(alternative) = alternative {
+                eprintln!(
+                    "Note: README.md not found, using {} as alternative",
+                    alternative
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                );
+                return Ok(vec![alternative]);
             } else {
-                // Neither README.md nor README.org exists - emit warning but don't fail
-                eprintln!("Warning: Neither README.md nor README.org found in {}", config.path.display());
+                // No README exists - emit warning but don't fail
+                eprintln!(
+                    "Warning: No README.md, README.org, README.adoc, or README.rst found in {}",
+                    config.path.display()
+                );
                 return Ok(vec![]);
             }
         }
@@ -192,3 +1246,72 @@ fn resolve_files(config: &Config) -> Result<Vec<PathBuf>, String> {
         }
     }
 }
+
+/// Files under `base` matching `pattern`, at most `max_depth` levels deep,
+/// counting binary matches left out in `skipped`
+fn glob_files(
+    base: &Path,
+    pattern: &str,
+    max_depth: Option<usize>,
+    skipped: &mut usize,
+) -> Result<Vec<PathBuf>, String> {
+    let patterns = match max_depth {
+        Some(depth) => depth_limited_patterns(pattern, depth),
+        None => vec![pattern.to_string()],
+    };
+    let mut paths = Vec::new();
+
+    for pattern in patterns {
+        let pattern = base.join(pattern).display().to_string();
+        for entry in glob(&pattern).map_err(|e| format!("Invalid glob pattern: {}", e))? {
+            match entry {
+                Ok(path) => {
+                    if path.is_file() {
+                        paths.push(path);
+                    }
+                }
+                Err(e) => eprintln!("Warning: Error reading glob entry: {}", e),
+            }
+        }
+    }
+
+    if let Some(depth) = max_depth {
+        paths.retain(|p| path_depth(p, base) <= depth);
+    }
+    // Broad patterns pick up images and archives, which have no text to check
+    paths.retain(|path| match sniff_binary(path) {
+        Ok(Some(kind)) => {
+            eprintln!("Note: Skipping binary file: {} ({})", path.display(), kind);
+            *skipped += 1;
+            false
+        }
+        _ => true,
+    });
+    Ok(paths)
+}
+
+/// Whether `path` is small enough to check, warning that it is skipped if not
+fn within_size_limit(path: &Path, config: &Config) -> bool {
+    let Some(limit) = config.max_file_size else {
+        return true;
+    };
+    match file_size(path) {
+        Ok(size) if size > limit.0 => {
+            eprintln!(
+                "Warning: Skipping large file: {} ({}, over the {} limit; use --force-large to check it)",
+                path.display(),
+                FileSize(size),
+                limit
+            );
+            false
+        }
+        _ => true,
+    }
+}
+
+/// Sort and deduplicate paths so output order is deterministic
+fn sorted(mut paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    paths.sort();
+    paths.dedup();
+    paths
+}
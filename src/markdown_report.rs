@@ -0,0 +1,157 @@
+//! Markdown report (`--format markdown`) for PR comments and audit artifacts.
+//!
+//! The report is itself ASCII-clean: characters outside printable ASCII in
+//! messages and paths are written as `U+XXXX`, so it passes this checker.
+
+use crate::ValidationResult;
+use crate::columns::ColumnUnit;
+use crate::json_report::FileReport;
+use crate::reporter::{Reporter, format_duration};
+use crate::statistics::Statistics;
+use std::path::Path;
+use std::time::Duration;
+
+/// Collects file reports and prints one markdown document at the end
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownReporter {
+    reports: Vec<FileReport>,
+    columns: ColumnUnit,
+}
+
+impl MarkdownReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count columns in `columns` instead of characters
+    pub fn columns(mut self, columns: ColumnUnit) -> Self {
+        self.columns = columns;
+        self
+    }
+}
+
+impl Reporter for MarkdownReporter {
+    fn report_file(&mut self, file: &Path, content: &str, results: &[ValidationResult]) -> String {
+        self.reports.push(FileReport::with_columns(
+            file,
+            content,
+            results,
+            self.columns,
+        ));
+        String::new()
+    }
+
+    fn report_summary(&mut self, stats: &Statistics, elapsed: Duration) -> String {
+        to_markdown(&self.reports, stats, elapsed)
+    }
+}
+
+/// Render a summary, violation counts per rule, and a table of violations for
+/// each failing file
+pub fn to_markdown(reports: &[FileReport], stats: &Statistics, elapsed: Duration) -> String {
+    let mut output = String::from("# Markdown Check Report\n\n");
+    output.push_str(&format!(
+        "{} violation(s) in {} of {} file(s) checked in {}.\n",
+        stats.total_violations(),
+        stats.files_with_violations(),
+        stats.files_checked(),
+        format_duration(elapsed)
+    ));
+
+    let by_rule = stats.by_rule();
+    if !by_rule.is_empty() {
+        output.push_str("\n| Rule | Violations |\n| --- | ---: |\n");
+        for (rule, count) in by_rule {
+            output.push_str(&format!("| {} | {} |\n", cell(rule), count));
+        }
+    }
+
+    for file in reports.iter().filter(|f| f.violation_count > 0) {
+        output.push_str(&format!(
+            "\n## {}\n\n| Line | Column | Rule | Message |\n| ---: | ---: | --- | --- |\n",
+            cell(&file.path)
+        ));
+        let mut rows: Vec<_> = file
+            .rules
+            .iter()
+            .flat_map(|rule| rule.violations.iter().map(move |v| (v, &rule.code)))
+            .collect();
+        rows.sort_by_key(|(violation, _)| (violation.line, violation.column));
+        for (violation, code) in rows {
+            let column = violation.column.map_or(String::new(), |c| c.to_string());
+            output.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                violation.line,
+                column,
+                cell(code),
+                cell(&violation.message)
+            ));
+        }
+    }
+    output
+}
+
+/// Text safe for a table cell: pipes escaped, and characters outside
+/// printable ASCII written as code points
+fn cell(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '|' => escaped.push_str("\\|"),
+            ' '..='~' => escaped.push(ch),
+            _ => escaped.push_str(&format!("U+{:04X}", ch as u32)),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::validate_all;
+
+    fn report(files: &[(&str, &str)]) -> String {
+        let mut reporter = MarkdownReporter::new();
+        let mut stats = Statistics::new();
+        for (path, content) in files {
+            let results = validate_all(content);
+            stats.record(Path::new(path), &results);
+            reporter.report_file(Path::new(path), content, &results);
+        }
+        reporter.report_summary(&stats, Duration::from_millis(2))
+    }
+
+    #[test]
+    fn test_markdown_report() {
+        let output = report(&[("ok.md", "ok\n"), ("docs/a.md", "x\n  ├\n")]);
+
+        assert!(output.starts_with(
+            "# Markdown Check Report\n\n3 violation(s) in 1 of 2 file(s) checked in 2.000ms.\n"
+        ));
+        assert!(output.contains("| MC003 Tree Symbols | 1 |\n"));
+        assert!(output.contains("\n## docs/a.md\n"));
+        assert!(output.contains(
+            "| 2 | 3 | MC003 | Tree symbol 'U+251C' (U+251C) detected. Use '+' or '\\|' instead |\n"
+        ));
+        assert!(!output.contains("## ok.md"));
+    }
+
+    #[test]
+    fn test_report_is_ascii_clean() {
+        let output = report(&[("caf\u{e9}.md", "\u{65e5}\u{1b}\n")]);
+        assert!(
+            validate_all(&output).iter().all(|r| r.is_pass()),
+            "{}",
+            output
+        );
+        assert!(output.contains("## cafU+00E9.md"));
+    }
+
+    #[test]
+    fn test_clean_run() {
+        assert_eq!(
+            report(&[("a.md", "ok\n")]),
+            "# Markdown Check Report\n\n0 violation(s) in 0 of 1 file(s) checked in 2.000ms.\n"
+        );
+    }
+}
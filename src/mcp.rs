@@ -0,0 +1,357 @@
+//! Model Context Protocol server mode (`serve --mcp`).
+//!
+//! Agents call `check_markdown` and `fix_markdown` as tools over stdio and get
+//! structured results, the `--format json` file report with a `passed` flag,
+//! instead of parsing text output and exit codes. Messages are JSON-RPC, one
+//! per line, as MCP's stdio transport specifies.
+
+use crate::ValidationResult;
+use crate::config::ConfigCascade;
+use crate::file_ops::{read_file_content, write_fix};
+use crate::fixer::fix_allowed;
+use crate::json_report::FileReport;
+use crate::policy::Policy;
+use serde_json::{Value, json};
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Protocol revisions this server speaks, newest first
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Name reported for content checked without a path
+const CONTENT_NAME: &str = "<content>";
+
+/// Serve MCP requests from `input` until it closes
+pub fn serve(input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut server = Server::default();
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => server.handle(&message),
+            Err(e) => Some(error(Value::Null, PARSE_ERROR, e.to_string())),
+        };
+        if let Some(response) = response {
+            writeln!(output, "{}", response)?;
+            output.flush()?;
+        }
+    }
+    Ok(())
+}
+
+fn error(id: Value, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() }
+    })
+}
+
+#[derive(Default)]
+struct Server {
+    cascade: ConfigCascade,
+}
+
+impl Server {
+    /// The response to `message`, or `None` for a notification
+    fn handle(&mut self, message: &Value) -> Option<Value> {
+        let id = message.get("id")?.clone();
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let result = match method {
+            "initialize" => {
+                let requested = params["protocolVersion"].as_str().unwrap_or_default();
+                let version = PROTOCOL_VERSIONS
+                    .iter()
+                    .find(|&&version| version == requested)
+                    .unwrap_or(&PROTOCOL_VERSIONS[0]);
+                json!({
+                    "protocolVersion": version,
+                    "capabilities": { "tools": { "listChanged": false } },
+                    "serverInfo": { "name": "markdown-checker", "version": env!("CARGO_PKG_VERSION") },
+                    "instructions": "Check markdown for non-ASCII, unprintable, and tree symbol \
+                        characters with check_markdown; replace the fixable ones with \
+                        fix_markdown. Rules come from .markdown-checker.toml files."
+                })
+            }
+            "ping" => json!({}),
+            "tools/list" => json!({ "tools": tools() }),
+            "tools/call" => {
+                let arguments = &params["arguments"];
+                let outcome = match params["name"].as_str() {
+                    Some("check_markdown") => self.check(arguments),
+                    Some("fix_markdown") => self.fix(arguments),
+                    _ => {
+                        let name = params["name"].as_str().unwrap_or_default();
+                        return Some(error(id, INVALID_PARAMS, format!("unknown tool: {}", name)));
+                    }
+                };
+                tool_result(outcome)
+            }
+            _ => {
+                let message = format!("method not supported: {}", method);
+                return Some(error(id, METHOD_NOT_FOUND, message));
+            }
+        };
+        Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+    }
+
+    fn check(&mut self, arguments: &Value) -> Result<Value, String> {
+        let document = self.document(arguments)?;
+        let results = document.policy.validate(&document.content);
+        let mut report = report(&document, &document.content, &results);
+        report["passed"] = json!(!results.iter().any(|result| result.is_error()));
+        Ok(report)
+    }
+
+    fn fix(&mut self, arguments: &Value) -> Result<Value, String> {
+        let document = self.document(arguments)?;
+        let dry_run = arguments["dry_run"].as_bool().unwrap_or(false);
+        let (fixed, replaced) = fix_allowed(&document.content, &document.policy);
+        let remaining = document.policy.validate(&fixed);
+        // As with --fix, a file is only rewritten when fixing resolves every
+        // violation, never left half fixed
+        let complete = remaining.iter().all(|result| result.is_pass());
+
+        // Only a file read from disk is written back
+        let written = document.read_from_disk && !dry_run && replaced > 0 && complete;
+        if written {
+            write_fix(
+                &document.path,
+                &document.content,
+                &fixed,
+                document.policy.bom,
+                None,
+                false,
+            )
+            .map_err(|e| format!("Cannot write {}: {}", document.path.display(), e))?;
+        }
+
+        let mut report = report(&document, &fixed, &remaining);
+        report["passed"] = json!(!remaining.iter().any(|result| result.is_error()));
+        report["complete"] = json!(complete);
+        report["replacements"] = json!(replaced);
+        report["written"] = json!(written);
+        if !document.read_from_disk {
+            report["content"] = json!(fixed);
+        }
+        Ok(report)
+    }
+
+    /// The content a tool call names and the rules for it
+    fn document(&mut self, arguments: &Value) -> Result<Document, String> {
+        let path = arguments["path"].as_str().map(PathBuf::from);
+        let (content, read_from_disk) = match (arguments["content"].as_str(), &path) {
+            (Some(content), _) => (content.to_string(), false),
+            (None, Some(path)) => {
+                let content = read_file_content(path)
+                    .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+                (content, true)
+            }
+            (None, None) => return Err("Pass `content` or `path`".to_string()),
+        };
+        let path = path.unwrap_or_else(|| PathBuf::from(CONTENT_NAME));
+        let policy = match self.cascade.for_file(&path) {
+            Ok(config) => config.policy_for(&path),
+            Err(e) => return Err(format!("Error loading config: {}", e)),
+        };
+        Ok(Document {
+            path,
+            content,
+            read_from_disk,
+            policy,
+        })
+    }
+}
+
+struct Document {
+    path: PathBuf,
+    content: String,
+    read_from_disk: bool,
+    policy: Policy,
+}
+
+/// The `--format json` report of `content`, as a JSON object
+fn report(document: &Document, content: &str, results: &[ValidationResult]) -> Value {
+    let report = FileReport::new(&document.path, content, results);
+    serde_json::to_value(report).expect("reports serialize to JSON")
+}
+
+/// Wrap a tool's outcome as MCP tool output: structured content plus the same
+/// JSON as text for clients that only read text, or an error the agent sees
+fn tool_result(outcome: Result<Value, String>) -> Value {
+    match outcome {
+        Ok(structured) => json!({
+            "content": [{ "type": "text", "text": structured.to_string() }],
+            "structuredContent": structured,
+            "isError": false
+        }),
+        Err(message) => json!({
+            "content": [{ "type": "text", "text": message }],
+            "isError": true
+        }),
+    }
+}
+
+fn tools() -> Value {
+    let content = json!({
+        "type": "string",
+        "description": "Markdown text to check. Takes precedence over reading `path`."
+    });
+    let path = json!({
+        "type": "string",
+        "description": "Markdown file, relative to the server's working directory. \
+            Its .markdown-checker.toml files select the rules; it is read unless \
+            `content` is given."
+    });
+    json!([
+        {
+            "name": "check_markdown",
+            "title": "Check markdown",
+            "description": "Report non-ASCII, unprintable, and tree symbol characters in \
+                markdown. Returns `passed` (no error-severity violations) and each rule's \
+                violations with line, column, byte span, and whether fix_markdown can \
+                replace the character.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "content": content, "path": path }
+            },
+            "annotations": { "readOnlyHint": true }
+        },
+        {
+            "name": "fix_markdown",
+            "title": "Fix markdown",
+            "description": "Replace fixable characters with ASCII equivalents. With `content`, \
+                returns the fixed text, even when some violations remain. With only `path`, \
+                rewrites the file unless `dry_run`, but like the CLI's --fix only when every \
+                violation can be fixed (`complete`); otherwise the file is left unchanged. \
+                Returns the replacement count, `passed`, `complete`, `written`, and the \
+                violations that remain after fixing, which need manual edits.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "content": content,
+                    "path": path,
+                    "dry_run": {
+                        "type": "boolean",
+                        "description": "Report what would change without writing the file"
+                    }
+                }
+            },
+            "annotations": { "idempotentHint": true }
+        }
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Run a session and return each response
+    fn session(messages: &[Value]) -> Vec<Value> {
+        let input: String = messages.iter().map(|m| format!("{}\n", m)).collect();
+        let mut output = Vec::new();
+        serve(input.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    fn call(id: u64, name: &str, arguments: Value) -> Value {
+        json!({
+            "jsonrpc": "2.0", "id": id, "method": "tools/call",
+            "params": { "name": name, "arguments": arguments }
+        })
+    }
+
+    #[test]
+    fn test_initialize_and_list_tools() {
+        let responses = session(&[
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                    "params": { "protocolVersion": "2025-03-26", "capabilities": {} } }),
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "resources/list" }),
+        ]);
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0]["result"]["protocolVersion"], "2025-03-26");
+        let names: Vec<_> = responses[1]["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].clone())
+            .collect();
+        assert_eq!(names, ["check_markdown", "fix_markdown"]);
+        assert_eq!(responses[2]["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_check_and_fix_content() {
+        let responses = session(&[
+            call(1, "check_markdown", json!({ "content": "a \u{2192} b\n" })),
+            call(2, "fix_markdown", json!({ "content": "a \u{2192} b\n" })),
+            call(3, "check_markdown", json!({})),
+        ]);
+        let check = &responses[0]["result"]["structuredContent"];
+        assert_eq!(check["passed"], false);
+        assert_eq!(check["path"], CONTENT_NAME);
+        assert_eq!(check["rules"][0]["violations"][0]["column"], 3);
+        assert_eq!(
+            check["rules"][0]["violations"][0]["fix"]["replacement"],
+            "->"
+        );
+
+        let fix = &responses[1]["result"]["structuredContent"];
+        assert_eq!(fix["content"], "a -> b\n");
+        assert_eq!(fix["replacements"], 1);
+        assert_eq!(fix["passed"], true);
+        assert_eq!(fix["written"], false);
+
+        assert_eq!(responses[2]["result"]["isError"], true);
+    }
+
+    #[test]
+    fn test_fix_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("notes.md");
+        fs::write(&path, "\u{251c}\u{2500}\u{2500} a\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        let responses = session(&[
+            call(1, "fix_markdown", json!({ "path": path, "dry_run": true })),
+            call(2, "fix_markdown", json!({ "path": path })),
+        ]);
+        let dry_run = &responses[0]["result"]["structuredContent"];
+        assert_eq!(dry_run["replacements"], 3);
+        assert_eq!(dry_run["written"], false);
+        assert!(dry_run.get("content").is_none());
+
+        assert_eq!(responses[1]["result"]["structuredContent"]["written"], true);
+        assert_eq!(fs::read_to_string(path).unwrap(), "+-- a\n");
+    }
+
+    #[test]
+    fn test_partly_fixable_file_is_not_written() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("notes.md");
+        fs::write(&path, "\u{251c} a\u{7}\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        let responses = session(&[call(1, "fix_markdown", json!({ "path": path }))]);
+        let fix = &responses[0]["result"]["structuredContent"];
+        assert_eq!(fix["replacements"], 1);
+        assert_eq!(fix["complete"], false);
+        assert_eq!(fix["written"], false);
+        assert_eq!(fs::read_to_string(path).unwrap(), "\u{251c} a\u{7}\n");
+    }
+}
@@ -0,0 +1,224 @@
+//! MDX syntax regions: ESM `import`/`export` blocks, JSX tags, and
+//! `{expressions}`.
+//!
+//! MDX files mix JavaScript into markdown, and component props, imports, and
+//! expressions are code rather than prose, so `.mdx` files exempt them the
+//! way `skip-code` exempts code. The text between tags is still markdown and
+//! is checked. Fenced code and code spans are recognized only so that a `<`
+//! or `{` in them is not taken for JSX.
+
+use crate::code_regions::{CodeRegions, Fence, code_spans, fence};
+
+/// Find the MDX syntax regions in `content`
+pub fn parse(content: &str) -> CodeRegions {
+    let mut parser = MdxParser::default();
+    for line in content.lines() {
+        parser.push_line(line);
+    }
+    parser.finish()
+}
+
+/// Builds the MDX syntax regions of a file one line at a time, so a file can
+/// be read as a stream
+#[derive(Debug, Default)]
+pub struct MdxParser {
+    regions: CodeRegions,
+    line_number: usize,
+    /// The open code fence, whose lines are markdown code, not MDX syntax
+    fence: Option<Fence>,
+    /// Inside an ESM block, which ends at a blank line
+    esm: bool,
+    /// A tag or expression continuing from an earlier line
+    open: Option<Open>,
+}
+
+/// A JSX tag or expression not yet closed
+#[derive(Debug, Clone, Copy, Default)]
+struct Open {
+    /// A tag, closed by `>`, rather than an expression, closed by `}`
+    tag: bool,
+    braces: usize,
+    quote: Option<char>,
+    comment: bool,
+}
+
+impl MdxParser {
+    /// Add the next line, without its line ending
+    pub fn push_line(&mut self, line: &str) {
+        self.line_number += 1;
+        let line_number = self.line_number;
+
+        if self.open.is_none() {
+            if let Some(opening) = self.fence {
+                if let Some((ch, len, rest)) = fence(line)
+                    && ch == opening.0
+                    && len >= opening.1
+                    && rest.trim().is_empty()
+                {
+                    self.fence = None;
+                }
+                return;
+            }
+            if self.esm {
+                if line.trim().is_empty() {
+                    self.esm = false;
+                } else {
+                    self.regions.insert_lines(line_number..=line_number);
+                }
+                return;
+            }
+            if line.starts_with("import ") || line.starts_with("export ") {
+                self.esm = true;
+                self.regions.insert_lines(line_number..=line_number);
+                return;
+            }
+            // Backtick fences cannot have backticks in their info string
+            if let Some((ch, len, rest)) = fence(line)
+                && (ch == '~' || !rest.contains('`'))
+            {
+                self.fence = Some((ch, len));
+                return;
+            }
+        }
+        self.scan(line);
+    }
+
+    /// Record the tags and expressions on a line outside fences and ESM
+    fn scan(&mut self, line: &str) {
+        let line_number = self.line_number;
+        let chars: Vec<char> = line.chars().collect();
+        let spans = code_spans(line);
+        // Where the current tag or expression started; 0 if on an earlier line
+        let mut start = 0;
+        let mut index = 0;
+
+        while index < chars.len() {
+            let ch = chars[index];
+            let next = chars.get(index + 1).copied();
+            match self.open.as_mut() {
+                Some(open) => {
+                    let closed = open.push(ch, next);
+                    if open.comment && ch == '/' && next == Some('*') {
+                        index += 1;
+                    }
+                    if closed {
+                        self.regions.insert_span(line_number, start + 1..=index + 1);
+                        self.open = None;
+                    }
+                }
+                None => {
+                    let column = index + 1;
+                    if let Some(span) = spans.iter().find(|span| span.contains(&column)) {
+                        index = *span.end();
+                        continue;
+                    }
+                    let opens_tag = ch == '<'
+                        && next.is_some_and(|c| c.is_alphabetic() || c == '/' || c == '>');
+                    if ch == '\\' {
+                        index += 1;
+                    } else if opens_tag || ch == '{' {
+                        start = index;
+                        self.open = Some(Open {
+                            tag: opens_tag,
+                            braces: usize::from(ch == '{'),
+                            ..Open::default()
+                        });
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        if self.open.is_some() {
+            if start == 0 {
+                self.regions.insert_lines(line_number..=line_number);
+            } else {
+                self.regions
+                    .insert_span(line_number, start + 1..=chars.len());
+            }
+        }
+    }
+
+    /// The regions found; a tag or expression left open runs to the end of
+    /// the file
+    pub fn finish(self) -> CodeRegions {
+        self.regions
+    }
+}
+
+impl Open {
+    /// Take the next character and its successor; whether it closes the tag
+    /// or expression
+    fn push(&mut self, ch: char, next: Option<char>) -> bool {
+        if self.comment {
+            if ch == '*' && next == Some('/') {
+                self.comment = false;
+            }
+            return false;
+        }
+        if let Some(quote) = self.quote {
+            if ch == quote {
+                self.quote = None;
+            }
+            return false;
+        }
+        match ch {
+            '/' if next == Some('*') => self.comment = true,
+            '"' | '\'' | '`' => self.quote = Some(ch),
+            '{' => self.braces += 1,
+            '}' => {
+                self.braces = self.braces.saturating_sub(1);
+                return !self.tag && self.braces == 0;
+            }
+            '>' => return self.tag && self.braces == 0,
+            _ => {}
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_esm_blocks() {
+        let content = "import {Chart} from './chart'\nimport Caf\u{e9} from './cafe'\n\n\
+                       Prose \u{e9}\nexport const meta = {\n  title: 'Caf\u{e9}',\n}\n";
+        let regions = parse(content);
+        for line in [1, 2, 5, 6, 7] {
+            assert!(regions.contains(line, None), "line {}", line);
+        }
+        assert!(!regions.contains(4, Some(7)));
+    }
+
+    #[test]
+    fn test_jsx_tags_and_expressions() {
+        let content = "<Note title=\"Caf\u{e9} \u{2192}\">\nText caf\u{e9}\n</Note>\n\
+                       Total: {count * 2 /* \u{d7} two, it's */} caf\u{e9}\n";
+        let regions = parse(content);
+        assert!(regions.contains(1, Some(14)));
+        // Children are prose
+        assert!(!regions.contains(2, Some(9)));
+        assert!(regions.contains(3, Some(1)));
+        assert!(regions.contains(4, Some(25)));
+        assert!(!regions.contains(4, Some(40)));
+    }
+
+    #[test]
+    fn test_multiline_tag() {
+        let content = "<Chart\n  label=\"\u{2192} up\"\n  data={[1, 2]}\n/> caf\u{e9}\n";
+        let regions = parse(content);
+        assert!(regions.contains(2, None));
+        assert!(regions.contains(4, Some(2)));
+        assert!(!regions.contains(4, Some(4)));
+    }
+
+    #[test]
+    fn test_code_is_not_mdx() {
+        let content = "```js\nimport x from 'y'\nconst a = <b>{c}</b>\n```\n\
+                       Use `<Tag>` and `{x}`, or a < b.\n";
+        let regions = parse(content);
+        assert!(regions.is_empty());
+    }
+}
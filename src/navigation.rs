@@ -0,0 +1,834 @@
+//! Site navigation checks: the `navigation` rule (MC005).
+//!
+//! With `--mdbook`, `--mkdocs`, or `--docusaurus`, the files checked are the
+//! site's navigation file (SUMMARY.md, mkdocs.yml, or the sidebars file), the
+//! pages it lists, and the markdown files under its content directory that it
+//! does not list. Each file's navigation result compares those sets and
+//! resolves the file's relative links against them.
+
+use crate::code_regions::CodeRegions;
+use crate::frontmatter::Frontmatter;
+use crate::lsp::percent_decode;
+use crate::validators::NavigationRule;
+use crate::{ValidationError, ValidationResult};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Static site generators whose navigation can be checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Site {
+    MdBook,
+    MkDocs,
+    Docusaurus,
+}
+
+/// A site's navigation: the file listing its pages and what it lists
+#[derive(Debug)]
+pub struct Navigation {
+    /// File that lists the pages, e.g. `src/SUMMARY.md`
+    index: PathBuf,
+    /// Directory whose markdown files should all be listed
+    content_dir: PathBuf,
+    /// Listed pages, in navigation order
+    entries: Vec<Entry>,
+    listed: BTreeSet<PathBuf>,
+    /// Directories whose pages are all listed, as MkDocs lists every page
+    /// without `nav` and Docusaurus does for autogenerated sidebars
+    listed_dirs: Vec<PathBuf>,
+}
+
+/// A page the navigation lists
+#[derive(Debug)]
+struct Entry {
+    link: Link,
+    path: PathBuf,
+}
+
+/// A relative link in markdown, located at its target
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Link {
+    line_number: usize,
+    column: usize,
+    /// Target as written, without any `#fragment`
+    target: String,
+}
+
+impl Navigation {
+    /// Load the navigation of the `site` rooted at `root`
+    pub fn load(site: Site, root: &Path) -> Result<Self, String> {
+        match site {
+            Site::MdBook => Self::mdbook(root),
+            Site::MkDocs => Self::mkdocs(root),
+            Site::Docusaurus => Self::docusaurus(root),
+        }
+    }
+
+    /// Load the mdBook rooted at `root`. Its `book.toml` names the directory
+    /// holding SUMMARY.md and the chapters, `src` unless set.
+    pub fn mdbook(root: &Path) -> Result<Self, String> {
+        let book_toml = root.join("book.toml");
+        let text = read(&book_toml)?;
+        let table: toml::Table = text
+            .parse()
+            .map_err(|e| format!("Invalid {}: {}", book_toml.display(), e))?;
+        let src = table
+            .get("book")
+            .and_then(|book| book.get("src"))
+            .and_then(|src| src.as_str())
+            .unwrap_or("src");
+        let content_dir = normalize(&root.join(src));
+        let index = content_dir.join("SUMMARY.md");
+        let summary = read(&index)?;
+        // Draft chapters, `[Title]()`, have no file and so no link
+        let entries = links(&summary)
+            .into_iter()
+            .map(|link| Entry {
+                path: normalize(&content_dir.join(&link.target)),
+                link,
+            })
+            .collect();
+        Ok(Self::new(index, content_dir, entries))
+    }
+
+    /// Load the MkDocs site rooted at `root`. Pages live in the `docs_dir`
+    /// mkdocs.yml names (`docs` unless set) and are listed by its `nav`;
+    /// without one, MkDocs lists every page.
+    pub fn mkdocs(root: &Path) -> Result<Self, String> {
+        let index = ["mkdocs.yml", "mkdocs.yaml"]
+            .into_iter()
+            .map(|name| root.join(name))
+            .find(|path| path.is_file())
+            .unwrap_or_else(|| root.join("mkdocs.yml"));
+        let index = normalize(&index);
+        let text = read(&index)?;
+        let docs_dir = text
+            .lines()
+            .find_map(|line| line.strip_prefix("docs_dir:"))
+            .map(|value| unquote(strip_comment(value)))
+            .unwrap_or("docs");
+        let content_dir = normalize(&root.join(docs_dir));
+        let Some(nav) = mkdocs_nav(&text) else {
+            let mut navigation = Self::new(index, content_dir.clone(), Vec::new());
+            navigation.listed_dirs.push(content_dir);
+            return Ok(navigation);
+        };
+        let entries = nav
+            .into_iter()
+            .map(|link| Entry {
+                path: normalize(&content_dir.join(&link.target)),
+                link,
+            })
+            .collect();
+        Ok(Self::new(index, content_dir, entries))
+    }
+
+    /// Load the Docusaurus docs rooted at `root`: the pages under `docs` and
+    /// the sidebars file listing them by doc ID
+    pub fn docusaurus(root: &Path) -> Result<Self, String> {
+        let names = [
+            "sidebars.js",
+            "sidebars.ts",
+            "sidebars.json",
+            "sidebars.cjs",
+            "sidebars.mjs",
+        ];
+        let Some(index) = names
+            .into_iter()
+            .map(|name| root.join(name))
+            .find(|path| path.is_file())
+        else {
+            return Err(format!(
+                "No sidebars.js, sidebars.ts, or sidebars.json in {}",
+                root.display()
+            ));
+        };
+        let index = normalize(&index);
+        let text = read(&index)?;
+        let content_dir = normalize(&root.join("docs"));
+
+        let mut files = Vec::new();
+        markdown_files(&content_dir, &mut files);
+        // Partials, whose names start with `_`, are imported rather than listed
+        let (partials, pages): (Vec<_>, Vec<_>) = files.into_iter().partition(|path| {
+            path.file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('_'))
+        });
+        let ids: BTreeMap<String, PathBuf> = pages
+            .into_iter()
+            .filter_map(|path| Some((doc_id(&content_dir, &path)?, path)))
+            .collect();
+
+        let (items, dirs) = sidebar_items(&text);
+        let entries = items
+            .into_iter()
+            .map(|link| Entry {
+                path: ids
+                    .get(&link.target)
+                    .cloned()
+                    .unwrap_or_else(|| content_dir.join(&link.target)),
+                link,
+            })
+            .collect();
+        let mut navigation = Self::new(index, content_dir.clone(), entries);
+        navigation.listed.extend(partials);
+        navigation.listed_dirs = dirs
+            .iter()
+            .map(|dir| normalize(&content_dir.join(dir)))
+            .collect();
+        Ok(navigation)
+    }
+
+    fn new(index: PathBuf, content_dir: PathBuf, entries: Vec<Entry>) -> Self {
+        let listed = entries.iter().map(|entry| entry.path.clone()).collect();
+        Self {
+            index,
+            content_dir,
+            entries,
+            listed,
+            listed_dirs: Vec::new(),
+        }
+    }
+
+    fn is_listed(&self, path: &Path) -> bool {
+        self.listed.contains(path) || self.listed_dirs.iter().any(|dir| path.starts_with(dir))
+    }
+
+    /// Files to check: the index, the listed pages that exist, then the
+    /// markdown files under the content directory that are not listed
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut seen = BTreeSet::from([self.index.clone()]);
+        let mut files = vec![self.index.clone()];
+        for entry in &self.entries {
+            if entry.path.is_file() && seen.insert(entry.path.clone()) {
+                files.push(entry.path.clone());
+            }
+        }
+        let mut unlisted = Vec::new();
+        markdown_files(&self.content_dir, &mut unlisted);
+        unlisted.retain(|path| !seen.contains(path));
+        unlisted.sort();
+        files.extend(unlisted);
+        files
+    }
+
+    /// The navigation result for `file`, one of [`Navigation::files`], whose
+    /// content is `content`
+    pub fn validate(&self, file: &Path, content: &str) -> ValidationResult {
+        let name = NavigationRule::METADATA.name.to_string();
+        let index_name = self.index.file_name().unwrap_or_default().to_string_lossy();
+        let file = normalize(file);
+        let mut errors = Vec::new();
+
+        if file == self.index {
+            for entry in &self.entries {
+                if !entry.path.is_file() {
+                    errors.push(
+                        entry
+                            .link
+                            .error(format!("Listed page not found: {}", entry.link.target)),
+                    );
+                }
+            }
+        } else {
+            if !self.is_listed(&file) {
+                errors.push(ValidationError::new(
+                    1,
+                    format!("Page not listed in {}", index_name),
+                ));
+            }
+            let dir = file.parent().unwrap_or(Path::new(""));
+            for link in links(content) {
+                let Some(target) = resolve(&dir.join(&link.target)) else {
+                    errors.push(link.error(format!("Link target not found: {}", link.target)));
+                    continue;
+                };
+                if is_markdown(&target)
+                    && target.starts_with(&self.content_dir)
+                    && target != self.index
+                    && !self.is_listed(&target)
+                {
+                    errors.push(link.error(format!(
+                        "Link to a page {} does not list: {}",
+                        index_name, link.target
+                    )));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            ValidationResult::pass(name)
+        } else {
+            ValidationResult::fail(name, errors)
+        }
+    }
+}
+
+impl Link {
+    fn error(&self, message: String) -> ValidationError {
+        ValidationError::new(self.line_number, message).with_column(self.column)
+    }
+}
+
+/// Relative links to files in `content`, inline (`[text](target)`) or
+/// reference definitions (`[id]: target`), outside code
+fn links(content: &str) -> Vec<Link> {
+    let code = CodeRegions::parse(content);
+    let mut links = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        let mut targets = Vec::new();
+        let mut search = 0;
+        while let Some(found) = line[search..].find("](") {
+            let start = search + found + 2;
+            if let Some(target) = inline_target(&line[start..]) {
+                targets.push((start, target));
+            }
+            search = start;
+        }
+        if let Some(target) = definition_target(line) {
+            targets.push((line.len() - line.trim_start().len(), target));
+        }
+        for (start, target) in targets {
+            let column = line[..start].chars().count() + 1;
+            if code.contains(line_number, Some(column)) {
+                continue;
+            }
+            if let Some(target) = local_path(target) {
+                links.push(Link {
+                    line_number,
+                    column,
+                    target,
+                });
+            }
+        }
+    }
+    links
+}
+
+/// The target of an inline link, given the text after its `](`
+fn inline_target(rest: &str) -> Option<&str> {
+    let rest = rest.trim_start();
+    if let Some(rest) = rest.strip_prefix('<') {
+        return rest.find('>').map(|end| &rest[..end]);
+    }
+    // Balanced parentheses may appear in the target
+    let mut depth = 0;
+    for (i, ch) in rest.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(&rest[..i]),
+            ')' => depth -= 1,
+            ' ' | '\t' => return Some(&rest[..i]),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The target of a reference definition line, `[id]: target "title"`
+fn definition_target(line: &str) -> Option<&str> {
+    let rest = line.trim_start().strip_prefix('[')?;
+    let (label, rest) = rest.split_once("]:")?;
+    if label.is_empty() || label.starts_with('^') {
+        return None;
+    }
+    let target = rest.split_whitespace().next()?;
+    Some(target.trim_start_matches('<').trim_end_matches('>'))
+}
+
+/// The decoded path of a link target naming a file relative to the page:
+/// not a URL, site-absolute path, or anchor within the page
+fn local_path(target: &str) -> Option<String> {
+    let path = target.split(['#', '?']).next().unwrap_or_default();
+    if path.is_empty() || path.starts_with('/') || path.contains(':') {
+        return None;
+    }
+    percent_decode(path)
+}
+
+/// The existing file or directory a link names. Links may leave off the
+/// extension, as Docusaurus allows.
+fn resolve(path: &Path) -> Option<PathBuf> {
+    let path = normalize(path);
+    if path.exists() {
+        return Some(path);
+    }
+    if path.extension().is_some() {
+        return None;
+    }
+    ["md", "mdx"]
+        .into_iter()
+        .map(|ext| path.with_extension(ext))
+        .find(|path| path.is_file())
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "md" || ext == "mdx" || ext == "markdown")
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))
+}
+
+/// The pages mkdocs.yml's top-level `nav` lists, located at their paths, or
+/// `None` without a `nav`
+fn mkdocs_nav(text: &str) -> Option<Vec<Link>> {
+    let mut lines = text.lines().enumerate();
+    lines.find(|(_, line)| line.trim_end() == "nav:")?;
+    let mut pages = Vec::new();
+    for (index, line) in lines {
+        let item = line.trim_start();
+        if item.is_empty() || item.starts_with('#') {
+            continue;
+        }
+        // The next top-level key ends the list
+        if item.len() == line.len() && !item.starts_with('-') {
+            break;
+        }
+        let item = strip_comment(item.trim_start_matches('-').trim_start());
+        // `- Title: page.md` or `- page.md`; `- Section:` starts a nested list
+        let value = match item.rsplit_once(": ") {
+            Some((_, value)) => value,
+            None if item.ends_with(':') => continue,
+            None => item,
+        };
+        let value = unquote(value.trim());
+        let Some(target) = local_path(value) else {
+            continue;
+        };
+        // The path comes last, after any title
+        let start = line.rfind(value).unwrap_or_default();
+        pages.push(Link {
+            line_number: index + 1,
+            column: line[..start].chars().count() + 1,
+            target,
+        });
+    }
+    Some(pages)
+}
+
+/// `value` without a trailing ` # comment`
+fn strip_comment(value: &str) -> &str {
+    value
+        .split_once(" #")
+        .map_or(value, |(value, _)| value)
+        .trim()
+}
+
+fn unquote(value: &str) -> &str {
+    ['"', '\'']
+        .into_iter()
+        .find_map(|quote| value.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(value)
+}
+
+/// The Docusaurus ID of the doc at `path`: its path under `content_dir`
+/// without the extension or number prefixes, ending in its frontmatter `id`
+/// if it sets one
+fn doc_id(content_dir: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(content_dir).ok()?.with_extension("");
+    let mut parts: Vec<String> = relative
+        .components()
+        .map(|part| strip_number_prefix(&part.as_os_str().to_string_lossy()).to_string())
+        .collect();
+    let content = fs::read_to_string(path).ok()?;
+    if let Some(id) = Frontmatter::find(&content).and_then(|frontmatter| frontmatter.value("id")) {
+        *parts.last_mut()? = id;
+    }
+    Some(parts.join("/"))
+}
+
+/// `name` without an ordering prefix such as `01-`
+fn strip_number_prefix(name: &str) -> &str {
+    let digits = name.len()
+        - name
+            .trim_start_matches(|ch: char| ch.is_ascii_digit())
+            .len();
+    if digits == 0 {
+        return name;
+    }
+    let rest = name[digits..].trim_start_matches([' ', '-', '_', '.']);
+    if rest.len() == name.len() - digits || rest.is_empty() {
+        name
+    } else {
+        rest
+    }
+}
+
+/// Doc IDs a Docusaurus sidebars file lists, located at their strings, and
+/// the directories of its autogenerated sidebars. The file is JavaScript,
+/// TypeScript, or JSON; only its string literals matter. A string followed
+/// by `:` is a key; one after `id:` or in an array is a doc ID, as in
+/// `items: ['intro', {type: 'doc', id: 'setup'}]`.
+fn sidebar_items(text: &str) -> (Vec<Link>, Vec<String>) {
+    let mut ids = Vec::new();
+    let mut dirs = Vec::new();
+    let mut key = String::new();
+    // The last punctuation seen: what a string follows
+    let mut after = ' ';
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, ch)) = chars.next() {
+        match ch {
+            '/' if text[start..].starts_with("//") => {
+                while chars.next_if(|&(_, ch)| ch != '\n').is_some() {}
+            }
+            '/' if text[start..].starts_with("/*") => {
+                let end = text[start + 2..]
+                    .find("*/")
+                    .map_or(text.len(), |i| start + i + 4);
+                while chars.next_if(|&(i, _)| i < end).is_some() {}
+            }
+            '\'' | '"' | '`' => {
+                let mut value = String::new();
+                while let Some((_, next)) = chars.next() {
+                    match next {
+                        '\\' => value.extend(chars.next().map(|(_, ch)| ch)),
+                        _ if next == ch => break,
+                        _ => value.push(next),
+                    }
+                }
+                if followed_by_colon(&mut chars) {
+                    key = value;
+                    continue;
+                }
+                match after {
+                    ':' if key == "id" => ids.push(located(text, start + 1, value)),
+                    ':' if key == "dirName" => dirs.push(value),
+                    '[' | ',' => ids.push(located(text, start + 1, value)),
+                    _ => {}
+                }
+                after = ' ';
+            }
+            _ if ch.is_alphanumeric() || ch == '_' || ch == '$' => {
+                let mut word = ch.to_string();
+                while let Some((_, next)) =
+                    chars.next_if(|&(_, ch)| ch.is_alphanumeric() || ch == '_' || ch == '$')
+                {
+                    word.push(next);
+                }
+                if followed_by_colon(&mut chars) {
+                    key = word;
+                } else {
+                    after = ' ';
+                }
+            }
+            _ if ch.is_whitespace() => {}
+            _ => after = ch,
+        }
+    }
+    (ids, dirs)
+}
+
+/// Whether the next non-space character is `:`
+fn followed_by_colon(chars: &mut std::iter::Peekable<std::str::CharIndices>) -> bool {
+    while chars.next_if(|&(_, ch)| ch.is_whitespace()).is_some() {}
+    chars.peek().is_some_and(|&(_, ch)| ch == ':')
+}
+
+/// A link to `target` at byte offset `start` of `text`
+fn located(text: &str, start: usize, target: String) -> Link {
+    let before = &text[..start];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Link {
+        line_number: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+        target,
+    }
+}
+
+/// Markdown files under `dir`, skipping hidden directories
+fn markdown_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if !entry.file_name().to_string_lossy().starts_with('.') {
+                markdown_files(&path, files);
+            }
+        } else if is_markdown(&path) {
+            files.push(normalize(&path));
+        }
+    }
+}
+
+/// `path` with `.` and `..` components resolved lexically, so the same file
+/// reached by different links compares equal
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                _ => normalized.push(".."),
+            },
+            other => normalized.push(other),
+        }
+    }
+    if normalized.as_os_str().is_empty() {
+        normalized.push(".");
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn book(files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (name, content) in files {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    fn messages(result: &ValidationResult) -> Vec<String> {
+        result
+            .errors
+            .iter()
+            .map(|e| format!("{}:{}", e.line_number, e.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_links() {
+        let content = "See [a](a.md#intro) and [b](<b c.md> \"B\").\n\
+                       ```\n[x](code.md)\n```\n\
+                       [web](https://example.com) [top](#top) `[y](span.md)`\n\
+                       [ref]: ../ref%20doc.md\n";
+        let targets: Vec<_> = links(content)
+            .into_iter()
+            .map(|link| (link.line_number, link.column, link.target))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                (1, 9, "a.md".to_string()),
+                (1, 29, "b c.md".to_string()),
+                (6, 1, "../ref doc.md".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(Path::new("./src/a/../b.md")),
+            Path::new("src/b.md")
+        );
+        assert_eq!(normalize(Path::new("../x.md")), Path::new("../x.md"));
+        assert_eq!(normalize(Path::new("a/..")), Path::new("."));
+    }
+
+    #[test]
+    fn test_mdbook_structure() {
+        let dir = book(&[
+            ("book.toml", "[book]\ntitle = \"Guide\"\nsrc = \"pages\"\n"),
+            (
+                "pages/SUMMARY.md",
+                "# Summary\n\n[Intro](intro.md)\n- [Setup](setup/index.md)\n- [Draft]()\n- [Gone](gone.md)\n",
+            ),
+            (
+                "pages/intro.md",
+                "# Intro\nSee [setup](setup/index.md) and [faq](faq.md).\n",
+            ),
+            (
+                "pages/setup/index.md",
+                "Back to [intro](../intro.md), [missing](nope.md).\n",
+            ),
+            ("pages/faq.md", "# FAQ\n"),
+        ]);
+        let navigation = Navigation::mdbook(dir.path()).unwrap();
+        let src = normalize(&dir.path().join("pages"));
+        assert_eq!(
+            navigation.files(),
+            vec![
+                src.join("SUMMARY.md"),
+                src.join("intro.md"),
+                src.join("setup/index.md"),
+                src.join("faq.md"),
+            ]
+        );
+
+        let check = |name: &str| {
+            let path = src.join(name);
+            navigation.validate(&path, &fs::read_to_string(&path).unwrap())
+        };
+        assert_eq!(
+            messages(&check("SUMMARY.md")),
+            vec!["6:Listed page not found: gone.md"]
+        );
+        assert_eq!(
+            messages(&check("intro.md")),
+            vec!["2:Link to a page SUMMARY.md does not list: faq.md"]
+        );
+        assert_eq!(
+            messages(&check("setup/index.md")),
+            vec!["1:Link target not found: nope.md"]
+        );
+        assert_eq!(
+            messages(&check("faq.md")),
+            vec!["1:Page not listed in SUMMARY.md"]
+        );
+        assert_eq!(check("faq.md").validator_name, "Navigation");
+    }
+
+    #[test]
+    fn test_mkdocs_nav() {
+        let dir = book(&[
+            (
+                "mkdocs.yml",
+                "site_name: Docs\ndocs_dir: 'content'\nnav:\n  - index.md\n  - Guide:\n      \
+                 - 'Setup: first': guide/setup.md  # start here\n      - Old: guide/old.md\n  \
+                 - Blog: https://example.com/blog\ntheme: material\n",
+            ),
+            ("content/index.md", "# Home\n[setup](guide/setup.md)\n"),
+            (
+                "content/guide/setup.md",
+                "[home](../index.md) [draft](draft)\n",
+            ),
+            ("content/guide/draft.md", "# Draft\n"),
+        ]);
+        let navigation = Navigation::mkdocs(dir.path()).unwrap();
+        let index = normalize(&dir.path().join("mkdocs.yml"));
+        let docs = normalize(&dir.path().join("content"));
+        let check = |path: &Path| navigation.validate(path, &fs::read_to_string(path).unwrap());
+
+        let entries: Vec<_> = navigation
+            .entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.link.line_number,
+                    entry.link.column,
+                    entry.link.target.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (4, 5, "index.md"),
+                (6, 25, "guide/setup.md"),
+                (7, 14, "guide/old.md")
+            ]
+        );
+        assert_eq!(
+            messages(&check(&index)),
+            vec!["7:Listed page not found: guide/old.md"]
+        );
+        assert!(check(&docs.join("index.md")).is_pass());
+        // Extensionless links resolve to the page
+        assert_eq!(
+            messages(&check(&docs.join("guide/setup.md"))),
+            vec!["1:Link to a page mkdocs.yml does not list: draft"]
+        );
+        assert_eq!(
+            messages(&check(&docs.join("guide/draft.md"))),
+            vec!["1:Page not listed in mkdocs.yml"]
+        );
+    }
+
+    #[test]
+    fn test_mkdocs_without_nav_lists_every_page() {
+        let dir = book(&[
+            ("mkdocs.yml", "site_name: Docs\n"),
+            ("docs/index.md", "[a](a.md)\n"),
+            ("docs/a.md", "# A\n"),
+        ]);
+        let navigation = Navigation::mkdocs(dir.path()).unwrap();
+        let docs = normalize(&dir.path().join("docs"));
+        assert_eq!(navigation.files().len(), 3);
+        for page in ["index.md", "a.md"] {
+            let path = docs.join(page);
+            assert!(
+                navigation
+                    .validate(&path, &fs::read_to_string(&path).unwrap())
+                    .is_pass()
+            );
+        }
+    }
+
+    #[test]
+    fn test_sidebar_items() {
+        let text = "// 'commented'\nconst sidebars: Config = {\n  docs: [\n    'intro',\n    \
+                    {type: 'category', label: 'Guides', items: [\"guides/setup\", /* 'x' */ \
+                    {type: 'doc', id: 'guides/deploy'}]},\n    \
+                    {type: 'autogenerated', dirName: 'api'},\n    \
+                    {type: 'link', label: 'Blog', href: 'https://example.com'},\n  ],\n};\n";
+        let (ids, dirs) = sidebar_items(text);
+        let ids: Vec<_> = ids
+            .iter()
+            .map(|link| (link.line_number, link.column, link.target.as_str()))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                (4, 6, "intro"),
+                (5, 50, "guides/setup"),
+                (5, 94, "guides/deploy")
+            ]
+        );
+        assert_eq!(dirs, vec!["api"]);
+
+        let (ids, _) = sidebar_items("{\"docs\": {\"Start\": [\"intro\"]}}");
+        assert_eq!(ids[0].target, "intro");
+    }
+
+    #[test]
+    fn test_docusaurus_ids() {
+        let dir = book(&[
+            (
+                "sidebars.json",
+                "{\"docs\": [\"intro\", \"guides/setup\", \"guides/gone\", \
+                 {\"type\": \"autogenerated\", \"dirName\": \"api\"}]}\n",
+            ),
+            (
+                "docs/01-intro.md",
+                "# Intro\n[setup](guides/02-setup.mdx) [faq](faq.md)\n",
+            ),
+            ("docs/guides/02-setup.mdx", "---\nid: setup\n---\n# Setup\n"),
+            ("docs/guides/_snippet.md", "Shared text\n"),
+            ("docs/api/client.md", "# Client\n"),
+            ("docs/faq.md", "# FAQ\n"),
+        ]);
+        let navigation = Navigation::docusaurus(dir.path()).unwrap();
+        let docs = normalize(&dir.path().join("docs"));
+        let check = |path: &Path| navigation.validate(path, &fs::read_to_string(path).unwrap());
+
+        assert_eq!(
+            messages(&check(&normalize(&dir.path().join("sidebars.json")))),
+            vec!["1:Listed page not found: guides/gone"]
+        );
+        assert_eq!(
+            messages(&check(&docs.join("01-intro.md"))),
+            vec!["2:Link to a page sidebars.json does not list: faq.md"]
+        );
+        assert!(check(&docs.join("guides/_snippet.md")).is_pass());
+        assert!(check(&docs.join("api/client.md")).is_pass());
+        assert_eq!(
+            messages(&check(&docs.join("faq.md"))),
+            vec!["1:Page not listed in sidebars.json"]
+        );
+        assert_eq!(strip_number_prefix("01-intro"), "intro");
+        assert_eq!(strip_number_prefix("2024"), "2024");
+        assert_eq!(strip_number_prefix("3d-models"), "3d-models");
+    }
+
+    #[test]
+    fn test_mdbook_requires_book_toml() {
+        let dir = book(&[("src/SUMMARY.md", "# Summary\n")]);
+        let err = Navigation::mdbook(dir.path()).unwrap_err();
+        assert!(err.contains("book.toml"));
+    }
+}
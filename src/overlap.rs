@@ -0,0 +1,202 @@
+//! Collapsing violations that several rules report for the same character.
+//!
+//! A tree symbol is not ASCII, not printable ASCII, and a tree symbol, so it
+//! trips MC001, MC002, and MC003 at once. [`keep_most_specific`] leaves it to
+//! the most specific rule, so every output and every count sees one violation
+//! per character; [`merge_overlapping`] groups whatever other rules still
+//! share a location for the location-sorted report.
+
+use crate::validators::find_rule;
+use crate::{Severity, ValidationError, ValidationResult};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap};
+
+/// Rule codes from the narrowest set of characters to the widest; the first
+/// rule flagging a location provides its message
+const SPECIFICITY: &[&str] = &["MC003", "MC001", "MC002"];
+
+/// One location with every rule that reported it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic<'a> {
+    /// The most specific rule's error
+    pub error: &'a ValidationError,
+    /// Validator names reporting this location, most specific first
+    pub validators: Vec<&'a str>,
+    /// Highest severity among those validators
+    pub severity: Severity,
+}
+
+/// Drop the character rules' errors at locations another character rule
+/// also reports, keeping one per location: an error outranks a warning, and
+/// among equal severities the most specific rule wins. Other rules' errors
+/// are kept as they are.
+pub fn keep_most_specific(results: Vec<ValidationResult>) -> Vec<ValidationResult> {
+    let key = |result: &ValidationResult| {
+        let rank = rank(&result.validator_name);
+        (rank < SPECIFICITY.len()).then_some((Reverse(result.severity), rank))
+    };
+    let mut best = HashMap::new();
+    for result in &results {
+        let Some(key) = key(result) else { continue };
+        for error in &result.errors {
+            if let Some(column) = error.column {
+                best.entry((error.line_number, column))
+                    .and_modify(|best| *best = key.min(*best))
+                    .or_insert(key);
+            }
+        }
+    }
+    results
+        .into_iter()
+        .map(|result| match key(&result) {
+            Some(key) => result.retain_errors(|error| {
+                error
+                    .column
+                    .is_none_or(|column| best[&(error.line_number, column)] == key)
+            }),
+            None => result,
+        })
+        .collect()
+}
+
+/// Whether more than one of the rules that can flag the same character is
+/// enabled
+pub fn can_overlap(disabled: &[&str]) -> bool {
+    SPECIFICITY
+        .iter()
+        .filter(|code| !disabled.contains(code))
+        .count()
+        > 1
+}
+
+/// Merge errors reported at the same line and column, ordered by location.
+/// Errors without a column are never merged.
+pub fn merge_overlapping(results: &[ValidationResult]) -> Vec<Diagnostic<'_>> {
+    let mut errors: Vec<(&ValidationError, &str, Severity)> = results
+        .iter()
+        .flat_map(|r| {
+            r.errors
+                .iter()
+                .map(move |e| (e, r.validator_name.as_str(), r.severity))
+        })
+        .collect();
+    errors.sort_by_key(|(error, name, _)| (error.line_number, error.column, rank(name)));
+
+    let mut merged: Vec<Diagnostic> = Vec::with_capacity(errors.len());
+    for (error, name, severity) in errors {
+        match merged.last_mut() {
+            Some(last)
+                if error.column.is_some()
+                    && (last.error.line_number, last.error.column)
+                        == (error.line_number, error.column) =>
+            {
+                last.validators.push(name);
+                last.severity = last.severity.max(severity);
+            }
+            _ => merged.push(Diagnostic {
+                error,
+                validators: vec![name],
+                severity,
+            }),
+        }
+    }
+    merged
+}
+
+/// Number of distinct locations with violations
+pub fn count_locations<'a>(results: impl IntoIterator<Item = &'a ValidationResult>) -> usize {
+    let mut locations = BTreeSet::new();
+    let mut without_column = 0;
+    for error in results.into_iter().flat_map(|r| &r.errors) {
+        match error.column {
+            Some(column) => {
+                locations.insert((error.line_number, column));
+            }
+            None => without_column += 1,
+        }
+    }
+    locations.len() + without_column
+}
+
+/// Position in [`SPECIFICITY`]; rules without a code rank after all others
+fn rank(validator_name: &str) -> usize {
+    find_rule(validator_name)
+        .and_then(|rule| SPECIFICITY.iter().position(|code| *code == rule.code))
+        .unwrap_or(SPECIFICITY.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::validate_all;
+
+    #[test]
+    fn test_merges_rules_reporting_the_same_character() {
+        let results = validate_all("├ x\nca\u{fe}\n");
+        let merged = merge_overlapping(&results);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(
+            merged[0].validators,
+            vec!["Tree Symbols", "ASCII Subset", "Printable Characters"]
+        );
+        assert!(merged[0].error.message.text().starts_with("Tree symbol"));
+        assert_eq!(
+            merged[1].validators,
+            vec!["ASCII Subset", "Printable Characters"]
+        );
+        assert_eq!(
+            (merged[1].error.line_number, merged[1].error.column),
+            (2, Some(3))
+        );
+        assert_eq!(count_locations(&results), 2);
+    }
+
+    #[test]
+    fn test_keeps_most_specific_rule() {
+        let results = keep_most_specific(validate_all("\u{251c} ca\u{fe} \u{7}\n"));
+        let codes: Vec<_> = results
+            .iter()
+            .flat_map(|r| {
+                r.errors
+                    .iter()
+                    .map(|e| (e.column.unwrap(), r.validator_name.as_str()))
+            })
+            .collect();
+        assert_eq!(
+            codes,
+            [
+                (5, "ASCII Subset"),
+                (7, "Printable Characters"),
+                (1, "Tree Symbols")
+            ]
+        );
+        assert_eq!(count_locations(&results), 3);
+
+        // An error is not hidden behind a warning for the same character
+        let results = validate_all("\u{251c}\n")
+            .into_iter()
+            .map(|r| match r.validator_name.as_str() {
+                "Tree Symbols" => r.with_severity(Severity::Warning),
+                _ => r,
+            })
+            .collect();
+        let failing: Vec<_> = keep_most_specific(results)
+            .into_iter()
+            .filter(|r| r.is_fail())
+            .map(|r| r.validator_name)
+            .collect();
+        assert_eq!(failing, ["ASCII Subset"]);
+    }
+
+    #[test]
+    fn test_errors_without_columns_stay_separate() {
+        let error = ValidationError::new(1, "bad".to_string());
+        let results = vec![
+            ValidationResult::fail("One".to_string(), vec![error.clone()]),
+            ValidationResult::fail("Two".to_string(), vec![error]),
+        ];
+        assert_eq!(merge_overlapping(&results).len(), 2);
+        assert_eq!(count_locations(&results), 2);
+    }
+}
@@ -0,0 +1,187 @@
+//! Bounded parallel processing of file batches.
+
+use std::collections::BTreeMap;
+use std::ops::ControlFlow;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Default number of worker threads: the available CPU count
+pub fn default_jobs() -> usize {
+    thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+/// Apply `f` to every item using up to `jobs` worker threads.
+///
+/// Results are returned in the same order as `items`, regardless of the order
+/// in which workers finish, so output stays deterministic.
+pub fn parallel_map<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+    if jobs == 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let slots: Mutex<Vec<Option<R>>> = Mutex::new((0..items.len()).map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    let result = f(item);
+                    slots.lock().unwrap()[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    slots
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|slot| slot.expect("every item is processed by a worker"))
+        .collect()
+}
+
+/// Apply `f` to every item using up to `jobs` worker threads, handing each
+/// result to `consume` on the calling thread as soon as it and every earlier
+/// result are ready.
+///
+/// Results arrive in the same order as `items`, like [`parallel_map`], but
+/// without waiting for the whole batch, so output can be streamed. Once
+/// `consume` breaks, no further items are started or consumed.
+pub fn parallel_for_each<T, R, F, C>(items: &[T], jobs: usize, f: F, mut consume: C)
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+    C: FnMut(&T, R) -> ControlFlow<()>,
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+    if jobs == 1 {
+        for item in items {
+            if consume(item, f(item)).is_break() {
+                break;
+            }
+        }
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (next, stop, f) = (&next, &stop, &f);
+            scope.spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    // The receiver only goes away once consuming has stopped
+                    let _ = sender.send((index, f(item)));
+                }
+            });
+        }
+        drop(sender);
+
+        // Results that finished ahead of an earlier, still running item
+        let mut pending = BTreeMap::new();
+        let mut expected = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&expected) {
+                if consume(&items[expected], result).is_break() {
+                    stop.store(true, Ordering::Relaxed);
+                    return;
+                }
+                expected += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_jobs_is_positive() {
+        assert!(default_jobs() >= 1);
+    }
+
+    #[test]
+    fn test_parallel_map_preserves_order() {
+        let items: Vec<usize> = (0..100).collect();
+        let results = parallel_map(&items, 8, |n| n * 2);
+        assert_eq!(results, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_parallel_for_each_streams_in_order() {
+        let items: Vec<usize> = (0..100).collect();
+        for jobs in [1, 8] {
+            let mut seen = Vec::new();
+            parallel_for_each(
+                &items,
+                jobs,
+                |n| n * 2,
+                |&n, doubled| {
+                    assert_eq!(doubled, n * 2);
+                    seen.push(n);
+                    ControlFlow::Continue(())
+                },
+            );
+            assert_eq!(seen, items);
+        }
+    }
+
+    #[test]
+    fn test_parallel_for_each_stops_on_break() {
+        let items: Vec<usize> = (0..100).collect();
+        let mut seen = Vec::new();
+        parallel_for_each(
+            &items,
+            4,
+            |n| *n,
+            |_, n| {
+                seen.push(n);
+                if n == 10 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            },
+        );
+        assert_eq!(seen, (0..=10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_parallel_map_single_job() {
+        let items = vec!["a", "b", "c"];
+        let results = parallel_map(&items, 1, |s| s.to_uppercase());
+        assert_eq!(results, vec!["A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_parallel_map_empty() {
+        let items: Vec<u32> = Vec::new();
+        let results = parallel_map(&items, 4, |n| *n);
+        assert!(results.is_empty());
+    }
+}
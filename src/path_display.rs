@@ -0,0 +1,153 @@
+//! How file paths are rendered in reports (`--paths`).
+
+use crate::git;
+use crate::remote::url_of;
+use std::path::{Component, Path, PathBuf};
+
+/// Style for file paths in reports
+#[derive(
+    clap::ValueEnum, serde::Deserialize, schemars::JsonSchema, Debug, Clone, Copy, PartialEq, Eq,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum PathStyle {
+    /// Relative to the current directory
+    Relative,
+    /// Absolute
+    Absolute,
+    /// Relative to the git repository root (the current directory outside a repository)
+    FromRoot,
+}
+
+/// Renders checked paths in the selected style, and resolves rendered paths
+/// back to absolute ones for hyperlinks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathDisplay {
+    style: Option<PathStyle>,
+    cwd: PathBuf,
+    root: PathBuf,
+}
+
+impl PathDisplay {
+    /// Paths are shown as given when `style` is `None`
+    pub fn new(style: Option<PathStyle>) -> Self {
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let root = match style {
+            Some(PathStyle::FromRoot) => git::repository_root(&cwd).unwrap_or_else(|_| cwd.clone()),
+            _ => cwd.clone(),
+        };
+        Self::with_dirs(style, cwd, root)
+    }
+
+    /// Use explicit current and repository root directories
+    pub fn with_dirs(style: Option<PathStyle>, cwd: PathBuf, root: PathBuf) -> Self {
+        Self { style, cwd, root }
+    }
+
+    /// Path as it should appear in reports
+    pub fn display(&self, path: &Path) -> PathBuf {
+        let Some(style) = self.style.filter(|_| url_of(path).is_none()) else {
+            return path.to_path_buf();
+        };
+        let absolute = normalize(&self.cwd.join(path));
+        let base = match style {
+            PathStyle::Absolute => return absolute,
+            PathStyle::Relative => &self.cwd,
+            PathStyle::FromRoot => &self.root,
+        };
+        absolute
+            .strip_prefix(base)
+            .map(Path::to_path_buf)
+            .unwrap_or(absolute)
+    }
+
+    /// Absolute path of a path produced by [`PathDisplay::display`]
+    pub fn absolute(&self, shown: &Path) -> PathBuf {
+        let base = match self.style {
+            Some(PathStyle::FromRoot) => &self.root,
+            _ => &self.cwd,
+        };
+        normalize(&base.join(shown))
+    }
+}
+
+/// Resolve `.` and `..` components without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// `file://` URL for an absolute path, percent-encoding reserved bytes
+pub fn file_url(path: &Path) -> String {
+    let mut url = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                url.push(byte as char)
+            }
+            _ => url.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(style: Option<PathStyle>) -> PathDisplay {
+        PathDisplay::with_dirs(style, PathBuf::from("/repo/docs"), PathBuf::from("/repo"))
+    }
+
+    #[test]
+    fn test_display_styles() {
+        let path = Path::new("./guide/intro.md");
+        assert_eq!(display(None).display(path), path);
+        assert_eq!(
+            display(Some(PathStyle::Relative)).display(path),
+            Path::new("guide/intro.md")
+        );
+        assert_eq!(
+            display(Some(PathStyle::Absolute)).display(path),
+            Path::new("/repo/docs/guide/intro.md")
+        );
+        assert_eq!(
+            display(Some(PathStyle::FromRoot)).display(path),
+            Path::new("docs/guide/intro.md")
+        );
+    }
+
+    #[test]
+    fn test_paths_outside_base_stay_absolute() {
+        let path = Path::new("../../elsewhere/a.md");
+        assert_eq!(
+            display(Some(PathStyle::FromRoot)).display(path),
+            Path::new("/elsewhere/a.md")
+        );
+    }
+
+    #[test]
+    fn test_absolute_round_trips() {
+        for style in [None, Some(PathStyle::Relative), Some(PathStyle::FromRoot)] {
+            let paths = display(style);
+            let shown = paths.display(Path::new("guide/a.md"));
+            assert_eq!(paths.absolute(&shown), Path::new("/repo/docs/guide/a.md"));
+        }
+    }
+
+    #[test]
+    fn test_file_url() {
+        assert_eq!(
+            file_url(Path::new("/repo/my docs/é.md")),
+            "file:///repo/my%20docs/%C3%A9.md"
+        );
+    }
+}
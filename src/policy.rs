@@ -0,0 +1,571 @@
+//! Per-file rule policy: which rules run and which characters are accepted.
+
+use crate::charset::{self, CharClass};
+use crate::code_regions::CodeRegions;
+use crate::commit_msg;
+use crate::doc_format::DocFormat;
+use crate::encoding::{BOM, Bom};
+use crate::frontmatter::Frontmatter;
+use crate::mdx;
+use crate::overlap::{can_overlap, keep_most_specific};
+use crate::rustdoc;
+use crate::suppressions::Suppressions;
+use crate::validators::{
+    EncodingRule, FrontmatterValidator, all_rules, count_enabled, find_char_rule, find_rule,
+    is_prose_rule, opt_in_rules, validate_until_timed,
+};
+use crate::{RuleOptions, Severity, StopAt, ValidationError, ValidationResult};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// How many violations of one rule a file has
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleCount {
+    pub code: &'static str,
+    pub severity: Severity,
+    pub count: usize,
+}
+
+/// Rule settings that apply to one file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Policy {
+    /// Codes of rules that are not run
+    pub disabled_rules: Vec<&'static str>,
+    /// Severities replacing rules' defaults, by rule code
+    pub severities: BTreeMap<&'static str, Severity>,
+    /// Characters no rule flags and fixing leaves alone
+    pub allowed: Vec<CharClass>,
+    /// Exceptions to `allowed`
+    pub forbidden: Vec<CharClass>,
+    /// Exempt fenced code blocks and inline code spans
+    pub skip_code: bool,
+    /// Exempt the frontmatter block from every rule but the frontmatter rule
+    pub skip_frontmatter: bool,
+    /// The markup the content is written in, which decides what is code and
+    /// whether MDX syntax and frontmatter are recognized
+    pub format: DocFormat,
+    /// How much of the file is checked once a violation is found
+    pub stop_at: StopAt,
+    /// Whether a byte order mark starting the file is accepted, and kept or
+    /// added when fixing
+    pub bom: Bom,
+    /// Settings of the rules that take them
+    pub options: RuleOptions,
+}
+
+impl Default for Policy {
+    /// The rules that are on by default, with no exemptions
+    fn default() -> Self {
+        Self {
+            disabled_rules: opt_in_rules(),
+            severities: BTreeMap::new(),
+            allowed: Vec::new(),
+            forbidden: Vec::new(),
+            skip_code: false,
+            skip_frontmatter: false,
+            format: DocFormat::Markdown,
+            stop_at: StopAt::Never,
+            bom: Bom::Remove,
+            options: RuleOptions::default(),
+        }
+    }
+}
+
+impl Policy {
+    pub fn new(disabled_rules: Vec<&'static str>) -> Self {
+        Self {
+            disabled_rules,
+            ..Self::default()
+        }
+    }
+
+    /// Report the rules with these codes at these severities
+    pub fn severities(
+        mut self,
+        severities: impl IntoIterator<Item = (&'static str, Severity)>,
+    ) -> Self {
+        self.severities.extend(severities);
+        self
+    }
+
+    /// Accept these characters
+    pub fn allow<C: Into<CharClass>>(mut self, classes: impl IntoIterator<Item = C>) -> Self {
+        self.allowed.extend(classes.into_iter().map(Into::into));
+        self
+    }
+
+    /// Keep flagging these characters even where `allow` would accept them
+    pub fn forbid<C: Into<CharClass>>(mut self, classes: impl IntoIterator<Item = C>) -> Self {
+        self.forbidden.extend(classes.into_iter().map(Into::into));
+        self
+    }
+
+    /// Exempt code regions from every rule
+    pub fn skip_code(mut self, skip: bool) -> Self {
+        self.skip_code = skip;
+        self
+    }
+
+    /// Exempt frontmatter from the character rules
+    pub fn skip_frontmatter(mut self, skip: bool) -> Self {
+        self.skip_frontmatter = skip;
+        self
+    }
+
+    /// Read the content as `format`. Formats without frontmatter do not run
+    /// the frontmatter rule, and plain text runs only the character rules and
+    /// the encoding check.
+    pub fn format(mut self, format: DocFormat) -> Self {
+        self.format = format;
+        self.options.format = format;
+        for rule in all_rules() {
+            let unsupported = match format {
+                DocFormat::Text | DocFormat::CommitMessage => {
+                    find_char_rule(rule.code).is_none() && rule.code != EncodingRule::METADATA.code
+                }
+                _ => rule.code == FrontmatterValidator::METADATA.code && !format.has_frontmatter(),
+            };
+            if unsupported && !self.disabled_rules.contains(&rule.code) {
+                self.disabled_rules.push(rule.code);
+            }
+        }
+        self
+    }
+
+    /// Stop validating once a violation is found, per rule or per file
+    pub fn stop_at(mut self, stop_at: StopAt) -> Self {
+        self.stop_at = stop_at;
+        self
+    }
+
+    /// Accept, keep, or add a byte order mark at the start of the file
+    pub fn bom(mut self, bom: Bom) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    /// Run the rules that take settings with these, keeping the format the
+    /// content is read as
+    pub fn options(mut self, options: RuleOptions) -> Self {
+        self.options = RuleOptions {
+            format: self.format,
+            ..options
+        };
+        self
+    }
+
+    /// Regions of `content` exempt from the rules: code if `skip_code` is
+    /// set, frontmatter if `skip_frontmatter` is, MDX syntax in MDX files, the
+    /// lines git strips from commit messages, Rust code outside doc comments,
+    /// and a byte order mark the `bom` setting accepts
+    pub fn exempt_regions(&self, content: &str) -> CodeRegions {
+        let mut regions = if self.skip_code {
+            CodeRegions::parse_as(content, self.format)
+        } else {
+            CodeRegions::default()
+        };
+        match self.format {
+            DocFormat::Mdx => regions.merge(mdx::parse(content)),
+            DocFormat::CommitMessage => regions.merge(commit_msg::parse(content)),
+            DocFormat::Rustdoc => regions.merge(rustdoc::parse(content)),
+            _ => {}
+        }
+        let frontmatter = Frontmatter::find(content)
+            .filter(|_| self.skip_frontmatter && self.format.has_frontmatter());
+        if let Some(frontmatter) = frontmatter {
+            regions.insert_lines(frontmatter.lines);
+        }
+        if self.bom.accepts() && content.starts_with(BOM) {
+            regions.insert_span(1, 1..=1);
+        }
+        regions
+    }
+
+    /// Whether `ch` is accepted everywhere
+    pub fn allows(&self, ch: char) -> bool {
+        charset::accepts(&self.allowed, &self.forbidden, ch)
+    }
+
+    /// Run the enabled rules and drop violations the policy or the file's
+    /// suppression comments exempt
+    pub fn validate(&self, content: &str) -> Vec<ValidationResult> {
+        let results = self
+            .validate_timed(content)
+            .into_iter()
+            .map(|(result, _)| result)
+            .collect();
+        self.filter(content, results).0
+    }
+
+    /// Run the enabled rules, measuring how long each took, for
+    /// [`Policy::filter`]. When stopping early, exempt violations are left
+    /// out as they are found so they cannot end validation.
+    pub fn validate_timed(&self, content: &str) -> Vec<(ValidationResult, Duration)> {
+        if self.stop_at == StopAt::Never {
+            return validate_until_timed(
+                content,
+                &self.disabled_rules,
+                &self.options,
+                StopAt::Never,
+                None,
+            );
+        }
+        let exempt = self.exemption(content);
+        validate_until_timed(
+            content,
+            &self.disabled_rules,
+            &self.options,
+            self.stop_at,
+            Some(&exempt),
+        )
+    }
+
+    /// Count each enabled rule's violations that the policy and suppression
+    /// comments do not exempt, without collecting them unless several rules
+    /// may flag one character, which is counted once as it is reported
+    pub fn count(&self, content: &str) -> Vec<RuleCount> {
+        if can_overlap(&self.disabled_rules) {
+            return self
+                .validate(content)
+                .into_iter()
+                .filter_map(|result| {
+                    Some(RuleCount {
+                        code: find_rule(&result.validator_name)?.code,
+                        severity: result.severity,
+                        count: result.errors.len(),
+                    })
+                })
+                .collect();
+        }
+        let exempt = self.exemption(content);
+        count_enabled(content, &self.disabled_rules, &self.options, Some(&exempt))
+            .into_iter()
+            .map(|(code, count)| RuleCount {
+                code,
+                severity: self.severity_of(code),
+                count,
+            })
+            .collect()
+    }
+
+    /// Whether files that are not UTF-8 are reported as violations of the
+    /// encoding rule rather than as files that cannot be read
+    pub fn checks_encoding(&self) -> bool {
+        !self.disabled_rules.contains(&EncodingRule::METADATA.code)
+    }
+
+    /// The severity the rule with `code` is reported at
+    pub fn severity_of(&self, code: &str) -> Severity {
+        match self.severities.get(code) {
+            Some(&severity) => severity,
+            None => find_rule(code).map_or(Severity::Error, |rule| rule.default_severity),
+        }
+    }
+
+    /// Whether a violation in `content` is exempt by the policy or by a
+    /// suppression comment, as [`Policy::filter`] decides
+    pub fn exemption<'a>(
+        &'a self,
+        content: &'a str,
+    ) -> impl Fn(&str, &ValidationError) -> bool + 'a {
+        let exempt = self.exempt_regions(content);
+        let suppressions = Suppressions::parse(content);
+        let lines: Vec<&str> = if self.allowed.is_empty() {
+            Vec::new()
+        } else {
+            content.lines().collect()
+        };
+        move |code, error| {
+            let ch = error.column.and_then(|column| {
+                lines
+                    .get(error.line_number.checked_sub(1)?)?
+                    .chars()
+                    .nth(column.checked_sub(1)?)
+            });
+            (ch.is_some_and(|ch| self.allows(ch)) && !is_prose_rule(code))
+                || (code != FrontmatterValidator::METADATA.code
+                    && exempt.contains(error.line_number, error.column))
+                || suppressions.is_suppressed(error.line_number, Some(code))
+        }
+    }
+
+    /// Drop violations of allowed characters, those in exempt code or
+    /// frontmatter, and those exempted by suppression comments, and apply
+    /// configured severities.
+    /// Returns the filtered results and the number suppressed by comments.
+    pub fn filter(
+        &self,
+        content: &str,
+        results: Vec<ValidationResult>,
+    ) -> (Vec<ValidationResult>, usize) {
+        let results = if self.allowed.is_empty() {
+            results
+        } else {
+            let lines: Vec<&str> = content.lines().collect();
+            self.drop_allowed(&lines, results)
+        };
+        self.apply(
+            results,
+            &self.exempt_regions(content),
+            &Suppressions::parse(content),
+        )
+    }
+
+    /// Drop violations of allowed characters in `lines`
+    pub fn drop_allowed(
+        &self,
+        lines: &[&str],
+        results: Vec<ValidationResult>,
+    ) -> Vec<ValidationResult> {
+        results
+            .into_iter()
+            .map(|result| {
+                if is_prose_rule(&result.validator_name) {
+                    return result;
+                }
+                result.retain_errors(|error| {
+                    let ch = error.column.and_then(|column| {
+                        lines
+                            .get(error.line_number.checked_sub(1)?)?
+                            .chars()
+                            .nth(column.checked_sub(1)?)
+                    });
+                    !ch.is_some_and(|ch| self.allows(ch))
+                })
+            })
+            .collect()
+    }
+
+    /// Drop violations in `exempt` regions and those exempted by
+    /// `suppressions`, and apply configured severities.
+    /// Returns the filtered results and the number suppressed by comments.
+    pub fn apply(
+        &self,
+        results: Vec<ValidationResult>,
+        exempt: &CodeRegions,
+        suppressions: &Suppressions,
+    ) -> (Vec<ValidationResult>, usize) {
+        let results = if exempt.is_empty() {
+            results
+        } else {
+            results
+                .into_iter()
+                .map(|result| {
+                    // Skipping frontmatter does not skip checking it, and
+                    // no region of a file is exempt from its encoding
+                    if [
+                        FrontmatterValidator::METADATA.name,
+                        EncodingRule::METADATA.name,
+                    ]
+                    .contains(&result.validator_name.as_str())
+                    {
+                        return result;
+                    }
+                    result.retain_errors(|error| !exempt.contains(error.line_number, error.column))
+                })
+                .collect()
+        };
+        let (results, suppressed) = suppressions.apply(results);
+        let results = results
+            .into_iter()
+            .map(|result| {
+                let configured = find_rule(&result.validator_name)
+                    .and_then(|rule| self.severities.get(rule.code));
+                match configured {
+                    Some(&severity) => result.with_severity(severity),
+                    None => result,
+                }
+            })
+            .collect();
+        (keep_most_specific(results), suppressed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_chars_are_not_reported() {
+        let policy = Policy::default().allow(['\u{2014}', '\u{a9}']);
+        let results = policy.validate("a \u{2014} b \u{a9} c \u{2192}\n");
+        let columns: Vec<_> = results
+            .iter()
+            .flat_map(|r| &r.errors)
+            .map(|e| e.column)
+            .collect();
+        assert_eq!(columns, vec![Some(11)]);
+    }
+
+    #[test]
+    fn test_mdx_syntax_is_exempt() {
+        let content = "import Badge from './badge'\n\n<Badge label=\"\u{2713}\" /> caf\u{e9}\n";
+        let columns = |policy: Policy| -> Vec<_> {
+            policy
+                .validate(content)
+                .iter()
+                .flat_map(|r| &r.errors)
+                .map(|e| (e.line_number, e.column))
+                .collect()
+        };
+        // Only the prose after the tag is checked
+        let prose = (3, Some(24));
+        assert_eq!(
+            columns(Policy::default().format(DocFormat::Mdx)),
+            vec![prose]
+        );
+        assert_eq!(columns(Policy::default()).len(), 2);
+    }
+
+    #[test]
+    fn test_formats_without_frontmatter() {
+        let content = "---\ntitle: Caf\u{e9}\n---\n";
+        let policy = Policy::default().skip_frontmatter(true);
+        assert!(policy.validate(content).iter().all(|r| r.is_pass()));
+        let rst = policy.format(DocFormat::Rst);
+        assert!(rst.disabled_rules.contains(&"MC004"));
+        // rST has no frontmatter, so the block is prose
+        assert!(rst.validate(content).iter().any(|r| r.is_fail()));
+
+        let text = Policy::new(Vec::new()).format(DocFormat::Text);
+        assert_eq!(
+            text.disabled_rules,
+            vec!["MC004", "MC005", "MC007", "MC008", "MC009"]
+        );
+    }
+
+    #[test]
+    fn test_forbidden_overrides_allowed() {
+        let latin1: CharClass = "block:Latin-1 Supplement".parse().unwrap();
+        let policy = Policy::default().allow([latin1]).forbid(['\u{a0}']);
+        assert!(policy.allows('\u{e9}'));
+        assert!(!policy.allows('\u{a0}'));
+        assert!(!policy.allows('\u{2014}'));
+    }
+
+    #[test]
+    fn test_skip_code() {
+        let content = "```\n\u{251c}\n```\n`\u{e9}` \u{e9}\n";
+        assert_eq!(Policy::default().validate(content).len(), 3);
+        let results = Policy::default().skip_code(true).validate(content);
+        let locations: Vec<_> = results
+            .iter()
+            .flat_map(|r| &r.errors)
+            .map(|e| (e.line_number, e.column))
+            .collect();
+        assert_eq!(locations, vec![(4, Some(5))]);
+    }
+
+    #[test]
+    fn test_bom() {
+        let content = "\u{feff}# Title\nzero\u{feff}width\n";
+        let locations = |policy: Policy| -> Vec<_> {
+            let results = policy.validate(content);
+            let errors = results.iter().flat_map(|r| &r.errors);
+            errors.map(|e| (e.line_number, e.column)).collect()
+        };
+        assert_eq!(
+            locations(Policy::default()),
+            vec![(1, Some(1)), (2, Some(5))]
+        );
+        // Only the mark starting the file is accepted
+        assert_eq!(
+            locations(Policy::default().bom(Bom::Keep)),
+            vec![(2, Some(5))]
+        );
+    }
+
+    #[test]
+    fn test_skip_frontmatter() {
+        let content = "---\nauthor: Ren\u{e9}e\nauthor: X\n---\n\u{e9}\n";
+        let policy = Policy::new(Vec::new());
+        let locations = |results: Vec<ValidationResult>| -> Vec<_> {
+            results
+                .iter()
+                .flat_map(|r| {
+                    r.errors
+                        .iter()
+                        .map(|e| (r.validator_name.clone(), e.line_number))
+                })
+                .collect()
+        };
+        assert_eq!(locations(policy.validate(content)).len(), 3);
+        let skipping = locations(policy.skip_frontmatter(true).validate(content));
+        assert_eq!(
+            skipping,
+            vec![
+                ("ASCII Subset".to_string(), 5),
+                ("Frontmatter Syntax".to_string(), 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stop_at_skips_exempt_violations() {
+        let content = "`\u{2192}` \u{2014}\n<!-- markdown-checker-disable-next-line -->\n\u{e9}\n\u{2022} \u{2022}\n";
+        let policy = Policy::new(vec!["MC002", "MC003"])
+            .skip_code(true)
+            .allow(['\u{2014}'])
+            .stop_at(StopAt::First);
+        let results = policy.validate(content);
+        assert_eq!(results[0].errors.len(), 1);
+        assert_eq!(results[0].errors[0].line_number, 4);
+    }
+
+    #[test]
+    fn test_count_matches_validate() {
+        let content = "`\u{2192}` \u{2014}\n<!-- markdown-checker-disable-next-line MC003 -->\n\u{251c} \u{e9}\n";
+        let policy = Policy::new(Vec::new())
+            .skip_code(true)
+            .allow(['\u{2014}'])
+            .severities([("MC002", Severity::Warning)]);
+        let counts: Vec<_> = policy
+            .count(content)
+            .iter()
+            .map(|count| (count.code, count.severity, count.count))
+            .collect();
+        let validated: Vec<_> = policy
+            .validate(content)
+            .iter()
+            .map(|result| {
+                let code = find_rule(&result.validator_name).unwrap().code;
+                (code, result.severity, result.errors.len())
+            })
+            .collect();
+        assert_eq!(counts, validated);
+        assert_eq!(counts[0], ("MC001", Severity::Error, 2));
+    }
+
+    #[test]
+    fn test_allowed_characters_do_not_excuse_words() {
+        let policy = Policy::new(Vec::new()).allow(['\u{e9}']);
+        let content = "\u{e9}lan \u{e9}lan\n";
+        assert_eq!(crate::testing::violations(content, &policy), ["MC007:1:6"]);
+        let counts = policy.count(content);
+        assert!(
+            counts
+                .iter()
+                .all(|count| count.count == usize::from(count.code == "MC007"))
+        );
+    }
+
+    #[test]
+    fn test_configured_severities() {
+        let policy = Policy::default().severities([("MC003", Severity::Warning)]);
+        let results = policy.validate("\u{251c}\n");
+        let severities: Vec<_> = results.iter().map(|r| r.severity).collect();
+        assert_eq!(
+            severities,
+            vec![Severity::Error, Severity::Error, Severity::Warning]
+        );
+        assert!(!results[2].is_error());
+    }
+
+    #[test]
+    fn test_disabled_rules_are_not_run() {
+        let results = Policy::new(vec!["MC001", "MC002", "MC004", "MC007", "MC008", "MC009"])
+            .validate("\u{251c}\n");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].validator_name, "Tree Symbols");
+    }
+}
@@ -0,0 +1,158 @@
+//! Built-in configuration presets (`--preset`, `extends = "<preset>"`).
+//!
+//! A preset is a configuration file shipped with the tool. Settings from
+//! configuration files and the command line apply over it.
+
+use crate::config::ConfigFile;
+
+/// A named built-in configuration
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    /// Every rule, ASCII only (the default behavior)
+    Strict,
+    /// Also accept Latin-1 letters and symbols, but not invisible or control characters
+    Portable,
+    /// Accept the Latin and Common scripts, but not other scripts, control,
+    /// format, or space characters other than U+0020, or box drawing
+    Latin,
+    /// Markdown rules only; no character rules
+    StructureOnly,
+}
+
+const STRICT: &str = "";
+
+const PORTABLE: &str = "\
+[characters]
+allow = [\"block:Latin-1 Supplement\"]
+# C1 controls, no-break space, soft hyphen
+forbid = [\"U+0080..U+009F\", \"U+00A0\", \"U+00AD\"]
+";
+
+const LATIN: &str = "\
+[characters]
+# Latin letters, punctuation and symbols shared by every script, and
+# combining accents
+allow = [\"script:Latin\", \"script:Common\", \"script:Inherited\"]
+# Controls (Cc), tree symbols, and, as of Unicode 15, every format character
+# (Cf) and space or separator (Zs, Zl, Zp) but U+0020: soft hyphen, no-break,
+# typographic, and zero-width spaces, direction marks and isolates, invisible
+# operators, byte order mark, interlinear annotations, and tag characters
+forbid = [
+    \"U+0000..U+001F\", \"U+007F..U+009F\", \"U+00A0\", \"U+00AD\", \"U+0600..U+0605\",
+    \"U+061C\", \"U+06DD\", \"U+070F\", \"U+0890..U+0891\", \"U+08E2\", \"U+1680\", \"U+180E\",
+    \"U+2000..U+200F\", \"U+2028..U+202F\", \"U+205F..U+206F\", \"U+3000\", \"U+FEFF\",
+    \"U+FFF9..U+FFFB\", \"U+110BD\", \"U+110CD\", \"U+13430..U+1343F\", \"U+1BCA0..U+1BCA3\",
+    \"U+1D173..U+1D17A\", \"U+E0000..U+E007F\", \"block:Box Drawing\",
+]
+";
+
+const STRUCTURE_ONLY: &str = "\
+[rules]
+ascii = \"off\"
+unprintable = \"off\"
+tree-symbols = \"off\"
+";
+
+impl Preset {
+    /// Name as accepted by `--preset` and `extends`
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::Strict => "strict",
+            Preset::Portable => "portable",
+            Preset::Latin => "latin",
+            Preset::StructureOnly => "structure-only",
+        }
+    }
+
+    /// The preset called `name`
+    pub fn from_name(name: &str) -> Option<Self> {
+        <Self as clap::ValueEnum>::from_str(name, true).ok()
+    }
+
+    /// Names of every preset, for error messages
+    pub fn names() -> Vec<&'static str> {
+        <Self as clap::ValueEnum>::value_variants()
+            .iter()
+            .map(|preset| preset.name())
+            .collect()
+    }
+
+    /// The preset's settings
+    pub fn config(self) -> ConfigFile {
+        let text = match self {
+            Preset::Strict => STRICT,
+            Preset::Portable => PORTABLE,
+            Preset::Latin => LATIN,
+            Preset::StructureOnly => STRUCTURE_ONLY,
+        };
+        ConfigFile::parse(text).expect("built-in preset is valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_presets_parse() {
+        for name in Preset::names() {
+            let preset = Preset::from_name(name).unwrap();
+            assert_eq!(preset.name(), name);
+            preset.config();
+        }
+        assert_eq!(Preset::from_name("Portable"), Some(Preset::Portable));
+        assert_eq!(Preset::from_name("lenient"), None);
+    }
+
+    #[test]
+    fn test_preset_policies() {
+        let path = Path::new("README.md");
+        let strict = Preset::Strict.config().policy_for(path);
+        assert_eq!(
+            strict.disabled_rules,
+            vec!["MC004", "MC007", "MC008", "MC009"]
+        );
+        assert!(!strict.allows('\u{e9}'));
+
+        let portable = Preset::Portable.config().policy_for(path);
+        assert!(portable.allows('\u{e9}'));
+        assert!(!portable.allows('\u{a0}'));
+        assert!(!portable.allows('\u{85}'));
+        assert!(!portable.allows('\u{2014}'));
+
+        let latin = Preset::Latin.config().policy_for(path);
+        for ch in ['\u{e9}', '\u{2014}', '\u{201c}', '\u{301}', '\u{1e9e}'] {
+            assert!(latin.allows(ch), "{ch:?}");
+        }
+        for ch in [
+            '\u{3b1}', '\u{65e5}', '\u{a0}', '\u{200b}', '\u{202e}', '\u{251c}',
+        ] {
+            assert!(!latin.allows(ch), "{ch:?}");
+        }
+        assert!(latin.allows(' '));
+
+        let structure = Preset::StructureOnly.config().policy_for(path);
+        assert_eq!(
+            structure.disabled_rules,
+            vec![
+                "MC001", "MC003", "MC002", "MC004", "MC007", "MC008", "MC009"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_latin_forbids_invisible_characters() {
+        let latin = Preset::Latin.config().policy_for(Path::new("README.md"));
+        // En quad through hair space, medium mathematical and ideographic
+        // spaces, Mongolian vowel separator, word joiner, isolates, deprecated
+        // format controls, interlinear annotations, Arabic number sign and
+        // letter mark, shorthand format controls, musical formatting, and tags
+        let invisible = "\u{2000}\u{2002}\u{2009}\u{200a}\u{205f}\u{3000}\u{180e}\u{2060}\
+            \u{2066}\u{2069}\u{206f}\u{fff9}\u{fffa}\u{fffb}\u{600}\u{61c}\u{1bca0}\u{1d173}\
+            \u{e0000}\u{e0001}\u{e0041}\u{e007f}";
+        for ch in invisible.chars() {
+            assert!(!latin.allows(ch), "{ch:?}");
+        }
+    }
+}
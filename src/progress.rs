@@ -0,0 +1,137 @@
+//! Progress indicator for large batches, drawn on stderr.
+
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Batches smaller than this finish too quickly to need a progress bar
+pub const MIN_FILES: usize = 20;
+
+const BAR_WIDTH: usize = 20;
+const LINE_WIDTH: usize = 79;
+const CLEAR_LINE: &str = "\r\x1b[2K";
+
+#[derive(Debug, Default)]
+struct State {
+    done: usize,
+    violations: usize,
+}
+
+/// Progress bar updated as files finish; safe to share between workers
+#[derive(Debug)]
+pub struct Progress {
+    total: usize,
+    enabled: bool,
+    state: Mutex<State>,
+}
+
+impl Progress {
+    pub fn new(total: usize, enabled: bool) -> Self {
+        Self {
+            total,
+            enabled,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Show progress only for large batches when stderr is a terminal
+    pub fn for_batch(total: usize, quiet: bool) -> Self {
+        let enabled = !quiet && total >= MIN_FILES && std::io::stderr().is_terminal();
+        Self::new(total, enabled)
+    }
+
+    /// Record a finished file and redraw the bar
+    pub fn tick(&self, file: &Path, violations: usize) {
+        if !self.enabled {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.done += 1;
+        state.violations += violations;
+
+        let line = render_line(
+            state.done,
+            self.total,
+            state.violations,
+            &file.display().to_string(),
+        );
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "{}{}", CLEAR_LINE, line);
+        let _ = stderr.flush();
+    }
+
+    /// Erase the bar so regular output starts on a clean line
+    pub fn finish(&self) {
+        if self.enabled && self.state.lock().unwrap().done > 0 {
+            eprint!("{}", CLEAR_LINE);
+        }
+    }
+}
+
+/// Render "[=====>    ] 12/340 files, 3 violation(s)  path", truncating the
+/// path from the left so the line fits a standard terminal
+pub fn render_line(done: usize, total: usize, violations: usize, file: &str) -> String {
+    let filled = (done * BAR_WIDTH).checked_div(total).unwrap_or(BAR_WIDTH);
+    let bar = if filled >= BAR_WIDTH {
+        "=".repeat(BAR_WIDTH)
+    } else {
+        format!(
+            "{}>{}",
+            "=".repeat(filled),
+            " ".repeat(BAR_WIDTH - filled - 1)
+        )
+    };
+    let prefix = format!(
+        "[{}] {}/{} files, {} violation(s)  ",
+        bar, done, total, violations
+    );
+
+    let room = LINE_WIDTH.saturating_sub(prefix.chars().count());
+    let count = file.chars().count();
+    let file = if count <= room {
+        file.to_string()
+    } else if room > 3 {
+        let tail: String = file.chars().skip(count - (room - 3)).collect();
+        format!("...{}", tail)
+    } else {
+        String::new()
+    };
+
+    prefix + &file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_line_partial() {
+        let line = render_line(5, 20, 2, "docs/a.md");
+        assert_eq!(
+            line,
+            "[=====>              ] 5/20 files, 2 violation(s)  docs/a.md"
+        );
+    }
+
+    #[test]
+    fn test_render_line_complete() {
+        let line = render_line(20, 20, 0, "a.md");
+        assert!(line.starts_with("[====================] 20/20 files"));
+    }
+
+    #[test]
+    fn test_render_line_truncates_long_paths() {
+        let path = format!("{}/README.md", "nested".repeat(20));
+        let line = render_line(1, 100, 0, &path);
+        assert_eq!(line.chars().count(), LINE_WIDTH);
+        assert!(line.ends_with("/README.md"));
+        assert!(line.contains("..."));
+    }
+
+    #[test]
+    fn test_disabled_progress_is_silent() {
+        let progress = Progress::new(100, false);
+        progress.tick(Path::new("a.md"), 1);
+        assert_eq!(progress.state.lock().unwrap().done, 0);
+    }
+}
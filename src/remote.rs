@@ -0,0 +1,86 @@
+//! Documents and configuration fetched over HTTP(S).
+//!
+//! URLs given on the command line are checked like files, so published docs
+//! can be spot-checked without a clone, and `extends` may name a shared
+//! configuration by URL. Downloads go through `curl`, which is everywhere git
+//! is, rather than an HTTP client compiled into the binary. Each download is
+//! bounded in time and size, and may only redirect to another HTTP(S) URL.
+
+use crate::encoding::decode;
+use crate::file_ops::{DEFAULT_MAX_FILE_SIZE, FileSize};
+use std::path::Path;
+use std::process::Command;
+
+/// Longest a download may take, in seconds
+const MAX_TIME_SECS: u32 = 60;
+
+/// Whether `target` is an `http://` or `https://` URL
+pub fn is_url(target: &str) -> bool {
+    target.starts_with("https://") || target.starts_with("http://")
+}
+
+/// The URL a checked "path" stands for, if it is one
+pub fn url_of(path: &Path) -> Option<&str> {
+    path.to_str().filter(|path| is_url(path))
+}
+
+/// Download `url` with curl, refusing anything over the default file size
+pub fn fetch(url: &str) -> Result<String, String> {
+    fetch_bytes(url, Some(DEFAULT_MAX_FILE_SIZE))
+        .and_then(|bytes| decode(bytes).map_err(|e| e.to_string()))
+}
+
+/// Download the document at `url` as it is, without decoding it, failing if
+/// it is larger than `max_size`
+pub fn fetch_bytes(url: &str, max_size: Option<FileSize>) -> Result<Vec<u8>, String> {
+    let output = Command::new("curl")
+        .args(curl_args(url, max_size))
+        .output()
+        .map_err(|e| format!("cannot run curl to download it: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(output.stdout)
+}
+
+/// Arguments for a bounded curl download of `url`
+fn curl_args(url: &str, max_size: Option<FileSize>) -> Vec<String> {
+    let mut args: Vec<String> = ["--fail", "--silent", "--show-error", "--location"]
+        .map(String::from)
+        .into();
+    args.extend(["--proto".into(), "=http,https".into()]);
+    args.extend(["--proto-redir".into(), "=http,https".into()]);
+    args.extend(["--max-time".into(), MAX_TIME_SECS.to_string()]);
+    if let Some(size) = max_size {
+        args.extend(["--max-filesize".into(), size.0.to_string()]);
+    }
+    args.push(url.to_string());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_of() {
+        let url = "https://raw.githubusercontent.com/org/repo/main/README.md";
+        assert_eq!(url_of(Path::new(url)), Some(url));
+        assert_eq!(url_of(Path::new("docs/https.md")), None);
+        assert!(!is_url("ftp://example.com/a.md"));
+    }
+
+    #[test]
+    fn test_curl_args() {
+        let url = "https://example.com/a.md";
+        let args = curl_args(url, Some(FileSize(1024))).join(" ");
+        assert!(args.contains("--proto =http,https"));
+        assert!(args.contains("--max-time 60"));
+        assert!(args.ends_with("--max-filesize 1024 https://example.com/a.md"));
+        assert!(
+            !curl_args(url, None)
+                .iter()
+                .any(|arg| arg == "--max-filesize")
+        );
+    }
+}
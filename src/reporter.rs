@@ -1,68 +1,998 @@
-use crate::{ValidationResult, ValidationStatus};
+use crate::cli::{Config, GroupBy, OutputFormat, RunMode, SortOrder};
+use crate::columns::ColumnUnit;
+use crate::json_report::{JsonReporter, JsonlReporter};
+use crate::junit::JunitReporter;
+use crate::markdown_report::MarkdownReporter;
+use crate::overlap::{count_locations, merge_overlapping};
+use crate::path_display::{PathDisplay, file_url};
+use crate::remote::url_of;
+use crate::rule_groups::RuleGroups;
+use crate::sarif::SarifReporter;
+use crate::statistics::Statistics;
+use crate::style::{Palette, hyperlink};
+use crate::timings::Timings;
+use crate::validators::find_rule;
+use crate::{RuleMetadata, Severity, ValidationError, ValidationResult};
+use serde::Serialize;
+use std::path::Path;
+use std::time::Duration;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
 
-pub fn format_results(results: &[ValidationResult], file_path: &str, verbose: bool) -> String {
-    let mut output = String::new();
+/// Renders check results as they come in; implement it to plug in a custom
+/// output format. Each method returns the text to print, possibly empty.
+pub trait Reporter {
+    /// Report the results for one checked file
+    fn report_file(&mut self, file: &Path, content: &str, results: &[ValidationResult]) -> String;
 
-    if verbose {
-        output.push_str(&format!("Checking file: {}\n\n", file_path));
-        output.push_str("Running validators...\n");
-        for (i, result) in results.iter().enumerate() {
-            let status_symbol = if result.is_pass() { "✓" } else { "✗" };
-            output.push_str(&format!(
-                "[{}/{}] {}... {} {}\n",
-                i + 1,
-                results.len(),
-                result.validator_name,
-                status_symbol,
-                if result.is_pass() { "Pass" } else { "Fail" }
+    /// Report what fixing did, or would do, to one file. `results` are the
+    /// violations found before fixing. Formats that only describe checks
+    /// report nothing.
+    fn report_fix(
+        &mut self,
+        _file: &Path,
+        _content: &str,
+        _results: &[ValidationResult],
+        _outcome: &FixOutcome,
+    ) -> String {
+        String::new()
+    }
+
+    /// Report once every file has been checked
+    fn report_summary(&mut self, stats: &Statistics, elapsed: Duration) -> String;
+}
+
+/// What fixing does to one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FixStatus {
+    /// Every violation is fixable; the file is (or would be) rewritten
+    Changed,
+    /// The file has no violations
+    Unchanged,
+    /// Some violations cannot be fixed; the file is left as is
+    Unfixable,
+}
+
+/// Outcome of fixing, or previewing the fix of, one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixOutcome {
+    pub mode: RunMode,
+    pub status: FixStatus,
+    /// Characters replaced, or that would be replaced
+    pub replacements: usize,
+}
+
+impl FixOutcome {
+    /// Whether the file was written
+    pub fn applied(&self) -> bool {
+        self.mode == RunMode::Fix && self.status == FixStatus::Changed
+    }
+}
+
+/// The reporter selected by `--format` and `--group-by` for a run over
+/// `file_count` files
+pub fn reporter_for(config: &Config, file_count: usize) -> Box<dyn Reporter> {
+    let palette = Palette::new(config.color);
+    match config.format {
+        OutputFormat::Text => {
+            let mut reporter = TextReporter::new(config.verbose, config.quiet, palette)
+                .max_display(config.max_display)
+                .columns(config.columns);
+            if config.failures_only {
+                reporter = reporter.failures_only();
+            }
+            if file_count == 1 {
+                reporter = reporter.single_file();
+            }
+            if config.sort == SortOrder::Location {
+                reporter = reporter.sorted_by_location();
+            }
+            if config.hyperlinks {
+                reporter = reporter.hyperlinks(PathDisplay::new(config.paths));
+            }
+            match config.group_by {
+                GroupBy::File => Box::new(reporter),
+                GroupBy::Rule => Box::new(reporter.grouped_by_rule()),
+            }
+        }
+        OutputFormat::Compact => Box::new(CompactReporter::new().columns(config.columns)),
+        OutputFormat::Json => Box::new(JsonReporter::new().columns(config.columns)),
+        OutputFormat::Jsonl => Box::new(JsonlReporter::new().columns(config.columns)),
+        OutputFormat::Sarif => Box::new(SarifReporter::new().columns(config.columns)),
+        OutputFormat::Junit => Box::new(JunitReporter::new().columns(config.columns)),
+        OutputFormat::Markdown => Box::new(MarkdownReporter::new().columns(config.columns)),
+    }
+}
+
+/// Human-readable output: a code frame per violation and a summary table
+#[derive(Debug, Clone)]
+pub struct TextReporter {
+    verbose: bool,
+    /// Only report failing files
+    quiet: bool,
+    /// Print nothing for passing files, even with `--verbose`
+    failures_only: bool,
+    palette: Palette,
+    /// Violations collected for `--group-by rule`, reported at the end
+    groups: Option<RuleGroups>,
+    /// Most violations shown per file; `None` shows all
+    max_display: Option<usize>,
+    /// Resolves reported paths for location hyperlinks, when enabled
+    links: Option<PathDisplay>,
+    /// Announce files fixing leaves unchanged even when not verbose
+    single_file: bool,
+    /// Merge each file's violations into one list ordered by location
+    by_location: bool,
+    columns: ColumnUnit,
+}
+
+impl TextReporter {
+    pub fn new(verbose: bool, quiet: bool, palette: Palette) -> Self {
+        Self {
+            verbose,
+            quiet,
+            failures_only: false,
+            palette,
+            groups: None,
+            max_display: None,
+            links: None,
+            single_file: false,
+            by_location: false,
+            columns: ColumnUnit::Char,
+        }
+    }
+
+    /// Count columns in `columns` instead of characters
+    pub fn columns(mut self, columns: ColumnUnit) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Print nothing for files that pass, keeping the summary
+    pub fn failures_only(mut self) -> Self {
+        self.failures_only = true;
+        self
+    }
+
+    /// List each file's violations by line and column rather than per rule
+    pub fn sorted_by_location(mut self) -> Self {
+        self.by_location = true;
+        self
+    }
+
+    /// The run covers one file, so files left unchanged by fixing are
+    /// announced
+    pub fn single_file(mut self) -> Self {
+        self.single_file = true;
+        self
+    }
+
+    /// Make locations clickable hyperlinks to the files `paths` resolves
+    pub fn hyperlinks(mut self, paths: PathDisplay) -> Self {
+        self.links = Some(paths);
+        self
+    }
+
+    /// Show at most `limit` violations per file, summarizing the rest
+    pub fn max_display(mut self, limit: Option<usize>) -> Self {
+        self.max_display = limit;
+        self
+    }
+
+    /// Report every location of each rule together at the end instead of
+    /// file by file
+    pub fn grouped_by_rule(mut self) -> Self {
+        self.groups = Some(RuleGroups::new());
+        self
+    }
+
+    fn format_file(
+        &self,
+        file: &Path,
+        content: &str,
+        results: &[ValidationResult],
+        verbose: bool,
+    ) -> String {
+        let link = self.links.as_ref().map(|paths| match url_of(file) {
+            Some(url) => url.to_string(),
+            None => file_url(&paths.absolute(file)),
+        });
+        let locations = Locations {
+            link: link.as_deref(),
+            columns: self.columns,
+        };
+        let path = file.display().to_string();
+        if self.by_location && !results.iter().all(|r| r.is_pass()) {
+            let mut output = String::new();
+            if verbose {
+                output.push_str(&format_validator_progress(results, &path, &self.palette));
+            }
+            output.push_str(&format_results_by_location(
+                results,
+                content,
+                &path,
+                &self.palette,
+                self.max_display,
+                &locations,
             ));
+            return output;
         }
-        output.push('\n');
+        format_results_limited(
+            results,
+            content,
+            &path,
+            verbose,
+            &self.palette,
+            self.max_display,
+            &locations,
+        )
+    }
+}
+
+impl Reporter for TextReporter {
+    fn report_file(&mut self, file: &Path, content: &str, results: &[ValidationResult]) -> String {
+        if let Some(groups) = &mut self.groups {
+            groups.record(file, &self.columns.convert(content, results));
+            return String::new();
+        }
+        if self.quiet && !should_exit_with_error(results) {
+            return String::new();
+        }
+        if self.failures_only && results.iter().all(|r| r.is_pass()) {
+            return String::new();
+        }
+        self.format_file(file, content, results, self.verbose)
+    }
+
+    fn report_fix(
+        &mut self,
+        file: &Path,
+        content: &str,
+        results: &[ValidationResult],
+        outcome: &FixOutcome,
+    ) -> String {
+        let path = file.display();
+        match (outcome.status, outcome.mode) {
+            (FixStatus::Changed, RunMode::FixCheck) => format!("Would fix: {}\n", path),
+            (FixStatus::Changed, RunMode::DryRun) => {
+                let mut output = format!(
+                    "🔍 Dry-run mode for: {}\n   Would replace {} character(s)\n",
+                    path, outcome.replacements
+                );
+                if self.verbose {
+                    output.push_str("\nOriginal violations:\n");
+                    output.push_str(&self.format_file(file, content, results, false));
+                    output.push_str(&format!(
+                        "\n{}\n",
+                        self.palette
+                            .green("✓ After fix: All violations would be resolved")
+                    ));
+                }
+                output.push('\n');
+                output
+            }
+            (FixStatus::Changed, _) => format!(
+                "{} {} character(s) in: {}\n",
+                self.palette.green("✓ Fixed"),
+                outcome.replacements,
+                path
+            ),
+            (FixStatus::Unfixable, _) => self.format_file(file, content, results, self.verbose),
+            (FixStatus::Unchanged, mode) => {
+                if self.quiet
+                    || self.failures_only
+                    || mode == RunMode::FixCheck
+                    || !(self.verbose || self.single_file)
+                {
+                    return String::new();
+                }
+                format!(
+                    "{} {}\n",
+                    self.palette.green("✓ File validation successful:"),
+                    path
+                )
+            }
+        }
+    }
+
+    fn report_summary(&mut self, stats: &Statistics, elapsed: Duration) -> String {
+        let mut output = String::new();
+        if let Some(groups) = &self.groups
+            && !(self.quiet && groups.is_empty())
+        {
+            output.push_str(&format_rule_groups(groups, &self.palette));
+        }
+        if stats.files_checked() > 1 && !self.quiet {
+            output.push_str(&format!("\n{}", format_summary(stats, elapsed)));
+        }
+        output
+    }
+}
+
+/// One "path:line:col: CODE message" line per violation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompactReporter {
+    columns: ColumnUnit,
+}
+
+impl CompactReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count columns in `columns` instead of characters
+    pub fn columns(mut self, columns: ColumnUnit) -> Self {
+        self.columns = columns;
+        self
+    }
+}
+
+impl Reporter for CompactReporter {
+    fn report_file(&mut self, file: &Path, content: &str, results: &[ValidationResult]) -> String {
+        format_compact(
+            &self.columns.convert(content, results),
+            &file.display().to_string(),
+        )
+    }
+
+    fn report_summary(&mut self, _: &Statistics, _: Duration) -> String {
+        String::new()
+    }
+}
+
+/// How the `path:line:col` location of a code frame is written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Locations<'a> {
+    /// URL the location links to as a terminal hyperlink
+    pub link: Option<&'a str>,
+    pub columns: ColumnUnit,
+}
+
+/// Lines of source shown above and below the offending line in a code frame
+pub const CONTEXT_LINES: usize = 1;
+
+/// Violations shown per file before the rest are summarized (`--max-display`)
+pub const DEFAULT_MAX_DISPLAY: usize = 50;
+
+pub fn format_results(
+    results: &[ValidationResult],
+    content: &str,
+    file_path: &str,
+    verbose: bool,
+) -> String {
+    format_results_styled(results, content, file_path, verbose, &Palette::plain())
+}
+
+/// Format results, coloring failures red, passes green, and locations dim.
+/// Each violation is shown as a code frame of `content` around its line.
+pub fn format_results_styled(
+    results: &[ValidationResult],
+    content: &str,
+    file_path: &str,
+    verbose: bool,
+    palette: &Palette,
+) -> String {
+    format_results_limited(
+        results,
+        content,
+        file_path,
+        verbose,
+        palette,
+        None,
+        &Locations::default(),
+    )
+}
+
+/// Like [`format_results_styled`], showing at most `max_display` violations
+/// and summarizing the rest per rule on a trailing line, with locations
+/// written as `locations` describes
+pub fn format_results_limited(
+    results: &[ValidationResult],
+    content: &str,
+    file_path: &str,
+    verbose: bool,
+    palette: &Palette,
+    max_display: Option<usize>,
+    locations: &Locations,
+) -> String {
+    let mut output = String::new();
+    let status = |result: &ValidationResult| pass_fail(result, palette);
+
+    if verbose {
+        output.push_str(&format_validator_progress(results, file_path, palette));
     }
 
     let all_pass = results.iter().all(|r| r.is_pass());
 
     if all_pass {
-        output.push_str(&format!("✓ File validation successful: {}\n", file_path));
+        output.push_str(&format!(
+            "{} {}\n",
+            palette.green("✓ File validation successful:"),
+            file_path
+        ));
     } else {
-        output.push_str(&format!("✗ File validation failed: {}\n\n", file_path));
+        output.push_str(&format!(
+            "{} {}\n\n",
+            failure_header(results, palette),
+            palette.bold(file_path)
+        ));
 
+        let lines: Vec<&str> = content.lines().collect();
+        let mut remaining = max_display.unwrap_or(usize::MAX);
+        let mut hidden: Vec<(&str, usize)> = Vec::new();
         for result in results {
-            let status_symbol = if result.is_pass() { "✓" } else { "✗" };
-            let status_text = if result.is_pass() { "Pass" } else { "Fail" };
-
-            output.push_str(&format!(
-                "{}: {} {}",
-                result.validator_name, status_symbol, status_text
-            ));
+            output.push_str(&format!("{}: {}", result.validator_name, status(result)));
 
             if !result.errors.is_empty() {
                 output.push_str(&format!(" ({} errors)", result.errors.len()));
             }
             output.push('\n');
 
-            for error in &result.errors {
-                output.push_str(&format!("  {}\n", error));
+            let shown = result.errors.len().min(remaining);
+            remaining -= shown;
+            for error in &result.errors[..shown] {
+                output.push_str(&format_code_frame(
+                    error,
+                    &[&result.validator_name],
+                    result.severity,
+                    &lines,
+                    file_path,
+                    palette,
+                    locations,
+                ));
+            }
+            if shown < result.errors.len() {
+                let code = find_rule(&result.validator_name)
+                    .map_or(result.validator_name.as_str(), |r| r.code);
+                hidden.push((code, result.errors.len() - shown));
+            }
+        }
+
+        if !hidden.is_empty() {
+            output.push_str(&format_hidden(&hidden));
+        }
+    }
+
+    output
+}
+
+/// Like [`format_results_limited`] for a failing file, but with every rule's
+/// violations merged into one list ordered by line and column. A character
+/// several rules report is shown once, tagged with each rule's code.
+pub fn format_results_by_location(
+    results: &[ValidationResult],
+    content: &str,
+    file_path: &str,
+    palette: &Palette,
+    max_display: Option<usize>,
+    locations: &Locations,
+) -> String {
+    let diagnostics = merge_overlapping(results);
+    let total = count_violations(results);
+    let plural = |n: usize| if n == 1 { "" } else { "s" };
+    let mut counts = format!("{} violation{}", format_count(total), plural(total));
+    if diagnostics.len() < total {
+        counts.push_str(&format!(
+            " at {} location{}",
+            format_count(diagnostics.len()),
+            plural(diagnostics.len())
+        ));
+    }
+
+    let mut output = format!(
+        "{} {} ({})\n\n",
+        failure_header(results, palette),
+        palette.bold(file_path),
+        counts
+    );
+    let lines: Vec<&str> = content.lines().collect();
+    let shown = diagnostics.len().min(max_display.unwrap_or(usize::MAX));
+    for diagnostic in &diagnostics[..shown] {
+        output.push_str(&format_code_frame(
+            diagnostic.error,
+            &diagnostic.validators,
+            diagnostic.severity,
+            &lines,
+            file_path,
+            palette,
+            locations,
+        ));
+    }
+
+    if shown < diagnostics.len() {
+        // Hidden locations counted under the rule that would have shown them
+        let mut hidden: Vec<(&str, usize)> = Vec::new();
+        for result in results {
+            let count = diagnostics[shown..]
+                .iter()
+                .filter(|d| d.validators[0] == result.validator_name)
+                .count();
+            if count > 0 {
+                let code = find_rule(&result.validator_name)
+                    .map_or(result.validator_name.as_str(), |r| r.code);
+                hidden.push((code, count));
+            }
+        }
+        output.push_str(&format_hidden(&hidden));
+    }
+    output
+}
+
+/// The `--verbose` preamble listing each validator's outcome
+fn format_validator_progress(
+    results: &[ValidationResult],
+    file_path: &str,
+    palette: &Palette,
+) -> String {
+    let mut output = format!("Checking file: {}\n\nRunning validators...\n", file_path);
+    for (i, result) in results.iter().enumerate() {
+        output.push_str(&format!(
+            "[{}/{}] {}... {}\n",
+            i + 1,
+            results.len(),
+            result.validator_name,
+            pass_fail(result, palette)
+        ));
+    }
+    output.push('\n');
+    output
+}
+
+fn pass_fail(result: &ValidationResult, palette: &Palette) -> String {
+    if result.is_pass() {
+        palette.green("✓ Pass")
+    } else if result.is_error() {
+        palette.red("✗ Fail")
+    } else {
+        palette.yellow("⚠ Warn")
+    }
+}
+
+/// Heading for a file with violations: failed, unless they are all warnings
+fn failure_header(results: &[ValidationResult], palette: &Palette) -> String {
+    if should_exit_with_error(results) {
+        palette.red("✗ File validation failed:")
+    } else {
+        palette.yellow("⚠ File validation passed with warnings:")
+    }
+}
+
+/// "... and 1,234 more violations (MC001: 1,200, MC003: 34)"
+fn format_hidden(hidden: &[(&str, usize)]) -> String {
+    let total: usize = hidden.iter().map(|(_, count)| count).sum();
+    let per_rule: Vec<String> = hidden
+        .iter()
+        .map(|(code, count)| format!("{}: {}", code, format_count(*count)))
+        .collect();
+    format!(
+        "... and {} more violation{} ({})\n",
+        format_count(total),
+        if total == 1 { "" } else { "s" },
+        per_rule.join(", ")
+    )
+}
+
+/// Format a count with thousands separators, e.g. 1,234
+pub fn format_count(count: usize) -> String {
+    let digits = count.to_string();
+    let groups: Vec<&str> = digits
+        .as_bytes()
+        .rchunks(3)
+        .rev()
+        .map(|group| std::str::from_utf8(group).expect("digits are ASCII"))
+        .collect();
+    groups.join(",")
+}
+
+/// Format violations one per line as "path:line:col: CODE message", in
+/// line order, the form editor quickfix lists and grep-style tools parse
+pub fn format_compact(results: &[ValidationResult], file_path: &str) -> String {
+    let mut violations: Vec<(&ValidationError, &str)> = results
+        .iter()
+        .flat_map(|result| {
+            let code = find_rule(&result.validator_name)
+                .map_or(result.validator_name.as_str(), |r| r.code);
+            result.errors.iter().map(move |error| (error, code))
+        })
+        .collect();
+    violations.sort_by_key(|(error, _)| (error.line_number, error.column));
+
+    let mut output = String::new();
+    for (error, code) in violations {
+        let mut location = format!("{}:{}", file_path, error.line_number);
+        if let Some(col) = error.column {
+            location.push_str(&format!(":{}", col));
+        }
+        output.push_str(&format!("{}: {} {}\n", location, code, error.message));
+    }
+    output
+}
+
+/// Format a violation rustc-style: a "severity[CODE]: message" header, the
+/// location, and the offending line with a caret under the column
+fn format_code_frame(
+    error: &ValidationError,
+    validators: &[&str],
+    severity: Severity,
+    lines: &[&str],
+    file_path: &str,
+    palette: &Palette,
+    locations: &Locations,
+) -> String {
+    let codes: Vec<&str> = validators
+        .iter()
+        .map(|name| find_rule(name).map_or(*name, |r| r.code))
+        .collect();
+    let label = format!("{}[{}]", severity, codes.join(", "));
+    let label = match severity {
+        Severity::Error => palette.red(&label),
+        Severity::Warning => palette.yellow(&label),
+    };
+
+    let line = error.line_number;
+    let mut location = format!("{}:{}", file_path, line);
+    if let Some(col) = error.column {
+        let col = locations.columns.locate(lines, line, col);
+        location.push_str(&format!(":{}", col));
+    }
+    if let Some(url) = locations.link {
+        location = hyperlink(&location, url);
+    }
+
+    let first = line.saturating_sub(CONTEXT_LINES).max(1);
+    let last = (line + CONTEXT_LINES).min(lines.len());
+    let width = last.max(line).to_string().len();
+    let gutter = |number: &str| palette.dim(&format!("{:>width$} |", number));
+
+    let mut output = format!("{}: {}\n", label, error.message);
+    output.push_str(&format!(
+        "{}{} {}\n",
+        " ".repeat(width),
+        palette.dim("-->"),
+        location
+    ));
+    if line > lines.len() {
+        // The line is not in the content (e.g. results from another version)
+        return output + "\n";
+    }
+
+    output.push_str(&format!("{}\n", gutter("")));
+    for number in first..=last {
+        let text = display_line(lines[number - 1]);
+        // Blank lines leave no trailing space after the gutter
+        let row = format!("{} {}", gutter(&number.to_string()), text);
+        output.push_str(row.trim_end());
+        output.push('\n');
+        if number == line
+            && let Some(col) = error.column
+        {
+            let (padding, carets) = caret_marker(lines[number - 1], col);
+            output.push_str(&format!(
+                "{} {}{}\n",
+                gutter(""),
+                padding,
+                palette.red(&carets)
+            ));
+        }
+    }
+    output.push_str(&format!("{}\n", gutter("")));
+    if let Some(context) = &error.context {
+        output.push_str(&format!(
+            "{} {}\n",
+            " ".repeat(width),
+            palette.dim(&format!("= {}", context))
+        ));
+    }
+    output.push('\n');
+    output
+}
+
+/// Source line safe to print: control characters other than tab are shown
+/// as U+FFFD so they cannot affect the terminal
+fn display_line(line: &str) -> String {
+    line.chars()
+        .map(|ch| {
+            if ch.is_control() && ch != '\t' {
+                '\u{FFFD}'
+            } else {
+                ch
+            }
+        })
+        .collect()
+}
+
+/// Padding up to the grapheme cluster holding the character at a 1-based
+/// character column, and carets as wide as that cluster, accounting for tabs,
+/// double-width characters, and combining marks
+fn caret_marker(line: &str, column: usize) -> (String, String) {
+    let offset = line
+        .char_indices()
+        .nth(column.saturating_sub(1))
+        .map_or(line.len(), |(offset, _)| offset);
+    let mut padding = String::new();
+    for (start, grapheme) in line.grapheme_indices(true) {
+        if start + grapheme.len() > offset {
+            return (padding, "^".repeat(grapheme_width(grapheme).max(1)));
+        }
+        match grapheme {
+            "\t" => padding.push('\t'),
+            grapheme => padding.push_str(&" ".repeat(grapheme_width(grapheme))),
+        }
+    }
+    (padding, "^".to_string())
+}
+
+fn grapheme_width(grapheme: &str) -> usize {
+    grapheme.chars().map(char_width).sum()
+}
+
+fn char_width(ch: char) -> usize {
+    if ch.is_control() {
+        // Shown as U+FFFD by display_line
+        1
+    } else {
+        ch.width().unwrap_or(0)
+    }
+}
+
+/// Format the rule catalog as an aligned table
+pub fn format_rules(rules: &[&RuleMetadata]) -> String {
+    let key_width = rules.iter().map(|r| r.key.len()).max().unwrap_or(0).max(3);
+    let name_width = rules.iter().map(|r| r.name.len()).max().unwrap_or(0).max(4);
+
+    let mut output = format!(
+        "{:<6} {:<key_width$} {:<name_width$} {:<8} {}\n",
+        "CODE", "KEY", "NAME", "SEVERITY", "FIXABLE"
+    );
+    for rule in rules {
+        output.push_str(&format!(
+            "{:<6} {:<key_width$} {:<name_width$} {:<8} {}\n",
+            rule.code,
+            rule.key,
+            rule.name,
+            rule.default_level(),
+            if rule.fixable { "yes" } else { "no" }
+        ));
+    }
+
+    output
+}
+
+/// Format a detailed explanation of a single rule
+pub fn format_rule_explanation(rule: &RuleMetadata) -> String {
+    let mut output = format!("{}: {} ({})\n\n", rule.code, rule.name, rule.key);
+    output.push_str(&format!("{}\n\n", rule.description));
+    output.push_str(&format!("Why it matters:\n  {}\n\n", rule.rationale));
+
+    output.push_str("Examples of violations:\n");
+    for example in rule.examples {
+        output.push_str(&format!("  {}\n", example));
+    }
+    output.push('\n');
+
+    output.push_str(&format!("Fix behavior:\n  {}\n\n", rule.fix_behavior));
+    output.push_str(&format!("Default severity: {}", rule.default_severity));
+    if !rule.enabled_by_default {
+        output.push_str(&format!(
+            " (off unless turned on in [rules], e.g. {} = \"error\")",
+            rule.key
+        ));
+    }
+    output.push_str(&format!(
+        "\nAuto-fixable: {}\n",
+        if rule.fixable { "yes" } else { "no" }
+    ));
+
+    output
+}
+
+/// Format violations grouped by rule: a header per rule with its counts,
+/// then every location as "path:line:col: message"
+pub fn format_rule_groups(groups: &RuleGroups, palette: &Palette) -> String {
+    if groups.is_empty() {
+        return format!("{}\n", palette.green("✓ No violations found"));
+    }
+
+    let mut output = String::new();
+    for (rule, locations) in groups.rules() {
+        output.push_str(&format!(
+            "{} ({} violation(s) in {} file(s))\n",
+            palette.bold(rule),
+            locations.len(),
+            RuleGroups::file_count(locations)
+        ));
+        for (path, error) in locations {
+            let mut location = format!("{}:{}", path.display(), error.line_number);
+            if let Some(col) = error.column {
+                location.push_str(&format!(":{}", col));
             }
+            output.push_str(&format!(
+                "  {}: {}\n",
+                palette.dim(&location),
+                error.message
+            ));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+/// Number of files listed under "Top files" in the statistics summary
+pub const TOP_FILES: usize = 10;
+
+/// Format the end-of-run statistics: counts per rule, top files, and totals
+pub fn format_statistics(stats: &Statistics) -> String {
+    let mut output = String::from("Statistics\n");
+
+    let by_rule = stats.by_rule();
+    if !by_rule.is_empty() {
+        let width = by_rule
+            .iter()
+            .map(|(rule, _)| rule.len())
+            .max()
+            .unwrap_or(0);
+        output.push_str("  Violations by rule:\n");
+        for (rule, count) in by_rule {
+            output.push_str(&format!("    {:<width$}  {:>6}\n", rule, count));
+        }
+
+        output.push_str("  Top files:\n");
+        for (path, count) in stats.top_files(TOP_FILES) {
+            output.push_str(&format!("    {:>6}  {}\n", count, path.display()));
+        }
+    }
+
+    output.push_str(&format!(
+        "  Total: {} violation(s) in {} of {} file(s)\n",
+        stats.total_violations(),
+        stats.files_with_violations(),
+        stats.files_checked()
+    ));
+    if stats.suppressed() > 0 {
+        output.push_str(&format!(
+            "  Suppressed: {} violation(s) by inline comments\n",
+            stats.suppressed()
+        ));
+    }
+
+    output
+}
+
+/// Format the end-of-run summary table for a multi-file run: file outcomes,
+/// violations by rule, and elapsed time
+pub fn format_summary(stats: &Statistics, elapsed: Duration) -> String {
+    let by_rule = stats.by_rule();
+    let rule_width = by_rule
+        .iter()
+        .map(|(rule, _)| rule.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut output = String::from("Summary\n");
+    let mut row = |label: &str, value: String| {
+        output.push_str(&format!("  {:<14} {:>8}\n", label, value));
+    };
+    row("Files checked:", stats.files_checked().to_string());
+    row("Passed:", stats.files_passed().to_string());
+    row("Failed:", stats.files_failed().to_string());
+    if stats.files_with_warnings() > 0 {
+        // Counted as passed or fixed above
+        row("With warnings:", stats.files_with_warnings().to_string());
+    }
+    if stats.files_fixed() > 0 {
+        row("Fixed:", stats.files_fixed().to_string());
+    }
+    row("Violations:", stats.total_violations().to_string());
+    if stats.locations() < stats.total_violations() {
+        // The same character reported by several rules counts once here
+        row("Locations:", stats.locations().to_string());
+    }
+    if stats.suppressed() > 0 {
+        row("Suppressed:", stats.suppressed().to_string());
+    }
+    row("Elapsed:", format_duration(elapsed));
+
+    for (rule, count) in by_rule {
+        output.push_str(&format!("    {:<rule_width$}  {:>6}\n", rule, count));
+    }
+    output
+}
+
+/// Format the end-of-run timings: time per rule, slowest files, and totals
+pub fn format_timings(timings: &Timings) -> String {
+    let mut output = String::from("Timings\n");
+
+    let by_rule = timings.by_rule();
+    if !by_rule.is_empty() {
+        let width = by_rule
+            .iter()
+            .map(|(rule, _)| rule.len())
+            .max()
+            .unwrap_or(0);
+        output.push_str("  Validators (summed over files):\n");
+        for (rule, duration) in by_rule {
+            output.push_str(&format!(
+                "    {:<width$}  {:>10}\n",
+                rule,
+                format_duration(duration)
+            ));
+        }
+    }
+
+    let slowest = timings.slowest_files(TOP_FILES);
+    if !slowest.is_empty() {
+        output.push_str("  Slowest files:\n");
+        for (path, duration) in slowest {
+            output.push_str(&format!(
+                "    {:>10}  {}\n",
+                format_duration(duration),
+                path.display()
+            ));
         }
     }
 
+    output.push_str(&format!(
+        "  Total: {} wall clock for {} file(s)\n",
+        format_duration(timings.total()),
+        timings.files_timed()
+    ));
+
     output
 }
 
+/// Render a duration in milliseconds, or seconds from one second up
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs_f64();
+    if secs >= 1.0 {
+        format!("{:.2}s", secs)
+    } else {
+        format!("{:.3}ms", secs * 1000.0)
+    }
+}
+
 pub fn should_exit_with_error(results: &[ValidationResult]) -> bool {
-    results.iter().any(|r| r.status == ValidationStatus::Fail)
+    results.iter().any(|r| r.is_error())
+}
+
+/// Count the violations reported across all validators
+pub fn count_violations(results: &[ValidationResult]) -> usize {
+    results.iter().map(|r| r.errors.len()).sum()
+}
+
+/// Count the locations with violations of rules at error severity, which
+/// fail the run; rules reporting the same character count once
+pub fn count_error_violations(results: &[ValidationResult]) -> usize {
+    count_locations(results.iter().filter(|r| r.severity == Severity::Error))
+}
+
+/// Count the locations with violations of rules at warning severity
+pub fn count_warning_violations(results: &[ValidationResult]) -> usize {
+    count_locations(results.iter().filter(|r| r.severity == Severity::Warning))
+}
+
+/// Check whether a violation count should fail the run given an optional threshold
+pub fn exceeds_error_threshold(violation_count: usize, max_errors: Option<usize>) -> bool {
+    match max_errors {
+        Some(max) => violation_count > max,
+        None => violation_count > 0,
+    }
+}
+
+/// Check whether a warning count should fail the run; warnings only fail it
+/// past a `--max-warnings` threshold
+pub fn exceeds_warning_threshold(warning_count: usize, max_warnings: Option<usize>) -> bool {
+    max_warnings.is_some_and(|max| warning_count > max)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ValidationError;
 
     #[test]
     fn test_format_success() {
         let results = vec![ValidationResult::pass("Test Validator".to_string())];
-        let output = format_results(&results, "test.md", false);
+        let output = format_results(&results, "ok\n", "test.md", false);
         assert!(output.contains("✓"));
         assert!(output.contains("successful"));
         assert!(output.contains("test.md"));
@@ -72,7 +1002,7 @@ mod tests {
     fn test_format_failure() {
         let errors = vec![ValidationError::new(1, "Error 1".to_string())];
         let results = vec![ValidationResult::fail("Test Validator".to_string(), errors)];
-        let output = format_results(&results, "test.md", false);
+        let output = format_results(&results, "bad\n", "test.md", false);
         assert!(output.contains("✗"));
         assert!(output.contains("failed"));
         assert!(output.contains("Error 1"));
@@ -84,13 +1014,292 @@ mod tests {
             ValidationResult::pass("Validator 1".to_string()),
             ValidationResult::pass("Validator 2".to_string()),
         ];
-        let output = format_results(&results, "test.md", true);
+        let output = format_results(&results, "ok\n", "test.md", true);
         assert!(output.contains("Checking file:"));
         assert!(output.contains("[1/2]"));
         assert!(output.contains("[2/2]"));
         assert!(output.contains("Running validators"));
     }
 
+    #[test]
+    fn test_plain_output_has_no_escape_codes() {
+        let errors = vec![ValidationError::new(1, "Error 1".to_string()).with_column(2)];
+        let results = vec![ValidationResult::fail("Test Validator".to_string(), errors)];
+        let output = format_results(&results, "abc\n", "test.md", true);
+        assert!(!output.contains('\x1b'));
+        assert!(output.contains("error[Test Validator]: Error 1\n --> test.md:1:2\n"));
+    }
+
+    #[test]
+    fn test_code_frame() {
+        let content = "# Title\n\n├── src\nend\n";
+        let results = crate::validators::validate_enabled(content, &["MC001", "MC002"]);
+        let output = format_results(&results, content, "docs/a.md", false);
+        let expected = "\
+error[MC003]: Tree symbol '├' (U+251C) detected. Use '+' or '|' instead
+ --> docs/a.md:3:1
+  |
+2 |
+3 | ├── src
+  | ^
+4 | end
+  |
+
+";
+        assert!(output.contains(expected), "{}", output);
+    }
+
+    #[test]
+    fn test_failures_only_keeps_failures_and_summary() {
+        let mut reporter = TextReporter::new(true, false, Palette::plain()).failures_only();
+        let mut stats = Statistics::new();
+        for (path, content) in [("a.md", "ok\n"), ("b.md", "├\n")] {
+            let results = crate::validators::validate_all(content);
+            stats.record(Path::new(path), &results);
+            let output = reporter.report_file(Path::new(path), content, &results);
+            assert_eq!(output.is_empty(), path == "a.md", "{}", output);
+        }
+        assert!(
+            reporter
+                .report_summary(&stats, Duration::ZERO)
+                .contains("Files checked:        2")
+        );
+    }
+
+    #[test]
+    fn test_text_reporter_quiet_skips_passing_files() {
+        let mut reporter = TextReporter::new(false, true, Palette::plain());
+        let path = Path::new("a.md");
+        assert_eq!(
+            reporter.report_file(path, "ok\n", &crate::validators::validate_all("ok\n")),
+            ""
+        );
+        assert!(
+            reporter
+                .report_file(path, "├\n", &crate::validators::validate_all("├\n"))
+                .contains("error[MC003]")
+        );
+    }
+
+    #[test]
+    fn test_text_reporter_grouped_by_rule() {
+        let mut reporter = TextReporter::new(false, false, Palette::plain()).grouped_by_rule();
+        let results = crate::validators::validate_enabled("├\n", &["MC001", "MC002"]);
+        assert_eq!(reporter.report_file(Path::new("a.md"), "├\n", &results), "");
+        assert_eq!(reporter.report_file(Path::new("b.md"), "├\n", &results), "");
+
+        let mut stats = Statistics::new();
+        stats.record(Path::new("a.md"), &results);
+        stats.record(Path::new("b.md"), &results);
+        let summary = reporter.report_summary(&stats, Duration::ZERO);
+        assert!(summary.starts_with("MC003 Tree Symbols (2 violation(s) in 2 file(s))\n"));
+        assert!(summary.contains("\nSummary\n"));
+    }
+
+    #[test]
+    fn test_results_sorted_by_location() {
+        let content = "a →\n├ b\n";
+        let results = crate::validators::validate_all(content);
+        let output = format_results_by_location(
+            &results,
+            content,
+            "a.md",
+            &Palette::plain(),
+            None,
+            &Locations::default(),
+        );
+
+        assert!(
+            output.starts_with("✗ File validation failed: a.md (5 violations at 2 locations)\n\n")
+        );
+        let locations: Vec<&str> = output
+            .lines()
+            .filter_map(|line| line.strip_prefix(" --> a.md:"))
+            .collect();
+        assert_eq!(locations, vec!["1:3", "2:1"]);
+        assert!(output.contains("error[MC001, MC002]: Non-ASCII character: '→'"));
+        assert!(output.contains("error[MC003, MC001, MC002]: Tree symbol '├'"));
+        assert!(!output.contains("ASCII Subset:"));
+
+        let limited = format_results_by_location(
+            &results,
+            content,
+            "a.md",
+            &Palette::plain(),
+            Some(1),
+            &Locations::default(),
+        );
+        assert!(limited.ends_with("... and 1 more violation (MC003: 1)\n"));
+    }
+
+    #[test]
+    fn test_report_fix_outcomes() {
+        let mut reporter = TextReporter::new(false, false, Palette::plain());
+        let file = Path::new("a.md");
+        let outcome = |mode, status| FixOutcome {
+            mode,
+            status,
+            replacements: 2,
+        };
+
+        let fixed = outcome(RunMode::Fix, FixStatus::Changed);
+        assert!(fixed.applied());
+        assert_eq!(
+            reporter.report_fix(file, "", &[], &fixed),
+            "✓ Fixed 2 character(s) in: a.md\n"
+        );
+        let check = outcome(RunMode::FixCheck, FixStatus::Changed);
+        assert!(!check.applied());
+        assert_eq!(
+            reporter.report_fix(file, "", &[], &check),
+            "Would fix: a.md\n"
+        );
+        let unchanged = outcome(RunMode::Fix, FixStatus::Unchanged);
+        assert_eq!(reporter.report_fix(file, "", &[], &unchanged), "");
+        let mut reporter = reporter.single_file();
+        assert_eq!(
+            reporter.report_fix(file, "", &[], &unchanged),
+            "✓ File validation successful: a.md\n"
+        );
+    }
+
+    #[test]
+    fn test_code_frame_columns() {
+        let content = "😀 ├\n";
+        let results = crate::validators::validate_enabled(content, &["MC001", "MC002"]);
+        let mut reporter =
+            TextReporter::new(false, false, Palette::plain()).columns(ColumnUnit::Utf16);
+        let output = reporter.report_file(Path::new("a.md"), content, &results);
+        assert!(output.contains(" --> a.md:1:4\n"));
+        // The caret still points at the character
+        assert!(output.contains("1 | 😀 ├\n  |    ^\n"));
+
+        let mut compact = CompactReporter::new().columns(ColumnUnit::Byte);
+        assert_eq!(
+            compact.report_file(Path::new("a.md"), content, &results),
+            "a.md:1:6: MC003 Tree symbol '├' (U+251C) detected. Use '+' or '|' instead\n"
+        );
+    }
+
+    #[test]
+    fn test_hyperlinked_locations() {
+        let paths = PathDisplay::with_dirs(None, "/repo".into(), "/repo".into());
+        let mut reporter = TextReporter::new(false, false, Palette::plain()).hyperlinks(paths);
+        let results = crate::validators::validate_enabled("├\n", &["MC001", "MC002"]);
+        let output = reporter.report_file(Path::new("docs/a.md"), "├\n", &results);
+        assert!(output.contains(&format!(
+            " --> {}\n",
+            hyperlink("docs/a.md:1:1", "file:///repo/docs/a.md")
+        )));
+    }
+
+    #[test]
+    fn test_max_display_summarizes_hidden_violations() {
+        let content = "├├├\n";
+        let results = crate::validators::validate_all(content);
+        let output = format_results_limited(
+            &results,
+            content,
+            "a.md",
+            false,
+            &Palette::plain(),
+            Some(2),
+            &Locations::default(),
+        );
+
+        assert_eq!(output.matches("error[").count(), 2);
+        assert!(output.contains("ASCII Subset: ✗ Fail (3 errors)"));
+        assert!(output.ends_with("... and 7 more violations (MC001: 1, MC002: 3, MC003: 3)\n"));
+
+        let all = format_results_limited(
+            &results,
+            content,
+            "a.md",
+            false,
+            &Palette::plain(),
+            None,
+            &Locations::default(),
+        );
+        assert_eq!(all.matches("error[").count(), 9);
+        assert!(!all.contains("more violation"));
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1234), "1,234");
+        assert_eq!(format_count(1234567), "1,234,567");
+    }
+
+    #[test]
+    fn test_format_compact() {
+        let results = crate::validators::validate_enabled("ok\nx ├\n", &["MC002"]);
+        assert_eq!(
+            format_compact(&results, "docs/a.md"),
+            "docs/a.md:2:3: MC001 Non-ASCII character: '├' (U+251C)\n\
+             docs/a.md:2:3: MC003 Tree symbol '├' (U+251C) detected. Use '+' or '|' instead\n"
+        );
+
+        let errors = vec![ValidationError::new(4, "Error".to_string())];
+        let results = vec![ValidationResult::fail("Custom".to_string(), errors)];
+        assert_eq!(format_compact(&results, "a.md"), "a.md:4: Custom Error\n");
+        assert_eq!(format_compact(&[], "a.md"), "");
+    }
+
+    #[test]
+    fn test_caret_marker_accounts_for_width() {
+        assert_eq!(caret_marker("ab├", 3), ("  ".to_string(), "^".to_string()));
+        assert_eq!(
+            caret_marker("\t日x", 2),
+            ("\t".to_string(), "^^".to_string())
+        );
+        assert_eq!(caret_marker("日x", 2), ("  ".to_string(), "^".to_string()));
+        assert_eq!(caret_marker("", 1), (String::new(), "^".to_string()));
+    }
+
+    #[test]
+    fn test_caret_marker_counts_graphemes() {
+        // The combining accent belongs to the cluster starting at 'e'
+        let line = "e\u{301} \u{2192} x";
+        assert_eq!(caret_marker(line, 1), (String::new(), "^".to_string()));
+        assert_eq!(caret_marker(line, 2), (String::new(), "^".to_string()));
+        assert_eq!(caret_marker(line, 4), ("  ".to_string(), "^".to_string()));
+
+        let content = "e\u{301} \u{2192} x\n";
+        let results = crate::validators::validate_enabled(content, &["MC001"]);
+        let mut reporter =
+            TextReporter::new(false, false, Palette::plain()).columns(ColumnUnit::Grapheme);
+        let output = reporter.report_file(Path::new("a.md"), content, &results);
+        assert!(output.contains(" --> a.md:1:1\n"));
+        assert!(output.contains("1 | e\u{301} \u{2192} x\n  | ^\n"));
+        assert!(output.contains(" --> a.md:1:3\n"));
+        assert!(output.contains("1 | e\u{301} \u{2192} x\n  |   ^\n"));
+    }
+
+    #[test]
+    fn test_display_line_hides_control_characters() {
+        assert_eq!(display_line("a\x1b[31mb\tc"), "a\u{FFFD}[31mb\tc");
+    }
+
+    #[test]
+    fn test_styled_output_colors_failures() {
+        let errors = vec![
+            ValidationError::new(1, "Error 1".to_string()).with_context("bad line".to_string()),
+        ];
+        let results = vec![ValidationResult::fail("Test Validator".to_string(), errors)];
+        let output = format_results_styled(
+            &results,
+            "bad line\n",
+            "test.md",
+            false,
+            &Palette::new(true),
+        );
+        assert!(output.contains("\x1b[31m✗ File validation failed:\x1b[0m"));
+        assert!(output.contains("\x1b[31merror[Test Validator]\x1b[0m: Error 1"));
+        assert!(output.contains("\x1b[2m= bad line\x1b[0m"));
+    }
+
     #[test]
     fn test_should_exit_with_error_on_failure() {
         let results = vec![
@@ -100,6 +1309,22 @@ mod tests {
         assert!(should_exit_with_error(&results));
     }
 
+    #[test]
+    fn test_warnings_do_not_fail() {
+        let results: Vec<_> = crate::validators::validate_all("\u{251c}\n")
+            .into_iter()
+            .map(|r| r.with_severity(Severity::Warning))
+            .collect();
+        assert!(!should_exit_with_error(&results));
+        assert_eq!(count_error_violations(&results), 0);
+        assert_eq!(count_violations(&results), 3);
+
+        let output = format_results(&results, "\u{251c}\n", "a.md", false);
+        assert!(output.contains("⚠ File validation passed with warnings: a.md"));
+        assert!(output.contains("Tree Symbols: ⚠ Warn (1 errors)"));
+        assert!(output.contains("warning[MC003]: Tree symbol"));
+    }
+
     #[test]
     fn test_should_not_exit_on_success() {
         let results = vec![
@@ -108,4 +1333,170 @@ mod tests {
         ];
         assert!(!should_exit_with_error(&results));
     }
+
+    #[test]
+    fn test_format_rules() {
+        let rules = crate::validators::all_rules();
+        let output = format_rules(&rules);
+        assert!(output.starts_with("CODE"));
+        assert!(output.contains("MC001"));
+        assert!(output.contains("Tree Symbols"));
+        assert_eq!(output.lines().count(), rules.len() + 1);
+    }
+
+    #[test]
+    fn test_format_rule_explanation() {
+        let rule = crate::validators::find_rule("MC003").unwrap();
+        let output = format_rule_explanation(rule);
+        assert!(output.starts_with("MC003: Tree Symbols"));
+        assert!(output.contains("Why it matters:"));
+        assert!(output.contains("Examples of violations:"));
+        assert!(output.contains("Fix behavior:"));
+        assert!(output.contains("Auto-fixable: yes"));
+    }
+
+    #[test]
+    fn test_count_violations() {
+        let results = vec![
+            ValidationResult::pass("Good".to_string()),
+            ValidationResult::fail(
+                "Bad".to_string(),
+                vec![
+                    ValidationError::new(1, "Error 1".to_string()),
+                    ValidationError::new(2, "Error 2".to_string()),
+                ],
+            ),
+        ];
+        assert_eq!(count_violations(&results), 2);
+    }
+
+    #[test]
+    fn test_exceeds_error_threshold() {
+        assert!(!exceeds_error_threshold(0, None));
+        assert!(exceeds_error_threshold(1, None));
+        assert!(!exceeds_error_threshold(10, Some(10)));
+        assert!(exceeds_error_threshold(11, Some(10)));
+        assert!(exceeds_error_threshold(1, Some(0)));
+
+        // The three rules flagging one tree symbol count once
+        let results = crate::validators::validate_all("\u{251c}\n");
+        assert_eq!(count_violations(&results), 3);
+        assert_eq!(count_error_violations(&results), 1);
+    }
+
+    #[test]
+    fn test_exceeds_warning_threshold() {
+        assert!(!exceeds_warning_threshold(100, None));
+        assert!(!exceeds_warning_threshold(0, Some(0)));
+        assert!(!exceeds_warning_threshold(5, Some(5)));
+        assert!(exceeds_warning_threshold(6, Some(5)));
+
+        let results = vec![
+            ValidationResult::fail(
+                "Duplicate Words".to_string(),
+                vec![
+                    ValidationError::new(1, "a".to_string()),
+                    ValidationError::new(2, "b".to_string()),
+                ],
+            )
+            .with_severity(Severity::Warning),
+            ValidationResult::fail(
+                "ASCII Subset".to_string(),
+                vec![ValidationError::new(1, "c".to_string())],
+            ),
+        ];
+        assert_eq!(count_warning_violations(&results), 2);
+        assert_eq!(count_error_violations(&results), 1);
+    }
+
+    #[test]
+    fn test_format_rule_groups() {
+        let mut groups = RuleGroups::new();
+        groups.record(
+            std::path::Path::new("docs/a.md"),
+            &crate::validators::validate_enabled("x ├\n└\n", &["MC001", "MC002"]),
+        );
+
+        assert_eq!(
+            format_rule_groups(&groups, &Palette::plain()),
+            "MC003 Tree Symbols (2 violation(s) in 1 file(s))\n\
+             \x20 docs/a.md:1:3: Tree symbol '├' (U+251C) detected. Use '+' or '|' instead\n\
+             \x20 docs/a.md:2:1: Tree symbol '└' (U+2514) detected. Use '+' or '`' instead\n\n"
+        );
+        assert_eq!(
+            format_rule_groups(&RuleGroups::new(), &Palette::plain()),
+            "✓ No violations found\n"
+        );
+    }
+
+    #[test]
+    fn test_format_statistics() {
+        let mut stats = Statistics::new();
+        stats.record(
+            std::path::Path::new("docs/a.md"),
+            &crate::validators::validate_all("├ tree\n"),
+        );
+        stats.record(std::path::Path::new("b.md"), &[]);
+
+        let output = format_statistics(&stats);
+        assert!(output.contains("    MC003 Tree Symbols               1\n"));
+        assert!(output.contains("         3  docs/a.md\n"));
+        assert!(output.ends_with("  Total: 3 violation(s) in 1 of 2 file(s)\n"));
+    }
+
+    #[test]
+    fn test_format_statistics_without_violations() {
+        let mut stats = Statistics::new();
+        stats.record(std::path::Path::new("a.md"), &[]);
+        assert_eq!(
+            format_statistics(&stats),
+            "Statistics\n  Total: 0 violation(s) in 0 of 1 file(s)\n"
+        );
+    }
+
+    #[test]
+    fn test_format_summary() {
+        let mut stats = Statistics::new();
+        stats.record(
+            std::path::Path::new("a.md"),
+            &crate::validators::validate_enabled("├\n", &["MC002"]),
+        );
+        stats.record(std::path::Path::new("b.md"), &[]);
+        stats.record_unreadable();
+
+        assert_eq!(
+            format_summary(&stats, Duration::from_millis(12)),
+            "Summary\n\
+             \x20 Files checked:        3\n\
+             \x20 Passed:               1\n\
+             \x20 Failed:               2\n\
+             \x20 Violations:           2\n\
+             \x20 Locations:            1\n\
+             \x20 Elapsed:       12.000ms\n\
+             \x20   MC001 ASCII Subset       1\n\
+             \x20   MC003 Tree Symbols       1\n"
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.500ms");
+        assert_eq!(format_duration(Duration::from_millis(2500)), "2.50s");
+    }
+
+    #[test]
+    fn test_format_timings() {
+        let mut timings = Timings::new();
+        timings.record(
+            std::path::Path::new("docs/a.md"),
+            Duration::from_millis(3),
+            &[("Tree Symbols".to_string(), Duration::from_millis(2))],
+        );
+        timings.set_total(Duration::from_millis(5));
+
+        let output = format_timings(&timings);
+        assert!(output.contains("    MC003 Tree Symbols     2.000ms\n"));
+        assert!(output.contains("       3.000ms  docs/a.md\n"));
+        assert!(output.ends_with("  Total: 5.000ms wall clock for 1 file(s)\n"));
+    }
 }
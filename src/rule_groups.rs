@@ -0,0 +1,92 @@
+//! Violations grouped by rule across all files (`--group-by rule`).
+
+use crate::statistics::rule_label;
+use crate::{ValidationError, ValidationResult};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Every violation of each rule, with the file it was found in
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuleGroups {
+    by_rule: BTreeMap<String, Vec<(PathBuf, ValidationError)>>,
+}
+
+impl RuleGroups {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the violations found in one file
+    pub fn record(&mut self, file: &Path, results: &[ValidationResult]) {
+        for result in results.iter().filter(|r| !r.errors.is_empty()) {
+            let locations = self
+                .by_rule
+                .entry(rule_label(&result.validator_name))
+                .or_default();
+            for error in &result.errors {
+                locations.push((file.to_path_buf(), error.clone()));
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_rule.is_empty()
+    }
+
+    /// Rules with violations, ordered by rule code, each with its locations
+    /// in the order files were checked
+    pub fn rules(&self) -> Vec<(&str, &[(PathBuf, ValidationError)])> {
+        self.by_rule
+            .iter()
+            .map(|(rule, locations)| (rule.as_str(), locations.as_slice()))
+            .collect()
+    }
+
+    /// Number of distinct files violating a rule
+    pub fn file_count(locations: &[(PathBuf, ValidationError)]) -> usize {
+        locations
+            .iter()
+            .map(|(path, _)| path)
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::validate_all;
+
+    #[test]
+    fn test_groups_violations_by_rule() {
+        let mut groups = RuleGroups::new();
+        groups.record(Path::new("b.md"), &validate_all("├ x\n"));
+        groups.record(Path::new("a.md"), &validate_all("ok\n"));
+        groups.record(Path::new("c.md"), &validate_all("x\n└\n"));
+
+        let rules = groups.rules();
+        let labels: Vec<_> = rules.iter().map(|(rule, _)| *rule).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "MC001 ASCII Subset",
+                "MC002 Printable Characters",
+                "MC003 Tree Symbols"
+            ]
+        );
+
+        let (_, tree) = rules[2];
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].0, PathBuf::from("b.md"));
+        assert_eq!(tree[1].0, PathBuf::from("c.md"));
+        assert_eq!(tree[1].1.line_number, 2);
+        assert_eq!(RuleGroups::file_count(tree), 2);
+    }
+
+    #[test]
+    fn test_empty_when_everything_passes() {
+        let mut groups = RuleGroups::new();
+        groups.record(Path::new("a.md"), &validate_all("ok\n"));
+        assert!(groups.is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+//! Doc comments of Rust source files (`--rustdoc`).
+//!
+//! `///` and `//!` comments are markdown that rustdoc renders into a crate's
+//! documentation, so they pick up the same smart quotes and tree symbols as a
+//! README. Everything else in a `.rs` file is code and exempt from every rule,
+//! so only the doc comments are checked and fixed. Nothing is extracted: lines
+//! and columns are those of the source file.
+
+use crate::code_regions::CodeRegions;
+
+/// The markdown of a `///` or `//!` doc comment line, and the number of
+/// characters before it on the line
+pub fn doc_text(line: &str) -> Option<(usize, &str)> {
+    let comment = line.trim_start();
+    let text = comment
+        .strip_prefix("//!")
+        // `////` and longer are ordinary comments
+        .or_else(|| {
+            comment
+                .strip_prefix("///")
+                .filter(|text| !text.starts_with('/'))
+        })?;
+    Some((line.chars().count() - text.chars().count(), text))
+}
+
+/// Find the lines of Rust source `content` outside doc comments
+pub fn parse(content: &str) -> CodeRegions {
+    let mut parser = RustdocParser::default();
+    for line in content.lines() {
+        parser.push_line(line);
+    }
+    parser.finish()
+}
+
+/// Builds the regions outside doc comments one line at a time, so a file can
+/// be read as a stream
+#[derive(Debug, Default)]
+pub struct RustdocParser {
+    regions: CodeRegions,
+    line_number: usize,
+    /// The first line of the code since the last doc comment
+    code_start: Option<usize>,
+}
+
+impl RustdocParser {
+    /// Add the next line, without its line ending
+    pub fn push_line(&mut self, line: &str) {
+        self.line_number += 1;
+        if doc_text(line).is_none() {
+            self.code_start.get_or_insert(self.line_number);
+        } else if let Some(start) = self.code_start.take() {
+            self.regions.insert_lines(start..=self.line_number - 1);
+        }
+    }
+
+    /// The regions outside doc comments found
+    pub fn finish(mut self) -> CodeRegions {
+        if let Some(start) = self.code_start {
+            self.regions.insert_lines(start..=usize::MAX);
+        }
+        self.regions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doc_text() {
+        assert_eq!(doc_text("/// Caf\u{e9}"), Some((3, " Caf\u{e9}")));
+        assert_eq!(doc_text("    //! Crate docs"), Some((7, " Crate docs")));
+        assert_eq!(doc_text("///"), Some((3, "")));
+        assert_eq!(doc_text("//// banner"), None);
+        assert_eq!(doc_text("// comment"), None);
+        assert_eq!(doc_text("let s = \"/// \u{2014}\";"), None);
+    }
+
+    #[test]
+    fn test_only_doc_comments_are_checked() {
+        let content = "//! Crate \u{2014} docs\n\nuse std::io;\n\n/// Reads \u{201c}it\u{201d}\n\
+                       fn read() {\n    let s = \"\u{251c}\";\n}\n";
+        let regions = parse(content);
+        let covered: Vec<usize> = (1..=8).filter(|&l| regions.contains(l, None)).collect();
+        assert_eq!(covered, vec![2, 3, 4, 6, 7, 8]);
+    }
+}
@@ -0,0 +1,256 @@
+//! SARIF 2.1.0 report (`--format sarif`) for GitHub code scanning and other
+//! SARIF consumers.
+
+use crate::build_info::BuildInfo;
+use crate::columns::ColumnUnit;
+use crate::json_report::{FileReport, RuleReport, Violation};
+use crate::reporter::Reporter;
+use crate::statistics::Statistics;
+use crate::validators::all_rules;
+use crate::{RuleMetadata, Severity, ValidationResult};
+use serde_json::{Value, json};
+use std::path::Path;
+use std::time::Duration;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Render reports as a SARIF log with a single run. SARIF can only count
+/// columns in characters or UTF-16 code units; reports counting bytes or
+/// graphemes would be mislabeled, so the CLI rejects those units.
+pub fn to_sarif(reports: &[FileReport], columns: ColumnUnit) -> String {
+    let rules = all_rules();
+    let results: Vec<Value> = reports
+        .iter()
+        .flat_map(|file| {
+            let uri = artifact_uri(&file.path);
+            let rule_index = |code: &str| rules.iter().position(|r| r.code == code);
+            file.rules.iter().flat_map(move |rule| {
+                let uri = uri.clone();
+                let index = rule_index(&rule.code);
+                rule.violations
+                    .iter()
+                    .map(move |violation| result(&uri, rule, index, violation))
+            })
+        })
+        .collect();
+
+    let info = BuildInfo::current();
+    let log = json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": info.name,
+                    "version": info.version,
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules.iter().map(|r| rule_descriptor(r)).collect::<Vec<_>>(),
+                }
+            },
+            "columnKind": match columns {
+                ColumnUnit::Utf16 => "utf16CodeUnits",
+                _ => "unicodeCodePoints",
+            },
+            "artifacts": reports.iter().map(artifact).collect::<Vec<_>>(),
+            "results": results,
+        }]
+    });
+    serde_json::to_string_pretty(&log).expect("SARIF log serializes to JSON")
+}
+
+/// Collects file reports and prints them as one SARIF log at the end
+#[derive(Debug, Clone, Default)]
+pub struct SarifReporter {
+    reports: Vec<FileReport>,
+    columns: ColumnUnit,
+}
+
+impl SarifReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count columns in `columns` instead of characters
+    pub fn columns(mut self, columns: ColumnUnit) -> Self {
+        self.columns = columns;
+        self
+    }
+}
+
+impl Reporter for SarifReporter {
+    fn report_file(&mut self, file: &Path, content: &str, results: &[ValidationResult]) -> String {
+        self.reports.push(FileReport::with_columns(
+            file,
+            content,
+            results,
+            self.columns,
+        ));
+        String::new()
+    }
+
+    fn report_summary(&mut self, _: &Statistics, _: Duration) -> String {
+        to_sarif(&self.reports, self.columns) + "\n"
+    }
+}
+
+/// A checked file, with the line endings regions were counted in
+fn artifact(file: &FileReport) -> Value {
+    json!({
+        "location": { "uri": artifact_uri(&file.path) },
+        "properties": { "lineEndings": file.line_endings },
+    })
+}
+
+fn rule_descriptor(rule: &RuleMetadata) -> Value {
+    json!({
+        "id": rule.code,
+        "name": rule.key,
+        "shortDescription": { "text": rule.summary },
+        "fullDescription": { "text": rule.description },
+        "help": { "text": format!("{}\n\nFix: {}", rule.rationale, rule.fix_behavior) },
+        "defaultConfiguration": { "level": level(rule.default_severity) },
+        "properties": { "fixable": rule.fixable },
+    })
+}
+
+fn result(uri: &str, rule: &RuleReport, rule_index: Option<usize>, violation: &Violation) -> Value {
+    let mut region = json!({ "startLine": violation.line });
+    if let (Some(column), Some(end)) = (violation.column, violation.end_column) {
+        region["startColumn"] = json!(column);
+        region["endColumn"] = json!(end);
+    }
+    if let Some(span) = violation.span {
+        region["byteOffset"] = json!(span.start);
+        region["byteLength"] = json!(span.end - span.start);
+    }
+    if let Some(context) = &violation.context {
+        region["snippet"] = json!({ "text": context });
+    }
+
+    let mut result = json!({
+        "ruleId": rule.code,
+        "level": level(rule.severity),
+        "message": { "text": violation.message },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": uri },
+                "region": region,
+            }
+        }],
+    });
+    if let Some(index) = rule_index {
+        result["ruleIndex"] = json!(index);
+    }
+
+    if let (Some(replacement), Some(column), Some(end)) = (
+        violation.fix.replacement,
+        violation.column,
+        violation.end_column,
+    ) {
+        result["fixes"] = json!([{
+            "description": { "text": format!("Replace with '{}'", replacement) },
+            "artifactChanges": [{
+                "artifactLocation": { "uri": uri },
+                "replacements": [{
+                    "deletedRegion": {
+                        "startLine": violation.line,
+                        "startColumn": column,
+                        "endColumn": end,
+                    },
+                    "insertedContent": { "text": replacement },
+                }],
+            }],
+        }]);
+    }
+    result
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    }
+}
+
+/// Relative URI with forward slashes, as SARIF consumers expect
+fn artifact_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.strip_prefix("./").unwrap_or(&path).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::validate_all;
+
+    fn sarif_for(path: &str, content: &str) -> Value {
+        let report = FileReport::new(Path::new(path), content, &validate_all(content));
+        serde_json::from_str(&to_sarif(&[report], ColumnUnit::Char)).unwrap()
+    }
+
+    #[test]
+    fn test_sarif_log_structure() {
+        let log = sarif_for("./docs/a.md", "ok\n");
+        assert_eq!(log["version"], "2.1.0");
+        let driver = &log["runs"][0]["tool"]["driver"];
+        assert_eq!(driver["name"], "markdown-checker");
+        assert_eq!(driver["rules"][2]["id"], "MC003");
+        assert_eq!(driver["rules"][0]["defaultConfiguration"]["level"], "error");
+        assert_eq!(log["runs"][0]["results"], json!([]));
+        let artifact = &log["runs"][0]["artifacts"][0];
+        assert_eq!(artifact["location"]["uri"], "docs/a.md");
+        assert_eq!(artifact["properties"]["lineEndings"], "lf");
+    }
+
+    #[test]
+    fn test_sarif_results_have_regions_and_fixes() {
+        let log = sarif_for("./docs/a.md", "x ├\n");
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+
+        let tree = &results[2];
+        assert_eq!(tree["ruleId"], "MC003");
+        assert_eq!(tree["ruleIndex"], 2);
+        let location = &tree["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "docs/a.md");
+        assert_eq!(
+            location["region"],
+            json!({
+                "startLine": 1,
+                "startColumn": 3,
+                "endColumn": 4,
+                "byteOffset": 2,
+                "byteLength": 3,
+            })
+        );
+        let replacement = &tree["fixes"][0]["artifactChanges"][0]["replacements"][0];
+        assert_eq!(replacement["insertedContent"]["text"], "+");
+    }
+
+    #[test]
+    fn test_utf16_columns() {
+        let content = "😀├\n";
+        let report = FileReport::with_columns(
+            Path::new("a.md"),
+            content,
+            &validate_all(content),
+            ColumnUnit::Utf16,
+        );
+        let log: Value = serde_json::from_str(&to_sarif(&[report], ColumnUnit::Utf16)).unwrap();
+        assert_eq!(log["runs"][0]["columnKind"], "utf16CodeUnits");
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        let tree = results.iter().find(|r| r["ruleId"] == "MC003").unwrap();
+        let region = &tree["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(
+            (&region["startColumn"], &region["endColumn"]),
+            (&json!(3), &json!(4))
+        );
+    }
+
+    #[test]
+    fn test_unfixable_results_have_no_fixes() {
+        let log = sarif_for("a.md", "日\n");
+        let results = log["runs"][0]["results"].as_array().unwrap();
+        assert!(results.iter().all(|r| r.get("fixes").is_none()));
+    }
+}
@@ -0,0 +1,393 @@
+//! Scaffolding for adopting markdown-checker in a project.
+
+use crate::validators::all_rules;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Default configuration file name
+pub const CONFIG_FILE_NAME: &str = ".markdown-checker.toml";
+
+/// Generate a commented starter configuration reflecting the built-in defaults
+pub fn starter_config() -> String {
+    let mut output = String::from(
+        "# markdown-checker configuration\n\
+         #\n\
+         # Generated by `markdown-checker init`. The values below are the built-in\n\
+         # defaults; edit them to tailor checks for this project.\n\
+         # Run `markdown-checker rules` to list rules and `markdown-checker explain <code>`\n\
+         # for details on any of them.\n\n\
+         # Settings merge with .markdown-checker.toml files in parent directories,\n\
+         # the closest winning; uncomment to stop the search here.\n\
+         # root = true\n\n\
+         # Configuration these settings apply over: a built-in preset, \"strict\"\n\
+         # (the default), \"portable\" (also Latin-1), \"latin\" (the Latin and Common\n\
+         # scripts), or \"structure-only\" (no character rules), or a shared file by\n\
+         # path or URL.\n\
+         # extends = \"portable\"\n\n\
+         [rules]\n\
+         # Setting for each rule: \"error\", \"warning\" (reported, but does not fail\n\
+         # the run), or \"off\". Rules that are \"off\" here run only when turned on.\n",
+    );
+
+    let rules = all_rules();
+    let key_width = rules.iter().map(|r| r.key.len()).max().unwrap_or(0);
+    for rule in rules {
+        let assignment = format!("{} = \"{}\"", rule.key, rule.default_level());
+        output.push_str(&format!(
+            "{:<width$}  # {} {}\n",
+            assignment,
+            rule.code,
+            rule.name,
+            width = key_width + 10
+        ));
+    }
+
+    output.push_str(
+        "\n[files]\n\
+         # Glob patterns of files to check when -f/-p are not given, relative to this file.\n\
+         # include = [\"README.md\", \"docs/**/*.md\"]\n\
+         # Glob patterns of files to skip, relative to this file.\n\
+         # ignore = [\"CHANGELOG.md\", \"vendor/**\"]\n\
+         \n[per-file-ignores]\n\
+         # Rules to skip for files matching a glob pattern, relative to this file.\n\
+         # \"docs/i18n/**\" = [\"MC001\", \"MC002\"]\n\
+         \n[characters]\n\
+         # Characters no rule flags and fixing leaves alone: characters (or U+XXXX),\n\
+         # ranges (U+0370..U+03FF), Unicode blocks, and scripts. forbid wins.\n\
+         # allow = [\"U+2014\", \"block:Latin-1 Supplement\", \"script:Greek\"]\n\
+         # forbid = [\"U+00A0\"]\n\
+         # Accept any character in fenced code blocks and inline code spans.\n\
+         # skip-code = true\n\
+         # Accept any character in YAML or TOML frontmatter; the frontmatter rule\n\
+         # still checks its syntax.\n\
+         # skip-frontmatter = true\n\
+         \n[duplicate-words]\n\
+         # Words the duplicate-words rule accepts twice in a row, besides \"had\",\n\
+         # \"that\", and a few others often repeated on purpose.\n\
+         # allow = [\"very\"]\n\
+         \n[repeated-punctuation]\n\
+         # Longest run of one mark among ! ? . , ; the repeated-punctuation rule\n\
+         # accepts, and whether \"...\" is accepted anyway.\n\
+         # max-run = 1\n\
+         # allow-ellipsis = true\n\
+         \n[link-text]\n\
+         # Link text the link-text rule flags, replacing the built-in phrases\n\
+         # (\"click here\", \"here\", \"this link\", ...); raw URLs are always flagged.\n\
+         # phrases = [\"click here\", \"here\", \"this link\", \"read more\"]\n\
+         \n[output]\n\
+         # Defaults for report options not given on the command line, e.g.\n\
+         # format = \"compact\"\n\
+         # max-display = 0\n\
+         \n[fix]\n\
+         # What a plain run does: \"off\" (report), \"apply\" (--fix), or \"dry-run\".\n\
+         auto = \"off\"\n",
+    );
+
+    output
+}
+
+/// Shell script for a git pre-commit hook that checks staged markdown files
+pub fn pre_commit_hook() -> String {
+    "#!/bin/sh\n\
+     # Installed by markdown-checker: validate staged markdown files before commit.\n\
+     exec markdown-checker --staged\n"
+        .to_string()
+}
+
+/// Shell script for a git commit-msg hook that checks the commit message
+pub fn commit_msg_hook() -> String {
+    "#!/bin/sh\n\
+     # Installed by markdown-checker: validate the commit message.\n\
+     exec markdown-checker --commit-msg \"$1\"\n"
+        .to_string()
+}
+
+/// A sample file written by `generate-fixtures`
+#[derive(Debug, Clone, Copy)]
+pub struct Fixture {
+    pub name: &'static str,
+    /// What the file exercises and how a default check treats it
+    pub description: &'static str,
+    /// Codes of the rules the file violates
+    pub rules: &'static [&'static str],
+    pub content: &'static [u8],
+}
+
+/// Sample files exercising each rule, so CI wiring and editor integrations
+/// can be tried without crafting Unicode by hand
+pub const FIXTURES: &[Fixture] = &[
+    Fixture {
+        name: "valid.md",
+        description: "passes every rule",
+        rules: &[],
+        content: "# Valid\n\n\
+            Plain ASCII with \"straight quotes\", a dash - and three dots...\n\n\
+            ```text\n+-- src/\n|   +-- main.rs\n```\n"
+            .as_bytes(),
+    },
+    Fixture {
+        name: "tree-symbols.md",
+        description: "a directory tree drawn with box-drawing characters; --fix resolves it",
+        rules: &["MC003"],
+        content: "# Tree Symbols\n\n\
+            ```text\n\u{251c}\u{2500}\u{2500} src/\n\
+            \u{2502}   \u{2514}\u{2500}\u{2500} main.rs\n\
+            \u{2514}\u{2500}\u{2500} Cargo.toml\n```\n"
+            .as_bytes(),
+    },
+    Fixture {
+        name: "smart-quotes.md",
+        description: "typographic quotes, dashes, and an ellipsis; --fix resolves them",
+        rules: &["MC001"],
+        content: "# Smart Quotes\n\n\
+            \u{201c}Double\u{201d} and \u{2018}single\u{2019} quotes, an em dash \u{2014} \
+            an en dash \u{2013} and an ellipsis\u{2026}\n"
+            .as_bytes(),
+    },
+    Fixture {
+        name: "invisibles.md",
+        description: "a byte order mark, zero-width and no-break spaces, a soft hyphen, \
+            and a control character; most need fixing by hand",
+        rules: &["MC001", "MC002"],
+        content: "\u{feff}# Invisibles\n\n\
+            Zero\u{200b}width space, no-break\u{a0}space, soft\u{ad}hyphen, \
+            and a bell\u{7} character.\n"
+            .as_bytes(),
+    },
+    Fixture {
+        name: "mixed.md",
+        description: "tree symbols, quotes, accents, emoji, and CJK text; --fix resolves \
+            only some of them",
+        rules: &["MC001", "MC003"],
+        content: "# Mixed\n\n\
+            \u{201c}Caf\u{e9}\u{201d} \u{2192} na\u{ef}ve \u{2705}\n\n\
+            \u{65e5}\u{672c}\u{8a9e} \u{1f680}\n\n\
+            ```text\n\u{2514}\u{2500}\u{2500} docs/\n```\n"
+            .as_bytes(),
+    },
+    Fixture {
+        name: "frontmatter.md",
+        description: "YAML frontmatter with a duplicate key and a tab in its indentation; checked only when the opt-in \
+            frontmatter rule is on (--rule frontmatter=error)",
+        rules: &["MC004"],
+        content: b"---\ntitle: Fixture\ntitle: Duplicate\ntags:\n\t- ci\n---\n# Frontmatter\n",
+    },
+    Fixture {
+        name: "duplicate-words.md",
+        description: "words repeated by accident; checked only when the opt-in \
+            duplicate-words rule is on (--rule duplicate-words=warning)",
+        rules: &["MC007"],
+        content: "# Duplicate Words\n\nCopy the the files, then run `echo echo`.\n\
+            This is is done, and that that was intended.\n"
+            .as_bytes(),
+    },
+    Fixture {
+        name: "repeated-punctuation.md",
+        description: "exclamation marks, question marks, and dots repeated for emphasis; \
+            checked only when the opt-in repeated-punctuation rule is on \
+            (--rule repeated-punctuation=warning)",
+        rules: &["MC008"],
+        content:
+            "# Repeated Punctuation\n\n!!! note\n    This is important!!! Why does it fail??\n\n\
+            Loading.... See ../docs, or wait...\n"
+                .as_bytes(),
+    },
+    Fixture {
+        name: "link-text.md",
+        description: "links whose text is \"click here\", \"this link\", or a raw URL; \
+            checked only when the opt-in link-text rule is on (--rule link-text=warning)",
+        rules: &["MC009"],
+        content: "# Link Text\n\n[Click here](https://example.com/install) to install, \
+            or see [this link][guide].\n\n\
+            Source: [https://example.com/src](https://example.com/src)\n\n\
+            [guide]: https://example.com/guide\n"
+            .as_bytes(),
+    },
+    Fixture {
+        name: "latin1.md",
+        description: "ISO-8859-1 bytes rather than UTF-8; --fix converts the file",
+        rules: &["MC001", "MC006"],
+        content: b"# Latin-1\n\nCaf\xe9 cr\xe8me br\xfbl\xe9e\n",
+    },
+];
+
+/// Write every fixture into `dir`, creating it if needed. Returns the paths
+/// written, in [`FIXTURES`] order.
+pub fn write_fixtures(dir: &Path, force: bool) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    FIXTURES
+        .iter()
+        .map(|fixture| {
+            let path = dir.join(fixture.name);
+            write_new_file(&path, fixture.content, force)?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// Write `content` to `path`, refusing to overwrite an existing file unless `force`
+pub fn write_new_file(path: &Path, content: impl AsRef<[u8]>, force: bool) -> io::Result<()> {
+    if path.exists() && !force {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!(
+                "{} already exists (use --force to overwrite)",
+                path.display()
+            ),
+        ));
+    }
+    fs::write(path, content)
+}
+
+/// Install the pre-commit hook into the git repository at `repo_dir`
+pub fn install_pre_commit_hook(repo_dir: &Path, force: bool) -> io::Result<PathBuf> {
+    if !repo_dir.join(".git").is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} is not the root of a git repository", repo_dir.display()),
+        ));
+    }
+    write_pre_commit_hook(&repo_dir.join(".git").join("hooks"), force)
+}
+
+/// Write the pre-commit hook into `hooks_dir`, creating it if needed
+pub fn write_pre_commit_hook(hooks_dir: &Path, force: bool) -> io::Result<PathBuf> {
+    write_hook(hooks_dir, "pre-commit", &pre_commit_hook(), force)
+}
+
+/// Write the commit-msg hook into `hooks_dir`, creating it if needed
+pub fn write_commit_msg_hook(hooks_dir: &Path, force: bool) -> io::Result<PathBuf> {
+    write_hook(hooks_dir, "commit-msg", &commit_msg_hook(), force)
+}
+
+/// Write an executable hook script named `name` into `hooks_dir`
+fn write_hook(hooks_dir: &Path, name: &str, script: &str, force: bool) -> io::Result<PathBuf> {
+    fs::create_dir_all(hooks_dir)?;
+
+    let hook_path = hooks_dir.join(name);
+    write_new_file(&hook_path, script, force)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(hook_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_starter_config_lists_every_rule() {
+        let config = starter_config();
+        assert!(config.contains("[rules]"));
+        for rule in all_rules() {
+            assert!(config.contains(&format!("{} = \"{}\"", rule.key, rule.default_level())));
+            assert!(config.contains(rule.code));
+        }
+    }
+
+    #[test]
+    fn test_starter_config_is_ascii() {
+        assert!(starter_config().is_ascii());
+    }
+
+    #[test]
+    fn test_write_new_file_refuses_overwrite() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(CONFIG_FILE_NAME);
+        write_new_file(&path, "a", false).unwrap();
+
+        let err = write_new_file(&path, "b", false).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+
+        write_new_file(&path, "b", true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "b");
+    }
+
+    #[test]
+    fn test_fixtures_violate_their_rules() {
+        use crate::fixer::fix_completely;
+        use crate::policy::Policy;
+        use crate::validators::find_rule;
+
+        let every_rule = Policy::new(Vec::new());
+        for fixture in FIXTURES {
+            let Ok(content) = std::str::from_utf8(fixture.content) else {
+                assert!(fixture.rules.contains(&"MC006"), "{}", fixture.name);
+                continue;
+            };
+            let mut violated: Vec<_> = every_rule
+                .validate(content)
+                .iter()
+                .filter(|result| result.is_fail())
+                .map(|result| find_rule(&result.validator_name).unwrap().code)
+                .collect();
+            violated.sort();
+            assert_eq!(violated, fixture.rules, "{}", fixture.name);
+        }
+
+        let fixable = |name| {
+            let fixture = FIXTURES.iter().find(|f| f.name == name).unwrap();
+            let content = std::str::from_utf8(fixture.content).unwrap();
+            fix_completely(content, &Policy::default()).is_some()
+        };
+        assert!(fixable("tree-symbols.md"));
+        assert!(fixable("smart-quotes.md"));
+        assert!(!fixable("invisibles.md"));
+        assert!(!fixable("mixed.md"));
+    }
+
+    #[test]
+    fn test_write_fixtures() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("fixtures");
+        let written = write_fixtures(&target, false).unwrap();
+        assert_eq!(written.len(), FIXTURES.len());
+        assert_eq!(fs::read(&written[0]).unwrap(), FIXTURES[0].content);
+
+        let err = write_fixtures(&target, false).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        write_fixtures(&target, true).unwrap();
+    }
+
+    #[test]
+    fn test_install_hook_requires_git_repo() {
+        let dir = TempDir::new().unwrap();
+        let err = install_pre_commit_hook(dir.path(), false).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_install_hook() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join(".git")).unwrap();
+
+        let hook = install_pre_commit_hook(dir.path(), false).unwrap();
+        let content = fs::read_to_string(&hook).unwrap();
+        assert!(content.contains("markdown-checker --staged"));
+    }
+
+    #[test]
+    fn test_write_hook_creates_hooks_dir() {
+        let dir = TempDir::new().unwrap();
+        let hooks_dir = dir.path().join("custom-hooks");
+
+        let hook = write_pre_commit_hook(&hooks_dir, false).unwrap();
+        assert_eq!(hook, hooks_dir.join("pre-commit"));
+
+        let err = write_pre_commit_hook(&hooks_dir, false).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        write_pre_commit_hook(&hooks_dir, true).unwrap();
+
+        let hook = write_commit_msg_hook(&hooks_dir, false).unwrap();
+        assert_eq!(hook, hooks_dir.join("commit-msg"));
+        let content = fs::read_to_string(&hook).unwrap();
+        assert!(content.contains("markdown-checker --commit-msg \"$1\""));
+    }
+}
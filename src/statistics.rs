@@ -0,0 +1,208 @@
+//! Violation statistics aggregated across a run.
+
+use crate::ValidationResult;
+use crate::overlap::count_locations;
+use crate::validators::find_rule;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Violation counts per rule and per file, and file outcomes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Statistics {
+    files_checked: usize,
+    files_unreadable: usize,
+    /// Files with violations of rules at error severity
+    failing: BTreeSet<PathBuf>,
+    /// Files whose violations were fixed in place
+    fixed: BTreeSet<PathBuf>,
+    /// Distinct locations with violations; see [`count_locations`]
+    locations: usize,
+    /// Violations hidden by inline suppression comments
+    suppressed: usize,
+    by_rule: BTreeMap<String, usize>,
+    by_file: BTreeMap<PathBuf, usize>,
+}
+
+impl Statistics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the results for one checked file
+    pub fn record(&mut self, file: &Path, results: &[ValidationResult]) {
+        self.files_checked += 1;
+        self.locations += count_locations(results);
+        for result in results.iter().filter(|r| !r.errors.is_empty()) {
+            *self
+                .by_rule
+                .entry(rule_label(&result.validator_name))
+                .or_insert(0) += result.errors.len();
+            *self.by_file.entry(file.to_path_buf()).or_insert(0) += result.errors.len();
+        }
+        if results.iter().any(|r| r.is_error()) {
+            self.failing.insert(file.to_path_buf());
+        }
+    }
+
+    /// Count a file that could not be read or checked
+    pub fn record_unreadable(&mut self) {
+        self.files_checked += 1;
+        self.files_unreadable += 1;
+    }
+
+    /// Count violations hidden by inline suppression comments
+    pub fn record_suppressed(&mut self, count: usize) {
+        self.suppressed += count;
+    }
+
+    /// Count a recorded file whose violations were fixed in place
+    pub fn record_fixed(&mut self, file: &Path) {
+        self.fixed.insert(file.to_path_buf());
+    }
+
+    pub fn files_checked(&self) -> usize {
+        self.files_checked
+    }
+
+    /// Files checked without violations, or with only warnings left unfixed
+    pub fn files_passed(&self) -> usize {
+        self.files_checked - self.files_unreadable - self.files_failed_checks() - self.fixed.len()
+    }
+
+    /// Files with error violations left unfixed, or that could not be checked
+    pub fn files_failed(&self) -> usize {
+        self.files_failed_checks() + self.files_unreadable
+    }
+
+    /// Files with only warnings, whether or not they were fixed
+    pub fn files_with_warnings(&self) -> usize {
+        self.files_with_violations() - self.failing.len()
+    }
+
+    pub fn files_fixed(&self) -> usize {
+        self.fixed.len()
+    }
+
+    fn files_failed_checks(&self) -> usize {
+        self.failing.difference(&self.fixed).count()
+    }
+
+    /// Number of files with at least one violation
+    pub fn files_with_violations(&self) -> usize {
+        self.by_file.len()
+    }
+
+    pub fn total_violations(&self) -> usize {
+        self.by_rule.values().sum()
+    }
+
+    /// Violations counting a character reported by several rules once
+    pub fn locations(&self) -> usize {
+        self.locations
+    }
+
+    pub fn suppressed(&self) -> usize {
+        self.suppressed
+    }
+
+    /// Violation counts per rule, ordered by rule code
+    pub fn by_rule(&self) -> Vec<(&str, usize)> {
+        self.by_rule
+            .iter()
+            .map(|(rule, count)| (rule.as_str(), *count))
+            .collect()
+    }
+
+    /// Files with the most violations, highest first (ties by path)
+    pub fn top_files(&self, limit: usize) -> Vec<(&Path, usize)> {
+        let mut files: Vec<(&Path, usize)> = self
+            .by_file
+            .iter()
+            .map(|(path, count)| (path.as_path(), *count))
+            .collect();
+        files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        files.truncate(limit);
+        files
+    }
+}
+
+/// "MC001 ASCII Subset", or the validator name for rules without metadata
+pub(crate) fn rule_label(validator_name: &str) -> String {
+    match find_rule(validator_name) {
+        Some(rule) => format!("{} {}", rule.code, rule.name),
+        None => validator_name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::validate_all;
+
+    #[test]
+    fn test_counts_per_rule_and_file() {
+        let mut stats = Statistics::new();
+        stats.record(Path::new("a.md"), &validate_all("café\n"));
+        stats.record(Path::new("b.md"), &validate_all("├ café\n"));
+        stats.record(Path::new("c.md"), &validate_all("ok\n"));
+
+        assert_eq!(stats.files_checked(), 3);
+        assert_eq!(stats.files_with_violations(), 2);
+        assert_eq!(
+            stats.by_rule(),
+            vec![
+                ("MC001 ASCII Subset", 3),
+                ("MC002 Printable Characters", 3),
+                ("MC003 Tree Symbols", 1),
+            ]
+        );
+        assert_eq!(stats.total_violations(), 7);
+        assert_eq!(stats.locations(), 3);
+    }
+
+    #[test]
+    fn test_file_outcomes() {
+        let mut stats = Statistics::new();
+        stats.record(Path::new("a.md"), &validate_all("ok\n"));
+        stats.record(Path::new("b.md"), &validate_all("├\n"));
+        stats.record(Path::new("c.md"), &validate_all("├\n"));
+        stats.record_fixed(Path::new("c.md"));
+        stats.record_unreadable();
+
+        assert_eq!(stats.files_checked(), 4);
+        assert_eq!(stats.files_passed(), 1);
+        assert_eq!(stats.files_fixed(), 1);
+        assert_eq!(stats.files_failed(), 2);
+    }
+
+    #[test]
+    fn test_warnings_only_files_pass() {
+        let warn = |content: &str| -> Vec<ValidationResult> {
+            validate_all(content)
+                .into_iter()
+                .map(|r| r.with_severity(crate::Severity::Warning))
+                .collect()
+        };
+        let mut stats = Statistics::new();
+        stats.record(Path::new("a.md"), &warn("├\n"));
+        stats.record(Path::new("b.md"), &warn("├\n"));
+        stats.record_fixed(Path::new("b.md"));
+        stats.record(Path::new("c.md"), &validate_all("├\n"));
+
+        assert_eq!(stats.files_with_warnings(), 2);
+        assert_eq!(stats.files_passed(), 1);
+        assert_eq!(stats.files_fixed(), 1);
+        assert_eq!(stats.files_failed(), 1);
+    }
+
+    #[test]
+    fn test_top_files_ordering() {
+        let mut stats = Statistics::new();
+        stats.record(Path::new("b.md"), &validate_all("é\n"));
+        stats.record(Path::new("a.md"), &validate_all("é\n"));
+        stats.record(Path::new("c.md"), &validate_all("é é\n"));
+
+        let top = stats.top_files(2);
+        assert_eq!(top, vec![(Path::new("c.md"), 4), (Path::new("a.md"), 2)]);
+    }
+}
@@ -0,0 +1,217 @@
+//! Validate files too large to hold in memory, one line at a time.
+//!
+//! Only the character and prose rules, code regions, and suppression comments
+//! are needed line by line; the frontmatter block is the one part of a file
+//! kept whole, and only while it is open.
+
+use crate::code_regions::CodeRegionsParser;
+use crate::commit_msg::CommitMessageParser;
+use crate::doc_format::DocFormat;
+use crate::encoding::BOM;
+use crate::frontmatter::{Frontmatter, FrontmatterFormat};
+use crate::mdx::MdxParser;
+use crate::policy::Policy;
+use crate::rustdoc::RustdocParser;
+use crate::suppressions::SuppressionsParser;
+use crate::validators::prose::ProseReader;
+use crate::validators::scan::Scanner;
+use crate::validators::{FrontmatterValidator, enabled_char_rules, enabled_prose_rules};
+use crate::{ValidationResult, Validator};
+use std::io::{self, BufRead};
+
+/// Files larger than this many bytes are streamed instead of read whole
+pub const STREAM_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+/// A frontmatter block longer than this many lines is not treated as one
+const MAX_FRONTMATTER_LINES: usize = 10_000;
+
+/// Run the policy's enabled rules over `reader` line by line and drop the
+/// violations it exempts, as [`Policy::filter`] does for content in memory.
+/// Returns the filtered results and the number suppressed by comments.
+pub fn validate_stream(
+    mut reader: impl BufRead,
+    policy: &Policy,
+) -> io::Result<(Vec<ValidationResult>, usize)> {
+    let rules = enabled_char_rules(&policy.disabled_rules);
+    let allowed = |ch: char| policy.allows(ch);
+    let mut scanner = Scanner::new(&rules);
+    if !policy.allowed.is_empty() {
+        scanner = scanner.skipping(&allowed);
+    }
+    let mut code = policy
+        .skip_code
+        .then(|| CodeRegionsParser::new(policy.format));
+    let mut mdx = (policy.format == DocFormat::Mdx).then(MdxParser::default);
+    let mut commit_msg =
+        (policy.format == DocFormat::CommitMessage).then(CommitMessageParser::default);
+    let mut rustdoc = (policy.format == DocFormat::Rustdoc).then(RustdocParser::default);
+    let prose_rules = enabled_prose_rules(&policy.disabled_rules);
+    let mut prose =
+        (!prose_rules.is_empty()).then(|| ProseReader::new(&prose_rules, policy.format));
+    let mut suppressions = SuppressionsParser::default();
+    let mut head = Head::default();
+    let mut leading_bom = None;
+
+    let mut buffer = String::new();
+    while reader.read_line(&mut buffer)? > 0 {
+        let line = buffer.strip_suffix('\n').unwrap_or(&buffer);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        scanner.push_line(line);
+        leading_bom.get_or_insert(line.starts_with(BOM));
+        if let Some(code) = code.as_mut() {
+            code.push_line(line);
+        }
+        if let Some(mdx) = mdx.as_mut() {
+            mdx.push_line(line);
+        }
+        if let Some(commit_msg) = commit_msg.as_mut() {
+            commit_msg.push_line(line);
+        }
+        if let Some(rustdoc) = rustdoc.as_mut() {
+            rustdoc.push_line(line);
+        }
+        if let Some(prose) = prose.as_mut() {
+            prose.push_line(line, &policy.options);
+        }
+        suppressions.push_line(line);
+        head.push_line(line);
+        buffer.clear();
+    }
+
+    let mut results = scanner.finish();
+    let mut exempt = code.map(CodeRegionsParser::finish).unwrap_or_default();
+    if let Some(mdx) = mdx {
+        exempt.merge(mdx.finish());
+    }
+    if let Some(commit_msg) = commit_msg {
+        exempt.merge(commit_msg.finish());
+    }
+    if let Some(rustdoc) = rustdoc {
+        exempt.merge(rustdoc.finish());
+    }
+    if policy.bom.accepts() && leading_bom == Some(true) {
+        exempt.insert_span(1, 1..=1);
+    }
+    let frontmatter = head.content;
+    if let Some(found) = Frontmatter::find(&frontmatter)
+        .filter(|_| policy.skip_frontmatter && policy.format.has_frontmatter())
+    {
+        exempt.insert_lines(found.lines);
+    }
+    if !policy
+        .disabled_rules
+        .contains(&FrontmatterValidator::METADATA.code)
+    {
+        let lines: Vec<&str> = frontmatter.lines().collect();
+        let result = FrontmatterValidator.validate(&frontmatter);
+        results.extend(policy.drop_allowed(&lines, vec![result]));
+    }
+    results.extend(prose.map(ProseReader::finish).unwrap_or_default());
+    Ok(policy.apply(results, &exempt, &suppressions.finish()))
+}
+
+/// Lines from the start of a file while they may be frontmatter
+#[derive(Debug, Default)]
+struct Head {
+    content: String,
+    lines: usize,
+    format: Option<FrontmatterFormat>,
+    done: bool,
+}
+
+impl Head {
+    fn push_line(&mut self, line: &str) {
+        if self.done {
+            return;
+        }
+        match self.format {
+            None => match FrontmatterFormat::opened_by(line) {
+                Some(format) => self.format = Some(format),
+                None => {
+                    self.done = true;
+                    return;
+                }
+            },
+            Some(format) => self.done = format.closed_by(line),
+        }
+        self.lines += 1;
+        if self.lines > MAX_FRONTMATTER_LINES {
+            self.content = String::new();
+            self.done = true;
+            return;
+        }
+        self.content.push_str(line);
+        self.content.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Severity;
+    use crate::charset::CharClass;
+    use crate::encoding::Bom;
+
+    fn streamed(content: &str, policy: &Policy) -> (Vec<ValidationResult>, usize) {
+        validate_stream(content.as_bytes(), policy).unwrap()
+    }
+
+    fn in_memory(content: &str, policy: &Policy) -> (Vec<ValidationResult>, usize) {
+        let results = policy
+            .validate_timed(content)
+            .into_iter()
+            .map(|(result, _)| result)
+            .collect();
+        policy.filter(content, results)
+    }
+
+    #[test]
+    fn test_stream_matches_in_memory() {
+        let content = "---\ntitle: Caf\u{e9}\ntitle: again\n---\n\
+            # Title \u{2192}\r\n\
+            ```\n\u{251c}\u{2500}\u{2500} code\n```\n\
+            Inline `\u{2014}` and \u{2014}, the the `x x`!! [here](x)\n\
+            ```\nthe the\n```\n\
+            <!-- markdown-checker-disable-next-line MC001 -->\n\
+            caf\u{e9} \u{1}\n\
+            <Note title=\"\u{2192}\">{\"\u{2014}\"}</Note>\n";
+        let policies = [
+            Policy::default(),
+            Policy::new(Vec::new()),
+            Policy::new(Vec::new())
+                .skip_code(true)
+                .skip_frontmatter(true)
+                .allow([CharClass::Range('\u{2192}', '\u{2192}')])
+                .severities([("MC002", Severity::Warning)]),
+            Policy::default().format(DocFormat::Mdx),
+            Policy::new(Vec::new())
+                .skip_code(true)
+                .format(DocFormat::AsciiDoc),
+            Policy::new(Vec::new())
+                .skip_code(true)
+                .format(DocFormat::Rst),
+            Policy::default().format(DocFormat::CommitMessage),
+            Policy::default().format(DocFormat::Rustdoc),
+        ];
+        for policy in &policies {
+            assert_eq!(streamed(content, policy), in_memory(content, policy));
+        }
+    }
+
+    #[test]
+    fn test_leading_bom() {
+        let content = "\u{feff}# Title \u{2192}\n\u{feff}\n";
+        for policy in [Policy::default(), Policy::default().bom(Bom::Keep)] {
+            assert_eq!(streamed(content, &policy), in_memory(content, &policy));
+        }
+    }
+
+    #[test]
+    fn test_unclosed_toml_frontmatter() {
+        let content = "+++\ntitle = \"x\"\n";
+        let policy = Policy::new(Vec::new());
+        let (results, _) = streamed(content, &policy);
+        assert_eq!(results, in_memory(content, &policy).0);
+        assert!(results.iter().any(|result| result.is_fail()));
+    }
+}
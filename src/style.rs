@@ -0,0 +1,138 @@
+//! ANSI color styling for terminal output.
+
+use std::io::IsTerminal;
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+/// When to emit ANSI colors
+#[derive(
+    clap::ValueEnum,
+    serde::Deserialize,
+    schemars::JsonSchema,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorChoice {
+    /// Color when writing to a terminal and NO_COLOR is not set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Decide whether to color output, honoring the NO_COLOR convention in auto mode
+    pub fn should_color(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+                !no_color && std::io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+/// Applies colors to text when enabled, or passes it through unchanged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Palette {
+    enabled: bool,
+}
+
+impl Palette {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// A palette that never emits escape codes
+    pub fn plain() -> Self {
+        Self::new(false)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.enabled {
+            format!("{}{}{}", code, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Failures and errors
+    pub fn red(&self, text: &str) -> String {
+        self.paint(RED, text)
+    }
+
+    /// Passes and successful fixes
+    pub fn green(&self, text: &str) -> String {
+        self.paint(GREEN, text)
+    }
+
+    /// Warnings and notices
+    pub fn yellow(&self, text: &str) -> String {
+        self.paint(YELLOW, text)
+    }
+
+    /// Headings and emphasis
+    pub fn bold(&self, text: &str) -> String {
+        self.paint(BOLD, text)
+    }
+
+    /// Secondary details such as locations and context
+    pub fn dim(&self, text: &str) -> String {
+        self.paint(DIM, text)
+    }
+}
+
+/// Wrap text in an OSC 8 terminal hyperlink to `url`
+pub fn hyperlink(text: &str, url: &str) -> String {
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hyperlink() {
+        assert_eq!(
+            hyperlink("a.md:1:2", "file:///a.md"),
+            "\x1b]8;;file:///a.md\x1b\\a.md:1:2\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_plain_palette_passes_text_through() {
+        let palette = Palette::plain();
+        assert_eq!(palette.red("fail"), "fail");
+        assert_eq!(palette.green("pass"), "pass");
+        assert_eq!(palette.dim("context"), "context");
+    }
+
+    #[test]
+    fn test_enabled_palette_wraps_text() {
+        let palette = Palette::new(true);
+        assert_eq!(palette.red("fail"), "\x1b[31mfail\x1b[0m");
+        assert_eq!(palette.green("pass"), "\x1b[32mpass\x1b[0m");
+    }
+
+    #[test]
+    fn test_explicit_color_choices() {
+        assert!(ColorChoice::Always.should_color());
+        assert!(!ColorChoice::Never.should_color());
+    }
+}
@@ -0,0 +1,382 @@
+//! Inline suppression comments.
+//!
+//! `<!-- markdown-checker-disable-next-line MC001 -->` exempts the following
+//! line from the listed rules (codes or keys, separated by spaces or commas),
+//! or from every rule when none are listed.
+//!
+//! `<!-- markdown-checker-disable -->` exempts the lines from the comment up to
+//! a matching `<!-- markdown-checker-enable -->` or the end of the file. Both
+//! take optional rule lists, so a region can exempt a few rules, or exempt
+//! every rule and then re-enable some.
+//!
+//! MDX has no HTML comments, so there the directives are written as
+//! expression comments: `{/* markdown-checker-disable-next-line MC001 */}`.
+//! AsciiDoc and reStructuredText files use their line comments,
+//! `// markdown-checker-disable` and `.. markdown-checker-disable`.
+
+use crate::ValidationResult;
+use crate::validators::find_rule;
+use std::ops::RangeInclusive;
+
+const DISABLE_NEXT_LINE: &str = "markdown-checker-disable-next-line";
+const DISABLE: &str = "markdown-checker-disable";
+const ENABLE: &str = "markdown-checker-enable";
+
+/// Lines exempted from some or all rules
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Suppressions {
+    entries: Vec<Suppression>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Suppression {
+    /// 1-based line numbers
+    lines: RangeInclusive<usize>,
+    rules: RuleSet,
+}
+
+/// Rules a suppression applies to
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RuleSet {
+    Only(Vec<&'static str>),
+    AllExcept(Vec<&'static str>),
+}
+
+impl RuleSet {
+    const NONE: RuleSet = RuleSet::Only(Vec::new());
+
+    /// Every rule for `None`, else the listed rules
+    fn from_list(rules: Option<Vec<&'static str>>) -> Self {
+        match rules {
+            None => RuleSet::AllExcept(Vec::new()),
+            Some(rules) => RuleSet::Only(rules),
+        }
+    }
+
+    fn contains(&self, code: Option<&str>) -> bool {
+        match (self, code) {
+            (RuleSet::Only(rules), Some(code)) => rules.contains(&code),
+            (RuleSet::Only(_), None) => false,
+            (RuleSet::AllExcept(rules), Some(code)) => !rules.contains(&code),
+            (RuleSet::AllExcept(_), None) => true,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        *self == RuleSet::NONE
+    }
+
+    /// Add `rules` (every rule for `None`)
+    fn disable(self, rules: Option<Vec<&'static str>>) -> Self {
+        match (self, rules) {
+            (_, None) => RuleSet::from_list(None),
+            (RuleSet::Only(current), Some(rules)) => RuleSet::Only(union(current, rules)),
+            (RuleSet::AllExcept(mut except), Some(rules)) => {
+                except.retain(|r| !rules.contains(r));
+                RuleSet::AllExcept(except)
+            }
+        }
+    }
+
+    /// Remove `rules` (every rule for `None`)
+    fn enable(self, rules: Option<Vec<&'static str>>) -> Self {
+        match (self, rules) {
+            (_, None) => RuleSet::NONE,
+            (RuleSet::Only(mut current), Some(rules)) => {
+                current.retain(|r| !rules.contains(r));
+                RuleSet::Only(current)
+            }
+            (RuleSet::AllExcept(except), Some(rules)) => RuleSet::AllExcept(union(except, rules)),
+        }
+    }
+}
+
+impl Suppressions {
+    /// Find the suppression comments in `content`
+    pub fn parse(content: &str) -> Self {
+        let mut parser = SuppressionsParser::default();
+        for line in content.lines() {
+            parser.push_line(line);
+        }
+        parser.finish()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether the rule with `code` is suppressed on `line`
+    pub fn is_suppressed(&self, line: usize, code: Option<&str>) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.lines.contains(&line) && entry.rules.contains(code))
+    }
+
+    /// Whether any rule is suppressed on `line`
+    pub fn covers_line(&self, line: usize) -> bool {
+        self.entries.iter().any(|entry| entry.lines.contains(&line))
+    }
+
+    /// Drop suppressed violations, passing results with none left.
+    /// Returns the filtered results and the number of suppressed violations.
+    pub fn apply(&self, results: Vec<ValidationResult>) -> (Vec<ValidationResult>, usize) {
+        if self.is_empty() {
+            return (results, 0);
+        }
+        let mut suppressed = 0;
+        let results = results
+            .into_iter()
+            .map(|result| {
+                let code = find_rule(&result.validator_name).map(|rule| rule.code);
+                result.retain_errors(|error| {
+                    let hide = self.is_suppressed(error.line_number, code);
+                    suppressed += usize::from(hide);
+                    !hide
+                })
+            })
+            .collect();
+        (results, suppressed)
+    }
+}
+
+/// Builds [`Suppressions`] one line at a time, so a file can be read as a
+/// stream
+#[derive(Debug)]
+pub struct SuppressionsParser {
+    entries: Vec<Suppression>,
+    /// Rules disabled by the open region, and the line it starts on
+    region: (RuleSet, usize),
+    /// 0-based index of the next line
+    index: usize,
+}
+
+impl Default for SuppressionsParser {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            region: (RuleSet::NONE, 1),
+            index: 0,
+        }
+    }
+}
+
+impl SuppressionsParser {
+    /// Add the next line, without its line ending
+    pub fn push_line(&mut self, line: &str) {
+        let index = self.index;
+        self.index += 1;
+        let next = index + 2;
+        for directive in comments(line) {
+            if let Some(rules) = strip_directive(directive, DISABLE_NEXT_LINE) {
+                self.entries.push(Suppression {
+                    lines: next..=next,
+                    rules: RuleSet::from_list(parse_rules(rules)),
+                });
+                continue;
+            }
+            let (rules, disable) = match (
+                strip_directive(directive, DISABLE),
+                strip_directive(directive, ENABLE),
+            ) {
+                (Some(rules), _) => (rules, true),
+                (None, Some(rules)) => (rules, false),
+                (None, None) => continue,
+            };
+            // Regions change on the comment's own line
+            let (current, start) = std::mem::replace(&mut self.region, (RuleSet::NONE, 1));
+            if !current.is_empty() && start <= index {
+                self.entries.push(Suppression {
+                    lines: start..=index,
+                    rules: current.clone(),
+                });
+            }
+            let rules = parse_rules(rules);
+            let updated = if disable {
+                current.disable(rules)
+            } else {
+                current.enable(rules)
+            };
+            self.region = (updated, index + 1);
+        }
+    }
+
+    /// The suppressions found; a region left open runs to the end of the file
+    pub fn finish(mut self) -> Suppressions {
+        let (current, start) = self.region;
+        if !current.is_empty() {
+            self.entries.push(Suppression {
+                lines: start..=usize::MAX,
+                rules: current,
+            });
+        }
+        Suppressions {
+            entries: self.entries,
+        }
+    }
+}
+
+fn union(mut rules: Vec<&'static str>, more: Vec<&'static str>) -> Vec<&'static str> {
+    for rule in more {
+        if !rules.contains(&rule) {
+            rules.push(rule);
+        }
+    }
+    rules
+}
+
+/// Text inside each `<!-- ... -->` comment on a line, then inside each MDX
+/// `{/* ... */}` comment, then of an AsciiDoc or rST line comment
+fn comments(line: &str) -> impl Iterator<Item = &str> {
+    delimited(line, "<!--", "-->")
+        .chain(delimited(line, "{/*", "*/}"))
+        .chain(line_comment(line))
+}
+
+/// Text of a `// ...` (AsciiDoc) or `.. ...` (rST) comment filling the line
+fn line_comment(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let rest = match line.strip_prefix("//") {
+        // `////` delimits a comment block rather than commenting a line
+        Some(rest) if !rest.starts_with('/') => rest,
+        _ => line.strip_prefix("..")?,
+    };
+    rest.starts_with(char::is_whitespace).then(|| rest.trim())
+}
+
+fn delimited<'a>(
+    line: &'a str,
+    open: &'static str,
+    close: &'static str,
+) -> impl Iterator<Item = &'a str> {
+    line.split(open)
+        .skip(1)
+        .filter_map(move |rest| rest.split_once(close).map(|(inner, _)| inner.trim()))
+}
+
+/// Arguments following `name` in a directive, if the comment is that directive
+fn strip_directive<'a>(comment: &'a str, name: &str) -> Option<&'a str> {
+    let rest = comment.strip_prefix(name)?;
+    (rest.is_empty() || rest.starts_with(char::is_whitespace)).then_some(rest)
+}
+
+/// Rule codes listed in a directive; none listed means every rule
+fn parse_rules(list: &str) -> Option<Vec<&'static str>> {
+    let names: Vec<&str> = list
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|name| !name.is_empty())
+        .collect();
+    if names.is_empty() {
+        return None;
+    }
+    Some(
+        names
+            .into_iter()
+            .filter_map(|name| find_rule(name).map(|rule| rule.code))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::validate_all;
+
+    #[test]
+    fn test_disable_next_line_for_listed_rules() {
+        let content = "<!-- markdown-checker-disable-next-line MC001, unprintable -->\n\
+                       caf\u{e9}\n\
+                       caf\u{e9}\n";
+        let (results, suppressed) = Suppressions::parse(content).apply(validate_all(content));
+        assert_eq!(suppressed, 2);
+        let lines: Vec<usize> = results
+            .iter()
+            .flat_map(|r| &r.errors)
+            .map(|e| e.line_number)
+            .collect();
+        assert_eq!(lines, vec![3, 3]);
+    }
+
+    #[test]
+    fn test_disable_next_line_without_rules_suppresses_all() {
+        let content = "<!-- markdown-checker-disable-next-line -->\n\u{251c} x\n";
+        let (results, suppressed) = Suppressions::parse(content).apply(validate_all(content));
+        assert_eq!(suppressed, 3);
+        assert!(results.iter().all(|r| r.is_pass()));
+    }
+
+    #[test]
+    fn test_other_rules_still_reported() {
+        let content = "<!-- markdown-checker-disable-next-line tree-symbols -->\n\u{251c}\n";
+        let (results, suppressed) = Suppressions::parse(content).apply(validate_all(content));
+        assert_eq!(suppressed, 1);
+        assert_eq!(results.iter().filter(|r| r.is_fail()).count(), 2);
+    }
+
+    #[test]
+    fn test_mdx_comments() {
+        let content = "{/* markdown-checker-disable-next-line MC003 */}\n\u{251c}\n\u{251c}\n";
+        let suppressions = Suppressions::parse(content);
+        assert!(suppressions.is_suppressed(2, Some("MC003")));
+        assert!(!suppressions.is_suppressed(3, Some("MC003")));
+    }
+
+    #[test]
+    fn test_line_comments() {
+        let content = "// markdown-checker-disable-next-line MC003\n\u{251c}\n\
+                       .. markdown-checker-disable MC001\n\u{251c}\n\
+                       //// markdown-checker-enable\n\u{251c}\n";
+        let suppressions = Suppressions::parse(content);
+        assert!(suppressions.is_suppressed(2, Some("MC003")));
+        assert!(suppressions.is_suppressed(4, Some("MC001")));
+        assert!(!suppressions.is_suppressed(4, Some("MC003")));
+        assert!(suppressions.is_suppressed(6, Some("MC001")));
+    }
+
+    #[test]
+    fn test_disable_enable_region() {
+        let content = "\u{2192}\n<!-- markdown-checker-disable -->\n\u{2192}\n\u{2192}\n\
+                       <!-- markdown-checker-enable -->\n\u{2192}\n";
+        let suppressions = Suppressions::parse(content);
+        let covered: Vec<usize> = (1..=6).filter(|&l| suppressions.covers_line(l)).collect();
+        assert_eq!(covered, vec![2, 3, 4]);
+
+        let (results, suppressed) = suppressions.apply(validate_all(content));
+        assert_eq!(suppressed, 4);
+        assert_eq!(count_errors(&results), 4);
+    }
+
+    #[test]
+    fn test_region_with_rule_lists() {
+        let content = "<!-- markdown-checker-disable -->\n\
+                       <!-- markdown-checker-enable tree-symbols -->\n\
+                       \u{251c}\n\
+                       <!-- markdown-checker-disable MC003 --><!-- markdown-checker-enable MC001 -->\n\
+                       \u{251c}\n";
+        let suppressions = Suppressions::parse(content);
+        assert!(suppressions.is_suppressed(3, Some("MC001")));
+        assert!(!suppressions.is_suppressed(3, Some("MC003")));
+        assert!(!suppressions.is_suppressed(5, Some("MC001")));
+        assert!(suppressions.is_suppressed(5, Some("MC002")));
+        assert!(suppressions.is_suppressed(5, Some("MC003")));
+    }
+
+    #[test]
+    fn test_unclosed_region_runs_to_end_of_file() {
+        let content = "<!-- markdown-checker-disable MC003 -->\n\u{251c}\n\u{251c}\n";
+        let (_, suppressed) = Suppressions::parse(content).apply(validate_all(content));
+        assert_eq!(suppressed, 2);
+    }
+
+    fn count_errors(results: &[ValidationResult]) -> usize {
+        results.iter().map(|r| r.errors.len()).sum()
+    }
+
+    #[test]
+    fn test_similar_comments_are_not_directives() {
+        let suppressions = Suppressions::parse(
+            "<!-- markdown-checker-disable-next-lines -->\n<!-- markdown-checker-enabled -->\n\
+             <!-- note -->\ntext\n",
+        );
+        assert!(suppressions.is_empty());
+    }
+}
@@ -0,0 +1,181 @@
+//! Helpers for regression tests of rule configurations.
+//!
+//! Projects that embed the library can check content against their own
+//! [`Policy`] in a line or two:
+//!
+//! ```
+//! use markdown_checker::policy::Policy;
+//! use markdown_checker::{assert_valid, assert_violations};
+//!
+//! assert_valid!("# Title\n");
+//! assert_violations!("# Title\n\u{251c}\u{2500} src\n", ["MC003:2:1", "MC003:2:2"]);
+//!
+//! let ascii_only = Policy::new(vec!["MC002", "MC003"]);
+//! assert_violations!("\u{251c}\u{2500} src\n", ["MC001:1:1", "MC001:1:2"], &ascii_only);
+//! ```
+//!
+//! Violations are written `CODE:LINE:COLUMN` (`CODE:LINE` when a rule reports
+//! no column), with columns counted in characters, and listed by location. A
+//! character several rules flag is listed once, under the most specific rule.
+//! [`render`] writes the full report as plain text that depends only on the
+//! content and the policy, for golden files and snapshot tests.
+
+use crate::policy::Policy;
+use crate::validators::find_rule;
+use crate::{Severity, ValidationError};
+use std::fmt::Write;
+
+/// A violation found by [`Policy::validate`], with its rule's code
+struct Found<'a> {
+    code: &'static str,
+    severity: Severity,
+    error: &'a ValidationError,
+}
+
+impl Found<'_> {
+    /// `CODE:LINE:COLUMN`, or `CODE:LINE` without a column
+    fn location(&self) -> String {
+        match self.error.column {
+            Some(column) => format!("{}:{}:{}", self.code, self.error.line_number, column),
+            None => format!("{}:{}", self.code, self.error.line_number),
+        }
+    }
+}
+
+/// Run `policy` on `content` and pass its violations, in location order, to `f`
+fn with_found<T>(content: &str, policy: &Policy, f: impl FnOnce(Vec<Found<'_>>) -> T) -> T {
+    let results = policy.validate(content);
+    let mut found: Vec<_> = results
+        .iter()
+        .flat_map(|result| {
+            let code = find_rule(&result.validator_name).map_or("", |rule| rule.code);
+            result.errors.iter().map(move |error| Found {
+                code,
+                severity: result.severity,
+                error,
+            })
+        })
+        .collect();
+    found.sort_by_key(|found| (found.error.line_number, found.error.column, found.code));
+    f(found)
+}
+
+/// The violations `policy` finds in `content`, as `CODE:LINE:COLUMN`, in
+/// location order
+pub fn violations(content: &str, policy: &Policy) -> Vec<String> {
+    with_found(content, policy, |found| {
+        found.iter().map(Found::location).collect()
+    })
+}
+
+/// A report of the violations `policy` finds in `content`, one
+/// `CODE:LINE:COLUMN: severity: message` line each in location order, or
+/// `no violations`. It has no paths, colors, or timings, so it is the same on
+/// every machine and run.
+pub fn render(content: &str, policy: &Policy) -> String {
+    with_found(content, policy, |found| {
+        if found.is_empty() {
+            return "no violations\n".to_string();
+        }
+        found.iter().fold(String::new(), |mut report, found| {
+            let _ = writeln!(
+                report,
+                "{}: {}: {}",
+                found.location(),
+                found.severity,
+                found.error.message.text()
+            );
+            report
+        })
+    })
+}
+
+/// Assert that `content` has no violations under a policy (the default one
+/// when none is given), printing the report when it does
+#[macro_export]
+macro_rules! assert_valid {
+    ($content:expr $(,)?) => {
+        $crate::assert_valid!($content, &$crate::policy::Policy::default())
+    };
+    ($content:expr, $policy:expr $(,)?) => {
+        // Matching keeps temporaries such as `&Policy::default()` alive
+        match (&$content, $policy) {
+            (content, policy) => {
+                let content: &str = ::core::convert::AsRef::as_ref(content);
+                let policy: &$crate::policy::Policy = policy;
+                if !$crate::testing::violations(content, policy).is_empty() {
+                    panic!(
+                        "expected no violations, found:\n{}",
+                        $crate::testing::render(content, policy)
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Assert that `content` has exactly these violations, written
+/// `CODE:LINE:COLUMN` in location order, under a policy (the default one when
+/// none is given)
+#[macro_export]
+macro_rules! assert_violations {
+    ($content:expr, [$($violation:expr),* $(,)?] $(,)?) => {
+        $crate::assert_violations!($content, [$($violation),*], &$crate::policy::Policy::default())
+    };
+    ($content:expr, [$($violation:expr),* $(,)?], $policy:expr $(,)?) => {
+        match (&$content, $policy) {
+            (content, policy) => {
+                let content: &str = ::core::convert::AsRef::as_ref(content);
+                let policy: &$crate::policy::Policy = policy;
+                let expected: &[&str] = &[$($violation),*];
+                let found = $crate::testing::violations(content, policy);
+                if found != expected {
+                    panic!(
+                        "violations differ\n  expected: {:?}\n     found: {:?}\n{}",
+                        expected,
+                        found,
+                        $crate::testing::render(content, policy)
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assertions() {
+        assert_valid!("# Title\n\nPlain text.\n");
+        assert_valid!(
+            String::from("caf\u{e9}"),
+            &Policy::default().allow(['\u{e9}'])
+        );
+        assert_violations!("ok\nna\u{ef}ve\n", ["MC001:2:3"]);
+        let ascii_only = Policy::new(vec!["MC002", "MC003", "MC004"]);
+        assert_violations!("\u{2014}", ["MC001:1:1"], &ascii_only);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected no violations")]
+    fn test_assert_valid_fails() {
+        assert_valid!("\u{251c}");
+    }
+
+    #[test]
+    fn test_render() {
+        let policy = Policy::new(vec!["MC002", "MC004"]).severities([("MC003", Severity::Warning)]);
+        // The error outranks the more specific rule's warning
+        assert_eq!(
+            render("a \u{2500}\n", &policy),
+            "MC001:1:3: error: Non-ASCII character: '\u{2500}' (U+2500)\n"
+        );
+        assert_eq!(
+            render("a \u{2500}\n", &Policy::new(vec!["MC002", "MC004"])),
+            "MC003:1:3: error: Tree symbol '\u{2500}' (U+2500) detected. Use '-' instead\n"
+        );
+        assert_eq!(render("a\n", &policy), "no violations\n");
+    }
+}
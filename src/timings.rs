@@ -0,0 +1,102 @@
+//! Wall-clock timings per validator and per file.
+
+use crate::statistics::rule_label;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Durations accumulated across a run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timings {
+    by_rule: BTreeMap<String, Duration>,
+    by_file: Vec<(PathBuf, Duration)>,
+    total: Duration,
+}
+
+impl Timings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one file: the time to read and check it, and the time spent in
+    /// each validator (empty when results came from the cache)
+    pub fn record(&mut self, file: &Path, elapsed: Duration, validators: &[(String, Duration)]) {
+        self.by_file.push((file.to_path_buf(), elapsed));
+        for (name, duration) in validators {
+            *self.by_rule.entry(rule_label(name)).or_default() += *duration;
+        }
+    }
+
+    /// Set the wall-clock duration of the whole run
+    pub fn set_total(&mut self, total: Duration) {
+        self.total = total;
+    }
+
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    pub fn files_timed(&self) -> usize {
+        self.by_file.len()
+    }
+
+    /// Time spent per rule, summed over all files, ordered by rule code
+    pub fn by_rule(&self) -> Vec<(&str, Duration)> {
+        self.by_rule
+            .iter()
+            .map(|(rule, duration)| (rule.as_str(), *duration))
+            .collect()
+    }
+
+    /// Files that took longest, slowest first (ties by path)
+    pub fn slowest_files(&self, limit: usize) -> Vec<(&Path, Duration)> {
+        let mut files: Vec<(&Path, Duration)> = self
+            .by_file
+            .iter()
+            .map(|(path, duration)| (path.as_path(), *duration))
+            .collect();
+        files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        files.truncate(limit);
+        files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_accumulates_per_rule() {
+        let mut timings = Timings::new();
+        let validators = vec![
+            ("ASCII Subset".to_string(), ms(2)),
+            ("Tree Symbols".to_string(), ms(1)),
+        ];
+        timings.record(Path::new("a.md"), ms(4), &validators);
+        timings.record(Path::new("b.md"), ms(3), &validators);
+        timings.record(Path::new("c.md"), ms(1), &[]);
+
+        assert_eq!(timings.files_timed(), 3);
+        assert_eq!(
+            timings.by_rule(),
+            vec![("MC001 ASCII Subset", ms(4)), ("MC003 Tree Symbols", ms(2))]
+        );
+    }
+
+    #[test]
+    fn test_slowest_files() {
+        let mut timings = Timings::new();
+        timings.record(Path::new("b.md"), ms(1), &[]);
+        timings.record(Path::new("a.md"), ms(5), &[]);
+        timings.record(Path::new("c.md"), ms(1), &[]);
+
+        assert_eq!(
+            timings.slowest_files(2),
+            vec![(Path::new("a.md"), ms(5)), (Path::new("b.md"), ms(1))]
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Interactive terminal UI for reviewing and fixing violations.
+//!
+//! The screen shows the files with violations, the violations in the selected
+//! file, and a preview of the content around the selected violation. Fixes are
+//! applied in memory and written when the user presses `w`.
+
+pub mod review;
+
+pub use review::{Focus, Item, Review, ReviewFile};
+
+use crate::statistics::rule_label;
+use ratatui::Frame;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use std::io;
+
+const HELP: &str = "Tab switch pane  ↑/↓ move  f fix item  r fix rule everywhere  w write  q quit";
+
+/// Run the review UI until the user quits
+pub fn run(review: &mut Review) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, review);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut ratatui::DefaultTerminal, review: &mut Review) -> io::Result<()> {
+    let mut confirm_quit = false;
+    loop {
+        terminal.draw(|frame| render(frame, review))?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        let quitting = matches!(key.code, KeyCode::Char('q') | KeyCode::Esc);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                if !review.has_unsaved_changes() || confirm_quit {
+                    return Ok(());
+                }
+                review.set_status("Unsaved fixes: press w to write them, or q again to discard");
+            }
+            KeyCode::Tab | KeyCode::Left | KeyCode::Right => review.toggle_focus(),
+            KeyCode::Down | KeyCode::Char('j') => review.move_down(),
+            KeyCode::Up | KeyCode::Char('k') => review.move_up(),
+            KeyCode::Char('f') => review.fix_selected(),
+            KeyCode::Char('r') => review.fix_rule(),
+            KeyCode::Char('w') => {
+                if let Err(e) = review.write() {
+                    review.set_status(format!("Error writing files: {}", e));
+                }
+            }
+            _ => {}
+        }
+        confirm_quit = quitting;
+    }
+}
+
+/// Draw the whole screen
+pub fn render(frame: &mut Frame, review: &Review) {
+    let [main, status] =
+        Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+    let [files, right] =
+        Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)]).areas(main);
+    let [violations, preview] =
+        Layout::vertical([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(right);
+
+    render_files(frame, review, files);
+    render_violations(frame, review, violations);
+    render_preview(frame, review, preview);
+
+    let text = if review.status().is_empty() {
+        format!("{} violation(s) remaining  |  {}", review.remaining(), HELP)
+    } else {
+        format!("{}  |  {}", review.status(), HELP)
+    };
+    frame.render_widget(
+        Paragraph::new(text).style(Style::new().add_modifier(Modifier::REVERSED)),
+        status,
+    );
+}
+
+fn pane(title: String, focused: bool) -> Block<'static> {
+    let style = if focused {
+        Style::new().fg(Color::Yellow)
+    } else {
+        Style::new()
+    };
+    Block::new()
+        .borders(Borders::ALL)
+        .border_style(style)
+        .title(title)
+}
+
+fn highlight() -> Style {
+    Style::new().add_modifier(Modifier::REVERSED)
+}
+
+fn render_files(frame: &mut Frame, review: &Review, area: Rect) {
+    let items: Vec<ListItem> = review
+        .files()
+        .iter()
+        .map(|file| {
+            let marker = if file.modified { "*" } else { " " };
+            let count = file.violation_count();
+            let style = if count == 0 {
+                Style::new().fg(Color::Green)
+            } else {
+                Style::new()
+            };
+            ListItem::new(format!("{}{:>4}  {}", marker, count, file.path.display())).style(style)
+        })
+        .collect();
+
+    let block = pane(
+        format!("Files ({})", review.files().len()),
+        review.focus() == Focus::Files,
+    );
+    let mut state = ListState::default().with_selected(Some(review.file_index()));
+    frame.render_stateful_widget(
+        List::new(items).block(block).highlight_style(highlight()),
+        area,
+        &mut state,
+    );
+}
+
+fn render_violations(frame: &mut Frame, review: &Review, area: Rect) {
+    let items: Vec<ListItem> = review
+        .current_file()
+        .map(|file| file.items())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|item| {
+            let location = match item.column {
+                Some(col) => format!("{}:{}", item.line, col),
+                None => item.line.to_string(),
+            };
+            let rule = rule_label(&item.rule);
+            let code = rule.split(' ').next().unwrap_or_default().to_string();
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<8} ", location),
+                    Style::new().add_modifier(Modifier::DIM),
+                ),
+                Span::styled(format!("{} ", code), Style::new().fg(Color::Red)),
+                Span::raw(item.message),
+            ]))
+        })
+        .collect();
+
+    let block = pane(
+        "Violations".to_string(),
+        review.focus() == Focus::Violations,
+    );
+    let selected = (!items.is_empty()).then_some(review.item_index());
+    let mut state = ListState::default().with_selected(selected);
+    frame.render_stateful_widget(
+        List::new(items).block(block).highlight_style(highlight()),
+        area,
+        &mut state,
+    );
+}
+
+fn render_preview(frame: &mut Frame, review: &Review, area: Rect) {
+    let Some(file) = review.current_file() else {
+        frame.render_widget(pane("Preview".to_string(), false), area);
+        return;
+    };
+    let item = review.selected_item();
+    let target_line = item.as_ref().map_or(1, |i| i.line);
+
+    // Center the selected line in the visible rows
+    let rows = area.height.saturating_sub(2) as usize;
+    let first = target_line.saturating_sub(rows / 2).max(1);
+    let lines: Vec<Line> = file
+        .content
+        .lines()
+        .enumerate()
+        .skip(first - 1)
+        .take(rows)
+        .map(|(index, text)| {
+            let number = index + 1;
+            let mut spans = vec![Span::styled(
+                format!("{:>5} ", number),
+                Style::new().add_modifier(Modifier::DIM),
+            )];
+            match item
+                .as_ref()
+                .and_then(|i| i.column.filter(|_| i.line == number))
+            {
+                Some(col) => {
+                    let before: String = text.chars().take(col - 1).collect();
+                    let target: String = text.chars().skip(col - 1).take(1).collect();
+                    let after: String = text.chars().skip(col).collect();
+                    spans.push(Span::raw(before));
+                    spans.push(Span::styled(
+                        target,
+                        Style::new().fg(Color::Red).add_modifier(Modifier::REVERSED),
+                    ));
+                    spans.push(Span::raw(after));
+                }
+                None => spans.push(Span::raw(text.to_string())),
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    let title = format!("Preview: {}", file.path.display());
+    frame.render_widget(Paragraph::new(lines).block(pane(title, false)), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::validate_all;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_render_shows_files_violations_and_preview() {
+        let content = "# Title\n├── src\n";
+        let file = ReviewFile::new(
+            PathBuf::from("docs/a.md"),
+            content.to_string(),
+            validate_all(content),
+        );
+        let review = Review::new(vec![file]);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| render(frame, &review)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("docs/a.md"));
+        assert!(screen.contains("MC001"));
+        assert!(screen.contains("# Title"));
+        assert!(screen.contains("9 violation(s) remaining"));
+    }
+}
@@ -0,0 +1,428 @@
+//! Review state for the interactive TUI, independent of the terminal.
+
+use crate::ValidationResult;
+use crate::file_ops::write_fix;
+use crate::fixer::fix_at;
+use crate::line_endings::LineEnding;
+use crate::policy::Policy;
+use std::io;
+use std::path::PathBuf;
+
+/// A file under review with its current (possibly fixed) content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewFile {
+    pub path: PathBuf,
+    pub content: String,
+    /// Content as it was checked, which must still be on disk when fixes are
+    /// written
+    pub checked: String,
+    pub results: Vec<ValidationResult>,
+    /// Content differs from what is on disk
+    pub modified: bool,
+    /// Rules and characters applied when re-validating this file
+    pub policy: Policy,
+}
+
+impl ReviewFile {
+    pub fn new(path: PathBuf, content: String, results: Vec<ValidationResult>) -> Self {
+        Self {
+            path,
+            checked: content.clone(),
+            content,
+            results,
+            modified: false,
+            policy: Policy::default(),
+        }
+    }
+
+    /// Re-validate under `policy` after a fix
+    pub fn policy(mut self, policy: Policy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Violations in line and column order
+    pub fn items(&self) -> Vec<Item> {
+        let mut items: Vec<Item> = self
+            .results
+            .iter()
+            .flat_map(|result| {
+                result.errors.iter().map(|error| Item {
+                    rule: result.validator_name.clone(),
+                    line: error.line_number,
+                    column: error.column,
+                    message: error.message.to_string(),
+                })
+            })
+            .collect();
+        items.sort_by(|a, b| (a.line, a.column, &a.rule).cmp(&(b.line, b.column, &b.rule)));
+        items
+    }
+
+    pub fn violation_count(&self) -> usize {
+        self.results.iter().map(|r| r.errors.len()).sum()
+    }
+}
+
+/// A single violation shown in the violation list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item {
+    /// Validator name of the rule that reported it
+    pub rule: String,
+    pub line: usize,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+/// Which list the cursor keys move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Focus {
+    #[default]
+    Files,
+    Violations,
+}
+
+/// Files, selection, and pending fixes for a review session
+#[derive(Debug)]
+pub struct Review {
+    files: Vec<ReviewFile>,
+    file_index: usize,
+    item_index: usize,
+    focus: Focus,
+    status: String,
+    /// Line endings of written files, when not kept as they were
+    eol: Option<LineEnding>,
+    /// Restore the modification time of written files
+    keep_mtime: bool,
+}
+
+impl Review {
+    pub fn new(files: Vec<ReviewFile>) -> Self {
+        Self {
+            files,
+            file_index: 0,
+            item_index: 0,
+            focus: Focus::Files,
+            status: String::new(),
+            eol: None,
+            keep_mtime: false,
+        }
+    }
+
+    /// Write files with these line endings, keeping their modification time
+    /// with `keep_mtime`, as `--fix` does
+    pub fn writing(mut self, eol: Option<LineEnding>, keep_mtime: bool) -> Self {
+        self.eol = eol;
+        self.keep_mtime = keep_mtime;
+        self
+    }
+
+    pub fn files(&self) -> &[ReviewFile] {
+        &self.files
+    }
+
+    pub fn file_index(&self) -> usize {
+        self.file_index
+    }
+
+    pub fn item_index(&self) -> usize {
+        self.item_index
+    }
+
+    pub fn focus(&self) -> Focus {
+        self.focus
+    }
+
+    /// Message describing the result of the last action
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    pub fn current_file(&self) -> Option<&ReviewFile> {
+        self.files.get(self.file_index)
+    }
+
+    pub fn selected_item(&self) -> Option<Item> {
+        self.current_file()?
+            .items()
+            .into_iter()
+            .nth(self.item_index)
+    }
+
+    /// Violations remaining across all files
+    pub fn remaining(&self) -> usize {
+        self.files.iter().map(ReviewFile::violation_count).sum()
+    }
+
+    pub fn has_unsaved_changes(&self) -> bool {
+        self.files.iter().any(|f| f.modified)
+    }
+
+    pub fn toggle_focus(&mut self) {
+        self.focus = match self.focus {
+            Focus::Files => Focus::Violations,
+            Focus::Violations => Focus::Files,
+        };
+    }
+
+    pub fn move_down(&mut self) {
+        match self.focus {
+            Focus::Files => {
+                if self.file_index + 1 < self.files.len() {
+                    self.file_index += 1;
+                    self.item_index = 0;
+                }
+            }
+            Focus::Violations => {
+                let count = self.current_file().map_or(0, |f| f.items().len());
+                if self.item_index + 1 < count {
+                    self.item_index += 1;
+                }
+            }
+        }
+    }
+
+    pub fn move_up(&mut self) {
+        match self.focus {
+            Focus::Files => {
+                if self.file_index > 0 {
+                    self.file_index -= 1;
+                    self.item_index = 0;
+                }
+            }
+            Focus::Violations => self.item_index = self.item_index.saturating_sub(1),
+        }
+    }
+
+    /// Fix the character at the selected violation
+    pub fn fix_selected(&mut self) {
+        let Some(item) = self.selected_item() else {
+            self.status = "No violation selected".to_string();
+            return;
+        };
+        let file = &mut self.files[self.file_index];
+        let fixed = item
+            .column
+            .and_then(|col| fix_at(&file.content, item.line, col));
+
+        self.status = match fixed {
+            Some(content) => {
+                file.content = content;
+                file.modified = true;
+                self.revalidate(self.file_index);
+                format!("Fixed line {}", item.line)
+            }
+            None => format!(
+                "Line {}: no automatic fix for this character; edit it by hand",
+                item.line
+            ),
+        };
+        self.clamp_item();
+    }
+
+    /// Fix every violation of the selected violation's rule, in all files
+    pub fn fix_rule(&mut self) {
+        let Some(rule) = self.selected_item().map(|item| item.rule) else {
+            self.status = "No violation selected".to_string();
+            return;
+        };
+
+        let mut fixed_count = 0;
+        for index in 0..self.files.len() {
+            let file = &mut self.files[index];
+            // Fix from the end so earlier positions stay valid
+            let mut items: Vec<Item> = file
+                .items()
+                .into_iter()
+                .filter(|i| i.rule == rule)
+                .collect();
+            items.reverse();
+            let mut changed = false;
+            for item in items {
+                if let Some(content) = item
+                    .column
+                    .and_then(|c| fix_at(&file.content, item.line, c))
+                {
+                    file.content = content;
+                    fixed_count += 1;
+                    changed = true;
+                }
+            }
+            if changed {
+                file.modified = true;
+                self.revalidate(index);
+            }
+        }
+
+        self.status = format!("Fixed {} {} violation(s)", fixed_count, rule);
+        self.clamp_item();
+    }
+
+    /// Write every modified file, returning how many were written. Like
+    /// `--fix`, a file edited on disk since it was checked is left alone with
+    /// an error.
+    pub fn write(&mut self) -> io::Result<usize> {
+        let mut written = 0;
+        for file in self.files.iter_mut().filter(|f| f.modified) {
+            file.checked = write_fix(
+                &file.path,
+                &file.checked,
+                &file.content,
+                file.policy.bom,
+                self.eol,
+                self.keep_mtime,
+            )?;
+            file.modified = false;
+            written += 1;
+        }
+        self.status = format!("Wrote {} file(s)", written);
+        Ok(written)
+    }
+
+    pub fn set_status(&mut self, status: impl Into<String>) {
+        self.status = status.into();
+    }
+
+    fn revalidate(&mut self, index: usize) {
+        let file = &mut self.files[index];
+        file.results = file.policy.validate(&file.content);
+    }
+
+    fn clamp_item(&mut self) {
+        let count = self.current_file().map_or(0, |f| f.items().len());
+        self.item_index = self.item_index.min(count.saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::validate_all;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn review(files: &[(&str, &str)]) -> Review {
+        let files = files
+            .iter()
+            .map(|(path, content)| {
+                ReviewFile::new(
+                    PathBuf::from(path),
+                    content.to_string(),
+                    validate_all(content),
+                )
+            })
+            .collect();
+        Review::new(files)
+    }
+
+    #[test]
+    fn test_items_sorted_by_position() {
+        let review = review(&[("a.md", "ok é\n├ x\n")]);
+        let items = review.current_file().unwrap().items();
+        let positions: Vec<_> = items.iter().map(|i| (i.line, i.column)).collect();
+        assert_eq!(
+            positions,
+            vec![
+                (1, Some(4)),
+                (1, Some(4)),
+                (2, Some(1)),
+                (2, Some(1)),
+                (2, Some(1))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_navigation_is_bounded() {
+        let mut review = review(&[("a.md", "é\n"), ("b.md", "é\n")]);
+        review.move_up();
+        assert_eq!(review.file_index(), 0);
+        review.move_down();
+        review.move_down();
+        assert_eq!(review.file_index(), 1);
+
+        review.toggle_focus();
+        review.move_down();
+        assert_eq!(review.item_index(), 1);
+        review.move_down();
+        assert_eq!(review.item_index(), 1);
+    }
+
+    #[test]
+    fn test_fix_selected_item() {
+        let mut review = review(&[("a.md", "café ├\n")]);
+        review.toggle_focus();
+        review.fix_selected();
+
+        let file = review.current_file().unwrap();
+        assert_eq!(file.content, "cafe ├\n");
+        assert!(file.modified);
+        assert_eq!(review.remaining(), 1);
+    }
+
+    #[test]
+    fn test_unfixable_item_reports_status() {
+        let mut review = review(&[("a.md", "日\n")]);
+        review.fix_selected();
+        assert!(review.status().contains("no automatic fix"));
+        assert!(!review.has_unsaved_changes());
+    }
+
+    #[test]
+    fn test_fix_rule_across_files() {
+        let mut review = review(&[("a.md", "├── a ├\n"), ("b.md", "└ b\n")]);
+        // Select the Tree Symbols violation on the first character
+        let index = review
+            .current_file()
+            .unwrap()
+            .items()
+            .iter()
+            .position(|i| i.rule == "Tree Symbols")
+            .unwrap();
+        review.toggle_focus();
+        for _ in 0..index {
+            review.move_down();
+        }
+        review.fix_rule();
+
+        assert_eq!(review.files()[0].content, "+-- a +\n");
+        assert_eq!(review.files()[1].content, "+ b\n");
+        assert_eq!(review.remaining(), 0);
+        assert_eq!(review.status(), "Fixed 5 Tree Symbols violation(s)");
+    }
+
+    #[test]
+    fn test_write_modified_files() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.md");
+        fs::write(&path, "├ x\n").unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let results = validate_all(&content);
+        let mut review = Review::new(vec![ReviewFile::new(path.clone(), content, results)]);
+        review.fix_selected();
+        assert_eq!(review.write().unwrap(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "+ x\n");
+        assert!(!review.has_unsaved_changes());
+    }
+
+    #[test]
+    fn test_write_leaves_files_changed_on_disk() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.md");
+        fs::write(&path, "\u{251c} x\n").unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let results = validate_all(&content);
+        let mut review = Review::new(vec![ReviewFile::new(path.clone(), content, results)])
+            .writing(Some(LineEnding::Crlf), false);
+        review.fix_selected();
+        fs::write(&path, "edited \u{251c} x\n").unwrap();
+        assert!(review.write().is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "edited \u{251c} x\n");
+
+        fs::write(&path, "\u{251c} x\n").unwrap();
+        assert_eq!(review.write().unwrap(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "+ x\r\n");
+    }
+}
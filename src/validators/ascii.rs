@@ -1,34 +1,63 @@
-use crate::{ValidationError, ValidationResult, Validator};
+use super::scan::scan;
+use crate::charset::{self, CharClass};
+use crate::{CharKind, CharRule, RuleMetadata, Severity, ValidationResult, Validator};
 
 pub struct AsciiValidator;
 
+impl AsciiValidator {
+    pub const METADATA: RuleMetadata = RuleMetadata {
+        code: "MC001",
+        key: "ascii",
+        name: "ASCII Subset",
+        summary: "Characters must be within the ASCII range (0-127)",
+        description: "Flags every character whose code point is above 127. \
+            This covers emojis, accented letters, smart quotes, dashes, arrows, \
+            math symbols, and any other Unicode text.",
+        rationale: "ASCII-only documentation renders identically in every editor, \
+            terminal, diff tool, CI log, and screen reader, regardless of font or locale.",
+        examples: &[
+            "Caf\u{e9} menu",
+            "\u{201C}quoted\u{201D} text",
+            "a \u{2192} b",
+            "Done \u{2705}",
+        ],
+        fix_behavior: "Common characters are replaced with ASCII equivalents: accented \
+            letters lose their accents, smart quotes become straight quotes, arrows become \
+            -> and <-, checkmarks become [x], and symbols like (c) and (TM) are spelled out. \
+            Characters without a known replacement must be fixed by hand.",
+        default_severity: Severity::Error,
+        fixable: true,
+        enabled_by_default: true,
+    };
+    /// Characters the rule accepts
+    const ACCEPTED: &'static [CharClass] = &[CharClass::Range('\0', '\x7f')];
+}
+
 impl Validator for AsciiValidator {
     fn name(&self) -> &str {
         "ASCII Subset"
     }
 
+    fn metadata(&self) -> &'static RuleMetadata {
+        &Self::METADATA
+    }
+
     fn validate(&self, content: &str) -> ValidationResult {
-        let mut errors = Vec::new();
+        scan(&[self], content).remove(0)
+    }
+}
+
+impl CharRule for AsciiValidator {
+    fn flags(&self, kind: CharKind) -> bool {
+        kind == CharKind::NonAscii
+    }
 
-        for (line_num, line) in content.lines().enumerate() {
-            for (col, ch) in line.chars().enumerate() {
-                if (ch as u32) > 127 {
-                    errors.push(
-                        ValidationError::new(
-                            line_num + 1,
-                            format!("Non-ASCII character: '{}' (U+{:04X})", ch, ch as u32),
-                        )
-                        .with_column(col + 1),
-                    );
-                }
-            }
-        }
+    fn violates(&self, ch: char) -> bool {
+        !charset::accepts(Self::ACCEPTED, &[], ch)
+    }
 
-        if errors.is_empty() {
-            ValidationResult::pass(self.name().to_string())
-        } else {
-            ValidationResult::fail(self.name().to_string(), errors)
-        }
+    fn message(&self, ch: char) -> String {
+        format!("Non-ASCII character: '{}' (U+{:04X})", ch, ch as u32)
     }
 }
 
@@ -51,7 +80,7 @@ mod tests {
         assert!(result.is_fail());
         assert_eq!(result.errors.len(), 1);
         assert_eq!(result.errors[0].line_number, 1);
-        assert!(result.errors[0].message.contains("👋"));
+        assert!(result.errors[0].message.text().contains("👋"));
     }
 
     #[test]
@@ -61,7 +90,7 @@ mod tests {
         assert!(result.is_fail());
         assert_eq!(result.errors.len(), 1);
         assert_eq!(result.errors[0].line_number, 1);
-        assert!(result.errors[0].message.contains("é"));
+        assert!(result.errors[0].message.text().contains("é"));
     }
 
     #[test]
@@ -0,0 +1,199 @@
+use super::prose;
+use crate::{
+    ProseRule, RuleMetadata, RuleOptions, Severity, ValidationError, ValidationResult, Validator,
+};
+
+pub struct DuplicateWordsValidator;
+
+impl DuplicateWordsValidator {
+    pub const METADATA: RuleMetadata = RuleMetadata {
+        code: "MC007",
+        key: "duplicate-words",
+        name: "Duplicate Words",
+        summary: "A word must not be repeated by accident",
+        description: "Flags a word directly followed by the same word, ignoring case, \
+            such as \"the the\" or \"is is\". Only whitespace may separate the two: \
+            punctuation, markup, and line breaks start over. Numbers, fenced code \
+            blocks, and inline code are not checked, and words that are often repeated \
+            on purpose (\"had had\", \"that that\") are accepted, as are those listed \
+            under [duplicate-words] allow.",
+        rationale: "Repeated words are among the most common typos and among the \
+            hardest to see when proofreading, since readers skip over the second copy.",
+        examples: &["Add the the file", "This is is done"],
+        fix_behavior: "Not auto-fixable. Which copy to keep, or whether the repetition \
+            is intended, is decided by hand.",
+        default_severity: Severity::Warning,
+        fixable: false,
+        enabled_by_default: false,
+    };
+
+    /// Words accepted repeated without configuration
+    const ALLOWED: &'static [&'static str] = &["bye", "ha", "had", "knock", "no", "that"];
+
+    fn is_allowed(word: &str, options: &RuleOptions) -> bool {
+        let lowercase = word.to_lowercase();
+        Self::ALLOWED.contains(&lowercase.as_str())
+            || options
+                .duplicate_words_allowed
+                .iter()
+                .any(|allowed| allowed.to_lowercase() == lowercase)
+    }
+}
+
+/// Whether two words are the same, ignoring case
+fn same_word(a: &str, b: &str) -> bool {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .eq(b.chars().flat_map(char::to_lowercase))
+}
+
+impl Validator for DuplicateWordsValidator {
+    fn name(&self) -> &str {
+        "Duplicate Words"
+    }
+
+    fn metadata(&self) -> &'static RuleMetadata {
+        &Self::METADATA
+    }
+
+    fn validate(&self, content: &str) -> ValidationResult {
+        self.validate_with(content, &RuleOptions::default())
+    }
+
+    fn validate_with(&self, content: &str, options: &RuleOptions) -> ValidationResult {
+        prose::validate(self, content, options)
+    }
+}
+
+impl ProseRule for DuplicateWordsValidator {
+    fn check_line(
+        &self,
+        line_number: usize,
+        line: &str,
+        options: &RuleOptions,
+    ) -> Vec<ValidationError> {
+        repeated_words(line, options)
+            .map(|(column, word)| {
+                ValidationError::new(line_number, format!("Duplicate word '{}'", word))
+                    .with_column(column)
+            })
+            .collect()
+    }
+}
+
+/// Second copies of repeated words in `line`, with their columns
+fn repeated_words<'a>(
+    line: &'a str,
+    options: &'a RuleOptions,
+) -> impl Iterator<Item = (usize, &'a str)> + 'a {
+    // The last word, while only whitespace has followed it
+    let mut previous: Option<&str> = None;
+    let mut chars = line.char_indices().enumerate().peekable();
+    std::iter::from_fn(move || {
+        while let Some((column, (start, ch))) = chars.next() {
+            if !ch.is_alphanumeric() {
+                if !ch.is_whitespace() {
+                    previous = None;
+                }
+                continue;
+            }
+            let mut end = start + ch.len_utf8();
+            while let Some(&(_, (next_start, next))) = chars.peek() {
+                if !(next.is_alphanumeric() || matches!(next, '\'' | '\u{2019}')) {
+                    break;
+                }
+                end = next_start + next.len_utf8();
+                chars.next();
+            }
+            let word = &line[start..end];
+            let repeated = previous.is_some_and(|previous| same_word(previous, word))
+                && word.chars().any(char::is_alphabetic)
+                && !DuplicateWordsValidator::is_allowed(word, options);
+            previous = Some(word);
+            if repeated {
+                return Some((column + 1, word));
+            }
+        }
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::doc_format::DocFormat;
+    use crate::policy::Policy;
+
+    fn columns(content: &str) -> Vec<(usize, usize)> {
+        DuplicateWordsValidator
+            .validate(content)
+            .errors
+            .iter()
+            .map(|error| (error.line_number, error.column.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_repeated_words() {
+        assert_eq!(
+            columns("Add the the file\nThis is is done\n"),
+            [(1, 9), (2, 9)]
+        );
+        assert_eq!(
+            columns("The the start, and na\u{ef}ve na\u{ef}ve\n"),
+            [(1, 5), (1, 26)]
+        );
+        assert!(DuplicateWordsValidator.validate("one two\n").is_pass());
+    }
+
+    #[test]
+    fn test_separators_start_over() {
+        assert!(columns("Yes. Yes, yes!\n").is_empty());
+        assert!(columns("[the](#the) the *a* a\n").is_empty());
+        assert!(columns("the\nthe\n").is_empty());
+        assert!(columns("| 1 | 1 |\nversion 2 2\n").is_empty());
+        assert_eq!(columns("it's it's\n"), [(1, 6)]);
+    }
+
+    #[test]
+    fn test_code_is_skipped() {
+        assert!(columns("Run `echo echo` now\n").is_empty());
+        assert!(columns("`the` the\n").is_empty());
+        assert!(columns("```\nthe the\n```\n").is_empty());
+    }
+
+    #[test]
+    fn test_code_of_other_formats_is_skipped() {
+        let content = "Example:\n\n.. code-block:: text\n\n   the the\n";
+        assert_eq!(columns(content), [(5, 8)]);
+        let options = RuleOptions {
+            format: DocFormat::Rst,
+            ..RuleOptions::default()
+        };
+        assert!(
+            DuplicateWordsValidator
+                .validate_with(content, &options)
+                .is_pass()
+        );
+        // The policy's format reaches the rule whichever is set first
+        let policy = Policy::new(Vec::new())
+            .format(DocFormat::Rst)
+            .options(RuleOptions::default());
+        let results = policy.validate(content);
+        assert!(results.iter().all(|r| r.is_pass()), "{results:?}");
+    }
+
+    #[test]
+    fn test_allowed_repetitions() {
+        assert!(columns("He had had enough, and that that was it.\n").is_empty());
+        let content = "Walla walla\n";
+        assert_eq!(columns(content), [(1, 7)]);
+        let mut options = RuleOptions::default();
+        options.duplicate_words_allowed.push("Walla".to_string());
+        assert!(
+            DuplicateWordsValidator
+                .validate_with(content, &options)
+                .is_pass()
+        );
+    }
+}
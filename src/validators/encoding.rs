@@ -0,0 +1,35 @@
+use crate::{RuleMetadata, Severity};
+
+/// Files must be UTF-8. Decoding happens before any rule can run, so the
+/// check is made when a file is read rather than on its content; it and the
+/// conversion `--fix` makes live in `encoding`.
+pub struct EncodingRule;
+
+impl EncodingRule {
+    pub const METADATA: RuleMetadata = RuleMetadata {
+        code: "MC006",
+        key: "encoding",
+        name: "UTF-8 Encoding",
+        summary: "Files must be UTF-8",
+        description: "Flags files that are not valid UTF-8, naming the encoding they \
+            appear to be in: UTF-16 (little- or big-endian, with or without a byte \
+            order mark), Windows-1252, or ISO-8859-1. Other invalid bytes, usually \
+            UTF-8 damaged by truncation or a bad merge, are reported at the first \
+            one. The other rules cannot run on a file that is not UTF-8.",
+        rationale: "Git, diff tools, and site generators treat UTF-16 files as \
+            binary, and 8-bit encodings turn accented letters and smart quotes into \
+            replacement characters wherever UTF-8 is assumed, which is almost \
+            everywhere.",
+        examples: &[
+            "FF FE 23 00 20 00  (UTF-16LE: \"# \" after a byte order mark)",
+            "Caf E9  (ISO-8859-1 \"Cafe\" with an accent)",
+        ],
+        fix_behavior: "UTF-16, Windows-1252, and ISO-8859-1 files are converted to \
+            UTF-8, without a byte order mark, and their characters are then fixed \
+            like any other file's. Damaged UTF-8 is not auto-fixable; find the bad \
+            bytes and retype the text around them.",
+        default_severity: Severity::Error,
+        fixable: true,
+        enabled_by_default: true,
+    };
+}
@@ -0,0 +1,69 @@
+use crate::frontmatter::Frontmatter;
+use crate::{RuleMetadata, Severity, ValidationResult, Validator};
+
+pub struct FrontmatterValidator;
+
+impl FrontmatterValidator {
+    pub const METADATA: RuleMetadata = RuleMetadata {
+        code: "MC004",
+        key: "frontmatter",
+        name: "Frontmatter Syntax",
+        summary: "YAML and TOML frontmatter must be well-formed",
+        description: "Checks the frontmatter block at the start of a file. TOML \
+            frontmatter (between +++ lines) must parse and be closed. YAML frontmatter \
+            (between --- lines) must not indent with tabs or repeat a top-level key.",
+        rationale: "Static site generators reject malformed frontmatter or silently \
+            keep only one of two duplicate keys, so a typo can drop a page's title or \
+            publish date.",
+        examples: &["+++\\ndate = \\n+++", "---\\ntitle: A\\ntitle: B\\n---"],
+        fix_behavior: "Not auto-fixable. Frontmatter is corrected by hand.",
+        default_severity: Severity::Error,
+        fixable: false,
+        enabled_by_default: false,
+    };
+}
+
+impl Validator for FrontmatterValidator {
+    fn name(&self) -> &str {
+        "Frontmatter Syntax"
+    }
+
+    fn metadata(&self) -> &'static RuleMetadata {
+        &Self::METADATA
+    }
+
+    fn validate(&self, content: &str) -> ValidationResult {
+        let errors = Frontmatter::find(content)
+            .map(|frontmatter| frontmatter.syntax_errors())
+            .unwrap_or_default();
+
+        if errors.is_empty() {
+            ValidationResult::pass(self.name().to_string())
+        } else {
+            ValidationResult::fail(self.name().to_string(), errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_frontmatter_passes() {
+        assert!(FrontmatterValidator.validate("# Title\n").is_pass());
+    }
+
+    #[test]
+    fn test_accented_frontmatter_passes() {
+        let content = "---\nauthor: Ren\u{e9}e\n---\n# Title\n";
+        assert!(FrontmatterValidator.validate(content).is_pass());
+    }
+
+    #[test]
+    fn test_invalid_frontmatter_fails() {
+        let result = FrontmatterValidator.validate("+++\ntitle = \n+++\n");
+        assert!(result.is_fail());
+        assert_eq!(result.errors[0].line_number, 2);
+    }
+}
@@ -0,0 +1,235 @@
+use super::prose;
+use crate::{
+    ProseRule, RuleMetadata, RuleOptions, Severity, ValidationError, ValidationResult, Validator,
+};
+
+pub struct LinkTextValidator;
+
+impl LinkTextValidator {
+    pub const METADATA: RuleMetadata = RuleMetadata {
+        code: "MC009",
+        key: "link-text",
+        name: "Link Text",
+        summary: "Link text must describe where the link goes",
+        description: "Flags inline and reference links whose text is a phrase that says \
+            nothing about the target, such as \"click here\", \"here\", or \"this link\", \
+            or a raw URL. Case, surrounding emphasis, and trailing punctuation are \
+            ignored. [link-text] phrases replaces the built-in list of phrases. Images, \
+            links around images, and autolinks (<https://...>) are not checked.",
+        rationale: "Screen reader users often jump from link to link or list a page's \
+            links out of context, where \"click here\" repeated down the page tells them \
+            nothing, and a URL is read out character by character.",
+        examples: &[
+            "[Click here](install.md) to install",
+            "See [https://example.com](https://example.com)",
+        ],
+        fix_behavior: "Not auto-fixable. Describing the target, e.g. \"[the installation \
+            guide](install.md)\", is done by hand.",
+        default_severity: Severity::Warning,
+        fixable: false,
+        enabled_by_default: false,
+    };
+
+    /// Link text flagged without configuration
+    pub const PHRASES: &'static [&'static str] = &[
+        "click here",
+        "click",
+        "here",
+        "this link",
+        "link",
+        "this",
+        "more",
+        "read more",
+    ];
+}
+
+impl Validator for LinkTextValidator {
+    fn name(&self) -> &str {
+        "Link Text"
+    }
+
+    fn metadata(&self) -> &'static RuleMetadata {
+        &Self::METADATA
+    }
+
+    fn validate(&self, content: &str) -> ValidationResult {
+        self.validate_with(content, &RuleOptions::default())
+    }
+
+    fn validate_with(&self, content: &str, options: &RuleOptions) -> ValidationResult {
+        prose::validate(self, content, options)
+    }
+}
+
+impl ProseRule for LinkTextValidator {
+    fn check_line(
+        &self,
+        line_number: usize,
+        line: &str,
+        options: &RuleOptions,
+    ) -> Vec<ValidationError> {
+        links(line)
+            .into_iter()
+            .filter_map(|(column, text)| {
+                let message = if is_url(text) {
+                    format!("Link text is a raw URL: '{}'", text)
+                } else {
+                    let phrase = normalize(text);
+                    options
+                        .link_text_phrases
+                        .iter()
+                        .any(|flagged| normalize(flagged) == phrase)
+                        .then(|| format!("Link text '{}' does not describe its target", text))?
+                };
+                Some(ValidationError::new(line_number, message).with_column(column))
+            })
+            .collect()
+    }
+}
+
+/// Inline (`[text](target)`) and reference (`[text][label]`) links in `line`,
+/// as the column of their `[` and their text, leaving out images and links
+/// whose text is an image
+fn links(line: &str) -> Vec<(usize, &str)> {
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    let mut links = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let (_, ch) = chars[index];
+        let escaped = index > 0 && chars[index - 1].1 == '\\';
+        if ch != '[' || escaped {
+            index += 1;
+            continue;
+        }
+        let Some(close) = closing_bracket(&chars, index) else {
+            index += 1;
+            continue;
+        };
+        let is_image = index > 0 && chars[index - 1].1 == '!';
+        let is_link = matches!(chars.get(close + 1), Some((_, '(' | '[')));
+        let text = &line[chars[index].0 + 1..chars[close].0];
+        if is_link && !is_image && !text.contains("![") {
+            links.push((index + 1, text));
+        }
+        // Brackets inside the text may open links of their own
+        index += 1;
+    }
+    links
+}
+
+/// Index of the `]` closing the `[` at `open`, skipping nested pairs and
+/// escaped brackets
+fn closing_bracket(chars: &[(usize, char)], open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for index in open..chars.len() {
+        if index > 0 && chars[index - 1].1 == '\\' {
+            continue;
+        }
+        match chars[index].1 {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// `text` without surrounding emphasis or trailing punctuation, in lowercase
+/// with single spaces
+fn normalize(text: &str) -> String {
+    let text = text
+        .trim()
+        .trim_matches(['*', '_', '`'])
+        .trim_end_matches(['.', ':', '!', ',']);
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Whether link text is a bare web address
+fn is_url(text: &str) -> bool {
+    let text = text.trim().trim_matches('`');
+    let lowercase = text.to_ascii_lowercase();
+    ["http://", "https://", "www."]
+        .iter()
+        .any(|prefix| lowercase.starts_with(prefix))
+        && !text.contains(char::is_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flagged(content: &str, options: &RuleOptions) -> Vec<(usize, String)> {
+        LinkTextValidator
+            .validate_with(content, options)
+            .errors
+            .iter()
+            .map(|error| (error.column.unwrap(), error.message.text().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_vague_link_text() {
+        let options = RuleOptions::default();
+        assert_eq!(
+            flagged(
+                "[Click here](a.md), see [**this link**][docs], or [here](#top).\n",
+                &options
+            ),
+            [
+                (
+                    1,
+                    "Link text 'Click here' does not describe its target".to_string()
+                ),
+                (
+                    25,
+                    "Link text '**this link**' does not describe its target".to_string()
+                ),
+                (
+                    51,
+                    "Link text 'here' does not describe its target".to_string()
+                ),
+            ]
+        );
+        assert!(flagged("Read [the installation guide](install.md).\n", &options).is_empty());
+        assert!(flagged("Click here [for details] or [here].\n", &options).is_empty());
+    }
+
+    #[test]
+    fn test_raw_urls() {
+        let options = RuleOptions::default();
+        assert_eq!(
+            flagged("[https://example.com](https://example.com)\n", &options),
+            [(
+                1,
+                "Link text is a raw URL: 'https://example.com'".to_string()
+            )]
+        );
+        assert!(flagged("<https://example.com>\n", &options).is_empty());
+    }
+
+    #[test]
+    fn test_images_and_code_are_skipped() {
+        let options = RuleOptions::default();
+        let content = "![here](logo.png) [![this](badge.svg)](ci.yml) \\[here](x)\n\
+            `[here](x)`\n```\n[here](x)\n```\n";
+        assert!(flagged(content, &options).is_empty());
+    }
+
+    #[test]
+    fn test_configured_phrases() {
+        let options = RuleOptions {
+            link_text_phrases: vec!["Docs".to_string()],
+            ..RuleOptions::default()
+        };
+        assert!(flagged("[here](a.md)\n", &options).is_empty());
+        assert_eq!(flagged("[docs](a.md)\n", &options).len(), 1);
+    }
+}
@@ -1,22 +1,251 @@
 pub mod ascii;
+pub mod duplicate_words;
+pub mod encoding;
+pub mod frontmatter;
+pub mod link_text;
+pub mod navigation;
+pub mod prose;
+pub mod repeated_punctuation;
+pub mod scan;
 pub mod tree_symbols;
 pub mod unprintable;
 
 pub use ascii::AsciiValidator;
+pub use duplicate_words::DuplicateWordsValidator;
+pub use encoding::EncodingRule;
+pub use frontmatter::FrontmatterValidator;
+pub use link_text::LinkTextValidator;
+pub use navigation::NavigationRule;
+pub use repeated_punctuation::RepeatedPunctuationValidator;
 pub use tree_symbols::TreeSymbolValidator;
 pub use unprintable::UnprintableValidator;
 
-use crate::{ValidationResult, Validator};
+use crate::{
+    CharRule, Exemption, ProseRule, RuleMetadata, RuleOptions, StopAt, ValidationResult, Validator,
+};
+use std::time::Duration;
 
-/// Run all validators on the content
-pub fn validate_all(content: &str) -> Vec<ValidationResult> {
+/// Metadata for every available rule, in rule-code order
+pub fn all_rules() -> Vec<&'static RuleMetadata> {
     vec![
-        AsciiValidator.validate(content),
-        UnprintableValidator.validate(content),
-        TreeSymbolValidator.validate(content),
+        AsciiValidator.metadata(),
+        UnprintableValidator.metadata(),
+        TreeSymbolValidator.metadata(),
+        FrontmatterValidator.metadata(),
+        &NavigationRule::METADATA,
+        &EncodingRule::METADATA,
+        DuplicateWordsValidator.metadata(),
+        RepeatedPunctuationValidator.metadata(),
+        LinkTextValidator.metadata(),
     ]
 }
 
+/// Codes of the rules that only run when turned on in `[rules]`
+pub fn opt_in_rules() -> Vec<&'static str> {
+    all_rules()
+        .into_iter()
+        .filter(|rule| !rule.enabled_by_default)
+        .map(|rule| rule.code)
+        .collect()
+}
+
+/// Look up a rule by code (case-insensitive), key, or name
+pub fn find_rule(query: &str) -> Option<&'static RuleMetadata> {
+    all_rules().into_iter().find(|rule| {
+        rule.code.eq_ignore_ascii_case(query)
+            || rule.key.eq_ignore_ascii_case(query)
+            || rule.name.eq_ignore_ascii_case(query)
+    })
+}
+
+/// Validators that judge each character on their own, run in one pass
+fn char_rules() -> Vec<&'static dyn CharRule> {
+    vec![&AsciiValidator, &UnprintableValidator, &TreeSymbolValidator]
+}
+
+/// The character rule with this code
+pub fn find_char_rule(code: &str) -> Option<&'static dyn CharRule> {
+    char_rules()
+        .into_iter()
+        .find(|rule| rule.metadata().code == code)
+}
+
+/// The character rules whose code is not listed in `disabled`
+pub fn enabled_char_rules(disabled: &[&str]) -> Vec<&'static dyn CharRule> {
+    char_rules()
+        .into_iter()
+        .filter(|rule| !disabled.contains(&rule.metadata().code))
+        .collect()
+}
+
+/// Validators that read prose line by line rather than character by character
+fn prose_rules() -> Vec<&'static dyn ProseRule> {
+    vec![
+        &DuplicateWordsValidator,
+        &RepeatedPunctuationValidator,
+        &LinkTextValidator,
+    ]
+}
+
+/// The prose rules whose code is not listed in `disabled`
+pub fn enabled_prose_rules(disabled: &[&str]) -> Vec<&'static dyn ProseRule> {
+    prose_rules()
+        .into_iter()
+        .filter(|rule| !disabled.contains(&rule.metadata().code))
+        .collect()
+}
+
+/// Whether the rule with this name or code is a prose rule, whose violations
+/// allowing a character does not answer
+pub fn is_prose_rule(rule: &str) -> bool {
+    prose_rules()
+        .iter()
+        .any(|prose| prose.name() == rule || prose.metadata().code == rule)
+}
+
+/// Every other validator
+fn other_validators() -> Vec<&'static dyn Validator> {
+    let mut validators: Vec<&'static dyn Validator> = vec![&FrontmatterValidator];
+    validators.extend(prose_rules().into_iter().map(|rule| rule as &dyn Validator));
+    validators
+}
+
+/// Run every rule that is on by default on the content
+pub fn validate_all(content: &str) -> Vec<ValidationResult> {
+    validate_enabled(content, &opt_in_rules())
+}
+
+/// Run every rule that is on by default, stopping once `stop_at` is reached
+pub fn validate_all_until(content: &str, stop_at: StopAt) -> Vec<ValidationResult> {
+    validate_until_timed(
+        content,
+        &opt_in_rules(),
+        &RuleOptions::default(),
+        stop_at,
+        None,
+    )
+    .into_iter()
+    .map(|(result, _)| result)
+    .collect()
+}
+
+/// Run the validators whose rule code is not listed in `disabled`
+pub fn validate_enabled(content: &str, disabled: &[&str]) -> Vec<ValidationResult> {
+    validate_enabled_timed(content, disabled)
+        .into_iter()
+        .map(|(result, _)| result)
+        .collect()
+}
+
+/// Like [`validate_enabled`], also measuring how long each validator took.
+/// The character rules share one pass; its time is split evenly between them.
+pub fn validate_enabled_timed(
+    content: &str,
+    disabled: &[&str],
+) -> Vec<(ValidationResult, Duration)> {
+    validate_until_timed(
+        content,
+        disabled,
+        &RuleOptions::default(),
+        StopAt::Never,
+        None,
+    )
+}
+
+/// Count the violations of each rule whose code is not listed in `disabled`,
+/// in rule-code order, without keeping them. Violations `exempt` accepts are
+/// not counted.
+pub fn count_enabled(
+    content: &str,
+    disabled: &[&str],
+    options: &RuleOptions,
+    exempt: Option<&Exemption>,
+) -> Vec<(&'static str, usize)> {
+    let chars = enabled_char_rules(disabled);
+    let mut counts: Vec<_> = chars
+        .iter()
+        .map(|rule| rule.metadata().code)
+        .zip(scan::count(&chars, content, exempt))
+        .collect();
+    for validator in other_validators() {
+        let code = validator.metadata().code;
+        if !disabled.contains(&code) {
+            let errors = validator.validate_with(content, options).errors;
+            let count = errors
+                .iter()
+                .filter(|error| !exempt.is_some_and(|exempt| exempt(code, error)))
+                .count();
+            counts.push((code, count));
+        }
+    }
+    counts.sort_by_key(|(code, _)| *code);
+    counts
+}
+
+/// Like [`validate_enabled_timed`], stopping once `stop_at` is reached and
+/// leaving out violations `exempt` accepts, which do not count toward it
+pub fn validate_until_timed(
+    content: &str,
+    disabled: &[&str],
+    options: &RuleOptions,
+    stop_at: StopAt,
+    exempt: Option<&Exemption>,
+) -> Vec<(ValidationResult, Duration)> {
+    let chars = enabled_char_rules(disabled);
+    let start = Stopwatch::start();
+    let scanned = scan::scan_until(&chars, content, stop_at, exempt);
+    let share = start.elapsed() / chars.len().max(1) as u32;
+
+    let mut found = scanned.iter().any(|result| result.is_fail());
+    let mut results: Vec<_> = chars
+        .iter()
+        .map(|rule| rule.metadata().code)
+        .zip(scanned.into_iter().map(|result| (result, share)))
+        .collect();
+    for validator in other_validators() {
+        let code = validator.metadata().code;
+        if disabled.contains(&code) || (found && stop_at == StopAt::First) {
+            continue;
+        }
+        let start = Stopwatch::start();
+        let mut result = validator.validate_with(content, options);
+        if let Some(exempt) = exempt {
+            result = result.retain_errors(|error| !exempt(code, error));
+        }
+        if stop_at != StopAt::Never {
+            result.errors.truncate(1);
+        }
+        found |= result.is_fail();
+        results.push((code, (result, start.elapsed())));
+    }
+    // Report in rule-code order
+    results.sort_by_key(|(code, _)| *code);
+    results.into_iter().map(|(_, timed)| timed).collect()
+}
+
+/// Measures validator time; reads zero on wasm32, where there is no clock and
+/// `Instant::now` panics
+struct Stopwatch {
+    #[cfg(not(target_arch = "wasm32"))]
+    started: std::time::Instant,
+}
+
+impl Stopwatch {
+    fn start() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            started: std::time::Instant::now(),
+        }
+    }
+
+    fn elapsed(&self) -> Duration {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.started.elapsed();
+        #[cfg(target_arch = "wasm32")]
+        Duration::ZERO
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -28,6 +257,87 @@ mod tests {
         assert_eq!(results.len(), 3);
     }
 
+    #[test]
+    fn test_validate_enabled_skips_disabled_rules() {
+        let results = validate_enabled(
+            "├── test",
+            &["MC001", "MC002", "MC004", "MC007", "MC008", "MC009"],
+        );
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].validator_name, "Tree Symbols");
+        assert!(results[0].is_fail());
+    }
+
+    #[test]
+    fn test_validate_all_until() {
+        let content = "caf\u{e9} \u{251c}\u{2500}\n";
+        let counts = |stop_at| -> Vec<usize> {
+            validate_all_until(content, stop_at)
+                .iter()
+                .map(|result| result.errors.len())
+                .collect()
+        };
+        assert_eq!(counts(StopAt::Never), vec![3, 3, 2]);
+        assert_eq!(counts(StopAt::FirstPerRule), vec![1, 1, 1]);
+        assert_eq!(counts(StopAt::First), vec![1, 0, 0]);
+    }
+
+    #[test]
+    fn test_count_enabled() {
+        let content = "caf\u{e9} \u{251c}\u{2500}\n";
+        assert_eq!(
+            count_enabled(content, &[], &RuleOptions::default(), None),
+            vec![
+                ("MC001", 3),
+                ("MC002", 3),
+                ("MC003", 2),
+                ("MC004", 0),
+                ("MC007", 0),
+                ("MC008", 0),
+                ("MC009", 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_all_rules_have_unique_codes() {
+        let rules = all_rules();
+        assert_eq!(rules.len(), 9);
+        let mut codes: Vec<_> = rules.iter().map(|r| r.code).collect();
+        codes.dedup();
+        assert_eq!(
+            codes,
+            vec![
+                "MC001", "MC002", "MC003", "MC004", "MC005", "MC006", "MC007", "MC008", "MC009"
+            ]
+        );
+        assert_eq!(opt_in_rules(), vec!["MC004", "MC007", "MC008", "MC009"]);
+    }
+
+    #[test]
+    fn test_rule_names_match_validator_names() {
+        let results = validate_enabled("test", &[]);
+        // The navigation rule checks whole sites, and the encoding rule bytes,
+        // not content
+        let names: Vec<_> = all_rules()
+            .iter()
+            .filter(|r| {
+                ![NavigationRule::METADATA.code, EncodingRule::METADATA.code].contains(&r.code)
+            })
+            .map(|r| r.name)
+            .collect();
+        let validator_names: Vec<_> = results.iter().map(|r| r.validator_name.as_str()).collect();
+        assert_eq!(names, validator_names);
+    }
+
+    #[test]
+    fn test_find_rule() {
+        assert_eq!(find_rule("MC003").unwrap().name, "Tree Symbols");
+        assert_eq!(find_rule("mc001").unwrap().key, "ascii");
+        assert_eq!(find_rule("tree-symbols").unwrap().code, "MC003");
+        assert!(find_rule("MC999").is_none());
+    }
+
     #[test]
     fn test_validate_all_with_violations() {
         let content = "├── test";
@@ -35,4 +345,14 @@ mod tests {
         let failed: Vec<_> = results.iter().filter(|r| r.is_fail()).collect();
         assert!(!failed.is_empty());
     }
+
+    #[test]
+    fn test_validate_enabled_timed_matches_untimed() {
+        let content = "├── café";
+        let timed: Vec<_> = validate_enabled_timed(content, &["MC002"])
+            .into_iter()
+            .map(|(result, _)| result)
+            .collect();
+        assert_eq!(timed, validate_enabled(content, &["MC002"]));
+    }
 }
@@ -0,0 +1,33 @@
+use crate::{RuleMetadata, Severity};
+
+/// Pages a site's navigation lists against the files on disk. It needs the
+/// whole site rather than one file's content, so it runs only when checking a
+/// site, with `--mdbook`, `--mkdocs`, or `--docusaurus`; the checks live in
+/// `navigation`.
+pub struct NavigationRule;
+
+impl NavigationRule {
+    pub const METADATA: RuleMetadata = RuleMetadata {
+        code: "MC005",
+        key: "navigation",
+        name: "Navigation",
+        summary: "Navigation must list every page, and relative links must resolve",
+        description: "Runs when checking a site with --mdbook, --mkdocs, or \
+            --docusaurus. Flags entries in the site's navigation (SUMMARY.md, the \
+            mkdocs.yml nav, or the sidebars file) whose page does not exist, markdown \
+            files under the content directory that no entry lists, and relative links \
+            to files that do not exist or to pages the navigation does not list.",
+        rationale: "Site generators build or show only the pages their navigation \
+            lists, so an unlisted file is silently orphaned, a link to it leads \
+            readers off the site's structure, and a renamed page leaves an entry \
+            pointing nowhere.",
+        examples: &[
+            "- [Setup](setup.md)  (no setup.md on disk)",
+            "See [the FAQ](faq.md)  (faq.md not in SUMMARY.md)",
+        ],
+        fix_behavior: "Not auto-fixable. Add, rename, or list the chapter, or correct the link.",
+        default_severity: Severity::Error,
+        fixable: false,
+        enabled_by_default: true,
+    };
+}
@@ -0,0 +1,67 @@
+//! Line-by-line runs of the prose rules.
+//!
+//! Prose rules look at words and punctuation rather than single characters,
+//! and skip code. A code span is set off by backticks, which end any word or
+//! run of punctuation, so a violation is either wholly inside code or wholly
+//! outside: each line is checked whole, and violations starting in code are
+//! dropped once the code regions are known.
+
+use crate::code_regions::CodeRegionsParser;
+use crate::doc_format::DocFormat;
+use crate::{ProseRule, RuleOptions, ValidationError, ValidationResult};
+
+/// Run `rule` over `content`, written in `options.format`
+pub fn validate(rule: &dyn ProseRule, content: &str, options: &RuleOptions) -> ValidationResult {
+    let rules = [rule];
+    let mut reader = ProseReader::new(&rules, options.format);
+    for line in content.lines() {
+        reader.push_line(line, options);
+    }
+    reader.finish().remove(0)
+}
+
+/// Runs the prose rules one line at a time, so a file can be read as a stream
+pub struct ProseReader<'a> {
+    rules: &'a [&'a dyn ProseRule],
+    code: CodeRegionsParser,
+    lines: usize,
+    /// Violations of each rule, in `rules` order
+    errors: Vec<Vec<ValidationError>>,
+}
+
+impl<'a> ProseReader<'a> {
+    /// Reader for content written in `format`, whose code is skipped
+    pub fn new(rules: &'a [&'a dyn ProseRule], format: DocFormat) -> Self {
+        Self {
+            rules,
+            code: CodeRegionsParser::new(format),
+            lines: 0,
+            errors: vec![Vec::new(); rules.len()],
+        }
+    }
+
+    pub fn push_line(&mut self, line: &str, options: &RuleOptions) {
+        self.code.push_line(line);
+        self.lines += 1;
+        for (rule, errors) in self.rules.iter().zip(&mut self.errors) {
+            errors.extend(rule.check_line(self.lines, line, options));
+        }
+    }
+
+    /// The result of each rule, in order
+    pub fn finish(self) -> Vec<ValidationResult> {
+        let code = self.code.finish();
+        self.rules
+            .iter()
+            .zip(self.errors)
+            .map(|(rule, mut errors)| {
+                errors.retain(|error| !code.contains(error.line_number, error.column));
+                if errors.is_empty() {
+                    ValidationResult::pass(rule.name().to_string())
+                } else {
+                    ValidationResult::fail(rule.name().to_string(), errors)
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,186 @@
+use super::prose;
+use crate::doc_format::DocFormat;
+use crate::{
+    ProseRule, RuleMetadata, RuleOptions, Severity, ValidationError, ValidationResult, Validator,
+};
+
+pub struct RepeatedPunctuationValidator;
+
+impl RepeatedPunctuationValidator {
+    pub const METADATA: RuleMetadata = RuleMetadata {
+        code: "MC008",
+        key: "repeated-punctuation",
+        name: "Repeated Punctuation",
+        summary: "Punctuation marks must not be repeated for emphasis",
+        description: "Flags runs of one punctuation mark among ! ? . , ; longer than \
+            [repeated-punctuation] max-run (1 by default), such as \"!!!\", \"??\", or \
+            \"....\". An ellipsis (\"...\") is accepted unless allow-ellipsis is false. \
+            Markdown syntax is not prose: code, MkDocs admonition markers (\"!!! note\", \
+            \"??? tip\"), reStructuredText directives and comments (\".. note::\"), \
+            relative paths (\"../docs\"), and ranges (\"1..10\") are not checked.",
+        rationale: "Repeated punctuation reads as shouting or uncertainty in user-facing \
+            documentation, and screen readers may read every mark aloud.",
+        examples: &["This is important!!!", "Why does it fail??", "Loading...."],
+        fix_behavior: "Not auto-fixable. Whether one mark, a period, or rewording is \
+            right is decided by hand.",
+        default_severity: Severity::Warning,
+        fixable: false,
+        enabled_by_default: false,
+    };
+
+    /// Marks whose runs are checked
+    const MARKS: &'static [char] = &['!', '?', '.', ',', ';'];
+}
+
+impl Validator for RepeatedPunctuationValidator {
+    fn name(&self) -> &str {
+        "Repeated Punctuation"
+    }
+
+    fn metadata(&self) -> &'static RuleMetadata {
+        &Self::METADATA
+    }
+
+    fn validate(&self, content: &str) -> ValidationResult {
+        self.validate_with(content, &RuleOptions::default())
+    }
+
+    fn validate_with(&self, content: &str, options: &RuleOptions) -> ValidationResult {
+        prose::validate(self, content, options)
+    }
+}
+
+impl ProseRule for RepeatedPunctuationValidator {
+    fn check_line(
+        &self,
+        line_number: usize,
+        line: &str,
+        options: &RuleOptions,
+    ) -> Vec<ValidationError> {
+        let chars: Vec<char> = line.chars().collect();
+        let mut errors = Vec::new();
+        let mut start = match options.format {
+            DocFormat::Rst => explicit_markup(&chars),
+            _ => admonition_marker(&chars),
+        };
+        while start < chars.len() {
+            let mark = chars[start];
+            let end = chars[start..]
+                .iter()
+                .position(|&ch| ch != mark)
+                .map_or(chars.len(), |length| start + length);
+            let length = end - start;
+            let accepted = !Self::MARKS.contains(&mark)
+                || length <= options.max_punctuation_run
+                || (mark == '.' && length == 3 && options.allow_ellipsis)
+                || (mark == '.' && is_path_or_range(&chars, start, end));
+            if !accepted {
+                let run: String = chars[start..end].iter().collect();
+                errors.push(
+                    ValidationError::new(line_number, format!("Repeated punctuation '{}'", run))
+                        .with_column(start + 1),
+                );
+            }
+            start = end;
+        }
+        errors
+    }
+}
+
+/// Length of the MkDocs admonition marker (`!!!`, `???`, or `???+`) opening
+/// `chars`, including its indentation, or 0
+fn admonition_marker(chars: &[char]) -> usize {
+    let indent = chars.iter().take_while(|ch| ch.is_whitespace()).count();
+    let rest = &chars[indent..];
+    let marker = match rest {
+        ['!', '!', '!', ..] => 3,
+        ['?', '?', '?', '+', ..] => 4,
+        ['?', '?', '?', ..] => 3,
+        _ => return 0,
+    };
+    match rest.get(marker) {
+        None | Some(' ' | '\t') => indent + marker,
+        _ => 0,
+    }
+}
+
+/// Length of the reStructuredText explicit markup start (`..` before a
+/// directive, comment, or target) opening `chars`, including its
+/// indentation, or 0
+fn explicit_markup(chars: &[char]) -> usize {
+    let indent = chars.iter().take_while(|ch| ch.is_whitespace()).count();
+    match &chars[indent..] {
+        ['.', '.'] | ['.', '.', ' ' | '\t', ..] => indent + 2,
+        _ => 0,
+    }
+}
+
+/// Whether the dots `chars[start..end]` are part of a relative path
+/// (`../docs`) or a range (`1..10`) rather than prose
+fn is_path_or_range(chars: &[char], start: usize, end: usize) -> bool {
+    let before = start.checked_sub(1).map(|index| chars[index]);
+    let after = chars.get(end).copied();
+    matches!(after, Some('/' | '\\'))
+        || (before.is_some_and(char::is_alphanumeric) && after.is_some_and(char::is_alphanumeric))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runs(content: &str, options: &RuleOptions) -> Vec<(usize, String)> {
+        RepeatedPunctuationValidator
+            .validate_with(content, options)
+            .errors
+            .iter()
+            .map(|error| (error.column.unwrap(), error.message.text().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_repeated_marks() {
+        let options = RuleOptions::default();
+        assert_eq!(
+            runs("Done!!! Really?? Loading.... ok,, fine;;\n", &options),
+            [
+                (5, "Repeated punctuation '!!!'".to_string()),
+                (15, "Repeated punctuation '??'".to_string()),
+                (25, "Repeated punctuation '....'".to_string()),
+                (32, "Repeated punctuation ',,'".to_string()),
+                (39, "Repeated punctuation ';;'".to_string()),
+            ]
+        );
+        assert!(runs("Wait. What? Yes! Then... done.\n", &options).is_empty());
+        assert!(runs("?!?!\n", &options).is_empty());
+    }
+
+    #[test]
+    fn test_thresholds() {
+        let options = RuleOptions {
+            max_punctuation_run: 2,
+            allow_ellipsis: false,
+            ..RuleOptions::default()
+        };
+        assert!(runs("Really?? Wow!!\n", &options).is_empty());
+        assert_eq!(runs("Wow!!! Then...\n", &options).len(), 2);
+    }
+
+    #[test]
+    fn test_markdown_syntax_is_skipped() {
+        let options = RuleOptions::default();
+        let content = "---\n\n!!! note \"Title\"\n    ???+ tip\n\n\
+            See [docs](../guide.md), run `echo !!`, and use 1..10.\n\
+            ```\nprint('?!')  # ???\n```\n";
+        assert!(runs(content, &options).is_empty());
+        assert_eq!(runs("!!!important\n", &options).len(), 1);
+
+        let rst = RuleOptions {
+            format: DocFormat::Rst,
+            ..RuleOptions::default()
+        };
+        let content = ".. note::\n\n   Text.\n\n  .. _target:\n..\n";
+        assert!(runs(content, &rst).is_empty());
+        assert_eq!(runs(content, &options).len(), 3);
+        assert_eq!(runs(".. Really??\n", &rst).len(), 1);
+    }
+}
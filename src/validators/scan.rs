@@ -0,0 +1,291 @@
+//! Single pass over the content for the character rules.
+//!
+//! Each character is classified once as a [`CharKind`] and offered only to the
+//! rules that flag that kind, so another character rule does not mean another
+//! pass over every file. Plain ASCII, most of a typical file, interests none
+//! of the built-in rules, so clean files and lines are recognized with a
+//! byte-level check and skipped without decoding characters.
+
+use crate::{CharKind, CharRule, Exemption, StopAt, ValidationError, ValidationResult};
+
+/// Run `rules` over `content` in one pass, returning their results in order
+pub fn scan(rules: &[&dyn CharRule], content: &str) -> Vec<ValidationResult> {
+    scan_until(rules, content, StopAt::Never, None)
+}
+
+/// Like [`scan`], stopping once `stop_at` is reached and not reporting
+/// violations `exempt` accepts
+pub fn scan_until(
+    rules: &[&dyn CharRule],
+    content: &str,
+    stop_at: StopAt,
+    exempt: Option<&Exemption>,
+) -> Vec<ValidationResult> {
+    let scanner = Scanner::new(rules).stop_at(stop_at);
+    run(scanner, content, exempt).finish()
+}
+
+/// Count each rule's violations in `content`, in order, without keeping them
+pub fn count(rules: &[&dyn CharRule], content: &str, exempt: Option<&Exemption>) -> Vec<usize> {
+    run(Scanner::new(rules).counting(), content, exempt).counts
+}
+
+fn run<'a>(
+    mut scanner: Scanner<'a>,
+    content: &str,
+    exempt: Option<&'a Exemption<'a>>,
+) -> Scanner<'a> {
+    if let Some(exempt) = exempt {
+        scanner = scanner.exempting(exempt);
+    }
+    if !(scanner.skips_plain() && is_plain(content)) {
+        for line in content.lines() {
+            if scanner.is_done() {
+                break;
+            }
+            scanner.push_line(line);
+        }
+    }
+    scanner
+}
+
+/// Runs the character rules one line at a time, so a file can be read as a
+/// stream
+pub struct Scanner<'a> {
+    rules: &'a [&'a dyn CharRule],
+    /// Indices of the rules that flag each kind
+    plain: Vec<usize>,
+    control: Vec<usize>,
+    non_ascii: Vec<usize>,
+    /// Characters no rule is asked about
+    skip: Option<&'a dyn Fn(char) -> bool>,
+    /// Violations not reported
+    exempt: Option<&'a Exemption<'a>>,
+    stop_at: StopAt,
+    /// Rules that stopped checking under `stop_at`
+    stopped: Vec<bool>,
+    errors: Vec<Vec<ValidationError>>,
+    /// Violations found per rule, kept or not
+    counts: Vec<usize>,
+    /// Whether violations are only counted
+    counting: bool,
+    line_number: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(rules: &'a [&'a dyn CharRule]) -> Self {
+        let interested = |kind: CharKind| -> Vec<usize> {
+            (0..rules.len())
+                .filter(|&index| rules[index].flags(kind))
+                .collect()
+        };
+        Self {
+            rules,
+            plain: interested(CharKind::Plain),
+            control: interested(CharKind::Control),
+            non_ascii: interested(CharKind::NonAscii),
+            skip: None,
+            exempt: None,
+            stop_at: StopAt::Never,
+            stopped: vec![false; rules.len()],
+            errors: vec![Vec::new(); rules.len()],
+            counts: vec![0; rules.len()],
+            counting: false,
+            line_number: 0,
+        }
+    }
+
+    /// Do not ask the rules about characters matching `skip`, such as those
+    /// a policy allows
+    pub fn skipping(mut self, skip: &'a dyn Fn(char) -> bool) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+
+    /// Do not report violations `exempt` accepts
+    pub fn exempting(mut self, exempt: &'a Exemption<'a>) -> Self {
+        self.exempt = Some(exempt);
+        self
+    }
+
+    /// Only count violations, without keeping them
+    pub fn counting(mut self) -> Self {
+        self.counting = true;
+        self
+    }
+
+    /// Stop checking once a violation is found, per rule or for every rule
+    pub fn stop_at(mut self, stop_at: StopAt) -> Self {
+        self.stop_at = stop_at;
+        self
+    }
+
+    /// Whether every rule has stopped, so later lines need not be read
+    pub fn is_done(&self) -> bool {
+        self.stopped.iter().all(|&stopped| stopped)
+    }
+
+    /// Whether no rule flags plain ASCII, so plain text can be skipped
+    fn skips_plain(&self) -> bool {
+        self.plain.is_empty()
+    }
+
+    /// Check the next line, without its line ending
+    pub fn push_line(&mut self, line: &str) {
+        self.line_number += 1;
+        if self.skips_plain() && is_plain(line) {
+            return;
+        }
+        for (col, ch) in line.chars().enumerate() {
+            let indices = match CharKind::of(ch) {
+                CharKind::Plain => &self.plain,
+                CharKind::Control => &self.control,
+                CharKind::NonAscii => &self.non_ascii,
+            };
+            if indices.is_empty() || self.skip.is_some_and(|skip| skip(ch)) {
+                continue;
+            }
+            for &index in indices {
+                if self.stopped[index] {
+                    continue;
+                }
+                if !self.rules[index].violates(ch) {
+                    continue;
+                }
+                let code = self.rules[index].metadata().code;
+                let error =
+                    ValidationError::flagged(self.line_number, code, ch).with_column(col + 1);
+                if self.exempt.is_some_and(|exempt| exempt(code, &error)) {
+                    continue;
+                }
+                self.counts[index] += 1;
+                if !self.counting {
+                    self.errors[index].push(error);
+                }
+                match self.stop_at {
+                    StopAt::Never => {}
+                    StopAt::FirstPerRule => self.stopped[index] = true,
+                    StopAt::First => {
+                        self.stopped.fill(true);
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Each rule's result, in order
+    pub fn finish(self) -> Vec<ValidationResult> {
+        self.rules
+            .iter()
+            .zip(self.errors)
+            .map(|(rule, errors)| {
+                if errors.is_empty() {
+                    ValidationResult::pass(rule.name().to_string())
+                } else {
+                    ValidationResult::fail(rule.name().to_string(), errors)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether every character of `text` is [`CharKind::Plain`] or a line feed.
+/// `is_ascii` checks a word at a time, and most text passes it.
+fn is_plain(text: &str) -> bool {
+    text.is_ascii()
+        && !text
+            .bytes()
+            .any(|byte| byte.is_ascii_control() && !matches!(byte, b'\t' | b'\r' | b'\n'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validators::{AsciiValidator, TreeSymbolValidator, UnprintableValidator};
+
+    #[test]
+    fn test_char_kinds() {
+        assert_eq!(CharKind::of('a'), CharKind::Plain);
+        assert_eq!(CharKind::of('\t'), CharKind::Plain);
+        assert_eq!(CharKind::of('\0'), CharKind::Control);
+        assert_eq!(CharKind::of('\u{7f}'), CharKind::Control);
+        assert_eq!(CharKind::of('\u{e9}'), CharKind::NonAscii);
+    }
+
+    #[test]
+    fn test_is_plain() {
+        assert!(is_plain("# Title\r\n\tcode ~ok~\n"));
+        assert!(!is_plain("bell\x07\n"));
+        assert!(!is_plain("del\x7f"));
+        assert!(!is_plain("caf\u{e9}"));
+    }
+
+    #[test]
+    fn test_clean_lines_are_skipped_without_losing_positions() {
+        let results = scan(&[&AsciiValidator], "plain\n\u{e9}\nplain \u{e9}\n");
+        let locations: Vec<_> = results[0]
+            .errors
+            .iter()
+            .map(|e| (e.line_number, e.column))
+            .collect();
+        assert_eq!(locations, vec![(2, Some(1)), (3, Some(7))]);
+        assert!(scan(&[&AsciiValidator], "all plain\n")[0].is_pass());
+    }
+
+    #[test]
+    fn test_stop_at() {
+        let rules: [&dyn CharRule; 2] = [&AsciiValidator, &TreeSymbolValidator];
+        let content = "caf\u{e9}\n\u{251c}\u{2500}\n";
+        let counts = |stop_at, exempt: Option<&Exemption>| -> Vec<usize> {
+            scan_until(&rules, content, stop_at, exempt)
+                .iter()
+                .map(|result| result.errors.len())
+                .collect()
+        };
+        assert_eq!(counts(StopAt::Never, None), vec![3, 2]);
+        assert_eq!(counts(StopAt::FirstPerRule, None), vec![1, 1]);
+        assert_eq!(counts(StopAt::First, None), vec![1, 0]);
+
+        // An exempt violation does not stop the scan
+        let first_line = |_: &str, error: &ValidationError| error.line_number == 1;
+        assert_eq!(counts(StopAt::First, Some(&first_line)), vec![1, 0]);
+        let result = &scan_until(&rules, content, StopAt::First, Some(&first_line))[0];
+        assert_eq!(result.errors[0].line_number, 2);
+    }
+
+    #[test]
+    fn test_count() {
+        let rules: [&dyn CharRule; 2] = [&AsciiValidator, &TreeSymbolValidator];
+        let content = "caf\u{e9}\n\u{251c}\u{2500}\n";
+        assert_eq!(count(&rules, content, None), vec![3, 2]);
+        let first_line = |_: &str, error: &ValidationError| error.line_number == 1;
+        assert_eq!(count(&rules, content, Some(&first_line)), vec![2, 2]);
+    }
+
+    #[test]
+    fn test_skipped_characters() {
+        let allowed = |ch: char| ch == '\u{e9}';
+        let rules: [&dyn CharRule; 1] = [&AsciiValidator];
+        let mut scanner = Scanner::new(&rules).skipping(&allowed);
+        scanner.push_line("caf\u{e9} \u{2192}");
+        let results = scanner.finish();
+        assert_eq!(results[0].errors.len(), 1);
+        assert_eq!(results[0].errors[0].column, Some(6));
+    }
+
+    #[test]
+    fn test_one_pass_matches_separate_passes() {
+        let content = "ok\n\u{251c}\u{2500} caf\u{e9}\x07\n\tend\r\n";
+        let rules: [&dyn CharRule; 3] =
+            [&AsciiValidator, &UnprintableValidator, &TreeSymbolValidator];
+        let together = scan(&rules, content);
+        let separate: Vec<_> = rules
+            .iter()
+            .map(|rule| scan(&[*rule], content).remove(0))
+            .collect();
+        assert_eq!(together, separate);
+        let counts: Vec<_> = together.iter().map(|r| r.errors.len()).collect();
+        assert_eq!(counts, vec![3, 4, 2]);
+    }
+}
@@ -1,16 +1,32 @@
-use crate::{ValidationError, ValidationResult, Validator};
+use super::scan::scan;
+use crate::charset::{self, CharClass};
+use crate::{CharKind, CharRule, RuleMetadata, Severity, ValidationResult, Validator};
 
 pub struct TreeSymbolValidator;
 
 impl TreeSymbolValidator {
-    const TREE_SYMBOLS: &'static [char] = &[
-        '├', '└', '│', '─', '┌', '┐', '┘', '┤', '┴', '┬', '┼', '╭', '╮', '╯', '╰', '╱', '╲', '╳',
-    ];
-
-    fn is_tree_symbol(ch: char) -> bool {
-        Self::TREE_SYMBOLS.contains(&ch) || (ch as u32 >= 0x2500 && ch as u32 <= 0x257F)
-        // Box Drawing block
-    }
+    pub const METADATA: RuleMetadata = RuleMetadata {
+        code: "MC003",
+        key: "tree-symbols",
+        name: "Tree Symbols",
+        summary: "Box-drawing characters must not be used for directory trees",
+        description: "Flags box-drawing characters (U+2500-U+257F) such as \
+            \u{251C}, \u{2514}, \u{2502}, and \u{2500}, which are commonly pasted from \
+            `tree` output to draw directory structures.",
+        rationale: "Box-drawing characters depend on font support, misalign in many \
+            editors, and are read aloud as noise by screen readers.",
+        examples: &[
+            "\u{251C}\u{2500}\u{2500} src/",
+            "\u{2502}   \u{2514}\u{2500}\u{2500} main.rs",
+        ],
+        fix_behavior: "Replaced with ASCII: junctions and corners become +, vertical \
+            lines become |, and horizontal lines become -.",
+        default_severity: Severity::Error,
+        fixable: true,
+        enabled_by_default: true,
+    };
+    /// Characters the rule flags: the Box Drawing block, U+2500-U+257F
+    const FLAGGED: &'static [CharClass] = &[CharClass::Block(unicode_blocks::BOX_DRAWING)];
 
     fn suggest_alternative(ch: char) -> &'static str {
         match ch {
@@ -28,33 +44,31 @@ impl Validator for TreeSymbolValidator {
         "Tree Symbols"
     }
 
+    fn metadata(&self) -> &'static RuleMetadata {
+        &Self::METADATA
+    }
+
     fn validate(&self, content: &str) -> ValidationResult {
-        let mut errors = Vec::new();
-
-        for (line_num, line) in content.lines().enumerate() {
-            for (col, ch) in line.chars().enumerate() {
-                if Self::is_tree_symbol(ch) {
-                    errors.push(
-                        ValidationError::new(
-                            line_num + 1,
-                            format!(
-                                "Tree symbol '{}' (U+{:04X}) detected. {}",
-                                ch,
-                                ch as u32,
-                                Self::suggest_alternative(ch)
-                            ),
-                        )
-                        .with_column(col + 1),
-                    );
-                }
-            }
-        }
+        scan(&[self], content).remove(0)
+    }
+}
 
-        if errors.is_empty() {
-            ValidationResult::pass(self.name().to_string())
-        } else {
-            ValidationResult::fail(self.name().to_string(), errors)
-        }
+impl CharRule for TreeSymbolValidator {
+    fn flags(&self, kind: CharKind) -> bool {
+        kind == CharKind::NonAscii
+    }
+
+    fn violates(&self, ch: char) -> bool {
+        charset::accepts(Self::FLAGGED, &[], ch)
+    }
+
+    fn message(&self, ch: char) -> String {
+        format!(
+            "Tree symbol '{}' (U+{:04X}) detected. {}",
+            ch,
+            ch as u32,
+            Self::suggest_alternative(ch)
+        )
     }
 }
 
@@ -86,7 +100,7 @@ mod tests {
         let validator = TreeSymbolValidator;
         let result = validator.validate(content);
         assert!(!result.errors.is_empty());
-        assert!(result.errors[0].message.contains("instead"));
+        assert!(result.errors[0].message.text().contains("instead"));
     }
 
     #[test]
@@ -114,7 +128,7 @@ mod tests {
         let validator = TreeSymbolValidator;
         let result = validator.validate(content);
         assert!(result.is_fail());
-        assert!(result.errors[0].message.contains("Use '|' instead"));
+        assert!(result.errors[0].message.text().contains("Use '|' instead"));
     }
 
     #[test]
@@ -123,6 +137,6 @@ mod tests {
         let validator = TreeSymbolValidator;
         let result = validator.validate(content);
         assert!(result.is_fail());
-        assert!(result.errors[0].message.contains("Use '-' instead"));
+        assert!(result.errors[0].message.text().contains("Use '-' instead"));
     }
 }
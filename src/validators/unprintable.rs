@@ -1,17 +1,36 @@
-use crate::{ValidationError, ValidationResult, Validator};
+use super::scan::scan;
+use crate::charset::{self, CharClass};
+use crate::{CharKind, CharRule, RuleMetadata, Severity, ValidationResult, Validator};
 
 pub struct UnprintableValidator;
 
 impl UnprintableValidator {
-    fn is_allowed_whitespace(ch: char) -> bool {
-        matches!(ch, ' ' | '\t' | '\n' | '\r')
-    }
-
-    fn is_printable(ch: char) -> bool {
-        let code = ch as u32;
-        // Printable ASCII: 32-126
-        (32..=126).contains(&code) || Self::is_allowed_whitespace(ch)
-    }
+    pub const METADATA: RuleMetadata = RuleMetadata {
+        code: "MC002",
+        key: "unprintable",
+        name: "Printable Characters",
+        summary: "Only printable ASCII characters and standard whitespace are allowed",
+        description: "Flags control characters and any character outside printable \
+            ASCII (32-126). Space, tab, line feed, and carriage return are allowed.",
+        rationale: "Invisible control characters such as NUL, BEL, or escape sequences \
+            corrupt rendering, break parsers, and can hide content from reviewers.",
+        examples: &[
+            "Hello\\0World (NUL byte)",
+            "Ring\\x07 (BEL)",
+            "ESC\\x1b[31m sequence",
+        ],
+        fix_behavior: "Not auto-fixable. Control characters are removed or replaced \
+            by hand because their intent cannot be inferred safely.",
+        default_severity: Severity::Error,
+        fixable: false,
+        enabled_by_default: true,
+    };
+    /// Printable ASCII (32-126), tab, line feed, and carriage return
+    const ACCEPTED: &'static [CharClass] = &[
+        CharClass::Range(' ', '~'),
+        CharClass::Range('\t', '\n'),
+        CharClass::Range('\r', '\r'),
+    ];
 }
 
 impl Validator for UnprintableValidator {
@@ -19,28 +38,26 @@ impl Validator for UnprintableValidator {
         "Printable Characters"
     }
 
+    fn metadata(&self) -> &'static RuleMetadata {
+        &Self::METADATA
+    }
+
     fn validate(&self, content: &str) -> ValidationResult {
-        let mut errors = Vec::new();
+        scan(&[self], content).remove(0)
+    }
+}
 
-        for (line_num, line) in content.lines().enumerate() {
-            for (col, ch) in line.chars().enumerate() {
-                if !Self::is_printable(ch) {
-                    errors.push(
-                        ValidationError::new(
-                            line_num + 1,
-                            format!("Unprintable character: U+{:04X}", ch as u32),
-                        )
-                        .with_column(col + 1),
-                    );
-                }
-            }
-        }
+impl CharRule for UnprintableValidator {
+    fn flags(&self, kind: CharKind) -> bool {
+        kind != CharKind::Plain
+    }
+
+    fn violates(&self, ch: char) -> bool {
+        !charset::accepts(Self::ACCEPTED, &[], ch)
+    }
 
-        if errors.is_empty() {
-            ValidationResult::pass(self.name().to_string())
-        } else {
-            ValidationResult::fail(self.name().to_string(), errors)
-        }
+    fn message(&self, ch: char) -> String {
+        format!("Unprintable character: U+{:04X}", ch as u32)
     }
 }
 
@@ -63,7 +80,7 @@ mod tests {
         let result = validator.validate(content);
         assert!(result.is_fail());
         assert_eq!(result.errors.len(), 1);
-        assert!(result.errors[0].message.contains("U+0000"));
+        assert!(result.errors[0].message.text().contains("U+0000"));
     }
 
     #[test]
@@ -73,7 +90,7 @@ mod tests {
         let result = validator.validate(content);
         assert!(result.is_fail());
         assert_eq!(result.errors.len(), 1);
-        assert!(result.errors[0].message.contains("U+0007"));
+        assert!(result.errors[0].message.text().contains("U+0007"));
     }
 
     #[test]
@@ -0,0 +1,52 @@
+//! JavaScript bindings for wasm32 builds (the `wasm` feature).
+//!
+//! `check` and `fix` apply the same rules as the command line with its default
+//! configuration, so a docs platform running them in the browser or in a Node
+//! build step gets the results `markdown-checker` would report.
+
+use crate::fixer::fix_allowed;
+use crate::policy::Policy;
+use serde_json::json;
+use wasm_bindgen::prelude::*;
+
+/// Check markdown `content`. Returns a JSON object: `passed` is false when any
+/// error-severity rule fails, and `results` lists each enabled rule with its
+/// status, severity, and violations, as `--format json` does.
+#[wasm_bindgen]
+pub fn check(content: &str) -> String {
+    let results = Policy::default().validate(content);
+    let passed = !results.iter().any(|result| result.is_error());
+    json!({ "passed": passed, "results": results }).to_string()
+}
+
+/// Replace every auto-fixable character in `content` with its ASCII
+/// equivalent. Violations without a fix are left for `check` to report.
+#[wasm_bindgen]
+pub fn fix(content: &str) -> String {
+    fix_allowed(content, &Policy::default()).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn test_check_reports_json() {
+        let report: Value = serde_json::from_str(&check("# Title\n")).unwrap();
+        assert_eq!(report["passed"], true);
+
+        let report: Value = serde_json::from_str(&check("a \u{2192} b\n")).unwrap();
+        assert_eq!(report["passed"], false);
+        let ascii = &report["results"][0];
+        assert_eq!(ascii["errors"][0]["line_number"], 1);
+        assert_eq!(ascii["errors"][0]["column"], 3);
+    }
+
+    #[test]
+    fn test_fix_then_check_passes() {
+        let fixed = fix("\u{251c}\u{2500}\u{2500} src \u{2192} lib\n");
+        assert_eq!(fixed, "+-- src -> lib\n");
+        assert!(check(&fixed).contains("\"passed\":true"));
+    }
+}
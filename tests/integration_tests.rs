@@ -2,7 +2,10 @@
 
 use markdown_checker::file_ops::read_file_content;
 use markdown_checker::validators::validate_all;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use tempfile::TempDir;
 
 /// Test that the project's own README.md passes all validation checks
 /// This ensures we practice what we preach!
@@ -105,3 +108,171 @@ fn test_non_ascii_fixture_fails() {
         "ASCII Subset validator should fail for non_ascii.md"
     );
 }
+
+/// Run the markdown-checker binary in `dir`, ignoring configuration files
+fn run_cli(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_markdown-checker"))
+        .current_dir(dir)
+        .arg("--no-config")
+        .args(args)
+        .output()
+        .expect("Failed to run markdown-checker")
+}
+
+/// Temporary directory holding `files`, as (name, content) pairs
+fn docs(files: &[(&str, &str)]) -> TempDir {
+    let dir = TempDir::new().expect("Failed to create temporary directory");
+    for (name, content) in files {
+        fs::write(dir.path().join(name), content).expect("Failed to write fixture");
+    }
+    dir
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+/// Test that --fail-fast does not stop --fix at a file it can fix
+#[test]
+fn test_fail_fast_fixes_every_file() {
+    let names = ["a.md", "b.md", "c.md"];
+    let dir = docs(&names.map(|name| (name, "# Tree\n\n\u{251c} src\n")));
+
+    let output = run_cli(dir.path(), &["--fix", "--fail-fast", "-q", "-f", "*.md"]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert!(!stderr(&output).contains("--fail-fast"));
+    for name in names {
+        let fixed = fs::read_to_string(dir.path().join(name)).unwrap();
+        assert_eq!(fixed, "# Tree\n\n+ src\n", "{name}");
+    }
+}
+
+/// Test that --fail-fast stops at a file --fix cannot fix
+#[test]
+fn test_fail_fast_stops_at_unfixable_file() {
+    let dir = docs(&[
+        ("a.md", "# A\n\n\u{251c} src\n"),
+        ("b.md", "# B\n\nBell \u{7}\n"),
+        ("c.md", "# C\n\n\u{251c} src\n"),
+    ]);
+
+    let output = run_cli(
+        dir.path(),
+        &["--fix", "--fail-fast", "-q", "-j", "1", "-f", "*.md"],
+    );
+    assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+    assert!(stderr(&output).contains("1 file(s) not checked"));
+    let unchecked = fs::read_to_string(dir.path().join("c.md")).unwrap();
+    assert!(unchecked.contains('\u{251c}'));
+}
+
+/// Test that --fail-fast keeps going while warnings stay within --max-warnings
+#[test]
+fn test_fail_fast_with_max_warnings() {
+    let names = ["a.md", "b.md", "c.md", "d.md"];
+    let dir = docs(&names.map(|name| (name, "# Notes\n\nRead the the notes.\n")));
+    let args = [
+        "--rule",
+        "duplicate-words=warning",
+        "--fail-fast",
+        "-f",
+        "*.md",
+    ];
+
+    let output = run_cli(dir.path(), &[&args[..], &["--max-warnings", "4"]].concat());
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert!(!stderr(&output).contains("not checked"));
+
+    let output = run_cli(dir.path(), &[&args[..], &["--max-warnings", "2"]].concat());
+    assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+    assert!(stderr(&output).contains("1 file(s) not checked"));
+}
+
+/// Test that a run whose only matches are skipped succeeds without a
+/// "No files found" error
+#[test]
+fn test_only_skipped_files() {
+    let dir = docs(&[("large.md", "# Large\n\nMore than sixteen bytes.\n")]);
+    let output = run_cli(dir.path(), &["--max-file-size", "16", "-f", "large.md"]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert!(stderr(&output).contains("Skipping large file"));
+    assert!(!stderr(&output).contains("No files found"));
+
+    let dir = docs(&[("binary.md", "a\0b\n")]);
+    let output = run_cli(dir.path(), &["-f", "*.md"]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert!(stderr(&output).contains("Skipping binary file"));
+    assert!(!stderr(&output).contains("No files found"));
+}
+
+/// Test the exit codes of --max-errors: 0 within the threshold, and the
+/// usual 3 (all fixable) or 1 over it
+#[test]
+fn test_max_errors_exit_codes() {
+    let dir = docs(&[
+        ("tree.md", "# Tree\n\n\u{251c} a\n\u{251c} b\n"),
+        ("bell.md", "# Bell\n\nBell \u{7}\n"),
+    ]);
+
+    let output = run_cli(dir.path(), &["-f", "tree.md"]);
+    assert_eq!(output.status.code(), Some(3), "{}", stderr(&output));
+    let output = run_cli(dir.path(), &["-f", "tree.md", "--max-errors", "2"]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert!(stderr(&output).contains("within --max-errors threshold of 2"));
+    let output = run_cli(dir.path(), &["-f", "tree.md", "--max-errors", "1"]);
+    assert_eq!(output.status.code(), Some(3), "{}", stderr(&output));
+
+    let output = run_cli(dir.path(), &["-f", "bell.md", "--max-errors", "0"]);
+    assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+    let output = run_cli(dir.path(), &["-f", "bell.md", "--exit-zero"]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+}
+
+/// Test that files over the size limit are skipped unless --force-large is given
+#[test]
+fn test_size_guard_exit_codes() {
+    let dir = docs(&[
+        ("small.md", "# Small\n"),
+        ("large.md", "# Large\n\n\u{251c} a\n\u{251c} b\n"),
+    ]);
+
+    let output = run_cli(dir.path(), &["--max-file-size", "16", "-f", "*.md"]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    assert!(stderr(&output).contains("Skipping large file"));
+
+    let output = run_cli(dir.path(), &["--max-file-size", "1K", "-f", "*.md"]);
+    assert_eq!(output.status.code(), Some(3), "{}", stderr(&output));
+    let output = run_cli(dir.path(), &["--force-large", "-f", "*.md"]);
+    assert_eq!(output.status.code(), Some(3), "{}", stderr(&output));
+
+    // The two say opposite things, which is a usage error
+    let output = run_cli(
+        dir.path(),
+        &["--max-file-size", "16", "--force-large", "-f", "*.md"],
+    );
+    assert_eq!(output.status.code(), Some(2), "{}", stderr(&output));
+}
+
+/// Test the exit codes of --fix and --dry-run, and that only --fix writes
+#[test]
+fn test_fix_mode_exit_codes() {
+    let dir = docs(&[
+        ("tree.md", "# Tree\n\n\u{251c} src\n"),
+        ("bell.md", "# Bell\n\nBell \u{7}\n"),
+    ]);
+
+    let output = run_cli(dir.path(), &["--dry-run", "-f", "tree.md"]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    let unchanged = fs::read_to_string(dir.path().join("tree.md")).unwrap();
+    assert!(unchanged.contains('\u{251c}'));
+
+    let output = run_cli(dir.path(), &["--fix", "-f", "tree.md"]);
+    assert_eq!(output.status.code(), Some(0), "{}", stderr(&output));
+    let fixed = fs::read_to_string(dir.path().join("tree.md")).unwrap();
+    assert_eq!(fixed, "# Tree\n\n+ src\n");
+
+    let output = run_cli(dir.path(), &["--fix", "-f", "bell.md"]);
+    assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+    let output = run_cli(dir.path(), &["--dry-run", "-f", "bell.md"]);
+    assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+}